@@ -0,0 +1,3 @@
+import { erc20HtlcExecutionTests } from "../../../lib/erc20_htlc_execution";
+
+erc20HtlcExecutionTests("Keccak-256");
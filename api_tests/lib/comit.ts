@@ -60,6 +60,7 @@ export interface SwapRequest {
     alpha_ledger_refund_identity?: string;
     alpha_expiry: number;
     beta_expiry: number;
+    hash_function?: string;
     peer: string;
 }
 
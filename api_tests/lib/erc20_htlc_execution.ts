@@ -0,0 +1,201 @@
+import "chai/register-should";
+import { ethers } from "ethers";
+import { Actor } from "./actor";
+import { ActionKind, SwapRequest } from "./comit";
+import "./setup_chai";
+import { createTests, Step } from "./test_creator";
+import { HarnessGlobal } from "./util";
+import { Wallet } from "./wallet";
+
+declare var global: HarnessGlobal;
+
+/**
+ * Swaps ERC20 tokens for ERC20 tokens on the same chain with the HTLCs
+ * checking the secret with `hashFunction`. Alice redeems the beta HTLC with
+ * the secret, Bob does not redeem and Alice refunds the alpha HTLC after its
+ * expiry. A wrong secret and a refund before the expiry have to fail.
+ */
+export async function erc20HtlcExecutionTests(hashFunction: string) {
+    const tobyWallet = new Wallet("toby", {
+        ledgerConfig: global.ledgerConfigs,
+    });
+    const alice = new Actor("alice", {
+        ledgerConfig: global.ledgerConfigs,
+    });
+    const bob = new Actor("bob", {
+        ledgerConfig: global.ledgerConfigs,
+    });
+
+    const aliceInitialErc20 = ethers.utils.parseEther("10000");
+    const bobInitialErc20 = ethers.utils.parseEther("10000");
+    const alphaAssetQuantity = ethers.utils.parseEther("5000");
+    const betaAssetQuantity = ethers.utils.parseEther("4000");
+
+    const alphaExpiry = Math.round(Date.now() / 1000) + 35;
+    const betaExpiry = Math.round(Date.now() / 1000) + 25;
+
+    const wrongSecret = "0x" + "00".repeat(32);
+
+    await tobyWallet.eth().fund("10");
+    await alice.wallet.eth().fund("1");
+    await bob.wallet.eth().fund("1");
+
+    const alphaTokenContract = await tobyWallet
+        .eth()
+        .deployErc20TokenContract(global.projectRoot);
+    const betaTokenContract = await tobyWallet
+        .eth()
+        .deployErc20TokenContract(global.projectRoot);
+    await tobyWallet
+        .eth()
+        .mintErc20To(
+            alice.wallet.eth().address(),
+            aliceInitialErc20,
+            alphaTokenContract
+        );
+    await tobyWallet
+        .eth()
+        .mintErc20To(
+            bob.wallet.eth().address(),
+            bobInitialErc20,
+            betaTokenContract
+        );
+
+    const swapRequest: SwapRequest = {
+        alpha_ledger: {
+            name: "ethereum",
+            chain_id: 17,
+        },
+        beta_ledger: {
+            name: "ethereum",
+            chain_id: 17,
+        },
+        alpha_asset: {
+            name: "erc20",
+            quantity: alphaAssetQuantity.toString(),
+            token_contract: alphaTokenContract,
+        },
+        beta_asset: {
+            name: "erc20",
+            quantity: betaAssetQuantity.toString(),
+            token_contract: betaTokenContract,
+        },
+        alpha_ledger_refund_identity: alice.wallet.eth().address(),
+        beta_ledger_redeem_identity: alice.wallet.eth().address(),
+        alpha_expiry: alphaExpiry,
+        beta_expiry: betaExpiry,
+        hash_function: hashFunction,
+        peer: await bob.peerId(),
+    };
+
+    const htlcLocation = async (
+        actor: Actor,
+        swapHref: string,
+        ledger: "alpha_ledger" | "beta_ledger"
+    ) => {
+        const body = await actor.pollCndUntil(swapHref, () => true);
+        return body.properties.state[ledger].htlc_location as string;
+    };
+
+    const steps: Step[] = [
+        {
+            actor: bob,
+            action: ActionKind.Accept,
+            waitUntil: state => state.communication.status === "ACCEPTED",
+        },
+        {
+            actor: alice,
+            action: ActionKind.Deploy,
+            waitUntil: state => state.alpha_ledger.status === "DEPLOYED",
+        },
+        {
+            actor: alice,
+            action: ActionKind.Fund,
+            waitUntil: state => state.alpha_ledger.status === "FUNDED",
+        },
+        {
+            actor: alice,
+            action: {
+                description: "Cannot refund the alpha HTLC before its expiry",
+                exec: async (actor, swapHref) => {
+                    const htlc = await htlcLocation(
+                        actor,
+                        swapHref,
+                        "alpha_ledger"
+                    );
+                    const refund = await actor.wallet
+                        .eth()
+                        .sendEthTransactionTo(htlc, "0x");
+
+                    await refund.wait(1).should.be.rejected;
+                },
+            },
+        },
+        {
+            actor: bob,
+            action: ActionKind.Deploy,
+            waitUntil: state => state.beta_ledger.status === "DEPLOYED",
+        },
+        {
+            actor: bob,
+            action: ActionKind.Fund,
+            waitUntil: state => state.beta_ledger.status === "FUNDED",
+        },
+        {
+            actor: alice,
+            action: {
+                description: "Cannot redeem the beta HTLC with a wrong secret",
+                exec: async (actor, swapHref) => {
+                    const htlc = await htlcLocation(
+                        actor,
+                        swapHref,
+                        "beta_ledger"
+                    );
+                    const redeem = await actor.wallet
+                        .eth()
+                        .sendEthTransactionTo(htlc, wrongSecret);
+
+                    await redeem.wait(1).should.be.rejected;
+                },
+            },
+        },
+        {
+            actor: alice,
+            action: ActionKind.Redeem,
+            waitUntil: state => state.beta_ledger.status === "REDEEMED",
+            test: {
+                description:
+                    "Should have received the beta asset after the redeem",
+                callback: async () => {
+                    const erc20Balance = await alice.wallet
+                        .eth()
+                        .erc20Balance(betaTokenContract);
+
+                    erc20Balance.eq(betaAssetQuantity).should.equal(true);
+                },
+            },
+        },
+        {
+            actor: alice,
+            action: ActionKind.Refund,
+            waitUntil: state => state.alpha_ledger.status === "REFUNDED",
+            test: {
+                description:
+                    "Should have received the alpha asset after the refund",
+                callback: async () => {
+                    const erc20Balance = await alice.wallet
+                        .eth()
+                        .erc20Balance(alphaTokenContract);
+
+                    erc20Balance.eq(aliceInitialErc20).should.equal(true);
+                },
+            },
+        },
+    ];
+
+    const description = `RFC003: ERC20 for ERC20 with ${hashFunction} - Beta redeemed, alpha refunded`;
+    describe(description, () => {
+        createTests(alice, bob, steps, "/swaps/rfc003", "/swaps", swapRequest);
+    });
+    run();
+}
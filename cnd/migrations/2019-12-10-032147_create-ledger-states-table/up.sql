@@ -0,0 +1,16 @@
+-- Your SQL goes here
+
+CREATE TABLE rfc003_ledger_states
+(
+    id INTEGER         NOT NULL PRIMARY KEY,
+    swap_id            NOT NULL,
+    ledger             NOT NULL,
+    htlc_state         NOT NULL,
+    htlc_location,
+    deploy_transaction,
+    fund_transaction,
+    redeem_transaction,
+    refund_transaction,
+    secret,
+    UNIQUE (swap_id, ledger)
+);
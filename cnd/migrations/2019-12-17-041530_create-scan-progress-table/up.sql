@@ -0,0 +1,11 @@
+-- Your SQL goes here
+
+CREATE TABLE rfc003_scan_progress
+(
+    id INTEGER   NOT NULL PRIMARY KEY,
+    swap_id      NOT NULL,
+    pattern      NOT NULL,
+    block_hash   NOT NULL,
+    block_height NOT NULL,
+    UNIQUE (swap_id, pattern)
+);
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+
+CREATE TABLE bitcoin_wallet
+(
+    id INTEGER NOT NULL PRIMARY KEY,
+    state      NOT NULL
+);
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+
+CREATE TABLE rfc003_sent_transactions
+(
+    id INTEGER     NOT NULL PRIMARY KEY,
+    swap_id        NOT NULL,
+    action         NOT NULL,
+    transaction_id NOT NULL,
+    UNIQUE (swap_id, action)
+);
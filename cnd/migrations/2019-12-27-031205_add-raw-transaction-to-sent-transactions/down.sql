@@ -0,0 +1,17 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE rfc003_sent_transactions RENAME TO rfc003_sent_transactions_old;
+
+CREATE TABLE rfc003_sent_transactions
+(
+    id INTEGER     NOT NULL PRIMARY KEY,
+    swap_id        NOT NULL,
+    action         NOT NULL,
+    transaction_id NOT NULL,
+    UNIQUE (swap_id, action)
+);
+
+INSERT INTO rfc003_sent_transactions (id, swap_id, action, transaction_id)
+SELECT id, swap_id, action, transaction_id FROM rfc003_sent_transactions_old;
+
+DROP TABLE rfc003_sent_transactions_old;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+
+ALTER TABLE rfc003_sent_transactions ADD COLUMN raw_transaction;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE rfc003_bitcoin_ethereum_bitcoin_erc721_request_messages;
+DROP TABLE rfc003_ethereum_bitcoin_erc721_bitcoin_request_messages;
@@ -0,0 +1,35 @@
+-- Your SQL goes here
+
+CREATE TABLE rfc003_bitcoin_ethereum_bitcoin_erc721_request_messages
+(
+    id INTEGER               NOT NULL PRIMARY KEY,
+    swap_id UNIQUE           NOT NULL,
+    bitcoin_network          NOT NULL,
+    ethereum_chain_id        NOT NULL,
+    bitcoin_amount           NOT NULL,
+    erc721_token_id          NOT NULL,
+    erc721_token_contract    NOT NULL,
+    hash_function            NOT NULL,
+    bitcoin_refund_identity  NOT NULL,
+    ethereum_redeem_identity NOT NULL,
+    bitcoin_expiry           NOT NULL,
+    ethereum_expiry          NOT NULL,
+    secret_hash              NOT NULL
+);
+
+CREATE TABLE rfc003_ethereum_bitcoin_erc721_bitcoin_request_messages
+(
+    id INTEGER               NOT NULL PRIMARY KEY,
+    swap_id UNIQUE           NOT NULL,
+    bitcoin_network          NOT NULL,
+    ethereum_chain_id        NOT NULL,
+    bitcoin_amount           NOT NULL,
+    erc721_token_id          NOT NULL,
+    erc721_token_contract    NOT NULL,
+    hash_function            NOT NULL,
+    bitcoin_redeem_identity  NOT NULL,
+    ethereum_refund_identity NOT NULL,
+    bitcoin_expiry           NOT NULL,
+    ethereum_expiry          NOT NULL,
+    secret_hash              NOT NULL
+);
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE rfc003_ethereum_ethereum_ether_ether_request_messages;
+DROP TABLE rfc003_ethereum_ethereum_ether_erc20_request_messages;
+DROP TABLE rfc003_ethereum_ethereum_erc20_ether_request_messages;
+DROP TABLE rfc003_ethereum_ethereum_erc20_erc20_request_messages;
+DROP TABLE rfc003_ethereum_ethereum_accept_messages;
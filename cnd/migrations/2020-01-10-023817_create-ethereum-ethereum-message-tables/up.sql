@@ -0,0 +1,78 @@
+-- Your SQL goes here
+
+CREATE TABLE rfc003_ethereum_ethereum_ether_ether_request_messages
+(
+    id INTEGER                     NOT NULL PRIMARY KEY,
+    swap_id UNIQUE                 NOT NULL,
+    alpha_ethereum_chain_id        NOT NULL,
+    beta_ethereum_chain_id         NOT NULL,
+    alpha_ether_amount             NOT NULL,
+    beta_ether_amount              NOT NULL,
+    hash_function                  NOT NULL,
+    alpha_ethereum_refund_identity NOT NULL,
+    beta_ethereum_redeem_identity  NOT NULL,
+    alpha_expiry                   NOT NULL,
+    beta_expiry                    NOT NULL,
+    secret_hash                    NOT NULL
+);
+
+CREATE TABLE rfc003_ethereum_ethereum_ether_erc20_request_messages
+(
+    id INTEGER                     NOT NULL PRIMARY KEY,
+    swap_id UNIQUE                 NOT NULL,
+    alpha_ethereum_chain_id        NOT NULL,
+    beta_ethereum_chain_id         NOT NULL,
+    alpha_ether_amount             NOT NULL,
+    beta_erc20_amount              NOT NULL,
+    beta_erc20_token_contract      NOT NULL,
+    hash_function                  NOT NULL,
+    alpha_ethereum_refund_identity NOT NULL,
+    beta_ethereum_redeem_identity  NOT NULL,
+    alpha_expiry                   NOT NULL,
+    beta_expiry                    NOT NULL,
+    secret_hash                    NOT NULL
+);
+
+CREATE TABLE rfc003_ethereum_ethereum_erc20_ether_request_messages
+(
+    id INTEGER                     NOT NULL PRIMARY KEY,
+    swap_id UNIQUE                 NOT NULL,
+    alpha_ethereum_chain_id        NOT NULL,
+    beta_ethereum_chain_id         NOT NULL,
+    alpha_erc20_amount             NOT NULL,
+    alpha_erc20_token_contract     NOT NULL,
+    beta_ether_amount              NOT NULL,
+    hash_function                  NOT NULL,
+    alpha_ethereum_refund_identity NOT NULL,
+    beta_ethereum_redeem_identity  NOT NULL,
+    alpha_expiry                   NOT NULL,
+    beta_expiry                    NOT NULL,
+    secret_hash                    NOT NULL
+);
+
+CREATE TABLE rfc003_ethereum_ethereum_erc20_erc20_request_messages
+(
+    id INTEGER                     NOT NULL PRIMARY KEY,
+    swap_id UNIQUE                 NOT NULL,
+    alpha_ethereum_chain_id        NOT NULL,
+    beta_ethereum_chain_id         NOT NULL,
+    alpha_erc20_amount             NOT NULL,
+    alpha_erc20_token_contract     NOT NULL,
+    beta_erc20_amount              NOT NULL,
+    beta_erc20_token_contract      NOT NULL,
+    hash_function                  NOT NULL,
+    alpha_ethereum_refund_identity NOT NULL,
+    beta_ethereum_redeem_identity  NOT NULL,
+    alpha_expiry                   NOT NULL,
+    beta_expiry                    NOT NULL,
+    secret_hash                    NOT NULL
+);
+
+CREATE TABLE rfc003_ethereum_ethereum_accept_messages
+(
+    id INTEGER                     NOT NULL PRIMARY KEY,
+    swap_id UNIQUE                 NOT NULL,
+    alpha_ethereum_redeem_identity NOT NULL,
+    beta_ethereum_refund_identity  NOT NULL,
+    at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE rfc003_litecoin_ethereum_litecoin_ether_request_messages;
+DROP TABLE rfc003_ethereum_litecoin_ether_litecoin_request_messages;
+DROP TABLE rfc003_litecoin_ethereum_litecoin_erc20_request_messages;
+DROP TABLE rfc003_ethereum_litecoin_erc20_litecoin_request_messages;
+DROP TABLE rfc003_litecoin_ethereum_accept_messages;
+DROP TABLE rfc003_ethereum_litecoin_accept_messages;
@@ -0,0 +1,85 @@
+-- Your SQL goes here
+
+CREATE TABLE rfc003_litecoin_ethereum_litecoin_ether_request_messages
+(
+    id INTEGER               NOT NULL PRIMARY KEY,
+    swap_id UNIQUE           NOT NULL,
+    litecoin_network         NOT NULL,
+    ethereum_chain_id        NOT NULL,
+    litecoin_amount          NOT NULL,
+    ether_amount             NOT NULL,
+    hash_function            NOT NULL,
+    litecoin_refund_identity NOT NULL,
+    ethereum_redeem_identity NOT NULL,
+    litecoin_expiry          NOT NULL,
+    ethereum_expiry          NOT NULL,
+    secret_hash              NOT NULL
+);
+
+CREATE TABLE rfc003_ethereum_litecoin_ether_litecoin_request_messages
+(
+    id INTEGER               NOT NULL PRIMARY KEY,
+    swap_id UNIQUE           NOT NULL,
+    litecoin_network         NOT NULL,
+    ethereum_chain_id        NOT NULL,
+    litecoin_amount          NOT NULL,
+    ether_amount             NOT NULL,
+    hash_function            NOT NULL,
+    litecoin_redeem_identity NOT NULL,
+    ethereum_refund_identity NOT NULL,
+    litecoin_expiry          NOT NULL,
+    ethereum_expiry          NOT NULL,
+    secret_hash              NOT NULL
+);
+
+CREATE TABLE rfc003_litecoin_ethereum_litecoin_erc20_request_messages
+(
+    id INTEGER               NOT NULL PRIMARY KEY,
+    swap_id UNIQUE           NOT NULL,
+    litecoin_network         NOT NULL,
+    ethereum_chain_id        NOT NULL,
+    litecoin_amount          NOT NULL,
+    erc20_amount             NOT NULL,
+    erc20_token_contract     NOT NULL,
+    hash_function            NOT NULL,
+    litecoin_refund_identity NOT NULL,
+    ethereum_redeem_identity NOT NULL,
+    litecoin_expiry          NOT NULL,
+    ethereum_expiry          NOT NULL,
+    secret_hash              NOT NULL
+);
+
+CREATE TABLE rfc003_ethereum_litecoin_erc20_litecoin_request_messages
+(
+    id INTEGER               NOT NULL PRIMARY KEY,
+    swap_id UNIQUE           NOT NULL,
+    litecoin_network         NOT NULL,
+    ethereum_chain_id        NOT NULL,
+    litecoin_amount          NOT NULL,
+    erc20_amount             NOT NULL,
+    erc20_token_contract     NOT NULL,
+    hash_function            NOT NULL,
+    litecoin_redeem_identity NOT NULL,
+    ethereum_refund_identity NOT NULL,
+    litecoin_expiry          NOT NULL,
+    ethereum_expiry          NOT NULL,
+    secret_hash              NOT NULL
+);
+
+CREATE TABLE rfc003_litecoin_ethereum_accept_messages
+(
+    id INTEGER               NOT NULL PRIMARY KEY,
+    swap_id UNIQUE           NOT NULL,
+    litecoin_redeem_identity NOT NULL,
+    ethereum_refund_identity NOT NULL,
+    at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE rfc003_ethereum_litecoin_accept_messages
+(
+    id INTEGER               NOT NULL PRIMARY KEY,
+    swap_id UNIQUE           NOT NULL,
+    litecoin_refund_identity NOT NULL,
+    ethereum_redeem_identity NOT NULL,
+    at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+
+CREATE TABLE rfc003_action_attempts
+(
+    id INTEGER     NOT NULL PRIMARY KEY,
+    swap_id        NOT NULL,
+    action         NOT NULL,
+    attempted_at   NOT NULL,
+    transaction_id,
+    error
+);
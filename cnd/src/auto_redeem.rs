@@ -0,0 +1,311 @@
+//! Redeems the alpha HTLC as Bob as soon as Alice revealed the secret by
+//! redeeming the beta HTLC, for nodes that opted in through the `auto_redeem`
+//! section of the config file.
+
+use crate::{
+    btsieve::{
+        bitcoin::{BitcoinFeeEstimator, BitcoinLedgerConnector},
+        ethereum::EthereumGasEstimator,
+    },
+    config::AutoRedeem,
+    db::{DetermineTypes, LedgerKind, LoadSentTransactions, Retrieve, Save, SentTransaction},
+    http_api::action::{self, ActionExecutionParameters},
+    swap_protocols::{
+        actions::Actions,
+        rfc003::{
+            actions::{Action, ActionKind},
+            state_store::StateStore,
+        },
+        Role,
+    },
+    wallet::{bitcoin::BitcoinWallet, ethereum::EthereumAccount, psbt::BitcoinWatchOnlyWallet},
+};
+use futures_core::compat::Future01CompatExt;
+use std::{
+    ops::Add,
+    time::{Duration, Instant},
+};
+use tokio::timer::Delay;
+
+/// How often we look for swaps in which the secret was revealed. Bob has until
+/// `alpha_expiry` to redeem, which is hours away usually.
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Redeems the alpha HTLC of every swap in which we are Bob once we learned
+/// the secret, forever.
+///
+/// A redeem recorded in the database is not sent again but broadcast again
+/// until it is mined.
+pub async fn redeem_revealed_htlcs<D>(dependencies: D, settings: AutoRedeem)
+where
+    D: StateStore
+        + Retrieve
+        + DetermineTypes
+        + BitcoinWallet
+        + EthereumAccount
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + BitcoinWatchOnlyWallet
+        + EthereumGasEstimator
+        + Save<SentTransaction>
+        + LoadSentTransactions,
+{
+    loop {
+        if let Err(e) = check_swaps(&dependencies, &settings).await {
+            log::warn!("Could not look for revealed secrets: {:?}", e);
+        }
+
+        delay(CHECK_INTERVAL).await;
+    }
+}
+
+#[allow(clippy::cognitive_complexity)]
+async fn check_swaps<D>(dependencies: &D, settings: &AutoRedeem) -> anyhow::Result<()>
+where
+    D: StateStore
+        + Retrieve
+        + DetermineTypes
+        + BitcoinWallet
+        + EthereumAccount
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + BitcoinWatchOnlyWallet
+        + EthereumGasEstimator
+        + Save<SentTransaction>
+        + LoadSentTransactions,
+{
+    for swap in Retrieve::all(dependencies).await?.iter() {
+        let swap_id = swap.swap_id;
+        if swap.role != Role::Bob {
+            continue;
+        }
+
+        // A swap we cannot look at must not keep us from redeeming the others.
+        let types = match DetermineTypes::determine_types(dependencies, &swap_id).await {
+            Ok(types) => types,
+            Err(e) => {
+                log::warn!("Could not determine the types of swap {}: {:?}", swap_id, e);
+                continue;
+            }
+        };
+
+        with_swap_types!(types, {
+            let state = match StateStore::get::<ROLE>(dependencies, &swap_id) {
+                Ok(Some(state)) => state,
+                Ok(None) => continue,
+                Err(e) => {
+                    log::warn!("Could not load the state of swap {}: {:?}", swap_id, e);
+                    continue;
+                }
+            };
+
+            // Bob can only redeem once the redeem of the beta HTLC revealed
+            // the secret.
+            let redeem = match state.actions().into_iter().find_map(|action| match action {
+                Action::Redeem(redeem) => Some(redeem),
+                _ => None,
+            }) {
+                Some(redeem) => redeem,
+                None => continue,
+            };
+
+            // The redeem action is available until our redeem is mined, it
+            // might have been dropped by the node in the meantime.
+            let sent_transactions =
+                match LoadSentTransactions::load_sent_transactions(dependencies, &swap_id).await {
+                    Ok(sent_transactions) => sent_transactions,
+                    Err(e) => {
+                        log::warn!(
+                            "Could not load the transactions sent for swap {}: {:?}",
+                            swap_id,
+                            e
+                        );
+                        continue;
+                    }
+                };
+            if let Some(sent_transaction) = sent_transactions
+                .iter()
+                .find(|sent_transaction| sent_transaction.action == ActionKind::Redeem)
+            {
+                let ledger = state.request().alpha_ledger.into();
+                if let Err(e) = action::rebroadcast(dependencies, ledger, sent_transaction).await {
+                    // Usually because the node still knows the transaction.
+                    log::debug!(
+                        "Could not broadcast redeem {} of swap {} again: {:?}",
+                        sent_transaction.transaction_id,
+                        swap_id,
+                        e
+                    );
+                }
+                continue;
+            }
+
+            let parameters = match types.alpha_ledger {
+                LedgerKind::Bitcoin => match &settings.bitcoin_payout_address {
+                    Some(address) => ActionExecutionParameters::AddressAndFee {
+                        address: address.to_string(),
+                        fee_per_wu: Some(settings.bitcoin_fee_per_wu.to_string()),
+                    },
+                    None => ActionExecutionParameters::BitcoinFee {
+                        fee_per_wu: settings.bitcoin_fee_per_wu.to_string(),
+                    },
+                },
+                // The HTLC pays out to the redeem identity of the swap.
+                LedgerKind::Ethereum => ActionExecutionParameters::EthereumGasPrice {
+                    gas_price: settings.ethereum_gas_price.to_string(),
+                },
+                // cnd cannot broadcast Litecoin transactions.
+                LedgerKind::Litecoin => continue,
+            };
+
+            log::info!(
+                "Redeeming the HTLC of swap {} with the revealed secret",
+                swap_id
+            );
+            match action::execute_action(
+                dependencies,
+                swap_id,
+                ActionKind::Redeem,
+                redeem,
+                parameters,
+            )
+            .await
+            {
+                Ok((_, transaction_id)) => {
+                    log::info!(
+                        "Redeemed the HTLC of swap {} in transaction {}",
+                        swap_id,
+                        transaction_id
+                    );
+                }
+                Err(e) => log::warn!("Could not redeem the HTLC of swap {}: {:?}", swap_id, e),
+            }
+        });
+    }
+
+    Ok(())
+}
+
+async fn delay(duration: Duration) {
+    Delay::new(Instant::now().add(duration))
+        .compat()
+        .await
+        .unwrap_or_else(|e| log::warn!("Failed to wait for delay: {:?}", e));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        swap_fixture::{serve_esplora, Dependencies},
+        swap_protocols::SwapId,
+    };
+    use bitcoin::{OutPoint, Transaction, TxIn, TxOut};
+    use futures_core::{FutureExt, TryFutureExt};
+    use std::sync::{Arc, Mutex};
+
+    fn check_swaps_twice(runtime: &mut tokio::runtime::Runtime, dependencies: Arc<Dependencies>) {
+        let settings = AutoRedeem {
+            bitcoin_fee_per_wu: 10,
+            bitcoin_payout_address: None,
+            ethereum_gas_price: 1,
+        };
+
+        runtime
+            .block_on(
+                async move {
+                    check_swaps(&*dependencies, &settings).await?;
+                    check_swaps(&*dependencies, &settings).await
+                }
+                .boxed()
+                .compat(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn given_revealed_secret_redeem_is_sent_once_and_broadcast_again_while_unmined() {
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let broadcast = Arc::new(Mutex::new(Vec::new()));
+        let dependencies = Arc::new(Dependencies::new(
+            &runtime,
+            serve_esplora(broadcast.clone()),
+            Role::Bob,
+        ));
+
+        check_swaps_twice(&mut runtime, dependencies.clone());
+
+        let sent_transactions = dependencies.sent_transactions.lock().unwrap().clone();
+        assert_eq!(sent_transactions.len(), 1);
+        assert_eq!(sent_transactions[0].action, ActionKind::Redeem);
+
+        // The second round sent the recorded redeem again.
+        let broadcast = broadcast.lock().unwrap().clone();
+        assert_eq!(broadcast.len(), 2);
+        assert_eq!(broadcast[0], broadcast[1]);
+        assert_eq!(
+            broadcast[0].txid().to_string(),
+            sent_transactions[0].transaction_id
+        );
+    }
+
+    #[test]
+    fn given_redeem_was_recorded_before_a_restart_it_is_not_sent_again() {
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let broadcast = Arc::new(Mutex::new(Vec::new()));
+        let dependencies = Dependencies::new(&runtime, serve_esplora(broadcast.clone()), Role::Bob);
+        let redeem = Transaction {
+            version: 2,
+            lock_time: 0,
+            input: vec![TxIn {
+                previous_output: OutPoint::null(),
+                script_sig: Default::default(),
+                sequence: 0xFFFF_FFFD,
+                witness: Vec::new(),
+            }],
+            output: vec![TxOut {
+                value: 99_990_000,
+                script_pubkey: dependencies
+                    .bitcoin_wallet
+                    .receive_address()
+                    .script_pubkey(),
+            }],
+        };
+        let recorded = SentTransaction {
+            swap_id: dependencies.swap_id,
+            action: ActionKind::Redeem,
+            transaction_id: redeem.txid().to_string(),
+            raw_transaction: Some(bitcoin::consensus::encode::serialize_hex(&redeem)),
+        };
+        dependencies
+            .sent_transactions
+            .lock()
+            .unwrap()
+            .push(recorded.clone());
+        let dependencies = Arc::new(dependencies);
+
+        check_swaps_twice(&mut runtime, dependencies.clone());
+
+        assert_eq!(*dependencies.sent_transactions.lock().unwrap(), vec![
+            recorded
+        ]);
+        assert_eq!(*broadcast.lock().unwrap(), vec![redeem.clone(), redeem]);
+    }
+
+    #[test]
+    fn given_swap_with_unknown_types_the_other_swaps_are_still_redeemed() {
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let broadcast = Arc::new(Mutex::new(Vec::new()));
+        let mut dependencies =
+            Dependencies::new(&runtime, serve_esplora(broadcast.clone()), Role::Bob);
+        dependencies.unknown_swaps.push(SwapId::default());
+        let dependencies = Arc::new(dependencies);
+
+        check_swaps_twice(&mut runtime, dependencies.clone());
+
+        let sent_transactions = dependencies.sent_transactions.lock().unwrap().clone();
+        assert_eq!(sent_transactions.len(), 1);
+        assert_eq!(sent_transactions[0].swap_id, dependencies.swap_id);
+        assert_eq!(broadcast.lock().unwrap().len(), 2);
+    }
+}
@@ -2,7 +2,17 @@
 #![forbid(unsafe_code)]
 
 pub mod bitcoin;
+mod block_poller;
+mod confirmations;
 pub mod ethereum;
+pub mod litecoin;
+mod scan_progress;
+
+pub use self::{
+    block_poller::{BlockPoller, Subscription, WakeUp},
+    confirmations::Confirmations,
+    scan_progress::{Checkpoint, ScanProgress},
+};
 
 use tokio::prelude::{Future, Stream};
 
@@ -16,6 +26,39 @@ pub trait MatchingTransactions<P>: Send + Sync + 'static {
     ) -> Box<dyn Stream<Item = Self::Transaction, Error = ()> + Send>;
 }
 
+/// Hands out the new blocks of a ledger as they are found instead of each
+/// caller polling the node for them.
+pub trait SubscribeBlocks: Send + Sync + 'static {
+    type Block;
+
+    fn subscribe(&self) -> Subscription<Self::Block>;
+}
+
+/// The block in which a transaction was found.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IncludingBlock<H> {
+    pub block_hash: H,
+    pub block_height: u64,
+}
+
+/// Remembers in which block a transaction matching a pattern was found, so
+/// that following the transaction afterwards can start from that block.
+pub trait IncludingBlocks: Send + Sync + 'static {
+    type TransactionHash;
+    type BlockHash;
+
+    fn remember_including_block(
+        &self,
+        transaction_hash: Self::TransactionHash,
+        block: IncludingBlock<Self::BlockHash>,
+    );
+    fn including_block(
+        &self,
+        transaction_hash: &Self::TransactionHash,
+    ) -> Option<IncludingBlock<Self::BlockHash>>;
+    fn forget_including_block(&self, transaction_hash: &Self::TransactionHash);
+}
+
 pub trait LatestBlock: Send + Sync + 'static {
     type Error: std::fmt::Debug;
     type Block;
@@ -47,3 +90,14 @@ pub trait ReceiptByHash: Send + Sync + 'static {
         transaction_hash: Self::TransactionHash,
     ) -> Box<dyn Future<Item = Self::Receipt, Error = Self::Error> + Send + 'static>;
 }
+
+pub trait LogsByFilter: Send + Sync + 'static {
+    type Filter;
+    type Log;
+    type Error: std::fmt::Debug;
+
+    fn logs_by_filter(
+        &self,
+        filter: Self::Filter,
+    ) -> Box<dyn Future<Item = Vec<Self::Log>, Error = Self::Error> + Send + 'static>;
+}
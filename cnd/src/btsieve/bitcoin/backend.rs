@@ -0,0 +1,271 @@
+use crate::btsieve::{
+    bitcoin::{
+        electrum_connector, follow_confirmations, matching_transaction_with_progress, median,
+        median_time_past, transaction_confirmed, transaction_retracted, BitcoindConnector,
+        BitcoindRpcConnector, ElectrumConnector, Error, EsploraConnector, FeeEstimate,
+        TransactionPattern, MEDIAN_TIME_SPAN,
+    },
+    BlockByHash, BlockPoller, Confirmations, IncludingBlocks, LatestBlock, ScanProgress,
+};
+use bitcoin::{hashes::sha256d, OutPoint, Script, Transaction, TxOut};
+use futures_core::compat::Future01CompatExt;
+use tokio::prelude::Future;
+
+/// The source of Bitcoin data selected through `backend` in the `bitcoin`
+/// section of the config file.
+#[derive(Clone, Debug)]
+pub enum BitcoinConnector {
+    /// A backend that hands out whole blocks, the blockchain is walked to find
+    /// and follow HTLC transactions.
+    Blocks(BlockPoller<BlockSource>),
+    /// An Electrum server, HTLC transactions are looked up in the history of
+    /// the HTLC script.
+    Electrum(ElectrumConnector),
+}
+
+#[derive(Clone, Debug)]
+pub enum BlockSource {
+    Bitcoind(BitcoindConnector),
+    BitcoindRpc(BitcoindRpcConnector),
+    Esplora(EsploraConnector),
+}
+
+/// Gives access to the Bitcoin connector of cnd, e.g. to broadcast
+/// transactions.
+pub trait BitcoinLedgerConnector: Send + Sync + 'static {
+    fn bitcoin_connector(&self) -> &BitcoinConnector;
+}
+
+/// Estimates the fee of the Bitcoin transactions cnd signs if the client did
+/// not choose one, aiming at the `confirmation_target` from the `bitcoin`
+/// section of the config file.
+#[derive(Clone, Debug)]
+pub struct FeeEstimator {
+    connector: BitcoinConnector,
+    confirmation_target: u32,
+}
+
+/// Gives access to the fee estimator of cnd.
+pub trait BitcoinFeeEstimator: Send + Sync + 'static {
+    fn bitcoin_fee_estimator(&self) -> &FeeEstimator;
+}
+
+impl FeeEstimator {
+    pub fn new(connector: BitcoinConnector, confirmation_target: u32) -> Self {
+        Self {
+            connector,
+            confirmation_target,
+        }
+    }
+
+    pub async fn estimate_fee(&self) -> Result<FeeEstimate, Error> {
+        self.connector.estimate_fee(self.confirmation_target).await
+    }
+}
+
+impl BitcoinConnector {
+    /// Resolves with the first transaction matching `pattern`.
+    ///
+    /// `script` is paid to or spent from by the transaction we are looking
+    /// for, Electrum servers only find transactions by script.
+    pub async fn matching_transaction<P>(
+        self,
+        pattern: TransactionPattern,
+        script: Script,
+        scan_progress: P,
+    ) -> Result<Transaction, ()>
+    where
+        P: ScanProgress<sha256d::Hash>,
+    {
+        match self {
+            BitcoinConnector::Blocks(connector) => {
+                matching_transaction_with_progress(connector, pattern, None, scan_progress).await
+            }
+            BitcoinConnector::Electrum(connector) => {
+                electrum_connector::matching_transaction(connector, script, pattern).await
+            }
+        }
+    }
+
+    /// See `bitcoin::transaction_confirmed`.
+    pub async fn transaction_confirmed(
+        self,
+        transaction: Transaction,
+        confirmations: Confirmations<sha256d::Hash>,
+    ) {
+        match self {
+            BitcoinConnector::Blocks(connector) => {
+                transaction_confirmed(connector, transaction.txid(), confirmations).await
+            }
+            BitcoinConnector::Electrum(connector) => {
+                electrum_connector::transaction_confirmed(connector, transaction, confirmations)
+                    .await
+            }
+        }
+    }
+
+    /// See `bitcoin::follow_confirmations`.
+    pub async fn follow_confirmations(
+        self,
+        transaction: Transaction,
+        confirmations: Confirmations<sha256d::Hash>,
+    ) {
+        match self {
+            BitcoinConnector::Blocks(connector) => {
+                follow_confirmations(connector, transaction.txid(), confirmations).await
+            }
+            BitcoinConnector::Electrum(connector) => {
+                electrum_connector::follow_confirmations(connector, transaction, confirmations)
+                    .await
+            }
+        }
+    }
+
+    /// Forgets in which block `transaction` was found once nobody follows it
+    /// anymore.
+    pub fn forget_transaction(&self, transaction: &Transaction) {
+        if let BitcoinConnector::Blocks(connector) = self {
+            connector.forget_including_block(&transaction.txid());
+        }
+    }
+
+    /// Hands `transaction` to the Bitcoin network, bitcoind can only do this
+    /// through its JSON-RPC interface.
+    pub async fn send_raw_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<sha256d::Hash, Error> {
+        let future = match self {
+            BitcoinConnector::Blocks(connector) => match connector.connector() {
+                BlockSource::Bitcoind(_) => {
+                    return Err(Error::BroadcastUnsupported(
+                        "the REST interface of bitcoind",
+                    ))
+                }
+                BlockSource::BitcoindRpc(connector) => connector.send_raw_transaction(transaction),
+                BlockSource::Esplora(connector) => connector.send_raw_transaction(transaction),
+            },
+            BitcoinConnector::Electrum(connector) => connector.send_raw_transaction(transaction),
+        };
+
+        future.compat().await
+    }
+
+    /// The fee rate that gets a transaction included within
+    /// `confirmation_target` blocks, bitcoind can only estimate it through its
+    /// JSON-RPC interface.
+    pub async fn estimate_fee(&self, confirmation_target: u32) -> Result<FeeEstimate, Error> {
+        let future = match self {
+            BitcoinConnector::Blocks(connector) => match connector.connector() {
+                BlockSource::Bitcoind(_) => {
+                    return Err(Error::FeeEstimationUnsupported(
+                        "the REST interface of bitcoind",
+                    ))
+                }
+                BlockSource::BitcoindRpc(connector) => {
+                    connector.estimate_smart_fee(confirmation_target)
+                }
+                BlockSource::Esplora(connector) => connector.estimate_fee(confirmation_target),
+            },
+            BitcoinConnector::Electrum(connector) => connector.estimate_fee(confirmation_target),
+        };
+
+        future.compat().await
+    }
+
+    /// The unspent outputs of the output descriptor `descriptor`, only the
+    /// JSON-RPC interface of bitcoind can scan for them.
+    pub async fn scan_descriptor(&self, descriptor: &str) -> Result<Vec<(OutPoint, TxOut)>, Error> {
+        let future = match self {
+            BitcoinConnector::Blocks(connector) => match connector.connector() {
+                BlockSource::Bitcoind(_) => {
+                    return Err(Error::DescriptorScanUnsupported(
+                        "the REST interface of bitcoind",
+                    ))
+                }
+                BlockSource::BitcoindRpc(connector) => connector.scan_tx_out_set(descriptor),
+                BlockSource::Esplora(_) => return Err(Error::DescriptorScanUnsupported("Esplora")),
+            },
+            BitcoinConnector::Electrum(_) => {
+                return Err(Error::DescriptorScanUnsupported("Electrum"))
+            }
+        };
+
+        future.compat().await
+    }
+
+    /// Looks up the transaction `txid`, e.g. the one that created an output a
+    /// PSBT spends.
+    pub async fn transaction(&self, txid: sha256d::Hash) -> Result<Transaction, Error> {
+        let future = match self {
+            BitcoinConnector::Blocks(connector) => match connector.connector() {
+                BlockSource::Bitcoind(connector) => connector.transaction(txid),
+                BlockSource::BitcoindRpc(connector) => connector.transaction(txid),
+                BlockSource::Esplora(connector) => connector.transaction(txid),
+            },
+            BitcoinConnector::Electrum(connector) => connector.transaction(txid),
+        };
+
+        future.compat().await
+    }
+
+    /// See `bitcoin::median_time_past`.
+    pub async fn median_time_past(&self) -> Result<u32, Error> {
+        match self {
+            BitcoinConnector::Blocks(connector) => median_time_past(connector.clone()).await,
+            BitcoinConnector::Electrum(connector) => {
+                let times = connector
+                    .block_times(MEDIAN_TIME_SPAN as u32)
+                    .compat()
+                    .await?;
+
+                Ok(median(times))
+            }
+        }
+    }
+
+    /// See `bitcoin::transaction_retracted`.
+    pub async fn transaction_retracted(self, transaction: Transaction) {
+        match self {
+            BitcoinConnector::Blocks(connector) => {
+                transaction_retracted(connector, transaction.txid()).await
+            }
+            BitcoinConnector::Electrum(connector) => {
+                electrum_connector::transaction_retracted(connector, transaction).await
+            }
+        }
+    }
+}
+
+impl LatestBlock for BlockSource {
+    type Error = Error;
+    type Block = bitcoin::Block;
+    type BlockHash = sha256d::Hash;
+
+    fn latest_block(
+        &mut self,
+    ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+        match self {
+            BlockSource::Bitcoind(connector) => connector.latest_block(),
+            BlockSource::BitcoindRpc(connector) => connector.latest_block(),
+            BlockSource::Esplora(connector) => connector.latest_block(),
+        }
+    }
+}
+
+impl BlockByHash for BlockSource {
+    type Error = Error;
+    type Block = bitcoin::Block;
+    type BlockHash = sha256d::Hash;
+
+    fn block_by_hash(
+        &self,
+        block_hash: Self::BlockHash,
+    ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+        match self {
+            BlockSource::Bitcoind(connector) => connector.block_by_hash(block_hash),
+            BlockSource::BitcoindRpc(connector) => connector.block_by_hash(block_hash),
+            BlockSource::Esplora(connector) => connector.block_by_hash(block_hash),
+        }
+    }
+}
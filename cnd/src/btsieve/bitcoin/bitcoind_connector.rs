@@ -1,10 +1,15 @@
 use crate::btsieve::{
-    bitcoin::bitcoin_http_request_for_hex_encoded_object, BlockByHash, LatestBlock,
+    bitcoin::{bitcoin_http_request_for_hex_encoded_object, ZmqBlockNotifications},
+    BlockByHash, LatestBlock,
 };
-use bitcoin::{hashes::sha256d, Network};
+use bitcoin::{hashes::sha256d, BitcoinHash, Network, Transaction};
 use reqwest::{r#async::Client, Url};
 use serde::Deserialize;
-use tokio::prelude::Future;
+use std::sync::{Arc, Mutex};
+use tokio::prelude::{
+    future::{self, Either},
+    Future,
+};
 
 #[derive(Deserialize)]
 struct ChainInfo {
@@ -15,7 +20,12 @@ struct ChainInfo {
 pub struct BitcoindConnector {
     chaininfo_url: Url,
     raw_block_by_hash_url: Url,
+    raw_transaction_url: Url,
     client: Client,
+    block_notifications: Option<ZmqBlockNotifications>,
+    /// The block we last fetched as the latest one, it is not downloaded again
+    /// while it stays the tip of the chain.
+    latest_block: Arc<Mutex<Option<bitcoin::Block>>>,
 }
 
 impl BitcoindConnector {
@@ -23,10 +33,43 @@ impl BitcoindConnector {
         Ok(Self {
             chaininfo_url: base_url.join("rest/chaininfo.json")?,
             raw_block_by_hash_url: base_url.join("rest/block/")?,
+            raw_transaction_url: base_url.join("rest/tx/")?,
             client: Client::new(),
+            block_notifications: None,
+            latest_block: Arc::new(Mutex::new(None)),
         })
     }
 
+    /// Take the latest block from the blocks bitcoind publishes over ZeroMQ
+    /// instead of asking the REST interface every time.
+    ///
+    /// The REST interface is only asked while we are not connected to the
+    /// publisher or have not been notified of a block for a while.
+    pub fn with_block_notifications(self, block_notifications: ZmqBlockNotifications) -> Self {
+        Self {
+            block_notifications: Some(block_notifications),
+            ..self
+        }
+    }
+
+    /// Looks up the transaction `txid`, bitcoind only knows confirmed
+    /// transactions that do not belong to its wallet with `-txindex`.
+    pub fn transaction(
+        &self,
+        txid: sha256d::Hash,
+    ) -> Box<dyn Future<Item = Transaction, Error = crate::btsieve::bitcoin::Error> + Send + 'static>
+    {
+        let url = self
+            .raw_transaction_url
+            .join(&format!("{}.hex", txid))
+            .expect("building url should work");
+
+        Box::new(bitcoin_http_request_for_hex_encoded_object(
+            url,
+            self.client.clone(),
+        ))
+    }
+
     fn raw_block_by_hash_url(&self, block_hash: &sha256d::Hash) -> Url {
         self.raw_block_by_hash_url
             .join(&format!("{}.hex", block_hash))
@@ -42,6 +85,14 @@ impl LatestBlock for BitcoindConnector {
     fn latest_block(
         &mut self,
     ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+        if let Some(block) = self
+            .block_notifications
+            .as_ref()
+            .and_then(ZmqBlockNotifications::latest_block)
+        {
+            return Box::new(future::ok(block));
+        }
+
         let latest_block_hash = self
             .client
             .get(self.chaininfo_url.clone())
@@ -60,10 +111,29 @@ impl LatestBlock for BitcoindConnector {
 
         let cloned_self = self.clone();
 
-        Box::new(
-            latest_block_hash
-                .and_then(move |latest_block_hash| cloned_self.block_by_hash(latest_block_hash)),
-        )
+        Box::new(latest_block_hash.and_then(move |latest_block_hash| {
+            let known_block = cloned_self
+                .latest_block
+                .lock()
+                .unwrap()
+                .clone()
+                .filter(|block| block.bitcoin_hash() == latest_block_hash);
+
+            match known_block {
+                Some(block) => Either::A(future::ok(block)),
+                None => {
+                    let cache = cloned_self.latest_block.clone();
+
+                    Either::B(
+                        cloned_self
+                            .block_by_hash(latest_block_hash)
+                            .inspect(move |block| {
+                                *cache.lock().unwrap() = Some(block.clone());
+                            }),
+                    )
+                }
+            }
+        }))
     }
 }
 
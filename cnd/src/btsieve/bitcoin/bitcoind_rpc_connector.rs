@@ -0,0 +1,374 @@
+use crate::btsieve::{
+    bitcoin::{
+        decode_response, fee_per_wu_from_btc_per_kvbyte, Error, FeeEstimate, ZmqBlockNotifications,
+    },
+    BlockByHash, LatestBlock,
+};
+use bitcoin::{
+    consensus::encode::serialize_hex, hashes::sha256d, Amount, BitcoinHash, Network, OutPoint,
+    Script, Transaction, TxOut,
+};
+use reqwest::{r#async::Client, StatusCode, Url};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use tokio::prelude::{future, Future};
+
+/// How we authenticate against the JSON-RPC interface of bitcoind.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RpcAuth {
+    /// The `rpcuser` and `rpcpassword` from `bitcoin.conf`.
+    UserPassword { username: String, password: String },
+    /// The `.cookie` file bitcoind writes to its data directory on startup.
+    /// It is read again for every request because bitcoind generates a new
+    /// one each time it starts.
+    CookieFile(PathBuf),
+}
+
+impl RpcAuth {
+    fn credentials(&self) -> Result<(String, String), Error> {
+        match self {
+            RpcAuth::UserPassword { username, password } => {
+                Ok((username.clone(), password.clone()))
+            }
+            RpcAuth::CookieFile(path) => {
+                let cookie = std::fs::read_to_string(path)?;
+
+                parse_cookie(&cookie)
+                    .ok_or_else(|| Error::Rpc(format!("malformed cookie file {}", path.display())))
+            }
+        }
+    }
+}
+
+fn parse_cookie(cookie: &str) -> Option<(String, String)> {
+    let mut parts = cookie.trim().splitn(2, ':');
+    let username = parts.next()?;
+    let password = parts.next()?;
+
+    Some((username.to_owned(), password.to_owned()))
+}
+
+/// The answer to `estimatesmartfee`, `feerate` is in BTC/kvB and missing if
+/// bitcoind has not seen enough blocks yet.
+#[derive(Debug, Deserialize)]
+struct SmartFee {
+    #[serde(default)]
+    feerate: Option<f64>,
+    blocks: u32,
+}
+
+/// The answer to `scantxoutset`, `amount` is in BTC.
+#[derive(Debug, Deserialize)]
+struct TxOutSet {
+    unspents: Vec<Unspent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Unspent {
+    txid: sha256d::Hash,
+    vout: u32,
+    #[serde(rename = "scriptPubKey")]
+    script_pubkey: String,
+    amount: f64,
+}
+
+impl Unspent {
+    fn into_output(self) -> Result<(OutPoint, TxOut), Error> {
+        let script_pubkey = Script::from(hex::decode(&self.script_pubkey)?);
+        let value = Amount::from_btc(self.amount)
+            .map_err(|e| Error::Rpc(format!("invalid amount {}: {}", self.amount, e)))?;
+
+        Ok((
+            OutPoint {
+                txid: self.txid,
+                vout: self.vout,
+            },
+            TxOut {
+                value: value.as_sat(),
+                script_pubkey,
+            },
+        ))
+    }
+}
+
+#[derive(Deserialize)]
+struct Response {
+    #[serde(default)]
+    result: Value,
+    #[serde(default)]
+    error: Option<Value>,
+}
+
+/// Talks to bitcoind through its authenticated JSON-RPC interface instead of
+/// the REST interface, which has to be exposed without authentication.
+#[derive(Clone, Debug)]
+pub struct BitcoindRpcConnector {
+    node_url: Url,
+    auth: RpcAuth,
+    client: Client,
+    block_notifications: Option<ZmqBlockNotifications>,
+    /// The block we last fetched as the latest one, it is not downloaded again
+    /// while it stays the tip of the chain.
+    latest_block: Arc<Mutex<Option<bitcoin::Block>>>,
+}
+
+impl BitcoindRpcConnector {
+    pub fn new(node_url: Url, _network: Network, auth: RpcAuth) -> Self {
+        Self {
+            node_url,
+            auth,
+            client: Client::new(),
+            block_notifications: None,
+            latest_block: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// See `BitcoindConnector::with_block_notifications`.
+    pub fn with_block_notifications(self, block_notifications: ZmqBlockNotifications) -> Self {
+        Self {
+            block_notifications: Some(block_notifications),
+            ..self
+        }
+    }
+
+    /// Hands `transaction` to bitcoind through `sendrawtransaction`.
+    pub fn send_raw_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Box<dyn Future<Item = sha256d::Hash, Error = Error> + Send + 'static> {
+        Box::new(
+            self.call("sendrawtransaction", vec![json!(serialize_hex(
+                transaction
+            ))])
+            .and_then(|result| {
+                serde_json::from_value::<sha256d::Hash>(result).map_err(Error::Json)
+            }),
+        )
+    }
+
+    /// Looks up the transaction `txid` through `getrawtransaction`, bitcoind
+    /// only knows confirmed transactions that do not belong to its wallet with
+    /// `-txindex`.
+    pub fn transaction(
+        &self,
+        txid: sha256d::Hash,
+    ) -> Box<dyn Future<Item = Transaction, Error = Error> + Send + 'static> {
+        // Without verbosity the transaction is returned hex encoded.
+        let params = vec![json!(txid.to_string()), json!(false)];
+
+        Box::new(
+            self.call("getrawtransaction", params)
+                .and_then(|result| match result {
+                    Value::String(hex) => decode_response(hex),
+                    result => Err(Error::Rpc(format!(
+                        "expected hex encoded transaction, got {}",
+                        result
+                    ))),
+                }),
+        )
+    }
+
+    /// Asks bitcoind through `estimatesmartfee` for the fee rate that gets a
+    /// transaction included within `confirmation_target` blocks.
+    pub fn estimate_smart_fee(
+        &self,
+        confirmation_target: u32,
+    ) -> Box<dyn Future<Item = FeeEstimate, Error = Error> + Send + 'static> {
+        Box::new(
+            self.call("estimatesmartfee", vec![json!(confirmation_target)])
+                .and_then(|result| serde_json::from_value::<SmartFee>(result).map_err(Error::Json))
+                .and_then(move |smart_fee| match smart_fee.feerate {
+                    Some(feerate) => Ok(FeeEstimate {
+                        fee_per_wu: fee_per_wu_from_btc_per_kvbyte(feerate),
+                        confirmation_target: smart_fee.blocks,
+                    }),
+                    None => Err(Error::NoFeeEstimate(confirmation_target)),
+                }),
+        )
+    }
+
+    /// Asks bitcoind through `scantxoutset` for the unspent outputs of
+    /// `descriptor`, without importing it into a wallet.
+    pub fn scan_tx_out_set(
+        &self,
+        descriptor: &str,
+    ) -> Box<dyn Future<Item = Vec<(OutPoint, TxOut)>, Error = Error> + Send + 'static> {
+        Box::new(
+            self.call("scantxoutset", vec![json!("start"), json!([descriptor])])
+                .and_then(|result| serde_json::from_value::<TxOutSet>(result).map_err(Error::Json))
+                .and_then(|tx_out_set| {
+                    tx_out_set
+                        .unspents
+                        .into_iter()
+                        .map(Unspent::into_output)
+                        .collect()
+                }),
+        )
+    }
+
+    fn call(
+        &self,
+        method: &str,
+        params: Vec<Value>,
+    ) -> Box<dyn Future<Item = Value, Error = Error> + Send + 'static> {
+        let (username, password) = match self.auth.credentials() {
+            Ok(credentials) => credentials,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let request = json!({
+            "jsonrpc": "1.0",
+            "id": "cnd",
+            "method": method,
+            "params": params,
+        });
+
+        Box::new(
+            self.client
+                .post(self.node_url.clone())
+                .basic_auth(username, Some(password))
+                .json(&request)
+                .send()
+                .map_err(Error::Reqwest)
+                .and_then(|mut response| {
+                    let status = response.status();
+
+                    // bitcoind answers failed calls with an error status but
+                    // still puts the error into the body.
+                    let response = match status {
+                        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                            return future::Either::A(future::err(Error::Rpc(format!(
+                                "bitcoind rejected our credentials: {}",
+                                status
+                            ))))
+                        }
+                        _ => response.json::<Response>().map_err(Error::Reqwest),
+                    };
+
+                    future::Either::B(response.and_then(|response| match response.error {
+                        Some(error) if !error.is_null() => Err(Error::Rpc(error.to_string())),
+                        _ => Ok(response.result),
+                    }))
+                }),
+        )
+    }
+}
+
+impl LatestBlock for BitcoindRpcConnector {
+    type Error = Error;
+    type Block = bitcoin::Block;
+    type BlockHash = sha256d::Hash;
+
+    fn latest_block(
+        &mut self,
+    ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+        if let Some(block) = self
+            .block_notifications
+            .as_ref()
+            .and_then(ZmqBlockNotifications::latest_block)
+        {
+            return Box::new(future::ok(block));
+        }
+
+        let latest_block_hash = self.call("getbestblockhash", vec![]).and_then(|result| {
+            serde_json::from_value::<sha256d::Hash>(result).map_err(Error::Json)
+        });
+
+        let cloned_self = self.clone();
+
+        Box::new(latest_block_hash.and_then(move |latest_block_hash| {
+            let known_block = cloned_self
+                .latest_block
+                .lock()
+                .unwrap()
+                .clone()
+                .filter(|block| block.bitcoin_hash() == latest_block_hash);
+
+            match known_block {
+                Some(block) => future::Either::A(future::ok(block)),
+                None => {
+                    let cache = cloned_self.latest_block.clone();
+
+                    future::Either::B(cloned_self.block_by_hash(latest_block_hash).inspect(
+                        move |block| {
+                            *cache.lock().unwrap() = Some(block.clone());
+                        },
+                    ))
+                }
+            }
+        }))
+    }
+}
+
+impl BlockByHash for BitcoindRpcConnector {
+    type Error = Error;
+    type Block = bitcoin::Block;
+    type BlockHash = sha256d::Hash;
+
+    fn block_by_hash(
+        &self,
+        block_hash: Self::BlockHash,
+    ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+        // Verbosity 0 returns the serialized block as hex.
+        let block = self
+            .call("getblock", vec![json!(block_hash.to_string()), json!(0)])
+            .and_then(|result| match result {
+                Value::String(hex) => decode_response(hex),
+                result => Err(Error::Rpc(format!(
+                    "expected hex encoded block, got {}",
+                    result
+                ))),
+            });
+
+        Box::new(block.inspect(|block| {
+            log::trace!("Fetched block from bitcoind: {:?}", block);
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cookie_is_split_at_the_first_colon() {
+        assert_eq!(
+            parse_cookie("__cookie__:a:b\n"),
+            Some(("__cookie__".to_owned(), "a:b".to_owned()))
+        );
+        assert_eq!(parse_cookie("no separator"), None);
+    }
+
+    #[test]
+    fn credentials_are_read_from_cookie_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".cookie");
+        std::fs::write(&path, "__cookie__:secret").unwrap();
+
+        let credentials = RpcAuth::CookieFile(path).credentials().unwrap();
+
+        assert_eq!(credentials, ("__cookie__".to_owned(), "secret".to_owned()));
+    }
+
+    #[test]
+    fn unspent_amount_is_converted_to_satoshis() {
+        let unspent = serde_json::from_value::<Unspent>(json!({
+            "txid": "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b",
+            "vout": 1,
+            "scriptPubKey": "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+            "desc": "wpkh([d34db33f/0/1]0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798)#abcdefgh",
+            "amount": 0.0001,
+            "height": 600_000
+        }))
+        .unwrap();
+
+        let (outpoint, output) = unspent.into_output().unwrap();
+
+        assert_eq!(outpoint.vout, 1);
+        assert_eq!(output.value, 10_000);
+        assert!(output.script_pubkey.is_v0_p2wpkh());
+    }
+}
@@ -0,0 +1,407 @@
+//! A client for the JSON-RPC protocol spoken by Electrum servers such as
+//! electrs or ElectrumX.
+//!
+//! Electrum servers do not hand out whole blocks, instead they index
+//! transactions by the scripts they pay to or spend from. Rather than walking
+//! the blockchain, we ask for the history of the HTLC script and look at the
+//! transactions in there.
+
+use crate::btsieve::{
+    bitcoin::{
+        decode_response, delay, fee_per_wu_from_btc_per_kvbyte, Error, FeeEstimate,
+        TransactionPattern, SEARCH_DEPTH,
+    },
+    Confirmations,
+};
+use bitcoin::{
+    consensus::encode::{deserialize, serialize_hex},
+    hashes::{sha256, sha256d, Hash},
+    BlockHeader, Script, Transaction,
+};
+use futures::{Future, Sink, Stream};
+use futures_core::compat::Future01CompatExt;
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    collections::HashSet,
+    net::{SocketAddr, ToSocketAddrs},
+};
+use tokio::{
+    codec::{Framed, LinesCodec},
+    net::TcpStream,
+};
+
+/// The size of a serialized block header.
+const HEADER_SIZE: usize = 80;
+
+#[derive(Clone, Debug)]
+pub struct ElectrumConnector {
+    address: SocketAddr,
+}
+
+#[derive(Debug, Deserialize)]
+struct Response {
+    #[serde(default)]
+    result: Value,
+    #[serde(default)]
+    error: Option<Value>,
+}
+
+/// A transaction in the history of a script, `height` is 0 or negative while
+/// the transaction is unconfirmed.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+pub struct HistoryEntry {
+    pub tx_hash: sha256d::Hash,
+    pub height: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Header {
+    height: u32,
+}
+
+/// The answer to `blockchain.block.headers`, `hex` holds `count` serialized
+/// headers back to back.
+#[derive(Debug, Deserialize)]
+struct Headers {
+    count: usize,
+    hex: String,
+}
+
+impl ElectrumConnector {
+    /// Connects to the Electrum server at `url`, e.g. `tcp://127.0.0.1:50001`.
+    pub fn new(url: &Url) -> anyhow::Result<Self> {
+        if url.scheme() != "tcp" {
+            anyhow::bail!("unsupported Electrum transport {}", url.scheme());
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow::anyhow!("Electrum url {} has no host", url))?;
+        let port = url
+            .port()
+            .ok_or_else(|| anyhow::anyhow!("Electrum url {} has no port", url))?;
+        let address = (host, port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("could not resolve {}", host))?;
+
+        Ok(Self { address })
+    }
+
+    /// The confirmed and unconfirmed transactions paying to or spending from
+    /// `script`.
+    pub fn history(
+        &self,
+        script: &Script,
+    ) -> Box<dyn Future<Item = Vec<HistoryEntry>, Error = Error> + Send + 'static> {
+        Box::new(
+            self.request("blockchain.scripthash.get_history", vec![json!(
+                script_hash(script)
+            )])
+            .and_then(|result| serde_json::from_value(result).map_err(Error::Json)),
+        )
+    }
+
+    pub fn transaction(
+        &self,
+        txid: sha256d::Hash,
+    ) -> Box<dyn Future<Item = Transaction, Error = Error> + Send + 'static> {
+        Box::new(
+            self.request("blockchain.transaction.get", vec![json!(txid.to_string())])
+                .and_then(|result| match result {
+                    Value::String(hex) => decode_response(hex),
+                    result => Err(Error::Electrum(format!(
+                        "expected hex encoded transaction, got {}",
+                        result
+                    ))),
+                }),
+        )
+    }
+
+    /// Broadcasts `transaction` to the Bitcoin network.
+    pub fn send_raw_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Box<dyn Future<Item = sha256d::Hash, Error = Error> + Send + 'static> {
+        Box::new(
+            self.request("blockchain.transaction.broadcast", vec![json!(
+                serialize_hex(transaction)
+            )])
+            .and_then(|result| serde_json::from_value(result).map_err(Error::Json)),
+        )
+    }
+
+    /// Asks the server through `blockchain.estimatefee` for the fee rate that
+    /// gets a transaction included within `confirmation_target` blocks.
+    pub fn estimate_fee(
+        &self,
+        confirmation_target: u32,
+    ) -> Box<dyn Future<Item = FeeEstimate, Error = Error> + Send + 'static> {
+        Box::new(
+            self.request("blockchain.estimatefee", vec![json!(confirmation_target)])
+                .and_then(|result| serde_json::from_value::<f64>(result).map_err(Error::Json))
+                .and_then(move |btc_per_kvbyte| {
+                    // The server answers with -1 if the node it is backed by
+                    // has no estimate.
+                    if btc_per_kvbyte <= 0.0 {
+                        return Err(Error::NoFeeEstimate(confirmation_target));
+                    }
+
+                    Ok(FeeEstimate {
+                        fee_per_wu: fee_per_wu_from_btc_per_kvbyte(btc_per_kvbyte),
+                        confirmation_target,
+                    })
+                }),
+        )
+    }
+
+    /// The height of the tip of the best chain.
+    pub fn tip_height(&self) -> Box<dyn Future<Item = u32, Error = Error> + Send + 'static> {
+        Box::new(
+            self.request("blockchain.headers.subscribe", vec![])
+                .and_then(|result| serde_json::from_value::<Header>(result).map_err(Error::Json))
+                .map(|header| header.height),
+        )
+    }
+
+    /// The times of the `count` blocks up to and including the tip.
+    pub fn block_times(
+        &self,
+        count: u32,
+    ) -> Box<dyn Future<Item = Vec<u32>, Error = Error> + Send + 'static> {
+        let connector = self.clone();
+
+        Box::new(self.tip_height().and_then(move |tip_height| {
+            let start_height = (tip_height + 1).saturating_sub(count);
+
+            connector
+                .request("blockchain.block.headers", vec![
+                    json!(start_height),
+                    json!(count),
+                ])
+                .and_then(|result| serde_json::from_value::<Headers>(result).map_err(Error::Json))
+                .and_then(|headers| {
+                    let bytes = hex::decode(&headers.hex)?;
+
+                    bytes
+                        .chunks(HEADER_SIZE)
+                        .take(headers.count)
+                        .map(|header| {
+                            deserialize::<BlockHeader>(header)
+                                .map(|header| header.time)
+                                .map_err(Error::Deserialization)
+                        })
+                        .collect::<Result<Vec<u32>, Error>>()
+                })
+        }))
+    }
+
+    /// Sends a single request over a fresh connection, we do not stay
+    /// subscribed to anything.
+    fn request(
+        &self,
+        method: &str,
+        params: Vec<Value>,
+    ) -> impl Future<Item = Value, Error = Error> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": method,
+            "params": params,
+        })
+        .to_string();
+
+        TcpStream::connect(&self.address)
+            .and_then(move |stream| Framed::new(stream, LinesCodec::new()).send(request))
+            .and_then(|framed| framed.into_future().map_err(|(e, _)| e))
+            .map_err(Error::Io)
+            .and_then(|(line, _)| {
+                let line = line.ok_or_else(|| {
+                    Error::Electrum("connection closed before a response arrived".to_owned())
+                })?;
+                let response = serde_json::from_str::<Response>(&line).map_err(Error::Json)?;
+
+                match response.error {
+                    Some(error) => Err(Error::Electrum(error.to_string())),
+                    None => Ok(response.result),
+                }
+            })
+    }
+}
+
+/// Electrum identifies scripts by the reversed SHA256 of the script.
+fn script_hash(script: &Script) -> String {
+    let mut hash = sha256::Hash::hash(script.as_bytes()).into_inner();
+    hash.reverse();
+
+    hex::encode(hash)
+}
+
+/// Resolves with the first confirmed transaction in the history of `script`
+/// that matches `pattern`.
+pub async fn matching_transaction(
+    connector: ElectrumConnector,
+    script: Script,
+    pattern: TransactionPattern,
+) -> Result<Transaction, ()> {
+    let mut checked = HashSet::new();
+    let mut connected = false;
+
+    loop {
+        let history = match connector.history(&script).compat().await {
+            Ok(history) => history,
+            Err(e) if !connected => {
+                log::error!("Failed to connect to the Electrum server: {:?}", e);
+                return Err(());
+            }
+            Err(e) => {
+                log::warn!("Could not get history of script {:?}: {:?}", script, e);
+                delay().await;
+                continue;
+            }
+        };
+        connected = true;
+
+        for entry in history {
+            if entry.height <= 0 || checked.contains(&entry.tx_hash) {
+                continue;
+            }
+
+            match connector.transaction(entry.tx_hash).compat().await {
+                Ok(transaction) => {
+                    if pattern.matches(&transaction) {
+                        return Ok(transaction);
+                    }
+                    checked.insert(entry.tx_hash);
+                }
+                Err(e) => log::warn!("Could not get transaction {}: {:?}", entry.tx_hash, e),
+            }
+        }
+
+        delay().await;
+    }
+}
+
+/// Resolves once `transaction` is no longer confirmed, see
+/// `bitcoin::transaction_retracted`.
+pub async fn transaction_retracted(connector: ElectrumConnector, transaction: Transaction) {
+    let txid = transaction.txid();
+
+    loop {
+        match count_confirmations(&connector, &transaction).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                log::info!("Transaction {} is no longer part of the best chain", txid);
+                return;
+            }
+            Err(e) => log::warn!("Could not ask Electrum server for confirmations: {:?}", e),
+        }
+
+        delay().await;
+    }
+}
+
+/// Resolves once `transaction` has at least
+/// `confirmations.min_confirmations()` confirmations, see
+/// `bitcoin::transaction_confirmed`.
+pub async fn transaction_confirmed(
+    connector: ElectrumConnector,
+    transaction: Transaction,
+    confirmations: Confirmations<sha256d::Hash>,
+) {
+    let txid = transaction.txid();
+
+    loop {
+        match count_confirmations(&connector, &transaction).await {
+            Ok(count) => {
+                let count = count.unwrap_or(0);
+                confirmations.insert(txid, count);
+
+                if count >= confirmations.min_confirmations() {
+                    return;
+                }
+            }
+            Err(e) => log::warn!("Could not ask Electrum server for confirmations: {:?}", e),
+        }
+
+        delay().await;
+    }
+}
+
+/// Keeps counting the confirmations of `transaction` after
+/// `transaction_confirmed` resolved, see `bitcoin::follow_confirmations`.
+pub async fn follow_confirmations(
+    connector: ElectrumConnector,
+    transaction: Transaction,
+    confirmations: Confirmations<sha256d::Hash>,
+) {
+    let txid = transaction.txid();
+
+    loop {
+        delay().await;
+
+        let count = match count_confirmations(&connector, &transaction).await {
+            Ok(count) => count.unwrap_or(0),
+            Err(e) => {
+                log::warn!("Could not ask Electrum server for confirmations: {:?}", e);
+                continue;
+            }
+        };
+
+        if !confirmations.update(&txid, count) || count == 0 || count >= SEARCH_DEPTH as u32 {
+            return;
+        }
+    }
+}
+
+/// Every transaction shows up in the history of the scripts it pays to, the
+/// first output is as good as any other to look it up.
+async fn count_confirmations(
+    connector: &ElectrumConnector,
+    transaction: &Transaction,
+) -> Result<Option<u32>, Error> {
+    let txid = transaction.txid();
+    let script = match transaction.output.first() {
+        Some(output) => &output.script_pubkey,
+        None => return Ok(None),
+    };
+
+    let history = connector.history(script).compat().await?;
+    let height = match history
+        .iter()
+        .find(|entry| entry.tx_hash == txid && entry.height > 0)
+    {
+        Some(entry) => entry.height as u32,
+        None => return Ok(None),
+    };
+    let tip_height = connector.tip_height().compat().await?;
+
+    Ok(Some(tip_height.saturating_sub(height) + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn script_hash_is_reversed_sha256_of_script() {
+        // Example from the Electrum protocol documentation for the P2PKH
+        // address 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa.
+        let script: Script = hex::decode("76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac")
+            .unwrap()
+            .into();
+
+        assert_eq!(
+            script_hash(&script),
+            "8b01df4e368ea28f8dc0423bcf7a4923e3a12d307c875e47a0cfbf90b5c39161"
+        );
+    }
+
+    #[test]
+    fn only_tcp_urls_are_supported() {
+        assert!(ElectrumConnector::new(&"ssl://127.0.0.1:50002".parse().unwrap()).is_err());
+        assert!(ElectrumConnector::new(&"tcp://127.0.0.1".parse().unwrap()).is_err());
+        assert!(ElectrumConnector::new(&"tcp://127.0.0.1:50001".parse().unwrap()).is_ok());
+    }
+}
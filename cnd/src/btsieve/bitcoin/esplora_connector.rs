@@ -0,0 +1,267 @@
+use crate::btsieve::{
+    bitcoin::{decode_response, fee_per_wu_from_sat_per_vbyte, FeeEstimate},
+    BlockByHash, LatestBlock,
+};
+use bitcoin::{
+    consensus::encode::{deserialize, serialize_hex},
+    hashes::sha256d,
+    Network, Transaction,
+};
+use futures::Stream;
+use reqwest::{r#async::Client, Url};
+use std::{collections::HashMap, str::FromStr};
+use tokio::prelude::Future;
+
+/// Talks to the REST API of an Esplora instance, e.g.
+/// `https://blockstream.info/api/` or a self-hosted electrs.
+#[derive(Clone, Debug)]
+pub struct EsploraConnector {
+    tip_hash_url: Url,
+    block_url: Url,
+    tx_url: Url,
+    transaction_url: Url,
+    fee_estimates_url: Url,
+    client: Client,
+}
+
+impl EsploraConnector {
+    pub fn new(base_url: Url, _network: Network) -> Result<Self, reqwest::UrlError> {
+        // Esplora is usually served under a path like `/api`, without the
+        // trailing slash joining would replace it instead of appending to it.
+        let base_url = if base_url.path().ends_with('/') {
+            base_url
+        } else {
+            let path = format!("{}/", base_url.path());
+            base_url.join(&path)?
+        };
+
+        Ok(Self {
+            tip_hash_url: base_url.join("blocks/tip/hash")?,
+            block_url: base_url.join("block/")?,
+            tx_url: base_url.join("tx")?,
+            transaction_url: base_url.join("tx/")?,
+            fee_estimates_url: base_url.join("fee-estimates")?,
+            client: Client::new(),
+        })
+    }
+
+    /// Broadcasts `transaction` through `POST /tx`.
+    pub fn send_raw_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Box<
+        dyn Future<Item = sha256d::Hash, Error = crate::btsieve::bitcoin::Error> + Send + 'static,
+    > {
+        Box::new(
+            self.client
+                .post(self.tx_url.clone())
+                .body(serialize_hex(transaction))
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|mut response| response.text())
+                .map_err(crate::btsieve::bitcoin::Error::Reqwest)
+                .and_then(|text| {
+                    sha256d::Hash::from_str(text.trim())
+                        .map_err(crate::btsieve::bitcoin::Error::BlockHash)
+                }),
+        )
+    }
+
+    /// Looks up the transaction `txid` through `GET /tx/:txid/hex`.
+    pub fn transaction(
+        &self,
+        txid: sha256d::Hash,
+    ) -> Box<dyn Future<Item = Transaction, Error = crate::btsieve::bitcoin::Error> + Send + 'static>
+    {
+        let url = self
+            .transaction_url
+            .join(&format!("{}/hex", txid))
+            .expect("building url should work");
+
+        Box::new(
+            self.client
+                .get(url)
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|mut response| response.text())
+                .map_err(crate::btsieve::bitcoin::Error::Reqwest)
+                .and_then(|text| decode_response(text.trim().to_owned())),
+        )
+    }
+
+    /// Looks up the fee rate for `confirmation_target` in the estimates of
+    /// `GET /fee-estimates`.
+    pub fn estimate_fee(
+        &self,
+        confirmation_target: u32,
+    ) -> Box<dyn Future<Item = FeeEstimate, Error = crate::btsieve::bitcoin::Error> + Send + 'static>
+    {
+        Box::new(
+            self.client
+                .get(self.fee_estimates_url.clone())
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|mut response| response.json::<HashMap<String, f64>>())
+                .map_err(crate::btsieve::bitcoin::Error::Reqwest)
+                .and_then(move |estimates| {
+                    closest_fee_estimate(&estimates, confirmation_target).ok_or(
+                        crate::btsieve::bitcoin::Error::NoFeeEstimate(confirmation_target),
+                    )
+                }),
+        )
+    }
+
+    fn raw_block_by_hash_url(&self, block_hash: &sha256d::Hash) -> Url {
+        self.block_url
+            .join(&format!("{}/raw", block_hash))
+            .expect("building url should work")
+    }
+}
+
+/// Esplora only has estimates for some targets (1 to 25, 144, 504 and 1008
+/// blocks), we take the one for the longest target that still confirms in
+/// time.
+fn closest_fee_estimate(
+    estimates: &HashMap<String, f64>,
+    confirmation_target: u32,
+) -> Option<FeeEstimate> {
+    estimates
+        .iter()
+        .filter_map(|(target, sat_per_vbyte)| Some((target.parse::<u32>().ok()?, *sat_per_vbyte)))
+        .filter(|(target, _)| *target <= confirmation_target)
+        .max_by_key(|(target, _)| *target)
+        .map(|(target, sat_per_vbyte)| FeeEstimate {
+            fee_per_wu: fee_per_wu_from_sat_per_vbyte(sat_per_vbyte),
+            confirmation_target: target,
+        })
+}
+
+impl LatestBlock for EsploraConnector {
+    type Error = crate::btsieve::bitcoin::Error;
+    type Block = bitcoin::Block;
+    type BlockHash = sha256d::Hash;
+
+    fn latest_block(
+        &mut self,
+    ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+        let latest_block_hash = self
+            .client
+            .get(self.tip_hash_url.clone())
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|mut response| response.text())
+            .map_err(|e| {
+                log::error!("Error when asking Esplora for the latest block");
+                Self::Error::Reqwest(e)
+            })
+            .and_then(|text| sha256d::Hash::from_str(text.trim()).map_err(Self::Error::BlockHash));
+
+        let cloned_self = self.clone();
+
+        Box::new(
+            latest_block_hash
+                .and_then(move |latest_block_hash| cloned_self.block_by_hash(latest_block_hash)),
+        )
+    }
+}
+
+impl BlockByHash for EsploraConnector {
+    type Error = crate::btsieve::bitcoin::Error;
+    type Block = bitcoin::Block;
+    type BlockHash = sha256d::Hash;
+
+    fn block_by_hash(
+        &self,
+        block_hash: Self::BlockHash,
+    ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+        let block = self
+            .client
+            .get(self.raw_block_by_hash_url(&block_hash))
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.into_body().concat2())
+            .map_err(Self::Error::Reqwest)
+            .and_then(|bytes| {
+                deserialize::<bitcoin::Block>(&bytes).map_err(Self::Error::Deserialization)
+            });
+
+        Box::new(block.inspect(|block| {
+            log::trace!("Fetched block from Esplora: {:?}", block);
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_different_base_urls_correct_sub_urls_are_built() {
+        let base_urls = vec![
+            ("http://localhost:3000", "http://localhost:3000/"),
+            ("http://localhost:3000/", "http://localhost:3000/"),
+            (
+                "https://blockstream.info/api",
+                "https://blockstream.info/api/",
+            ),
+            (
+                "https://blockstream.info/api/",
+                "https://blockstream.info/api/",
+            ),
+        ];
+
+        for (base_url, expected) in base_urls {
+            let connector =
+                EsploraConnector::new(base_url.parse().unwrap(), Network::Regtest).unwrap();
+
+            assert_eq!(
+                connector.tip_hash_url,
+                Url::parse(&format!("{}blocks/tip/hash", expected)).unwrap()
+            );
+            assert_eq!(
+                connector.tx_url,
+                Url::parse(&format!("{}tx", expected)).unwrap()
+            );
+            assert_eq!(
+                connector.fee_estimates_url,
+                Url::parse(&format!("{}fee-estimates", expected)).unwrap()
+            );
+
+            let block_id = "2a593b84b1943521be01f97a59fc7feba30e7e8527fb2ba20b0158ca09016d02"
+                .parse()
+                .unwrap();
+            assert_eq!(
+                connector.raw_block_by_hash_url(&block_id),
+                Url::parse(&format!(
+                    "{}block/2a593b84b1943521be01f97a59fc7feba30e7e8527fb2ba20b0158ca09016d02/raw",
+                    expected
+                ))
+                .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn fee_estimate_for_the_longest_target_within_the_requested_one_is_taken() {
+        let estimates = vec![("1", 87.882), ("6", 41.0), ("144", 1.027)]
+            .into_iter()
+            .map(|(target, fee)| (target.to_owned(), fee))
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(
+            closest_fee_estimate(&estimates, 10),
+            Some(FeeEstimate {
+                fee_per_wu: 11,
+                confirmation_target: 6,
+            })
+        );
+        assert_eq!(
+            closest_fee_estimate(&estimates, 1),
+            Some(FeeEstimate {
+                fee_per_wu: 22,
+                confirmation_target: 1,
+            })
+        );
+        assert_eq!(closest_fee_estimate(&estimates, 0), None);
+    }
+}
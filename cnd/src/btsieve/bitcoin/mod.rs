@@ -1,14 +1,31 @@
+mod backend;
 mod bitcoind_connector;
+mod bitcoind_rpc_connector;
 mod blockchain_info_connector;
+mod electrum_connector;
+mod esplora_connector;
 mod transaction_ext;
 mod transaction_pattern;
+mod zmq_notifications;
 
 pub use self::{
-    bitcoind_connector::BitcoindConnector, blockchain_info_connector::BlockchainInfoConnector,
-    transaction_ext::TransactionExt, transaction_pattern::TransactionPattern,
+    backend::{
+        BitcoinConnector, BitcoinFeeEstimator, BitcoinLedgerConnector, BlockSource, FeeEstimator,
+    },
+    bitcoind_connector::BitcoindConnector,
+    bitcoind_rpc_connector::{BitcoindRpcConnector, RpcAuth},
+    blockchain_info_connector::BlockchainInfoConnector,
+    electrum_connector::{ElectrumConnector, HistoryEntry},
+    esplora_connector::EsploraConnector,
+    transaction_ext::TransactionExt,
+    transaction_pattern::TransactionPattern,
+    zmq_notifications::ZmqBlockNotifications,
 };
 
-use crate::btsieve::{BlockByHash, LatestBlock};
+use crate::btsieve::{
+    scan_progress::ScannedBlocks, BlockByHash, Confirmations, IncludingBlock, IncludingBlocks,
+    LatestBlock, ScanProgress, SubscribeBlocks, Subscription,
+};
 use bitcoin::{
     consensus::{encode::deserialize, Decodable},
     hashes::sha256d,
@@ -16,20 +33,55 @@ use bitcoin::{
 };
 use futures_core::compat::Future01CompatExt;
 use reqwest::{r#async::Client, Url};
-use std::{collections::HashSet, fmt::Debug, ops::Add};
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    ops::Add,
+};
 use tokio::{prelude::future::Future, timer::Delay};
 
 pub async fn matching_transaction<C, E>(
+    blockchain_connector: C,
+    pattern: TransactionPattern,
+    reference_timestamp: Option<u32>,
+) -> Result<bitcoin::Transaction, ()>
+where
+    C: LatestBlock<Block = bitcoin::Block, Error = E>
+        + BlockByHash<Block = bitcoin::Block, BlockHash = sha256d::Hash, Error = E>
+        + SubscribeBlocks<Block = bitcoin::Block>
+        + IncludingBlocks<TransactionHash = sha256d::Hash, BlockHash = sha256d::Hash>
+        + Clone,
+    E: Debug + Send + 'static,
+{
+    matching_transaction_with_progress(blockchain_connector, pattern, reference_timestamp, ()).await
+}
+
+/// Like `matching_transaction` but resumes the scan from the checkpoint in
+/// `scan_progress` instead of walking back the whole blockchain again and
+/// records there how far the blockchain was scanned.
+pub async fn matching_transaction_with_progress<C, E, P>(
     mut blockchain_connector: C,
     pattern: TransactionPattern,
     reference_timestamp: Option<u32>,
+    scan_progress: P,
 ) -> Result<bitcoin::Transaction, ()>
 where
     C: LatestBlock<Block = bitcoin::Block, Error = E>
         + BlockByHash<Block = bitcoin::Block, BlockHash = sha256d::Hash, Error = E>
+        + SubscribeBlocks<Block = bitcoin::Block>
+        + IncludingBlocks<TransactionHash = sha256d::Hash, BlockHash = sha256d::Hash>
         + Clone,
     E: Debug + Send + 'static,
+    P: ScanProgress<sha256d::Hash>,
 {
+    let resumed_from = scan_progress.load_checkpoint().await;
+    let mut scanned_blocks = ScannedBlocks::new(resumed_from);
+
+    // Subscribe before looking at the latest block so that we do not miss the
+    // blocks mined in the meantime.
+    let mut new_blocks = blockchain_connector.subscribe();
+
     // Verify that we can successfully connect to the blockchain connector and check
     // if the transaction is in the latest block.
     let latest_block = match blockchain_connector.latest_block().compat().await {
@@ -39,47 +91,60 @@ where
             return Err(());
         }
     };
-    if let Some(transaction) = check_block_against_pattern(&latest_block.clone(), &pattern) {
-        return Ok(transaction.clone());
+    if let Some(transaction) = check_block_against_pattern(&latest_block, &pattern) {
+        return Ok(found(&blockchain_connector, &latest_block, transaction));
     };
+    // Without a reference timestamp nothing before the latest block needs to be
+    // checked, unless we have to fill the gap to the checkpoint we resumed from.
+    let starts_here = reference_timestamp.is_none() && resumed_from.is_none();
+    record_scanned_block(
+        &mut scanned_blocks,
+        &scan_progress,
+        &latest_block,
+        starts_here || older_than(&latest_block, reference_timestamp),
+    )
+    .await;
 
-    // We didn't find the transaction, now we need to do two things; keep polling
-    // for latest block so that we see transactions in new blocks and also go
+    // We didn't find the transaction, now we need to do two things; look at
+    // every new block so that we see transactions in new blocks and also go
     // back up the blockchain until 'reference_timestamp' i.e., look back in the
     // past.
 
     let mut prev_blockhashes: HashSet<sha256d::Hash> = HashSet::new();
-    let mut missing_block_futures: Vec<_> = Vec::new();
+    let mut missing_blockhashes: Vec<sha256d::Hash> = Vec::new();
 
     let mut oldest_block: Option<bitcoin::Block> = Some(latest_block.clone());
     prev_blockhashes.insert(latest_block.bitcoin_hash());
 
-    let prev_blockhash = latest_block.header.prev_blockhash;
-    let future = blockchain_connector.block_by_hash(prev_blockhash).compat();
-    missing_block_futures.push((future, prev_blockhash));
+    if parent_needs_scan(&scanned_blocks, &latest_block) {
+        missing_blockhashes.push(latest_block.header.prev_blockhash);
+    }
 
     loop {
-        // Delay so that we don't overload the CPU in the event that
-        // latest_block() and block_by_hash() resolve quickly.
-        Delay::new(std::time::Instant::now().add(std::time::Duration::from_secs(1)))
-            .compat()
-            .await
-            .unwrap_or_else(|e| log::warn!("Failed to wait for delay: {:?}", e));
+        let mut failed = false;
 
-        let mut new_missing_block_futures = Vec::new();
-        for (block_future, blockhash) in missing_block_futures.into_iter() {
-            match block_future.await {
+        let mut new_missing_blockhashes = Vec::new();
+        for blockhash in missing_blockhashes.into_iter() {
+            match blockchain_connector.block_by_hash(blockhash).compat().await {
                 Ok(block) => {
                     match check_block_against_pattern(&block, &pattern) {
-                        Some(transaction) => return Ok(transaction.clone()),
+                        Some(transaction) => {
+                            return Ok(found(&blockchain_connector, &block, transaction))
+                        }
                         None => {
+                            record_scanned_block(
+                                &mut scanned_blocks,
+                                &scan_progress,
+                                &block,
+                                older_than(&block, reference_timestamp),
+                            )
+                            .await;
+
                             let prev_blockhash = block.header.prev_blockhash;
                             let unknown_parent = prev_blockhashes.insert(prev_blockhash);
 
-                            if unknown_parent {
-                                let future =
-                                    blockchain_connector.block_by_hash(prev_blockhash).compat();
-                                new_missing_block_futures.push((future, prev_blockhash));
+                            if unknown_parent && parent_needs_scan(&scanned_blocks, &block) {
+                                new_missing_blockhashes.push(prev_blockhash);
                             }
                         }
                     };
@@ -87,60 +152,574 @@ where
                 Err(e) => {
                     log::warn!("Could not get block with hash {}: {:?}", blockhash, e);
 
-                    let future = blockchain_connector.block_by_hash(blockhash).compat();
-                    new_missing_block_futures.push((future, blockhash));
+                    failed = true;
+                    new_missing_blockhashes.push(blockhash);
                 }
             };
         }
-        missing_block_futures = new_missing_block_futures;
+        missing_blockhashes = new_missing_blockhashes;
 
         // Look back into the past (upto timestamp) for one block.
 
-        if let (Some(block), Some(reference_timestamp)) =
-            (oldest_block.as_ref(), reference_timestamp)
-        {
-            if block.header.time >= reference_timestamp {
-                match blockchain_connector
-                    .block_by_hash(block.header.prev_blockhash)
-                    .compat()
-                    .await
-                {
-                    Ok(block) => match check_block_against_pattern(&block, &pattern) {
-                        Some(transaction) => return Ok(transaction.clone()),
-                        None => {
-                            oldest_block.replace(block);
-                        }
-                    },
-                    Err(e) => log::warn!(
+        let looking_back = match (oldest_block.as_ref(), reference_timestamp) {
+            (Some(block), Some(reference_timestamp)) => block.header.time >= reference_timestamp,
+            _ => false,
+        };
+        if let (true, Some(block)) = (looking_back, oldest_block.as_ref()) {
+            match blockchain_connector
+                .block_by_hash(block.header.prev_blockhash)
+                .compat()
+                .await
+            {
+                Ok(block) => match check_block_against_pattern(&block, &pattern) {
+                    Some(transaction) => {
+                        return Ok(found(&blockchain_connector, &block, transaction))
+                    }
+                    None => {
+                        record_scanned_block(
+                            &mut scanned_blocks,
+                            &scan_progress,
+                            &block,
+                            older_than(&block, reference_timestamp),
+                        )
+                        .await;
+                        oldest_block.replace(block);
+                    }
+                },
+                Err(e) => {
+                    log::warn!(
                         "Could not get block with hash {}: {:?}",
                         block.bitcoin_hash(),
                         e
-                    ),
-                };
-            }
+                    );
+                    failed = true;
+                }
+            };
         }
 
-        // Check if a new block has been mined.
+        // Wait for a new block unless we are still busy with the past.
+
+        let new_block = if missing_blockhashes.is_empty() && !looking_back {
+            match new_blocks.next().await {
+                Some(block) => Some(block),
+                None => {
+                    log::error!("Lost the subscription to new blocks");
+                    return Err(());
+                }
+            }
+        } else {
+            if failed {
+                delay().await;
+            }
+            new_blocks.try_next().await
+        };
 
-        if let Ok(latest_block) = blockchain_connector.latest_block().compat().await {
+        if let Some(latest_block) = new_block {
             // If we can insert then we have not seen this block.
             if prev_blockhashes.insert(latest_block.bitcoin_hash()) {
                 if let Some(transaction) = check_block_against_pattern(&latest_block, &pattern) {
-                    return Ok(transaction.clone());
+                    return Ok(found(&blockchain_connector, &latest_block, transaction));
                 };
+                record_scanned_block(&mut scanned_blocks, &scan_progress, &latest_block, false)
+                    .await;
 
                 // In case we missed a block somehow, check this blocks parent.
-                if !prev_blockhashes.contains(&latest_block.header.prev_blockhash) {
-                    let prev_blockhash = latest_block.header.prev_blockhash;
-                    let future = blockchain_connector.block_by_hash(prev_blockhash).compat();
+                if !prev_blockhashes.contains(&latest_block.header.prev_blockhash)
+                    && parent_needs_scan(&scanned_blocks, &latest_block)
+                {
+                    missing_blockhashes.push(latest_block.header.prev_blockhash);
+                }
+            }
+        }
+    }
+}
 
-                    missing_block_futures.push((future, prev_blockhash));
+/// Remembers that `transaction` was found in `block` for whoever follows the
+/// transaction afterwards.
+fn found<C>(
+    blockchain_connector: &C,
+    block: &bitcoin::Block,
+    transaction: &bitcoin::Transaction,
+) -> bitcoin::Transaction
+where
+    C: IncludingBlocks<TransactionHash = sha256d::Hash, BlockHash = sha256d::Hash>,
+{
+    if let Some(height) = block_height(block) {
+        blockchain_connector.remember_including_block(transaction.txid(), IncludingBlock {
+            block_hash: block.bitcoin_hash(),
+            block_height: height.into(),
+        });
+    }
+
+    transaction.clone()
+}
+
+/// Records that `block` was checked. If it is the `reference_point` of the
+/// scan, none of the blocks before it need to be checked.
+async fn record_scanned_block<P>(
+    scanned_blocks: &mut ScannedBlocks<sha256d::Hash>,
+    scan_progress: &P,
+    block: &bitcoin::Block,
+    reference_point: bool,
+) where
+    P: ScanProgress<sha256d::Hash>,
+{
+    let mut checkpoint = block_height(block).and_then(|height| {
+        scanned_blocks.insert(
+            block.bitcoin_hash(),
+            block.header.prev_blockhash,
+            height.into(),
+        )
+    });
+    if reference_point {
+        checkpoint = scanned_blocks
+            .reached_reference_point(block.bitcoin_hash())
+            .or(checkpoint);
+    }
+
+    if let Some(checkpoint) = checkpoint {
+        scan_progress.save_checkpoint(checkpoint).await;
+    }
+}
+
+/// Whether `block` was mined before the reference timestamp, i.e. neither it
+/// nor any block before it can include the transaction we are looking for.
+fn older_than(block: &bitcoin::Block, reference_timestamp: Option<u32>) -> bool {
+    reference_timestamp
+        .map(|reference_timestamp| block.header.time < reference_timestamp)
+        .unwrap_or(false)
+}
+
+fn parent_needs_scan(
+    scanned_blocks: &ScannedBlocks<sha256d::Hash>,
+    block: &bitcoin::Block,
+) -> bool {
+    !scanned_blocks.already_scanned(&block.bitcoin_hash())
+        && !scanned_blocks.already_scanned(&block.header.prev_blockhash)
+}
+
+/// Resolves once the transaction with the given id is no longer part of the
+/// best chain, i.e. the block that included it was orphaned and none of the
+/// blocks that replaced it include the transaction again.
+///
+/// The transaction is followed from the block it was matched in. If we do not
+/// know that block, because the transaction was matched before a restart, and
+/// the transaction cannot be found within the latest `SEARCH_DEPTH` blocks, it
+/// is buried too deep to be retracted and this never resolves.
+pub async fn transaction_retracted<C, E>(mut blockchain_connector: C, txid: sha256d::Hash)
+where
+    C: LatestBlock<Block = bitcoin::Block, Error = E>
+        + BlockByHash<Block = bitcoin::Block, BlockHash = sha256d::Hash, Error = E>
+        + SubscribeBlocks<Block = bitcoin::Block>
+        + IncludingBlocks<TransactionHash = sha256d::Hash, BlockHash = sha256d::Hash>
+        + Clone,
+    E: Debug + Send + 'static,
+{
+    let mut new_blocks = blockchain_connector.subscribe();
+    let mut tracker = Tracker::new(txid, blockchain_connector.including_block(&txid));
+
+    loop {
+        match tracker.depth(&mut blockchain_connector).await {
+            Ok(Depth::Confirmations(_)) | Ok(Depth::BeyondSearchDepth) => {}
+            Ok(Depth::NotIncluded) => {
+                log::info!("Transaction {} is no longer part of the best chain", txid);
+                return;
+            }
+            Err(e) => {
+                log::warn!("Could not walk back the blockchain: {:?}", e);
+                delay().await;
+                continue;
+            }
+        }
+
+        wait_for_new_block(&mut new_blocks).await;
+    }
+}
+
+/// Resolves once the transaction with the given id is part of the best chain
+/// and has at least `confirmations.min_confirmations()` confirmations.
+///
+/// While waiting, the current number of confirmations of the transaction is
+/// recorded in `confirmations`.
+pub async fn transaction_confirmed<C, E>(
+    mut blockchain_connector: C,
+    txid: sha256d::Hash,
+    confirmations: Confirmations<sha256d::Hash>,
+) where
+    C: LatestBlock<Block = bitcoin::Block, Error = E>
+        + BlockByHash<Block = bitcoin::Block, BlockHash = sha256d::Hash, Error = E>
+        + SubscribeBlocks<Block = bitcoin::Block>
+        + IncludingBlocks<TransactionHash = sha256d::Hash, BlockHash = sha256d::Hash>
+        + Clone,
+    E: Debug + Send + 'static,
+{
+    let mut new_blocks = blockchain_connector.subscribe();
+    let mut tracker = Tracker::new(txid, blockchain_connector.including_block(&txid));
+
+    loop {
+        match tracker.depth(&mut blockchain_connector).await {
+            Ok(depth) => {
+                let count = depth.confirmations();
+                confirmations.insert(txid, count);
+
+                if count >= confirmations.min_confirmations() {
+                    return;
                 }
             }
+            Err(e) => {
+                log::warn!("Could not walk back the blockchain: {:?}", e);
+                delay().await;
+                continue;
+            }
+        }
+
+        wait_for_new_block(&mut new_blocks).await;
+    }
+}
+
+/// Keeps counting the confirmations of a transaction after
+/// `transaction_confirmed` resolved, so that `confirmations` reports the
+/// current number instead of the one the transaction had when it reached
+/// `min_confirmations`.
+///
+/// Resolves once the transaction is removed from `confirmations`, is no
+/// longer part of the best chain or has `SEARCH_DEPTH` confirmations.
+pub async fn follow_confirmations<C, E>(
+    mut blockchain_connector: C,
+    txid: sha256d::Hash,
+    confirmations: Confirmations<sha256d::Hash>,
+) where
+    C: LatestBlock<Block = bitcoin::Block, Error = E>
+        + BlockByHash<Block = bitcoin::Block, BlockHash = sha256d::Hash, Error = E>
+        + SubscribeBlocks<Block = bitcoin::Block>
+        + IncludingBlocks<TransactionHash = sha256d::Hash, BlockHash = sha256d::Hash>
+        + Clone,
+    E: Debug + Send + 'static,
+{
+    let mut new_blocks = blockchain_connector.subscribe();
+    let mut tracker = Tracker::new(txid, blockchain_connector.including_block(&txid));
+
+    loop {
+        wait_for_new_block(&mut new_blocks).await;
+
+        let depth = match tracker.depth(&mut blockchain_connector).await {
+            Ok(depth) => depth,
+            Err(e) => {
+                log::warn!("Could not walk back the blockchain: {:?}", e);
+                continue;
+            }
+        };
+        let count = depth.confirmations();
+
+        if !confirmations.update(&txid, count) || depth.is_final() {
+            return;
+        }
+    }
+}
+
+/// How many blocks we look back to find the block that includes a transaction
+/// if we do not know in which block it was matched. Transactions that are
+/// included deeper than that are considered final, they have `SEARCH_DEPTH`
+/// confirmations as far as we are concerned.
+pub const SEARCH_DEPTH: usize = 100;
+
+/// Where a transaction is in the best chain as far as a `Tracker` can tell.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Depth {
+    Confirmations(u32),
+    NotIncluded,
+    /// We do not know in which block the transaction was included and it is
+    /// not within the latest `SEARCH_DEPTH` blocks.
+    BeyondSearchDepth,
+}
+
+impl Depth {
+    fn confirmations(self) -> u32 {
+        match self {
+            Depth::Confirmations(confirmations) => confirmations,
+            Depth::NotIncluded => 0,
+            Depth::BeyondSearchDepth => SEARCH_DEPTH as u32,
+        }
+    }
+
+    /// Whether the transaction is too deep to still be followed.
+    fn is_final(self) -> bool {
+        match self {
+            Depth::Confirmations(confirmations) => confirmations >= SEARCH_DEPTH as u32,
+            Depth::NotIncluded => true,
+            Depth::BeyondSearchDepth => true,
+        }
+    }
+}
+
+/// Follows the position of a transaction in the best chain across polls of
+/// the latest block.
+#[derive(Debug)]
+struct Tracker {
+    txid: sha256d::Hash,
+    /// The block including the transaction and all its known descendants,
+    /// mapped to the number of confirmations of the transaction if that block
+    /// is the tip of the chain.
+    best_chain: HashMap<sha256d::Hash, u32>,
+    including_block_height: Option<u32>,
+    /// Whether we know that the transaction was part of the best chain at
+    /// some point, either because it was matched in a known block or because
+    /// we saw it while watching it.
+    seen: bool,
+    /// Whether the transaction was never seen and not within the latest
+    /// `SEARCH_DEPTH` blocks the first time we looked for it.
+    buried: bool,
+}
+
+impl Tracker {
+    /// Starts following the transaction from `including_block`, the block it
+    /// was matched in, if we know it.
+    fn new(txid: sha256d::Hash, including_block: Option<IncludingBlock<sha256d::Hash>>) -> Self {
+        let mut best_chain = HashMap::new();
+        let including_block_height = including_block.map(|including_block| {
+            best_chain.insert(including_block.block_hash, 1);
+            including_block.block_height as u32
+        });
+
+        Self {
+            txid,
+            best_chain,
+            including_block_height,
+            seen: including_block.is_some(),
+            buried: false,
+        }
+    }
+
+    /// Returns the number of confirmations of the transaction in the current
+    /// best chain, whether the best chain does not include it or whether we
+    /// gave up looking for it.
+    async fn depth<C, E>(&mut self, blockchain_connector: &mut C) -> Result<Depth, E>
+    where
+        C: LatestBlock<Block = bitcoin::Block, Error = E>
+            + BlockByHash<Block = bitcoin::Block, BlockHash = sha256d::Hash, Error = E>,
+        E: Debug,
+    {
+        if self.buried {
+            return Ok(Depth::BeyondSearchDepth);
+        }
+
+        let latest_block = blockchain_connector.latest_block().compat().await?;
+        let latest_blockhash = latest_block.bitcoin_hash();
+
+        match walk_back(
+            blockchain_connector,
+            latest_block,
+            self.txid,
+            &self.best_chain,
+            self.including_block_height,
+            self.seen,
+        )
+        .await?
+        {
+            Walk::Connected {
+                visited,
+                confirmations,
+            } => self.extend(visited, confirmations),
+            Walk::Found {
+                visited,
+                including_block_height,
+            } => {
+                self.seen = true;
+                self.best_chain.clear();
+                self.including_block_height = including_block_height;
+                self.extend(visited, 0);
+            }
+            Walk::NotFound => {
+                self.best_chain.clear();
+                self.including_block_height = None;
+            }
+            Walk::BeyondSearchDepth => {
+                self.buried = true;
+                return Ok(Depth::BeyondSearchDepth);
+            }
+        }
+
+        Ok(match self.best_chain.get(&latest_blockhash) {
+            Some(confirmations) => Depth::Confirmations(*confirmations),
+            None => Depth::NotIncluded,
+        })
+    }
+
+    /// Adds the `visited` blocks, ordered from newest to oldest, on top of a
+    /// block at which the transaction has `confirmations` confirmations.
+    fn extend(&mut self, visited: Vec<sha256d::Hash>, confirmations: u32) {
+        for (depth, blockhash) in visited.into_iter().rev().enumerate() {
+            self.best_chain
+                .insert(blockhash, confirmations + depth as u32 + 1);
         }
     }
 }
 
+#[derive(Debug)]
+enum Walk {
+    Connected {
+        visited: Vec<sha256d::Hash>,
+        confirmations: u32,
+    },
+    Found {
+        visited: Vec<sha256d::Hash>,
+        including_block_height: Option<u32>,
+    },
+    NotFound,
+    /// The transaction was never seen and is not within the latest
+    /// `SEARCH_DEPTH` blocks.
+    BeyondSearchDepth,
+}
+
+async fn walk_back<C, E>(
+    blockchain_connector: &C,
+    latest_block: bitcoin::Block,
+    txid: sha256d::Hash,
+    best_chain: &HashMap<sha256d::Hash, u32>,
+    including_block_height: Option<u32>,
+    seen: bool,
+) -> Result<Walk, E>
+where
+    C: BlockByHash<Block = bitcoin::Block, BlockHash = sha256d::Hash, Error = E>,
+    E: Debug,
+{
+    let mut visited = Vec::new();
+    let mut block = latest_block;
+
+    loop {
+        let blockhash = block.bitcoin_hash();
+
+        if let Some(confirmations) = best_chain.get(&blockhash) {
+            return Ok(Walk::Connected {
+                visited,
+                confirmations: *confirmations,
+            });
+        }
+        visited.push(blockhash);
+
+        if block
+            .txdata
+            .iter()
+            .any(|transaction| transaction.txid() == txid)
+        {
+            return Ok(Walk::Found {
+                visited,
+                including_block_height: block_height(&block),
+            });
+        }
+
+        let walked_past_including_block = match (including_block_height, block_height(&block)) {
+            (Some(including_block_height), Some(height)) => height <= including_block_height,
+            _ => visited.len() >= SEARCH_DEPTH,
+        };
+        if walked_past_including_block {
+            return Ok(if seen {
+                Walk::NotFound
+            } else {
+                Walk::BeyondSearchDepth
+            });
+        }
+
+        block = blockchain_connector
+            .block_by_hash(block.header.prev_blockhash)
+            .compat()
+            .await?;
+    }
+}
+
+/// How many blocks the median time past is calculated over (BIP113).
+const MEDIAN_TIME_SPAN: usize = 11;
+
+/// The median time of the last 11 blocks, timelocks are compared against it
+/// instead of the time of the latest block since BIP113.
+pub async fn median_time_past<C, E>(mut blockchain_connector: C) -> Result<u32, E>
+where
+    C: LatestBlock<Block = bitcoin::Block, Error = E>
+        + BlockByHash<Block = bitcoin::Block, BlockHash = sha256d::Hash, Error = E>,
+    E: Debug,
+{
+    let mut block = blockchain_connector.latest_block().compat().await?;
+    let mut times = vec![block.header.time];
+
+    while times.len() < MEDIAN_TIME_SPAN && block.header.prev_blockhash != Default::default() {
+        block = blockchain_connector
+            .block_by_hash(block.header.prev_blockhash)
+            .compat()
+            .await?;
+        times.push(block.header.time);
+    }
+
+    Ok(median(times))
+}
+
+/// Block times are not necessarily increasing, hence they have to be sorted
+/// first.
+fn median(mut times: Vec<u32>) -> u32 {
+    times.sort();
+
+    times[times.len() / 2]
+}
+
+/// The fee rate a backend suggests for a transaction to be included within
+/// `confirmation_target` blocks. The target can differ from the one that was
+/// asked for if the backend has no estimate for it.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct FeeEstimate {
+    pub fee_per_wu: u64,
+    pub confirmation_target: u32,
+}
+
+/// Converts a fee rate in satoshis per virtual byte, rounding up so the
+/// estimate is not undercut. A virtual byte is 4 weight units.
+fn fee_per_wu_from_sat_per_vbyte(sat_per_vbyte: f64) -> u64 {
+    let fee_per_wu = (sat_per_vbyte / 4.0).ceil() as u64;
+
+    fee_per_wu.max(1)
+}
+
+/// Converts a fee rate in BTC per 1000 virtual bytes, the unit of bitcoind and
+/// Electrum.
+fn fee_per_wu_from_btc_per_kvbyte(btc_per_kvbyte: f64) -> u64 {
+    fee_per_wu_from_sat_per_vbyte(btc_per_kvbyte * 100_000.0)
+}
+
+/// Decodes the height of a block from the coinbase transaction (BIP34).
+pub fn block_height(block: &bitcoin::Block) -> Option<u32> {
+    let coinbase = block.txdata.first()?;
+    let script_sig = coinbase.input.first()?.script_sig.as_bytes();
+
+    match *script_sig.first()? {
+        0x00 => Some(0),
+        opcode @ 0x51..=0x60 => Some(u32::from(opcode - 0x50)),
+        length @ 0x01..=0x04 => {
+            let bytes = script_sig.get(1..=usize::from(length))?;
+
+            Some(
+                bytes
+                    .iter()
+                    .rev()
+                    .fold(0, |height, byte| (height << 8) | u32::from(*byte)),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Waits for the next block, the subscription only ends if the poller is gone
+/// in which case we fall back to polling ourselves.
+async fn wait_for_new_block<B>(new_blocks: &mut Subscription<B>) {
+    if new_blocks.next().await.is_none() {
+        delay().await;
+    }
+}
+
+async fn delay() {
+    Delay::new(std::time::Instant::now().add(std::time::Duration::from_secs(1)))
+        .compat()
+        .await
+        .unwrap_or_else(|e| log::warn!("Failed to wait for delay: {:?}", e));
+}
+
 fn check_block_against_pattern<'b>(
     block: &'b bitcoin::Block,
     pattern: &TransactionPattern,
@@ -173,6 +752,24 @@ pub enum Error {
     Hex(#[from] hex::FromHexError),
     #[error("deserialization: ")]
     Deserialization(#[from] bitcoin::consensus::encode::Error),
+    #[error("block hash: ")]
+    BlockHash(#[from] bitcoin::hashes::hex::Error),
+    #[error("io: ")]
+    Io(#[from] std::io::Error),
+    #[error("json: ")]
+    Json(#[from] serde_json::Error),
+    #[error("electrum: {0}")]
+    Electrum(String),
+    #[error("rpc: {0}")]
+    Rpc(String),
+    #[error("{0} cannot broadcast transactions")]
+    BroadcastUnsupported(&'static str),
+    #[error("{0} cannot estimate fees")]
+    FeeEstimationUnsupported(&'static str),
+    #[error("no fee estimate available for a confirmation target of {0} blocks")]
+    NoFeeEstimate(u32),
+    #[error("{0} cannot scan for the outputs of a descriptor")]
+    DescriptorScanUnsupported(&'static str),
 }
 
 pub fn decode_response<T: Decodable>(response_text: String) -> Result<T, Error> {
@@ -186,6 +783,23 @@ mod tests {
     use super::*;
     use spectral::prelude::*;
 
+    #[test]
+    fn median_time_past_ignores_out_of_order_block_times() {
+        let times = vec![10, 1, 9, 2, 8, 3, 7, 4, 6, 5, 100];
+
+        assert_eq!(median(times), 6);
+        assert_eq!(median(vec![42]), 42);
+    }
+
+    #[test]
+    fn fee_rates_are_converted_to_fee_per_wu_rounding_up() {
+        assert_eq!(fee_per_wu_from_sat_per_vbyte(20.0), 5);
+        assert_eq!(fee_per_wu_from_sat_per_vbyte(20.5), 6);
+        assert_eq!(fee_per_wu_from_btc_per_kvbyte(0.0002), 5);
+        assert_eq!(fee_per_wu_from_btc_per_kvbyte(0.00001), 1);
+        assert_eq!(fee_per_wu_from_sat_per_vbyte(0.0), 1);
+    }
+
     #[test]
     fn can_decode_tx_from_bitcoind_http_interface() {
         // the line break here is on purpose, as it is returned like that from bitcoind
@@ -1,7 +1,7 @@
 use crate::btsieve::bitcoin::transaction_ext::TransactionExt;
 use ::bitcoin::{Address, OutPoint, Transaction};
 
-#[derive(Clone, Default, Debug, Eq, PartialEq)]
+#[derive(Clone, Default, Debug, Eq, PartialEq, serde::Serialize)]
 /// If the field is set to Some(foo) then only transactions matching foo are
 /// returned. Otherwise, when the field is set to None, no pattern matching is
 /// done for this field.
@@ -0,0 +1,275 @@
+//! A minimal ZeroMQ subscriber for the `rawblock` notifications of bitcoind.
+//!
+//! Only what is needed to talk to bitcoind is implemented: ZMTP 3.0 over TCP
+//! with the NULL security mechanism and a single subscription.
+
+use crate::btsieve::WakeUp;
+use bitcoin::consensus::encode::deserialize;
+use reqwest::Url;
+use std::{
+    io::{self, Read, Write},
+    net::TcpStream,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How long the last notified block is handed out as the latest block before
+/// the REST interface is asked again, in case notifications got lost.
+pub const QUIET_PERIOD: Duration = Duration::from_secs(30);
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// bitcoind does not send heartbeats, a connection that stays silent for
+/// longer than this is considered dead and reestablished.
+const READ_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Bitcoin blocks are limited to 4 MB, anything bigger is not from bitcoind.
+const MAX_FRAME_SIZE: u64 = 32 * 1024 * 1024;
+
+const TOPIC: &[u8] = b"rawblock";
+
+const MORE: u8 = 0x01;
+const LONG: u8 = 0x02;
+const COMMAND: u8 = 0x04;
+
+/// Keeps the latest block published on the `zmqpubrawblock` socket of
+/// bitcoind and wakes up the block pollers that want to hear about it.
+#[derive(Clone, Debug, Default)]
+pub struct ZmqBlockNotifications {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    connected: bool,
+    latest_block: Option<(Instant, bitcoin::Block)>,
+    wake_ups: Vec<WakeUp>,
+}
+
+impl ZmqBlockNotifications {
+    /// Subscribes to `rawblock` notifications published at `url`, e.g.
+    /// `tcp://127.0.0.1:28332`.
+    ///
+    /// The subscription lives on a background thread which reconnects
+    /// whenever the connection is lost.
+    pub fn subscribe(url: &Url) -> anyhow::Result<Self> {
+        if url.scheme() != "tcp" {
+            anyhow::bail!("unsupported ZeroMQ transport {}", url.scheme());
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow::anyhow!("ZeroMQ url {} has no host", url))?
+            .to_owned();
+        let port = url
+            .port()
+            .ok_or_else(|| anyhow::anyhow!("ZeroMQ url {} has no port", url))?;
+
+        let notifications = Self::default();
+
+        thread::Builder::new()
+            .name("bitcoind-zmq".to_owned())
+            .spawn({
+                let notifications = notifications.clone();
+
+                move || loop {
+                    if let Err(e) = notifications.receive_blocks((host.as_str(), port)) {
+                        log::warn!("Lost connection to bitcoind ZeroMQ publisher: {:?}", e);
+                    }
+                    thread::sleep(RECONNECT_DELAY);
+                }
+            })?;
+
+        Ok(notifications)
+    }
+
+    /// The latest block we know of, unless we are not connected to bitcoind
+    /// or have not heard of a new block for `QUIET_PERIOD`.
+    pub fn latest_block(&self) -> Option<bitcoin::Block> {
+        let state = self.state.lock().unwrap();
+
+        match &state.latest_block {
+            Some((received_at, block))
+                if state.connected && received_at.elapsed() < QUIET_PERIOD =>
+            {
+                Some(block.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Wakes up `wake_up` whenever bitcoind publishes a new block.
+    pub fn wake_up_on_new_blocks(&self, wake_up: WakeUp) {
+        self.state.lock().unwrap().wake_ups.push(wake_up);
+    }
+
+    fn new_block(&self, block: bitcoin::Block) {
+        let mut state = self.state.lock().unwrap();
+        state.latest_block = Some((Instant::now(), block));
+
+        for wake_up in state.wake_ups.iter() {
+            wake_up.wake_up();
+        }
+    }
+
+    fn receive_blocks(&self, address: (&str, u16)) -> io::Result<()> {
+        let mut stream = TcpStream::connect(address)?;
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        handshake(&mut stream)?;
+        write_frame(&mut stream, 0, &[&[0x01], TOPIC].concat())?;
+
+        self.state.lock().unwrap().connected = true;
+        log::info!("Subscribed to blocks published by bitcoind over ZeroMQ");
+
+        let result = loop {
+            let message = match read_message(&mut stream) {
+                Ok(message) => message,
+                Err(e) => break Err(e),
+            };
+
+            match (message.get(0), message.get(1)) {
+                (Some(topic), Some(body)) if topic.as_slice() == TOPIC => {
+                    match deserialize::<bitcoin::Block>(body) {
+                        Ok(block) => self.new_block(block),
+                        Err(e) => log::warn!("Could not decode block from bitcoind: {:?}", e),
+                    }
+                }
+                _ => log::debug!("Ignoring unexpected ZeroMQ message"),
+            }
+        };
+
+        self.state.lock().unwrap().connected = false;
+
+        result
+    }
+}
+
+fn handshake(stream: &mut TcpStream) -> io::Result<()> {
+    stream.write_all(&greeting())?;
+
+    let mut peer_greeting = [0u8; 64];
+    stream.read_exact(&mut peer_greeting)?;
+    if peer_greeting[0] != 0xff || peer_greeting[9] != 0x7f || peer_greeting[10] < 3 {
+        return Err(invalid_data("peer does not speak ZMTP 3"));
+    }
+
+    write_frame(stream, COMMAND, &ready_command(b"SUB"))?;
+
+    let (flags, _) = read_frame(stream)?;
+    if flags & COMMAND == 0 {
+        return Err(invalid_data("expected READY command from peer"));
+    }
+
+    Ok(())
+}
+
+/// ZMTP 3.0 greeting for the NULL mechanism, we are never the server.
+fn greeting() -> [u8; 64] {
+    let mut greeting = [0u8; 64];
+    greeting[0] = 0xff;
+    greeting[9] = 0x7f;
+    greeting[10] = 3;
+    greeting[11] = 0;
+    greeting[12..16].copy_from_slice(b"NULL");
+
+    greeting
+}
+
+fn ready_command(socket_type: &[u8]) -> Vec<u8> {
+    let mut command = Vec::new();
+    command.push(5);
+    command.extend_from_slice(b"READY");
+    command.push(11);
+    command.extend_from_slice(b"Socket-Type");
+    command.extend_from_slice(&(socket_type.len() as u32).to_be_bytes());
+    command.extend_from_slice(socket_type);
+
+    command
+}
+
+fn write_frame<W: Write>(writer: &mut W, flags: u8, body: &[u8]) -> io::Result<()> {
+    if body.len() > 255 {
+        writer.write_all(&[flags | LONG])?;
+        writer.write_all(&(body.len() as u64).to_be_bytes())?;
+    } else {
+        writer.write_all(&[flags, body.len() as u8])?;
+    }
+    writer.write_all(body)
+}
+
+fn read_frame<R: Read>(reader: &mut R) -> io::Result<(u8, Vec<u8>)> {
+    let mut flags = [0u8; 1];
+    reader.read_exact(&mut flags)?;
+    let flags = flags[0];
+
+    let size = if flags & LONG == 0 {
+        let mut size = [0u8; 1];
+        reader.read_exact(&mut size)?;
+        u64::from(size[0])
+    } else {
+        let mut size = [0u8; 8];
+        reader.read_exact(&mut size)?;
+        u64::from_be_bytes(size)
+    };
+    if size > MAX_FRAME_SIZE {
+        return Err(invalid_data("frame too big"));
+    }
+
+    let mut body = vec![0u8; size as usize];
+    reader.read_exact(&mut body)?;
+
+    Ok((flags, body))
+}
+
+/// Reads the frames of the next multi-part message, skipping commands.
+fn read_message<R: Read>(reader: &mut R) -> io::Result<Vec<Vec<u8>>> {
+    let mut frames = Vec::new();
+
+    loop {
+        let (flags, body) = read_frame(reader)?;
+        if flags & COMMAND != 0 {
+            continue;
+        }
+
+        frames.push(body);
+        if flags & MORE == 0 {
+            return Ok(frames);
+        }
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn frames_roundtrip() {
+        for size in &[0, 1, 255, 256, 70_000] {
+            let body = vec![0xab; *size];
+            let mut buffer = Vec::new();
+
+            write_frame(&mut buffer, MORE, &body).unwrap();
+            let (flags, read) = read_frame(&mut Cursor::new(buffer)).unwrap();
+
+            assert_eq!(flags & MORE, MORE);
+            assert_eq!(read, body);
+        }
+    }
+
+    #[test]
+    fn commands_are_skipped_when_reading_messages() {
+        let mut buffer = Vec::new();
+        write_frame(&mut buffer, COMMAND, &ready_command(b"PUB")).unwrap();
+        write_frame(&mut buffer, MORE, TOPIC).unwrap();
+        write_frame(&mut buffer, 0, b"block").unwrap();
+
+        let message = read_message(&mut Cursor::new(buffer)).unwrap();
+
+        assert_eq!(message, vec![TOPIC.to_vec(), b"block".to_vec()]);
+    }
+}
@@ -0,0 +1,663 @@
+use crate::btsieve::{
+    BlockByHash, IncludingBlock, IncludingBlocks, LatestBlock, LogsByFilter, ReceiptByHash,
+    SubscribeBlocks,
+};
+use derivative::Derivative;
+use futures::{
+    future::{self, Either, Shared},
+    sync::mpsc,
+    Async, Future, Stream,
+};
+use futures_core::compat::Future01CompatExt;
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    sync::{Arc, Mutex, Weak},
+    thread,
+    time::Duration,
+};
+use tokio::timer::Interval;
+
+/// How often the node is asked for the latest block unless it announced one
+/// in the meantime.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How many blocks are kept in memory per ledger.
+pub const DEFAULT_CAPACITY: usize = 100;
+
+/// The single source of blocks of a ledger, shared between all the
+/// transaction patterns we are watching on it.
+///
+/// A background thread asks the node for the latest block every
+/// `POLL_INTERVAL`, or right away when woken up through `wake_up` because the
+/// node announced a new block, and hands every new latest block to all
+/// subscribers. Walking the chain backwards is served from a cache of recent
+/// blocks. A block is only fetched once, subscribers asking for a block that
+/// is already being fetched wait for that request instead of making their own.
+#[derive(Derivative)]
+#[derivative(Debug(bound = "C: std::fmt::Debug"))]
+pub struct BlockPoller<C>
+where
+    C: LatestBlock + BlockByHash,
+{
+    connector: C,
+    #[derivative(Debug = "ignore")]
+    inner: Arc<Mutex<Inner<<C as BlockByHash>::Block, <C as BlockByHash>::BlockHash>>>,
+    wake_up: WakeUp,
+}
+
+type PendingBlock<B> = Shared<Box<dyn Future<Item = B, Error = ()> + Send>>;
+
+struct Inner<B, H> {
+    latest_block: Option<B>,
+    subscribers: Vec<mpsc::UnboundedSender<B>>,
+    blocks: HashMap<H, B>,
+    pending: HashMap<H, PendingBlock<B>>,
+    insertion_order: VecDeque<H>,
+    capacity: usize,
+    /// The transactions are the ones matched on this ledger, hence their
+    /// hashes are of the same type as block hashes.
+    including_blocks: HashMap<H, IncludingBlock<H>>,
+}
+
+/// Makes a `BlockPoller` ask the node for the latest block right away instead
+/// of waiting for its next poll.
+#[derive(Clone, Debug)]
+pub struct WakeUp(mpsc::UnboundedSender<()>);
+
+impl WakeUp {
+    pub fn wake_up(&self) {
+        // The poller is gone, there is nobody left to wake up.
+        let _ = self.0.unbounded_send(());
+    }
+}
+
+/// The latest block of a ledger at the time of subscribing followed by every
+/// new latest block in the order the `BlockPoller` saw them.
+///
+/// Blocks that were mined in between two polls are not part of the
+/// subscription, they have to be looked up through their children.
+#[derive(Debug)]
+pub struct Subscription<B> {
+    receiver: mpsc::UnboundedReceiver<B>,
+}
+
+impl<B> Subscription<B> {
+    /// Waits for the next new block, `None` once the poller is gone.
+    pub async fn next(&mut self) -> Option<B> {
+        let receiver = &mut self.receiver;
+
+        future::poll_fn(|| receiver.poll())
+            .compat()
+            .await
+            .unwrap_or(None)
+    }
+
+    /// The next new block if the poller already handed one out, does not wait
+    /// for one.
+    pub async fn try_next(&mut self) -> Option<B> {
+        let receiver = &mut self.receiver;
+
+        future::poll_fn(|| match receiver.poll() {
+            Ok(Async::NotReady) => Ok(Async::Ready(None)),
+            polled => polled,
+        })
+        .compat()
+        .await
+        .unwrap_or(None)
+    }
+}
+
+impl<C> Clone for BlockPoller<C>
+where
+    C: LatestBlock + BlockByHash + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            connector: self.connector.clone(),
+            inner: Arc::clone(&self.inner),
+            wake_up: self.wake_up.clone(),
+        }
+    }
+}
+
+impl<C, B, H, E> BlockPoller<C>
+where
+    C: LatestBlock<Block = B, BlockHash = H, Error = E>
+        + BlockByHash<Block = B, BlockHash = H, Error = E>
+        + Clone,
+    B: Clone + PartialEq + Send + Sync + 'static,
+    H: Eq + Hash + Clone + Send + Sync + 'static,
+    E: std::fmt::Debug + Send + 'static,
+{
+    pub fn new(connector: C) -> Self {
+        Self::with_capacity(connector, DEFAULT_CAPACITY)
+    }
+
+    /// Starts polling the node behind `connector`, the poller stops once all
+    /// clones of the returned `BlockPoller` are dropped.
+    pub fn with_capacity(connector: C, capacity: usize) -> Self {
+        let inner = Arc::new(Mutex::new(Inner {
+            latest_block: None,
+            subscribers: Vec::new(),
+            blocks: HashMap::new(),
+            pending: HashMap::new(),
+            insertion_order: VecDeque::new(),
+            capacity,
+            including_blocks: HashMap::new(),
+        }));
+        let (wake_up, wake_ups) = mpsc::unbounded();
+
+        let polling = poll(connector.clone(), Arc::downgrade(&inner), wake_ups);
+        thread::Builder::new()
+            .name("block-poller".to_owned())
+            .spawn(
+                move || match tokio::runtime::current_thread::Runtime::new() {
+                    Ok(mut runtime) => {
+                        let _ = runtime.block_on(polling);
+                    }
+                    Err(e) => log::error!("Could not start to poll for blocks: {:?}", e),
+                },
+            )
+            .expect("failed to spawn block poller thread");
+
+        Self {
+            connector,
+            inner,
+            wake_up: WakeUp(wake_up),
+        }
+    }
+
+    /// The connector the poller is in front of.
+    pub fn connector(&self) -> &C {
+        &self.connector
+    }
+
+    /// Hand this to whatever learns about new blocks before the next poll,
+    /// e.g. a subscription on the node.
+    pub fn wake_up(&self) -> WakeUp {
+        self.wake_up.clone()
+    }
+
+    fn fetch_block_by_hash(&self, block_hash: H) -> impl Future<Item = B, Error = E> + Send {
+        let pending = {
+            let mut inner = self.inner.lock().unwrap();
+
+            if let Some(block) = inner.blocks.get(&block_hash) {
+                return Either::A(future::ok(block.clone()));
+            }
+
+            match inner.pending.get(&block_hash) {
+                Some(pending) => pending.clone(),
+                None => {
+                    let pending = self.request_block(block_hash.clone());
+                    inner.pending.insert(block_hash.clone(), pending.clone());
+
+                    pending
+                }
+            }
+        };
+
+        // Only the outcome of the request is shared, not its error. Whoever
+        // waited for a failed request asks the node again.
+        let connector = self.connector.clone();
+        Either::B(pending.then(move |result| match result {
+            Ok(block) => Either::A(future::ok((*block).clone())),
+            Err(_) => Either::B(connector.block_by_hash(block_hash)),
+        }))
+    }
+
+    fn request_block(&self, block_hash: H) -> PendingBlock<B> {
+        let inner = Arc::clone(&self.inner);
+
+        let request: Box<dyn Future<Item = B, Error = ()> + Send> = Box::new(
+            self.connector
+                .block_by_hash(block_hash.clone())
+                .then(move |result| {
+                    let mut inner = inner.lock().unwrap();
+                    inner.pending.remove(&block_hash);
+
+                    match result {
+                        Ok(block) => {
+                            inner.insert(block_hash, block.clone());
+                            Ok(block)
+                        }
+                        Err(e) => {
+                            log::warn!("Could not get block: {:?}", e);
+                            Err(())
+                        }
+                    }
+                }),
+        );
+
+        request.shared()
+    }
+}
+
+/// Polls for the latest block until the `BlockPoller` is dropped.
+fn poll<C, B, H, E>(
+    mut connector: C,
+    inner: Weak<Mutex<Inner<B, H>>>,
+    wake_ups: mpsc::UnboundedReceiver<()>,
+) -> impl Future<Item = (), Error = ()>
+where
+    C: LatestBlock<Block = B, Error = E>,
+    B: Clone + PartialEq,
+    H: Eq + Hash + Clone,
+    E: std::fmt::Debug,
+{
+    let ticks = Interval::new_interval(POLL_INTERVAL)
+        .map(|_| ())
+        .map_err(|e| log::warn!("Failed to wait for the next poll: {:?}", e));
+
+    ticks.select(wake_ups).for_each(move |()| {
+        let inner = inner.clone();
+
+        connector.latest_block().then(move |result| {
+            let inner = inner.upgrade().ok_or(())?;
+
+            match result {
+                Ok(block) => inner.lock().unwrap().new_latest_block(block),
+                Err(e) => log::warn!("Could not get latest block: {:?}", e),
+            }
+
+            Ok(())
+        })
+    })
+}
+
+impl<B, H> Inner<B, H>
+where
+    B: Clone + PartialEq,
+    H: Eq + Hash + Clone,
+{
+    fn new_latest_block(&mut self, block: B) {
+        if self.latest_block.as_ref() == Some(&block) {
+            return;
+        }
+
+        // Subscribers that dropped their end are not interested anymore.
+        self.subscribers
+            .retain(|subscriber| subscriber.unbounded_send(block.clone()).is_ok());
+        self.latest_block = Some(block);
+    }
+
+    fn insert(&mut self, block_hash: H, block: B) {
+        if self.insertion_order.len() >= self.capacity {
+            if let Some(oldest) = self.insertion_order.pop_front() {
+                self.blocks.remove(&oldest);
+            }
+        }
+
+        self.insertion_order.push_back(block_hash.clone());
+        self.blocks.insert(block_hash, block);
+    }
+}
+
+impl<C, B, H, E> SubscribeBlocks for BlockPoller<C>
+where
+    C: LatestBlock<Block = B, BlockHash = H, Error = E>
+        + BlockByHash<Block = B, BlockHash = H, Error = E>
+        + Clone,
+    B: Clone + PartialEq + Send + Sync + 'static,
+    H: Eq + Hash + Clone + Send + Sync + 'static,
+    E: std::fmt::Debug + Send + 'static,
+{
+    type Block = B;
+
+    fn subscribe(&self) -> Subscription<Self::Block> {
+        let (sender, receiver) = mpsc::unbounded();
+        let mut inner = self.inner.lock().unwrap();
+
+        if let Some(latest_block) = inner.latest_block.clone() {
+            let _ = sender.unbounded_send(latest_block);
+        }
+        inner.subscribers.push(sender);
+
+        Subscription { receiver }
+    }
+}
+
+impl<C, B, H, E> IncludingBlocks for BlockPoller<C>
+where
+    C: LatestBlock<Block = B, BlockHash = H, Error = E>
+        + BlockByHash<Block = B, BlockHash = H, Error = E>
+        + Clone,
+    B: Clone + PartialEq + Send + Sync + 'static,
+    H: Eq + Hash + Clone + Send + Sync + 'static,
+    E: std::fmt::Debug + Send + 'static,
+{
+    type TransactionHash = H;
+    type BlockHash = H;
+
+    fn remember_including_block(&self, transaction_hash: H, block: IncludingBlock<H>) {
+        self.inner
+            .lock()
+            .unwrap()
+            .including_blocks
+            .insert(transaction_hash, block);
+    }
+
+    fn including_block(&self, transaction_hash: &H) -> Option<IncludingBlock<H>> {
+        self.inner
+            .lock()
+            .unwrap()
+            .including_blocks
+            .get(transaction_hash)
+            .cloned()
+    }
+
+    fn forget_including_block(&self, transaction_hash: &H) {
+        self.inner
+            .lock()
+            .unwrap()
+            .including_blocks
+            .remove(transaction_hash);
+    }
+}
+
+impl<C, B, H, E> LatestBlock for BlockPoller<C>
+where
+    C: LatestBlock<Block = B, BlockHash = H, Error = E>
+        + BlockByHash<Block = B, BlockHash = H, Error = E>
+        + Clone,
+    B: Clone + PartialEq + Send + Sync + 'static,
+    H: Eq + Hash + Clone + Send + Sync + 'static,
+    E: std::fmt::Debug + Send + 'static,
+{
+    type Error = E;
+    type Block = B;
+    type BlockHash = H;
+
+    /// The latest block the poller saw, the node is only asked if the poller
+    /// did not get an answer yet.
+    fn latest_block(
+        &mut self,
+    ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+        let latest_block = self.inner.lock().unwrap().latest_block.clone();
+
+        match latest_block {
+            Some(block) => Box::new(future::ok(block)),
+            None => self.connector.latest_block(),
+        }
+    }
+}
+
+impl<C, B, H, E> BlockByHash for BlockPoller<C>
+where
+    C: LatestBlock<Block = B, BlockHash = H, Error = E>
+        + BlockByHash<Block = B, BlockHash = H, Error = E>
+        + Clone,
+    B: Clone + PartialEq + Send + Sync + 'static,
+    H: Eq + Hash + Clone + Send + Sync + 'static,
+    E: std::fmt::Debug + Send + 'static,
+{
+    type Error = E;
+    type Block = B;
+    type BlockHash = H;
+
+    fn block_by_hash(
+        &self,
+        block_hash: Self::BlockHash,
+    ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+        Box::new(self.fetch_block_by_hash(block_hash))
+    }
+}
+
+impl<C, B, H, E> ReceiptByHash for BlockPoller<C>
+where
+    C: LatestBlock<Block = B, BlockHash = H, Error = E>
+        + BlockByHash<Block = B, BlockHash = H, Error = E>
+        + ReceiptByHash,
+    B: Clone + Send + 'static,
+    H: Eq + Hash + Clone + Send + Sync + 'static,
+    E: std::fmt::Debug + Send + 'static,
+{
+    type Receipt = C::Receipt;
+    type TransactionHash = C::TransactionHash;
+    type Error = <C as ReceiptByHash>::Error;
+
+    fn receipt_by_hash(
+        &self,
+        transaction_hash: Self::TransactionHash,
+    ) -> Box<dyn Future<Item = Self::Receipt, Error = Self::Error> + Send + 'static> {
+        self.connector.receipt_by_hash(transaction_hash)
+    }
+}
+
+impl<C, B, H, E> LogsByFilter for BlockPoller<C>
+where
+    C: LatestBlock<Block = B, BlockHash = H, Error = E>
+        + BlockByHash<Block = B, BlockHash = H, Error = E>
+        + LogsByFilter,
+    B: Clone + Send + 'static,
+    H: Eq + Hash + Clone + Send + Sync + 'static,
+    E: std::fmt::Debug + Send + 'static,
+{
+    type Filter = C::Filter;
+    type Log = C::Log;
+    type Error = <C as LogsByFilter>::Error;
+
+    fn logs_by_filter(
+        &self,
+        filter: Self::Filter,
+    ) -> Box<dyn Future<Item = Vec<Self::Log>, Error = Self::Error> + Send + 'static> {
+        self.connector.logs_by_filter(filter)
+    }
+}
+
+impl<C, B, H, E> tokio::executor::Executor for BlockPoller<C>
+where
+    C: LatestBlock<Block = B, BlockHash = H, Error = E>
+        + BlockByHash<Block = B, BlockHash = H, Error = E>
+        + tokio::executor::Executor,
+    B: Clone + Send + 'static,
+    H: Eq + Hash + Clone + Send + Sync + 'static,
+    E: std::fmt::Debug + Send + 'static,
+{
+    fn spawn(
+        &mut self,
+        future: Box<dyn Future<Item = (), Error = ()> + Send>,
+    ) -> Result<(), tokio::executor::SpawnError> {
+        self.connector.spawn(future)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_core::{FutureExt, TryFutureExt};
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Instant,
+    };
+    use tokio::{
+        prelude::IntoFuture,
+        timer::{Delay, Timeout},
+    };
+
+    /// Hands out a new latest block on every call and answers requests for a
+    /// block after `delay`.
+    #[derive(Clone, Default)]
+    struct CountingConnector {
+        latest_block_calls: Arc<AtomicUsize>,
+        block_by_hash_calls: Arc<AtomicUsize>,
+        delay: Duration,
+    }
+
+    impl LatestBlock for CountingConnector {
+        type Error = ();
+        type Block = u32;
+        type BlockHash = u32;
+
+        fn latest_block(
+            &mut self,
+        ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+            let calls = self.latest_block_calls.fetch_add(1, Ordering::SeqCst);
+
+            Box::new(Ok(calls as u32).into_future())
+        }
+    }
+
+    impl BlockByHash for CountingConnector {
+        type Error = ();
+        type Block = u32;
+        type BlockHash = u32;
+
+        fn block_by_hash(
+            &self,
+            block_hash: Self::BlockHash,
+        ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+            self.block_by_hash_calls.fetch_add(1, Ordering::SeqCst);
+
+            if self.delay == Duration::default() {
+                return Box::new(Ok(block_hash).into_future());
+            }
+
+            Box::new(
+                Delay::new(Instant::now() + self.delay)
+                    .map(move |_| block_hash)
+                    .map_err(|_| ()),
+            )
+        }
+    }
+
+    /// Never answers requests for block 0.
+    #[derive(Clone, Default)]
+    struct StallingConnector;
+
+    impl LatestBlock for StallingConnector {
+        type Error = ();
+        type Block = u32;
+        type BlockHash = u32;
+
+        fn latest_block(
+            &mut self,
+        ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+            Box::new(future::empty())
+        }
+    }
+
+    impl BlockByHash for StallingConnector {
+        type Error = ();
+        type Block = u32;
+        type BlockHash = u32;
+
+        fn block_by_hash(
+            &self,
+            block_hash: Self::BlockHash,
+        ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
+            match block_hash {
+                0 => Box::new(future::empty()),
+                _ => Box::new(Ok(block_hash).into_future()),
+            }
+        }
+    }
+
+    fn next_block(
+        mut subscription: Subscription<u32>,
+    ) -> impl Future<Item = (Option<u32>, Subscription<u32>), Error = ()> {
+        async move { Ok::<_, ()>((subscription.next().await, subscription)) }
+            .boxed()
+            .compat()
+    }
+
+    #[test]
+    fn given_many_subscribers_all_of_them_get_the_same_new_block() {
+        let connector = CountingConnector::default();
+        let poller = BlockPoller::new(connector.clone());
+
+        let subscriptions = (0..10).map(|_| poller.subscribe()).collect::<Vec<_>>();
+        let blocks = subscriptions
+            .into_iter()
+            .map(|subscription| next_block(subscription).wait().unwrap().0)
+            .collect::<Vec<_>>();
+
+        let first_block = blocks[0];
+        assert!(first_block.is_some());
+        assert!(blocks.iter().all(|block| *block == first_block));
+    }
+
+    #[test]
+    fn given_a_wake_up_the_latest_block_is_polled_before_the_next_poll_is_due() {
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let poller = BlockPoller::new(CountingConnector::default());
+        let subscription = poller.subscribe();
+
+        // Waiting for the second block aligns us with the poll interval.
+        let (_, subscription) = next_block(subscription).wait().unwrap();
+        let (polled_block, subscription) = next_block(subscription).wait().unwrap();
+        poller.wake_up().wake_up();
+        let (woken_up_block, _) = runtime
+            .block_on(Timeout::new(next_block(subscription), POLL_INTERVAL / 4))
+            .unwrap();
+
+        assert_ne!(polled_block, woken_up_block);
+    }
+
+    #[test]
+    fn given_many_subscribers_each_block_is_only_fetched_once() {
+        let connector = CountingConnector::default();
+        let poller = BlockPoller::new(connector.clone());
+
+        for _ in 0..10 {
+            for block_hash in 0..5 {
+                let block = poller.block_by_hash(block_hash).wait().unwrap();
+                assert_eq!(block, block_hash);
+            }
+        }
+
+        assert_eq!(connector.block_by_hash_calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn given_concurrent_requests_for_a_block_it_is_only_fetched_once() {
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let connector = CountingConnector {
+            delay: Duration::from_millis(100),
+            ..CountingConnector::default()
+        };
+        let poller = BlockPoller::new(connector.clone());
+
+        let requests = (0..10).map(|_| poller.block_by_hash(1)).collect::<Vec<_>>();
+        let blocks = runtime.block_on(future::join_all(requests)).unwrap();
+
+        assert_eq!(blocks, vec![1; 10]);
+        assert_eq!(connector.block_by_hash_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn given_cache_is_full_oldest_block_is_evicted() {
+        let connector = CountingConnector::default();
+        let poller = BlockPoller::with_capacity(connector.clone(), 2);
+
+        poller.block_by_hash(1).wait().unwrap();
+        poller.block_by_hash(2).wait().unwrap();
+        poller.block_by_hash(3).wait().unwrap();
+        poller.block_by_hash(1).wait().unwrap();
+
+        assert_eq!(connector.block_by_hash_calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn given_a_request_is_pending_cached_blocks_are_still_served() {
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let poller = BlockPoller::new(StallingConnector);
+        poller.block_by_hash(1).wait().unwrap();
+
+        let cached_block = future::lazy(move || {
+            let mut pending = poller.block_by_hash(0);
+            assert!(pending.poll().unwrap().is_not_ready());
+
+            poller.block_by_hash(1).map(move |block| {
+                drop(pending);
+                block
+            })
+        });
+
+        let block = runtime
+            .block_on(Timeout::new(cached_block, Duration::from_secs(1)))
+            .unwrap();
+        assert_eq!(block, 1);
+    }
+}
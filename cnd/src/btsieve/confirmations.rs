@@ -0,0 +1,67 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex},
+};
+
+/// The number of confirmations a transaction needs before we consider it to
+/// be final, together with the current number of confirmations of all the
+/// transactions we are waiting for.
+///
+/// Clones share the recorded confirmations, which allows the HTTP API to
+/// report the progress of the watchers. The confirmations of a transaction
+/// keep being counted after it reached `min_confirmations` until it is
+/// removed.
+#[derive(Clone, Debug)]
+pub struct Confirmations<H>
+where
+    H: Eq + Hash,
+{
+    min_confirmations: u32,
+    counts: Arc<Mutex<HashMap<H, u32>>>,
+}
+
+impl<H> Confirmations<H>
+where
+    H: Eq + Hash,
+{
+    pub fn new(min_confirmations: u32) -> Self {
+        Self {
+            min_confirmations,
+            counts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn min_confirmations(&self) -> u32 {
+        self.min_confirmations
+    }
+
+    pub fn get(&self, transaction_hash: &H) -> Option<u32> {
+        self.counts.lock().unwrap().get(transaction_hash).copied()
+    }
+
+    pub fn insert(&self, transaction_hash: H, confirmations: u32) {
+        self.counts
+            .lock()
+            .unwrap()
+            .insert(transaction_hash, confirmations);
+    }
+
+    /// Updates the number of confirmations of a transaction unless it was
+    /// forgotten in the meantime, returns whether it was updated.
+    pub fn update(&self, transaction_hash: &H, confirmations: u32) -> bool {
+        match self.counts.lock().unwrap().get_mut(transaction_hash) {
+            Some(count) => {
+                *count = confirmations;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stops reporting the confirmations of a transaction, e.g. because the
+    /// swap it belongs to finished.
+    pub fn remove(&self, transaction_hash: &H) {
+        self.counts.lock().unwrap().remove(transaction_hash);
+    }
+}
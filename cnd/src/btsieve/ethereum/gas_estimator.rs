@@ -0,0 +1,114 @@
+use crate::{
+    btsieve::ethereum::{UnsupportedEthereumChain, Web3Connector},
+    ethereum::{Address, Bytes, U256},
+    swap_protocols::ledger::ethereum::ChainId,
+};
+use futures_core::compat::Future01CompatExt;
+use std::collections::HashMap;
+
+/// Estimates the gas limit of the Ethereum transactions of the HTLC actions
+/// and suggests a gas price, both by asking the node of the chain the
+/// transaction is for. Estimates are raised by the `gas_safety_margin` from
+/// the `ethereum` section of the config file.
+#[derive(Clone, Debug)]
+pub struct GasEstimator {
+    connectors: HashMap<ChainId, Web3Connector>,
+    safety_margin: u32,
+}
+
+/// Gives access to the gas estimator of cnd.
+pub trait EthereumGasEstimator: Send + Sync + 'static {
+    fn ethereum_gas_estimator(&self) -> &GasEstimator;
+}
+
+impl GasEstimator {
+    pub fn new(connectors: HashMap<ChainId, Web3Connector>, safety_margin: u32) -> Self {
+        Self {
+            connectors,
+            safety_margin,
+        }
+    }
+
+    /// The gas the transaction uses plus the safety margin. `fallback` is
+    /// returned if the node cannot estimate it, e.g. because the transaction
+    /// reverts when sent from `from` or before the HTLC expired. Chains
+    /// without a node are an error rather than a reason to fall back.
+    pub async fn gas_limit(
+        &self,
+        chain_id: ChainId,
+        from: Option<Address>,
+        to: Option<Address>,
+        value: U256,
+        data: &Bytes,
+        fallback: U256,
+    ) -> anyhow::Result<U256> {
+        let connector = self.connector(chain_id)?;
+
+        match connector.estimate_gas(from, to, value, data).compat().await {
+            Ok(gas) => Ok(with_safety_margin(gas, self.safety_margin)),
+            Err(e) => {
+                log::warn!("Could not estimate gas, using {}: {:?}", fallback, e);
+                Ok(fallback)
+            }
+        }
+    }
+
+    /// The gas price the node of the chain `chain_id` suggests, `None` if it
+    /// cannot suggest one.
+    pub async fn gas_price(&self, chain_id: ChainId) -> anyhow::Result<Option<U256>> {
+        let gas_price = self
+            .connector(chain_id)?
+            .gas_price()
+            .compat()
+            .await
+            .map_err(|e| log::warn!("Could not get gas price: {:?}", e))
+            .ok();
+
+        Ok(gas_price)
+    }
+
+    fn connector(&self, chain_id: ChainId) -> anyhow::Result<&Web3Connector> {
+        self.connectors
+            .get(&chain_id)
+            .ok_or_else(|| anyhow::Error::from(UnsupportedEthereumChain { chain_id }))
+    }
+}
+
+fn with_safety_margin(gas: U256, safety_margin: u32) -> U256 {
+    gas + gas * U256::from(safety_margin) / U256::from(100)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_no_node_for_the_chain_gas_is_not_estimated() {
+        let estimator = GasEstimator::new(HashMap::new(), 20);
+
+        let gas_limit = async_std::task::block_on(estimator.gas_limit(
+            ChainId::mainnet(),
+            None,
+            None,
+            U256::zero(),
+            &Bytes(vec![]),
+            U256::from(100_000),
+        ));
+        let gas_price = async_std::task::block_on(estimator.gas_price(ChainId::mainnet()));
+
+        assert!(gas_limit.unwrap_err().is::<UnsupportedEthereumChain>());
+        assert!(gas_price.unwrap_err().is::<UnsupportedEthereumChain>());
+    }
+
+    #[test]
+    fn safety_margin_is_added_in_percent() {
+        assert_eq!(
+            with_safety_margin(U256::from(100_000), 20),
+            U256::from(120_000)
+        );
+        assert_eq!(
+            with_safety_margin(U256::from(21_000), 0),
+            U256::from(21_000)
+        );
+    }
+}
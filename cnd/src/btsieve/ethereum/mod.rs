@@ -1,18 +1,50 @@
+mod gas_estimator;
 mod transaction_pattern;
 mod web3_connector;
+mod websocket_transport;
 
 pub use self::{
+    gas_estimator::{EthereumGasEstimator, GasEstimator},
     transaction_pattern::{Event, Topic, TransactionPattern},
     web3_connector::Web3Connector,
+    websocket_transport::WebSocket,
 };
 use crate::{
-    btsieve::{BlockByHash, LatestBlock, ReceiptByHash},
-    ethereum::{Block, Transaction, TransactionAndReceipt, TransactionReceipt, H256, U256},
+    btsieve::{
+        scan_progress::ScannedBlocks, BlockByHash, Checkpoint, Confirmations, IncludingBlock,
+        LatestBlock, LogsByFilter, ReceiptByHash, ScanProgress, SubscribeBlocks, Subscription,
+    },
+    ethereum::{
+        Block, Log, Transaction, TransactionAndReceipt, TransactionReceipt, H256, U128, U256,
+    },
+    swap_protocols::ledger::ethereum::ChainId,
+};
+use futures_core::{
+    compat::Future01CompatExt,
+    future::{abortable, join, AbortHandle},
+    FutureExt, TryFutureExt,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    ops::Add,
 };
-use futures_core::{compat::Future01CompatExt, future::join, FutureExt, TryFutureExt};
-use std::{collections::HashSet, fmt::Debug, ops::Add};
 use tokio::timer::Delay;
 
+/// Tells whether cnd has a node for an Ethereum chain. Swaps on chains
+/// without a node are declined because their HTLCs cannot be watched.
+pub trait EthereumChains: Send + Sync + 'static {
+    fn supports_ethereum_chain(&self, chain_id: ChainId) -> bool;
+}
+
+/// An error type for describing that cnd has no node for the Ethereum chain
+/// of a swap.
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("no node is configured for Ethereum chain {chain_id}")]
+pub struct UnsupportedEthereumChain {
+    pub chain_id: ChainId,
+}
+
 pub async fn matching_transaction<C, E>(
     blockchain_connector: C,
     pattern: TransactionPattern,
@@ -22,17 +54,46 @@ where
     C: LatestBlock<Block = Option<Block<Transaction>>, Error = E>
         + BlockByHash<Block = Option<Block<Transaction>>, BlockHash = H256, Error = E>
         + ReceiptByHash<Receipt = Option<TransactionReceipt>, TransactionHash = H256, Error = E>
+        + SubscribeBlocks<Block = Option<Block<Transaction>>>
+        + tokio::executor::Executor
+        + Clone,
+    E: Debug + Send + 'static,
+{
+    matching_transaction_with_progress(blockchain_connector, pattern, reference_timestamp, ()).await
+}
+
+/// Like `matching_transaction` but resumes the scan from the checkpoint in
+/// `scan_progress` instead of only looking at new blocks and records there how
+/// far the blockchain was scanned.
+pub async fn matching_transaction_with_progress<C, E, P>(
+    blockchain_connector: C,
+    pattern: TransactionPattern,
+    reference_timestamp: Option<u32>,
+    scan_progress: P,
+) -> TransactionAndReceipt
+where
+    C: LatestBlock<Block = Option<Block<Transaction>>, Error = E>
+        + BlockByHash<Block = Option<Block<Transaction>>, BlockHash = H256, Error = E>
+        + ReceiptByHash<Receipt = Option<TransactionReceipt>, TransactionHash = H256, Error = E>
+        + SubscribeBlocks<Block = Option<Block<Transaction>>>
         + tokio::executor::Executor
         + Clone,
     E: Debug + Send + 'static,
+    P: ScanProgress<H256>,
 {
+    let resumed_from = scan_progress.load_checkpoint().await;
+
     let (block_queue, next_block) = async_std::sync::channel(1);
     let (find_parent_queue, next_find_parent) = async_std::sync::channel(5);
     let (look_in_the_past_queue, next_look_in_the_past) = async_std::sync::channel(5);
 
     let reference_timestamp = reference_timestamp.map(U256::from);
 
-    spawn(blockchain_connector.clone(), {
+    // All tasks spawned below are aborted once this future resolves or is
+    // dropped, e.g. because the swap that is waiting for it finished.
+    let mut tasks = AbortOnDrop::default();
+
+    tasks.spawn(blockchain_connector.clone(), {
         let mut connector = blockchain_connector.clone();
         let block_queue = block_queue.clone();
         let find_parent_queue = find_parent_queue.clone();
@@ -41,13 +102,11 @@ where
         async move {
             let mut sent_blockhashes: HashSet<H256> = HashSet::new();
 
-            loop {
-                Delay::new(std::time::Instant::now().add(std::time::Duration::from_secs(1)))
-                    .compat()
-                    .await
-                    .unwrap();
+            let mut new_blocks = connector.subscribe();
+            let mut latest_block = connector.latest_block().compat().await;
 
-                match connector.latest_block().compat().await {
+            loop {
+                match latest_block {
                     Ok(Some(block)) if block.hash.is_some() => {
                         let blockhash = block.hash.expect("cannot fail");
 
@@ -56,7 +115,11 @@ where
 
                             join(
                                 block_queue.send(block.clone()),
-                                find_parent_queue.send((blockhash, block.parent_hash)),
+                                find_parent_queue.send((
+                                    blockhash,
+                                    block.parent_hash,
+                                    block.timestamp,
+                                )),
                             )
                             .await;
 
@@ -75,13 +138,19 @@ where
                         log::warn!("Could not get latest block: {:?}", e);
                     }
                 };
+
+                latest_block = match new_blocks.next().await {
+                    Some(block) => Ok(block),
+                    // The poller is gone because we are shutting down.
+                    None => return,
+                };
             }
         }
     });
 
     let (fetch_block_by_hash_queue, next_hash) = async_std::sync::channel(5);
 
-    spawn(blockchain_connector.clone(), {
+    tasks.spawn(blockchain_connector.clone(), {
         let connector = blockchain_connector.clone();
         let block_queue = block_queue.clone();
         let fetch_block_by_hash_queue = fetch_block_by_hash_queue.clone();
@@ -94,7 +163,11 @@ where
                             Ok(Some(block)) => {
                                 join(
                                     block_queue.send(block.clone()),
-                                    find_parent_queue.send((blockhash, block.parent_hash)),
+                                    find_parent_queue.send((
+                                        blockhash,
+                                        block.parent_hash,
+                                        block.timestamp,
+                                    )),
                                 )
                                 .await;
                             }
@@ -108,34 +181,45 @@ where
                             }
                         };
                     }
-                    None => unreachable!("sender cannot be dropped"),
+                    // All senders are gone because we are shutting down.
+                    None => return,
                 }
             }
         }
     });
 
-    spawn(blockchain_connector.clone(), {
+    tasks.spawn(blockchain_connector.clone(), {
         async move {
             let mut prev_blockhashes: HashSet<H256> = HashSet::new();
 
             loop {
                 match next_find_parent.recv().await {
-                    Some((blockhash, parent_blockhash)) => {
+                    Some((blockhash, parent_blockhash, timestamp)) => {
                         prev_blockhashes.insert(blockhash);
 
+                        // When resuming we also fill the gap between the
+                        // checkpoint and the first block we see.
+                        let parent_already_scanned = resumed_from
+                            .map(|checkpoint| checkpoint.covers(&parent_blockhash))
+                            .unwrap_or(false);
+                        let look_for_parent = prev_blockhashes.len() > 1 || resumed_from.is_some();
+
                         if !prev_blockhashes.contains(&parent_blockhash)
-                            && prev_blockhashes.len() > 1
+                            && look_for_parent
+                            && !parent_already_scanned
+                            && !older_than(timestamp, reference_timestamp)
                         {
                             fetch_block_by_hash_queue.send(parent_blockhash).await
                         }
                     }
-                    None => unreachable!("senders cannot be dropped"),
+                    // All senders are gone because we are shutting down.
+                    None => return,
                 }
             }
         }
     });
 
-    spawn(blockchain_connector.clone(), {
+    tasks.spawn(blockchain_connector.clone(), {
         let connector = blockchain_connector.clone();
 
         async move {
@@ -155,6 +239,9 @@ where
                                         look_in_the_past_queue.send(block.parent_hash),
                                     )
                                     .await;
+                                } else if older_than(block.timestamp, reference_timestamp) {
+                                    // This is the reference point of the scan.
+                                    block_queue.send(block).await
                                 }
                             }
                             Ok(None) => {
@@ -171,7 +258,8 @@ where
                             }
                         }
                     }
-                    None => unreachable!("senders cannot be dropped"),
+                    // All senders are gone because we are shutting down.
+                    None => return,
                 }
             }
         }
@@ -179,14 +267,45 @@ where
 
     let (matching_transaction_queue, matching_transaction) = async_std::sync::channel(1);
 
-    spawn(blockchain_connector.clone(), {
+    tasks.spawn(blockchain_connector.clone(), {
         let connector = blockchain_connector.clone();
 
         async move {
+            let mut scanned_blocks = ScannedBlocks::new(resumed_from);
+            let mut first_block = true;
+
             loop {
                 match next_block.recv().await {
                     Some(block) => {
+                        // Without a reference timestamp nothing before the
+                        // first block needs to be checked, unless we have to
+                        // fill the gap to the checkpoint we resumed from.
+                        let starts_here = first_block
+                            && reference_timestamp.is_none()
+                            && resumed_from.is_none();
+                        first_block = false;
+
+                        if older_than(block.timestamp, reference_timestamp) {
+                            if let Some(checkpoint) = block
+                                .hash
+                                .and_then(|hash| scanned_blocks.reached_reference_point(hash))
+                            {
+                                scan_progress.save_checkpoint(checkpoint).await;
+                            }
+                            continue;
+                        }
+
                         let needs_receipt = pattern.needs_receipts(&block);
+                        let scanned_block = match block {
+                            Block {
+                                hash: Some(hash),
+                                number: Some(number),
+                                parent_hash,
+                                ..
+                            } => Some((hash, parent_hash, number.as_u64())),
+                            _ => None,
+                        };
+                        let mut fully_checked = true;
 
                         for transaction in block.transactions.into_iter() {
                             if needs_receipt {
@@ -197,6 +316,7 @@ where
                                     Ok(Some(receipt)) => receipt,
                                     Ok(None) => {
                                         log::warn!("Could not get transaction receipt");
+                                        fully_checked = false;
                                         continue;
                                     }
                                     Err(e) => {
@@ -205,6 +325,7 @@ where
                                             transaction.hash,
                                             e
                                         );
+                                        fully_checked = false;
                                         continue;
                                     }
                                 };
@@ -245,8 +366,23 @@ where
                                     .await;
                             }
                         }
+
+                        if let (true, Some((hash, parent_hash, number))) =
+                            (fully_checked, scanned_block)
+                        {
+                            let mut checkpoint = scanned_blocks.insert(hash, parent_hash, number);
+                            if starts_here {
+                                checkpoint =
+                                    scanned_blocks.reached_reference_point(hash).or(checkpoint);
+                            }
+
+                            if let Some(checkpoint) = checkpoint {
+                                scan_progress.save_checkpoint(checkpoint).await;
+                            }
+                        }
                     }
-                    None => unreachable!("senders cannot be dropped"),
+                    // All senders are gone because we are shutting down.
+                    None => return,
                 }
             }
         }
@@ -258,11 +394,635 @@ where
         .expect("sender cannot be dropped")
 }
 
-fn spawn(
-    mut executor: impl tokio::executor::Executor,
-    future: impl std::future::Future<Output = ()> + Send + 'static + Sized,
-) {
-    executor
-        .spawn(Box::new(future.unit_error().boxed().compat()))
-        .unwrap()
+/// Whether a block mined at `timestamp` was mined before the reference
+/// timestamp, i.e. neither it nor any block before it can include the
+/// transaction we are looking for.
+fn older_than(timestamp: U256, reference_timestamp: Option<U256>) -> bool {
+    reference_timestamp
+        .map(|reference_timestamp| timestamp < reference_timestamp)
+        .unwrap_or(false)
+}
+
+/// Selects the logs emitted in the blocks `from_block..=to_block` that could
+/// have been emitted for `event`, i.e. an `eth_getLogs` query.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogFilter {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub event: Event,
+}
+
+/// How many blocks a single `eth_getLogs` query covers at most.
+pub const LOGS_QUERY_RANGE: u64 = 1_000;
+
+/// How many of the blocks before the next unscanned one are queried again so
+/// that we see logs in blocks that replaced orphaned ones.
+pub const LOGS_REORG_DEPTH: u64 = 12;
+
+/// Like `matching_transaction_with_progress` but finds the transaction through
+/// the logs it emitted instead of looking at every transaction of every block.
+///
+/// The node is asked for the logs matching the first event of `pattern`, only
+/// the blocks and receipts of the transactions that emitted such a log are
+/// fetched to check them against the whole pattern. Patterns without events
+/// are handed to `matching_transaction_with_progress`.
+pub async fn matching_transaction_by_logs<C, E, P>(
+    mut blockchain_connector: C,
+    pattern: TransactionPattern,
+    scan_progress: P,
+) -> TransactionAndReceipt
+where
+    C: LatestBlock<Block = Option<Block<Transaction>>, Error = E>
+        + BlockByHash<Block = Option<Block<Transaction>>, BlockHash = H256, Error = E>
+        + ReceiptByHash<Receipt = Option<TransactionReceipt>, TransactionHash = H256, Error = E>
+        + LogsByFilter<Filter = LogFilter, Log = Log, Error = E>
+        + SubscribeBlocks<Block = Option<Block<Transaction>>>
+        + tokio::executor::Executor
+        + Clone,
+    E: Debug + Send + 'static,
+    P: ScanProgress<H256>,
+{
+    let event = match pattern.events.as_ref().and_then(|events| events.first()) {
+        Some(event) => event.clone(),
+        None => {
+            return matching_transaction_with_progress(
+                blockchain_connector,
+                pattern,
+                None,
+                scan_progress,
+            )
+            .await
+        }
+    };
+
+    let mut checkpoint = scan_progress.load_checkpoint().await;
+    let mut new_blocks = blockchain_connector.subscribe();
+
+    loop {
+        let (latest_hash, latest_number) = match blockchain_connector.latest_block().compat().await
+        {
+            Ok(Some(Block {
+                hash: Some(hash),
+                number: Some(number),
+                ..
+            })) => (hash, number.as_u64()),
+            Ok(_) => {
+                log::warn!("Could not get latest block");
+                delay().await;
+                continue;
+            }
+            Err(e) => {
+                log::warn!("Could not get latest block: {:?}", e);
+                delay().await;
+                continue;
+            }
+        };
+
+        let already_scanned = checkpoint
+            .map(|checkpoint| checkpoint.block_hash == latest_hash)
+            .unwrap_or(false);
+        if !already_scanned {
+            let from_block = checkpoint
+                .map(|checkpoint| (checkpoint.block_height + 1).saturating_sub(LOGS_REORG_DEPTH))
+                .unwrap_or(latest_number);
+
+            match matching_transaction_in_range(
+                &blockchain_connector,
+                &pattern,
+                &event,
+                from_block,
+                latest_number,
+            )
+            .await
+            {
+                Ok(Some(transaction_and_receipt)) => return transaction_and_receipt,
+                Ok(None) => {
+                    let latest = Checkpoint {
+                        block_hash: latest_hash,
+                        block_height: latest_number,
+                    };
+                    scan_progress.save_checkpoint(latest).await;
+                    checkpoint = Some(latest);
+                }
+                Err(()) => {
+                    log::warn!(
+                        "Could not scan blocks {} to {} for logs, trying again",
+                        from_block,
+                        latest_number
+                    );
+                    delay().await;
+                    continue;
+                }
+            }
+        }
+
+        wait_for_new_block(&mut new_blocks).await;
+    }
+}
+
+async fn matching_transaction_in_range<C, E>(
+    blockchain_connector: &C,
+    pattern: &TransactionPattern,
+    event: &Event,
+    from_block: u64,
+    to_block: u64,
+) -> Result<Option<TransactionAndReceipt>, ()>
+where
+    C: BlockByHash<Block = Option<Block<Transaction>>, BlockHash = H256, Error = E>
+        + ReceiptByHash<Receipt = Option<TransactionReceipt>, TransactionHash = H256, Error = E>
+        + LogsByFilter<Filter = LogFilter, Log = Log, Error = E>,
+    E: Debug + Send + 'static,
+{
+    let mut range_start = from_block;
+
+    while range_start <= to_block {
+        let range_end = std::cmp::min(range_start + LOGS_QUERY_RANGE - 1, to_block);
+
+        let logs = blockchain_connector
+            .logs_by_filter(LogFilter {
+                from_block: range_start,
+                to_block: range_end,
+                event: event.clone(),
+            })
+            .compat()
+            .await
+            .map_err(|e| {
+                log::warn!(
+                    "Could not get logs of blocks {} to {}: {:?}",
+                    range_start,
+                    range_end,
+                    e
+                )
+            })?;
+
+        for log in logs.into_iter().filter(|log| !log.is_removed()) {
+            if let Some(transaction_and_receipt) =
+                transaction_emitting_log(blockchain_connector, pattern, log).await?
+            {
+                return Ok(Some(transaction_and_receipt));
+            }
+        }
+
+        range_start = range_end + 1;
+    }
+
+    Ok(None)
+}
+
+async fn transaction_emitting_log<C, E>(
+    blockchain_connector: &C,
+    pattern: &TransactionPattern,
+    log: Log,
+) -> Result<Option<TransactionAndReceipt>, ()>
+where
+    C: BlockByHash<Block = Option<Block<Transaction>>, BlockHash = H256, Error = E>
+        + ReceiptByHash<Receipt = Option<TransactionReceipt>, TransactionHash = H256, Error = E>,
+    E: Debug + Send + 'static,
+{
+    let (block_hash, transaction_hash) = match (log.block_hash, log.transaction_hash) {
+        (Some(block_hash), Some(transaction_hash)) => (block_hash, transaction_hash),
+        _ => {
+            log::warn!("Ignoring pending log {:?}", log);
+            return Ok(None);
+        }
+    };
+
+    let block = blockchain_connector
+        .block_by_hash(block_hash)
+        .compat()
+        .await
+        .map_err(|e| log::warn!("Could not get block with hash {}: {:?}", block_hash, e))?
+        .ok_or_else(|| log::warn!("Block with hash {} does not exist", block_hash))?;
+    let transaction = match block
+        .transactions
+        .into_iter()
+        .find(|transaction| transaction.hash == transaction_hash)
+    {
+        Some(transaction) => transaction,
+        None => {
+            log::warn!(
+                "Block {} does not include transaction {} that emitted a log in it",
+                block_hash,
+                transaction_hash
+            );
+            return Ok(None);
+        }
+    };
+
+    let receipt = blockchain_connector
+        .receipt_by_hash(transaction_hash)
+        .compat()
+        .await
+        .map_err(|e| {
+            log::warn!(
+                "Could not retrieve transaction receipt for {}: {:?}",
+                transaction_hash,
+                e
+            )
+        })?
+        .ok_or_else(|| log::warn!("Could not get transaction receipt for {}", transaction_hash))?;
+
+    if pattern.matches(&transaction, Some(&receipt)) {
+        Ok(Some(TransactionAndReceipt {
+            transaction,
+            receipt,
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Resolves once `transaction` is no longer part of the best chain, i.e. the
+/// block that included it was orphaned and none of the blocks that replaced it
+/// include the transaction again.
+///
+/// The transaction is followed from the block it was mined in according to
+/// the node. For a transaction without that information that cannot be found
+/// within the latest `SEARCH_DEPTH` blocks, it is buried too deep to be
+/// retracted and this never resolves.
+pub async fn transaction_retracted<C, E>(mut blockchain_connector: C, transaction: Transaction)
+where
+    C: LatestBlock<Block = Option<Block<Transaction>>, Error = E>
+        + BlockByHash<Block = Option<Block<Transaction>>, BlockHash = H256, Error = E>
+        + SubscribeBlocks<Block = Option<Block<Transaction>>>
+        + Clone,
+    E: Debug + Send + 'static,
+{
+    let mut new_blocks = blockchain_connector.subscribe();
+    let mut tracker = Tracker::new(&transaction);
+
+    loop {
+        match tracker.depth(&mut blockchain_connector).await {
+            Ok(Depth::Confirmations(_)) | Ok(Depth::BeyondSearchDepth) => {}
+            Ok(Depth::NotIncluded) => {
+                log::info!(
+                    "Transaction {} is no longer part of the best chain",
+                    transaction.hash
+                );
+                return;
+            }
+            Err(e) => {
+                log::warn!("Could not walk back the blockchain: {:?}", e);
+                delay().await;
+                continue;
+            }
+        }
+
+        wait_for_new_block(&mut new_blocks).await;
+    }
+}
+
+/// Resolves once `transaction` is part of the best chain and has at least
+/// `confirmations.min_confirmations()` confirmations.
+///
+/// While waiting, the current number of confirmations of the transaction is
+/// recorded in `confirmations`.
+pub async fn transaction_confirmed<C, E>(
+    mut blockchain_connector: C,
+    transaction: Transaction,
+    confirmations: Confirmations<H256>,
+) where
+    C: LatestBlock<Block = Option<Block<Transaction>>, Error = E>
+        + BlockByHash<Block = Option<Block<Transaction>>, BlockHash = H256, Error = E>
+        + SubscribeBlocks<Block = Option<Block<Transaction>>>
+        + Clone,
+    E: Debug + Send + 'static,
+{
+    let mut new_blocks = blockchain_connector.subscribe();
+    let mut tracker = Tracker::new(&transaction);
+
+    loop {
+        match tracker.depth(&mut blockchain_connector).await {
+            Ok(depth) => {
+                let count = depth.confirmations();
+                confirmations.insert(transaction.hash, count);
+
+                if count >= confirmations.min_confirmations() {
+                    return;
+                }
+            }
+            Err(e) => {
+                log::warn!("Could not walk back the blockchain: {:?}", e);
+                delay().await;
+                continue;
+            }
+        }
+
+        wait_for_new_block(&mut new_blocks).await;
+    }
+}
+
+/// Keeps counting the confirmations of `transaction` after
+/// `transaction_confirmed` resolved, see `bitcoin::follow_confirmations`.
+pub async fn follow_confirmations<C, E>(
+    mut blockchain_connector: C,
+    transaction: Transaction,
+    confirmations: Confirmations<H256>,
+) where
+    C: LatestBlock<Block = Option<Block<Transaction>>, Error = E>
+        + BlockByHash<Block = Option<Block<Transaction>>, BlockHash = H256, Error = E>
+        + SubscribeBlocks<Block = Option<Block<Transaction>>>
+        + Clone,
+    E: Debug + Send + 'static,
+{
+    let mut new_blocks = blockchain_connector.subscribe();
+    let mut tracker = Tracker::new(&transaction);
+
+    loop {
+        wait_for_new_block(&mut new_blocks).await;
+
+        let depth = match tracker.depth(&mut blockchain_connector).await {
+            Ok(depth) => depth,
+            Err(e) => {
+                log::warn!("Could not walk back the blockchain: {:?}", e);
+                continue;
+            }
+        };
+        let count = depth.confirmations();
+
+        if !confirmations.update(&transaction.hash, count) || depth.is_final() {
+            return;
+        }
+    }
+}
+
+/// How many blocks we look back to find the block that includes a transaction
+/// if we do not know in which block it was mined. Transactions that are
+/// included deeper than that are considered final, they have `SEARCH_DEPTH`
+/// confirmations as far as we are concerned.
+pub const SEARCH_DEPTH: usize = 100;
+
+/// Where a transaction is in the best chain as far as a `Tracker` can tell.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Depth {
+    Confirmations(u32),
+    NotIncluded,
+    /// We do not know in which block the transaction was included and it is
+    /// not within the latest `SEARCH_DEPTH` blocks.
+    BeyondSearchDepth,
+}
+
+impl Depth {
+    fn confirmations(self) -> u32 {
+        match self {
+            Depth::Confirmations(confirmations) => confirmations,
+            Depth::NotIncluded => 0,
+            Depth::BeyondSearchDepth => SEARCH_DEPTH as u32,
+        }
+    }
+
+    /// Whether the transaction is too deep to still be followed.
+    fn is_final(self) -> bool {
+        match self {
+            Depth::Confirmations(confirmations) => confirmations >= SEARCH_DEPTH as u32,
+            Depth::NotIncluded => true,
+            Depth::BeyondSearchDepth => true,
+        }
+    }
+}
+
+/// Follows the position of a transaction in the best chain across polls of
+/// the latest block.
+#[derive(Debug)]
+struct Tracker {
+    transaction_hash: H256,
+    /// The block including the transaction and all its known descendants,
+    /// mapped to the number of confirmations of the transaction if that block
+    /// is the tip of the chain.
+    best_chain: HashMap<H256, u32>,
+    including_block_number: Option<U128>,
+    /// Whether we know that the transaction was part of the best chain at
+    /// some point, either because we know the block it was mined in or
+    /// because we saw it while watching it.
+    seen: bool,
+    /// Whether the transaction was never seen and not within the latest
+    /// `SEARCH_DEPTH` blocks the first time we looked for it.
+    buried: bool,
+}
+
+impl Tracker {
+    /// Starts following `transaction` from the block it was mined in, if the
+    /// node told us that block.
+    fn new(transaction: &Transaction) -> Self {
+        let mut best_chain = HashMap::new();
+        let including_block_number = including_block(transaction).map(|including_block| {
+            best_chain.insert(including_block.block_hash, 1);
+            U128::from(including_block.block_height)
+        });
+
+        Self {
+            transaction_hash: transaction.hash,
+            seen: including_block_number.is_some(),
+            best_chain,
+            including_block_number,
+            buried: false,
+        }
+    }
+
+    /// Returns the number of confirmations of the transaction in the current
+    /// best chain, whether the best chain does not include it or whether we
+    /// gave up looking for it.
+    async fn depth<C, E>(&mut self, blockchain_connector: &mut C) -> Result<Depth, WalkError<E>>
+    where
+        C: LatestBlock<Block = Option<Block<Transaction>>, Error = E>
+            + BlockByHash<Block = Option<Block<Transaction>>, BlockHash = H256, Error = E>,
+        E: Debug,
+    {
+        if self.buried {
+            return Ok(Depth::BeyondSearchDepth);
+        }
+
+        let latest_block = blockchain_connector
+            .latest_block()
+            .compat()
+            .await
+            .map_err(WalkError::Connector)?
+            .ok_or(WalkError::MissingLatestBlock)?;
+        let latest_blockhash = latest_block.hash.ok_or(WalkError::PendingBlock)?;
+
+        match walk_back(
+            blockchain_connector,
+            latest_block,
+            self.transaction_hash,
+            &self.best_chain,
+            self.including_block_number,
+            self.seen,
+        )
+        .await?
+        {
+            Walk::Connected {
+                visited,
+                confirmations,
+            } => self.extend(visited, confirmations),
+            Walk::Found {
+                visited,
+                including_block_number,
+            } => {
+                self.seen = true;
+                self.best_chain.clear();
+                self.including_block_number = Some(including_block_number);
+                self.extend(visited, 0);
+            }
+            Walk::NotFound => {
+                self.best_chain.clear();
+                self.including_block_number = None;
+            }
+            Walk::BeyondSearchDepth => {
+                self.buried = true;
+                return Ok(Depth::BeyondSearchDepth);
+            }
+        }
+
+        Ok(match self.best_chain.get(&latest_blockhash) {
+            Some(confirmations) => Depth::Confirmations(*confirmations),
+            None => Depth::NotIncluded,
+        })
+    }
+
+    /// Adds the `visited` blocks, ordered from newest to oldest, on top of a
+    /// block at which the transaction has `confirmations` confirmations.
+    fn extend(&mut self, visited: Vec<H256>, confirmations: u32) {
+        for (depth, blockhash) in visited.into_iter().rev().enumerate() {
+            self.best_chain
+                .insert(blockhash, confirmations + depth as u32 + 1);
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Walk {
+    Connected {
+        visited: Vec<H256>,
+        confirmations: u32,
+    },
+    Found {
+        visited: Vec<H256>,
+        including_block_number: U128,
+    },
+    NotFound,
+    /// The transaction was never seen and is not within the latest
+    /// `SEARCH_DEPTH` blocks.
+    BeyondSearchDepth,
+}
+
+#[derive(Debug)]
+enum WalkError<E> {
+    Connector(E),
+    MissingLatestBlock,
+    MissingBlock(H256),
+    PendingBlock,
+}
+
+async fn walk_back<C, E>(
+    blockchain_connector: &C,
+    latest_block: Block<Transaction>,
+    transaction_hash: H256,
+    best_chain: &HashMap<H256, u32>,
+    including_block_number: Option<U128>,
+    seen: bool,
+) -> Result<Walk, WalkError<E>>
+where
+    C: BlockByHash<Block = Option<Block<Transaction>>, BlockHash = H256, Error = E>,
+    E: Debug,
+{
+    let mut visited = Vec::new();
+    let mut block = latest_block;
+
+    loop {
+        let (blockhash, number) = match (block.hash, block.number) {
+            (Some(blockhash), Some(number)) => (blockhash, number),
+            _ => return Err(WalkError::PendingBlock),
+        };
+
+        if let Some(confirmations) = best_chain.get(&blockhash) {
+            return Ok(Walk::Connected {
+                visited,
+                confirmations: *confirmations,
+            });
+        }
+        visited.push(blockhash);
+
+        if block
+            .transactions
+            .iter()
+            .any(|transaction| transaction.hash == transaction_hash)
+        {
+            return Ok(Walk::Found {
+                visited,
+                including_block_number: number,
+            });
+        }
+
+        let walked_past_including_block = match including_block_number {
+            Some(including_block_number) => number <= including_block_number,
+            None => visited.len() >= SEARCH_DEPTH,
+        };
+        if walked_past_including_block {
+            return Ok(if seen {
+                Walk::NotFound
+            } else {
+                Walk::BeyondSearchDepth
+            });
+        }
+
+        block = blockchain_connector
+            .block_by_hash(block.parent_hash)
+            .compat()
+            .await
+            .map_err(WalkError::Connector)?
+            .ok_or_else(|| WalkError::MissingBlock(block.parent_hash))?;
+    }
+}
+
+/// The block a mined transaction was included in according to the node.
+fn including_block(transaction: &Transaction) -> Option<IncludingBlock<H256>> {
+    match (transaction.block_hash, transaction.block_number) {
+        (Some(block_hash), Some(block_number)) => Some(IncludingBlock {
+            block_hash,
+            block_height: block_number.low_u64(),
+        }),
+        _ => None,
+    }
+}
+
+/// Waits for the next block, the subscription only ends if the poller is gone
+/// in which case we fall back to polling ourselves.
+async fn wait_for_new_block<B>(new_blocks: &mut Subscription<B>) {
+    if new_blocks.next().await.is_none() {
+        delay().await;
+    }
+}
+
+async fn delay() {
+    Delay::new(std::time::Instant::now().add(std::time::Duration::from_secs(1)))
+        .compat()
+        .await
+        .unwrap_or_else(|e| log::warn!("Failed to wait for delay: {:?}", e));
+}
+
+#[derive(Default)]
+struct AbortOnDrop(Vec<AbortHandle>);
+
+impl AbortOnDrop {
+    fn spawn(
+        &mut self,
+        mut executor: impl tokio::executor::Executor,
+        future: impl std::future::Future<Output = ()> + Send + 'static + Sized,
+    ) {
+        let (future, handle) = abortable(future);
+
+        executor
+            .spawn(Box::new(future.map(|_| Ok::<(), ()>(())).boxed().compat()))
+            .unwrap();
+
+        self.0.push(handle);
+    }
+}
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        for handle in self.0.iter() {
+            handle.abort();
+        }
+    }
 }
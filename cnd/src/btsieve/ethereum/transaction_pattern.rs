@@ -1,7 +1,7 @@
 use crate::ethereum::{Address, Block, Bytes, Transaction, TransactionReceipt, H256};
 use ethbloom::Input;
 
-#[derive(Clone, Default, Debug, Eq, PartialEq)]
+#[derive(Clone, Default, Debug, Eq, PartialEq, serde::Serialize)]
 /// If the field is set to Some(foo) then only transactions matching foo are
 /// returned. Otherwise, when the field is set to None, no pattern matching is
 /// done for this field.
@@ -123,7 +123,7 @@ fn events_exist_in_receipt(events: &[Event], receipt: &TransactionReceipt) -> bo
     })
 }
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, serde::Serialize)]
 pub struct Topic(pub H256);
 
 /// Event  work similar as web3 filters:
@@ -152,7 +152,7 @@ pub struct Topic(pub H256);
 /// .. ] //Other data omitted
 /// }
 /// ```
-#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[derive(Debug, Clone, Default, Eq, PartialEq, serde::Serialize)]
 pub struct Event {
     pub address: Option<Address>,
     pub data: Option<Bytes>,
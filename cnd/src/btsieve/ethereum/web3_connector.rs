@@ -1,38 +1,146 @@
 use crate::{
-    btsieve::{BlockByHash, LatestBlock, ReceiptByHash},
+    btsieve::{
+        ethereum::{LogFilter, Topic, WebSocket},
+        BlockByHash, LatestBlock, LogsByFilter, ReceiptByHash, WakeUp,
+    },
     ethereum::{
         web3::{
             self,
+            helpers::CallFuture,
             transports::{EventLoopHandle, Http},
-            Web3,
+            RequestId, Transport, Web3,
         },
-        BlockId, BlockNumber,
+        Address, Block, BlockId, BlockNumber, Bytes, FilterBuilder, Transaction, H256, U256,
     },
 };
-use futures::Future;
+use futures::{future, Future};
+use jsonrpc_core as rpc;
 use reqwest::Url;
-use std::sync::Arc;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
 
 #[derive(Clone, Debug)]
 pub struct Web3Connector {
-    web3: Arc<Web3<Http>>,
+    web3: Arc<Web3<NodeTransport>>,
+    latest_block: Arc<Mutex<Option<Block<Transaction>>>>,
     task_executor: tokio::runtime::TaskExecutor,
 }
 
 impl Web3Connector {
+    /// Connects to the node over HTTP or, for `ws://` urls, over a WebSocket.
+    ///
+    /// The HTTP transport runs on its own event loop which stops once the
+    /// returned handle is dropped.
     pub fn new(
         node_url: Url,
         task_executor: tokio::runtime::TaskExecutor,
-    ) -> Result<(Self, EventLoopHandle), web3::Error> {
-        let (event_loop_handle, http_transport) = Http::new(node_url.as_str())?;
+    ) -> Result<(Self, Option<EventLoopHandle>), web3::Error> {
+        let (transport, event_loop_handle) = match node_url.scheme() {
+            "ws" => (
+                NodeTransport::WebSocket(WebSocket::connect(&node_url, task_executor.clone())?),
+                None,
+            ),
+            _ => {
+                let (event_loop_handle, http_transport) = Http::new(node_url.as_str())?;
+                (NodeTransport::Http(http_transport), Some(event_loop_handle))
+            }
+        };
+
         Ok((
             Self {
-                web3: Arc::new(Web3::new(http_transport)),
+                web3: Arc::new(Web3::new(transport)),
+                latest_block: Arc::new(Mutex::new(None)),
                 task_executor,
             },
             event_loop_handle,
         ))
     }
+
+    /// Hands a signed transaction to the node through
+    /// `eth_sendRawTransaction`.
+    pub fn send_raw_transaction(
+        &self,
+        transaction: Bytes,
+    ) -> impl Future<Item = H256, Error = web3::Error> + Send {
+        self.web3.eth().send_raw_transaction(transaction)
+    }
+
+    /// The number of transactions sent from `address` up to and including
+    /// `block`, i.e. the nonce of the next transaction.
+    pub fn transaction_count(
+        &self,
+        address: Address,
+        block: BlockNumber,
+    ) -> impl Future<Item = U256, Error = web3::Error> + Send {
+        self.web3.eth().transaction_count(address, Some(block))
+    }
+
+    /// Asks the node through `eth_estimateGas` how much gas a transaction
+    /// uses, without `to` it deploys a contract.
+    pub fn estimate_gas(
+        &self,
+        from: Option<Address>,
+        to: Option<Address>,
+        value: U256,
+        data: &Bytes,
+    ) -> impl Future<Item = U256, Error = web3::Error> + Send {
+        let request = json!({
+            "from": from,
+            "to": to,
+            "value": value,
+            "data": data,
+        });
+
+        CallFuture::new(
+            self.web3
+                .transport()
+                .execute("eth_estimateGas", vec![request]),
+        )
+    }
+
+    /// The gas price the node suggests through `eth_gasPrice`.
+    pub fn gas_price(&self) -> impl Future<Item = U256, Error = web3::Error> + Send {
+        self.web3.eth().gas_price()
+    }
+
+    /// Wakes up `wake_up` whenever the node announces a new block through the
+    /// `newHeads` subscription, over HTTP there is nothing to subscribe to.
+    pub fn wake_up_on_new_blocks(&self, wake_up: WakeUp) {
+        if let NodeTransport::WebSocket(websocket) = self.web3.transport() {
+            websocket.wake_up_on_new_blocks(wake_up)
+        }
+    }
+
+    fn latest_head(&self) -> Option<crate::ethereum::H256> {
+        match self.web3.transport() {
+            NodeTransport::WebSocket(websocket) => websocket.latest_head(),
+            NodeTransport::Http(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum NodeTransport {
+    Http(Http),
+    WebSocket(WebSocket),
+}
+
+impl Transport for NodeTransport {
+    type Out = Box<dyn Future<Item = rpc::Value, Error = web3::Error> + Send>;
+
+    fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+        match self {
+            NodeTransport::Http(http) => http.prepare(method, params),
+            NodeTransport::WebSocket(websocket) => websocket.prepare(method, params),
+        }
+    }
+
+    fn send(&self, id: RequestId, request: rpc::Call) -> Self::Out {
+        match self {
+            NodeTransport::Http(http) => Box::new(http.send(id, request)),
+            NodeTransport::WebSocket(websocket) => websocket.send(id, request),
+        }
+    }
 }
 
 impl LatestBlock for Web3Connector {
@@ -44,10 +152,35 @@ impl LatestBlock for Web3Connector {
         &mut self,
     ) -> Box<dyn Future<Item = Self::Block, Error = Self::Error> + Send + 'static> {
         let web = self.web3.clone();
-        Box::new(
-            web.eth()
-                .block_with_txs(BlockId::Number(BlockNumber::Latest)),
-        )
+
+        // Without a `newHeads` subscription we have to poll for the latest block.
+        let latest_head = match self.latest_head() {
+            Some(latest_head) => latest_head,
+            None => {
+                return Box::new(
+                    web.eth()
+                        .block_with_txs(BlockId::Number(BlockNumber::Latest)),
+                )
+            }
+        };
+
+        let latest_block = self.latest_block.lock().unwrap().clone();
+        match latest_block {
+            Some(block) if block.hash == Some(latest_head) => Box::new(future::ok(Some(block))),
+            _ => {
+                let cache = self.latest_block.clone();
+
+                Box::new(
+                    web.eth()
+                        .block_with_txs(BlockId::Hash(latest_head))
+                        .inspect(move |block| {
+                            if let Some(block) = block {
+                                *cache.lock().unwrap() = Some(block.clone());
+                            }
+                        }),
+                )
+            }
+        }
     }
 }
 
@@ -79,6 +212,45 @@ impl ReceiptByHash for Web3Connector {
     }
 }
 
+impl LogsByFilter for Web3Connector {
+    type Filter = LogFilter;
+    type Log = crate::ethereum::Log;
+    type Error = crate::ethereum::web3::Error;
+
+    fn logs_by_filter(
+        &self,
+        filter: Self::Filter,
+    ) -> Box<dyn Future<Item = Vec<Self::Log>, Error = Self::Error> + Send + 'static> {
+        let LogFilter {
+            from_block,
+            to_block,
+            event,
+        } = filter;
+
+        // `eth_getLogs` supports at most four topics, the remaining ones are
+        // checked by whoever receives the logs.
+        let mut topics = event
+            .topics
+            .into_iter()
+            .map(|topic| topic.map(|Topic(topic)| vec![topic]));
+        let mut builder = FilterBuilder::default()
+            .from_block(BlockNumber::Number(from_block))
+            .to_block(BlockNumber::Number(to_block))
+            .topics(
+                topics.next().flatten(),
+                topics.next().flatten(),
+                topics.next().flatten(),
+                topics.next().flatten(),
+            );
+        if let Some(address) = event.address {
+            builder = builder.address(vec![address]);
+        }
+
+        let web = self.web3.clone();
+        Box::new(web.eth().logs(builder.build()))
+    }
+}
+
 impl tokio::executor::Executor for Web3Connector {
     fn spawn(
         &mut self,
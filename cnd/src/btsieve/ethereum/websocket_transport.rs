@@ -0,0 +1,303 @@
+//! JSON-RPC over a WebSocket connection to the Ethereum node.
+//!
+//! Besides answering requests like the HTTP transport of web3, the connection
+//! is subscribed to `newHeads` so that we learn about new blocks without
+//! polling. The connection is re-established and the subscription renewed
+//! whenever it is lost.
+
+use crate::{
+    btsieve::WakeUp,
+    ethereum::{
+        web3::{helpers, Error, RequestId, Transport},
+        H256,
+    },
+};
+use bytes::BytesMut;
+use futures::{
+    future::{self, Either, Loop},
+    sync::{mpsc, oneshot},
+    Future, Sink, Stream,
+};
+use jsonrpc_core as rpc;
+use reqwest::Url;
+use soketto::{
+    base,
+    connection::{Connection, Mode},
+    handshake,
+};
+use std::{
+    collections::HashMap,
+    net::ToSocketAddrs,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tokio::{
+    codec::{Framed, FramedParts},
+    net::TcpStream,
+    timer::Delay,
+};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// `prepare` hands out ids starting at 1, this one is never used by a request
+/// made through web3.
+const SUBSCRIBE_REQUEST_ID: u64 = 0;
+
+#[derive(Clone, Debug)]
+pub struct WebSocket {
+    id: Arc<AtomicUsize>,
+    shared: Arc<Mutex<Shared>>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    outgoing: Option<mpsc::UnboundedSender<String>>,
+    pending: HashMap<RequestId, oneshot::Sender<Result<rpc::Value, Error>>>,
+    subscription: Option<String>,
+    latest_head: Option<H256>,
+    wake_ups: Vec<WakeUp>,
+}
+
+impl WebSocket {
+    /// Connects to the node at `url` in the background, requests made before
+    /// the connection is established fail.
+    pub fn connect(url: &Url, task_executor: tokio::runtime::TaskExecutor) -> Result<Self, Error> {
+        if url.scheme() != "ws" {
+            return Err(Error::Transport(format!(
+                "unsupported WebSocket scheme {}",
+                url.scheme()
+            )));
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| Error::Transport(format!("WebSocket url {} has no host", url)))?;
+        let port = url.port_or_known_default().unwrap_or(80);
+        let host = format!("{}:{}", host, port);
+        let resource = url.path().to_owned();
+
+        let transport = Self {
+            id: Arc::new(AtomicUsize::new(1)),
+            shared: Arc::new(Mutex::new(Shared::default())),
+        };
+
+        let shared = transport.shared.clone();
+        task_executor.spawn(future::loop_fn((), move |()| {
+            let shared = shared.clone();
+
+            run_connection(host.clone(), resource.clone(), shared.clone()).then(move |result| {
+                shared.lock().unwrap().disconnected();
+                if let Err(e) = result {
+                    log::warn!("Lost WebSocket connection to Ethereum node: {:?}", e);
+                }
+
+                Delay::new(Instant::now() + RECONNECT_DELAY).then(|_| Ok(Loop::Continue(())))
+            })
+        }));
+
+        Ok(transport)
+    }
+
+    /// The hash of the latest block announced through the `newHeads`
+    /// subscription, `None` while we are not subscribed.
+    pub fn latest_head(&self) -> Option<H256> {
+        self.shared.lock().unwrap().latest_head
+    }
+
+    /// Wakes up `wake_up` whenever the node announces a new block.
+    pub fn wake_up_on_new_blocks(&self, wake_up: WakeUp) {
+        self.shared.lock().unwrap().wake_ups.push(wake_up);
+    }
+}
+
+impl Transport for WebSocket {
+    type Out = Box<dyn Future<Item = rpc::Value, Error = Error> + Send>;
+
+    fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+        let id = self.id.fetch_add(1, Ordering::AcqRel);
+
+        (id, helpers::build_request(id, method, params))
+    }
+
+    fn send(&self, id: RequestId, request: rpc::Call) -> Self::Out {
+        let (sender, receiver) = oneshot::channel();
+
+        {
+            let mut shared = self.shared.lock().unwrap();
+            let sent = shared.outgoing.as_ref().map(|outgoing| {
+                outgoing.unbounded_send(helpers::to_string(&rpc::Request::Single(request)))
+            });
+            match sent {
+                Some(Ok(())) => {
+                    shared.pending.insert(id, sender);
+                }
+                _ => {
+                    return Box::new(future::err(Error::Transport(
+                        "not connected to Ethereum node".to_owned(),
+                    )))
+                }
+            }
+        }
+
+        Box::new(
+            receiver
+                .map_err(|_| Error::Transport("lost connection to Ethereum node".to_owned()))
+                .and_then(|result| result),
+        )
+    }
+}
+
+impl Shared {
+    fn handle_message(&mut self, message: &[u8]) {
+        if let Ok(notification) = helpers::to_notification_from_slice(message) {
+            if let rpc::Params::Map(params) = notification.params {
+                self.handle_notification(params.get("subscription"), params.get("result"));
+            }
+            return;
+        }
+
+        let output = match helpers::to_response_from_slice(message) {
+            Ok(rpc::Response::Single(output)) => output,
+            _ => {
+                log::warn!("Unsupported message from Ethereum node");
+                return;
+            }
+        };
+        let id = match &output {
+            rpc::Output::Success(success) => success.id.clone(),
+            rpc::Output::Failure(failure) => failure.id.clone(),
+        };
+
+        match id {
+            rpc::Id::Num(SUBSCRIBE_REQUEST_ID) => match helpers::to_result_from_output(output) {
+                Ok(rpc::Value::String(subscription)) => {
+                    log::info!("Subscribed to new blocks of the Ethereum node");
+                    self.subscription = Some(subscription);
+                }
+                result => log::warn!("Could not subscribe to new blocks: {:?}", result),
+            },
+            rpc::Id::Num(id) => match self.pending.remove(&(id as usize)) {
+                Some(pending) => {
+                    let _ = pending.send(helpers::to_result_from_output(output));
+                }
+                None => log::warn!("Got response for unknown request {}", id),
+            },
+            id => log::warn!("Got response with unsupported id {:?}", id),
+        }
+    }
+
+    fn handle_notification(
+        &mut self,
+        subscription: Option<&rpc::Value>,
+        header: Option<&rpc::Value>,
+    ) {
+        match (subscription, header) {
+            (Some(rpc::Value::String(subscription)), Some(header))
+                if Some(subscription) == self.subscription.as_ref() =>
+            {
+                match header
+                    .get("hash")
+                    .cloned()
+                    .map(serde_json::from_value::<H256>)
+                {
+                    Some(Ok(hash)) => {
+                        self.latest_head = Some(hash);
+
+                        for wake_up in self.wake_ups.iter() {
+                            wake_up.wake_up();
+                        }
+                    }
+                    _ => log::warn!("Block header without hash: {:?}", header),
+                }
+            }
+            _ => log::debug!("Ignoring notification of unknown subscription"),
+        }
+    }
+
+    fn disconnected(&mut self) {
+        // Dropping the senders fails all requests that are still waiting for
+        // an answer.
+        self.outgoing = None;
+        self.pending.clear();
+        self.subscription = None;
+        self.latest_head = None;
+    }
+}
+
+fn run_connection(
+    host: String,
+    resource: String,
+    shared: Arc<Mutex<Shared>>,
+) -> impl Future<Item = (), Error = Error> {
+    // The address of the node may change while we are disconnected, hence we
+    // resolve it again for every connection.
+    future::lazy({
+        let host = host.clone();
+        move || {
+            host.to_socket_addrs()
+                .map_err(Error::Io)?
+                .next()
+                .ok_or_else(|| Error::Transport(format!("could not resolve {}", host)))
+        }
+    })
+    .and_then(|address| TcpStream::connect(&address).map_err(Error::Io))
+    .and_then(move |stream| {
+        Framed::new(stream, handshake::Client::new(host, resource))
+            .send(())
+            .map_err(|e| Error::Transport(format!("{:?}", e)))
+    })
+    .and_then(|framed| {
+        framed
+            .into_future()
+            .map_err(|(e, _)| Error::Transport(format!("{:?}", e)))
+    })
+    .and_then(move |(response, framed)| {
+        match response {
+            Some(handshake::Response::Accepted(_)) => {}
+            response => {
+                return Either::A(future::err(Error::Transport(format!(
+                    "WebSocket handshake failed: {:?}",
+                    response.map(|_| "redirect")
+                ))))
+            }
+        }
+
+        let handshake = framed.into_parts();
+        let mut parts = FramedParts::new(handshake.io, base::Codec::new());
+        parts.read_buf = handshake.read_buf;
+        parts.write_buf = handshake.write_buf;
+        let (sink, stream) =
+            Connection::from_framed(Framed::from_parts(parts), Mode::Client).split();
+
+        let (outgoing, receiver) = mpsc::unbounded();
+        let subscribe =
+            helpers::build_request(SUBSCRIBE_REQUEST_ID as usize, "eth_subscribe", vec![
+                rpc::Value::String("newHeads".to_owned()),
+            ]);
+        outgoing
+            .unbounded_send(helpers::to_string(&rpc::Request::Single(subscribe)))
+            .expect("receiver is alive");
+        shared.lock().unwrap().outgoing = Some(outgoing);
+
+        let writer = sink
+            .send_all(
+                receiver
+                    .map(|message| base::Data::Text(BytesMut::from(message)))
+                    .map_err(|()| soketto::connection::Error::Closed),
+            )
+            .map(|_| ());
+        let reader = stream.for_each(move |message| {
+            shared.lock().unwrap().handle_message(&message.into_bytes());
+            Ok(())
+        });
+
+        Either::B(
+            reader
+                .select(writer)
+                .map(|_| ())
+                .map_err(|(e, _)| Error::Transport(format!("{:?}", e))),
+        )
+    })
+}
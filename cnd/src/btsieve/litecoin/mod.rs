@@ -0,0 +1,7 @@
+//! Litecoin blocks and transactions have the format of Bitcoin ones, hence
+//! the HTLC transactions are found and followed with the functions of
+//! `btsieve::bitcoin`.
+
+/// litecoind has the same REST interface as bitcoind, so its blocks are
+/// fetched by the connector to bitcoind.
+pub type LitecoindConnector = crate::btsieve::bitcoin::BitcoindConnector;
@@ -0,0 +1,237 @@
+use async_trait::async_trait;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+/// A block up to which the blockchain was fully scanned for a transaction
+/// pattern, i.e. this block and all the blocks between it and the block the
+/// scan started at have been checked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Checkpoint<H> {
+    pub block_hash: H,
+    pub block_height: u64,
+}
+
+impl<H> Checkpoint<H>
+where
+    H: PartialEq,
+{
+    /// Whether walking back the blockchain can stop at the given block because
+    /// it was already checked when this checkpoint was recorded.
+    ///
+    /// Only the checkpoint itself is covered. A block at or below its height
+    /// may be on a competing chain that was never checked, the walk has to
+    /// follow the parent hashes until it reaches the checkpoint.
+    pub fn covers(&self, block_hash: &H) -> bool {
+        *block_hash == self.block_hash
+    }
+}
+
+/// Remembers how far the blockchain was scanned for a transaction pattern so
+/// that a restarted scan does not check the same blocks again.
+#[async_trait]
+pub trait ScanProgress<H>: Send + Sync + 'static {
+    async fn load_checkpoint(&self) -> Option<Checkpoint<H>>;
+    async fn save_checkpoint(&self, checkpoint: Checkpoint<H>);
+}
+
+/// Does not remember anything, every scan starts from scratch.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+#[async_trait]
+impl<H> ScanProgress<H> for ()
+where
+    H: Send + 'static,
+{
+    async fn load_checkpoint(&self) -> Option<Checkpoint<H>> {
+        None
+    }
+
+    async fn save_checkpoint(&self, _: Checkpoint<H>) {}
+}
+
+/// Keeps track of the blocks a scan checked to figure out up to which block
+/// the blockchain was fully scanned.
+///
+/// Blocks can be checked in any order, e.g. a new block may be checked before
+/// the gap between it and the previously checked block is filled. The
+/// checkpoint only advances once the parent hashes lead from the newest
+/// checked block to the checkpoint we resumed from or to the reference point
+/// of the scan without such a gap.
+#[derive(Debug)]
+pub struct ScannedBlocks<H>
+where
+    H: Eq + Hash,
+{
+    parents: HashMap<H, (H, u64)>,
+    /// Blocks that, together with all their ancestors, do not need to be
+    /// checked (anymore).
+    complete: HashSet<H>,
+    tip: Option<(H, u64)>,
+    checkpoint: Option<Checkpoint<H>>,
+}
+
+impl<H> ScannedBlocks<H>
+where
+    H: Eq + Hash + Copy,
+{
+    pub fn new(resumed_from: Option<Checkpoint<H>>) -> Self {
+        let mut complete = HashSet::new();
+        if let Some(checkpoint) = resumed_from {
+            complete.insert(checkpoint.block_hash);
+        }
+
+        Self {
+            parents: HashMap::new(),
+            complete,
+            tip: None,
+            checkpoint: resumed_from,
+        }
+    }
+
+    /// Whether walking back the blockchain can stop at the given block because
+    /// it and all its ancestors were already checked or do not need to be.
+    pub fn already_scanned(&self, block_hash: &H) -> bool {
+        self.complete.contains(block_hash)
+    }
+
+    /// Records that the given block was checked and returns the new
+    /// checkpoint if this closed the gap to the newest checked block.
+    pub fn insert(
+        &mut self,
+        block_hash: H,
+        parent_hash: H,
+        block_height: u64,
+    ) -> Option<Checkpoint<H>> {
+        self.parents.insert(block_hash, (parent_hash, block_height));
+
+        match self.tip {
+            Some((_, tip_height)) if tip_height >= block_height => {}
+            _ => self.tip = Some((block_hash, block_height)),
+        }
+
+        self.advance()
+    }
+
+    /// Records that neither the given block nor any of its ancestors need to
+    /// be checked, e.g. because they are older than the swap, and returns the
+    /// new checkpoint if this closed the gap to the newest checked block.
+    pub fn reached_reference_point(&mut self, block_hash: H) -> Option<Checkpoint<H>> {
+        self.complete.insert(block_hash);
+
+        self.advance()
+    }
+
+    fn advance(&mut self) -> Option<Checkpoint<H>> {
+        let (tip_hash, tip_height) = self.tip?;
+
+        let mut path = Vec::new();
+        let mut current = tip_hash;
+        while !self.complete.contains(&current) {
+            let (parent, _) = *self.parents.get(&current)?;
+            path.push(current);
+            current = parent;
+        }
+        self.complete.extend(path);
+
+        let advanced = self
+            .checkpoint
+            .map(|checkpoint| checkpoint.block_hash != tip_hash)
+            .unwrap_or(true);
+        if !advanced {
+            return None;
+        }
+
+        let checkpoint = Checkpoint {
+            block_hash: tip_hash,
+            block_height: tip_height,
+        };
+        self.checkpoint = Some(checkpoint);
+
+        Some(checkpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_no_checkpoint_checkpoint_is_saved_once_reference_point_is_reached() {
+        let mut scanned = ScannedBlocks::new(None);
+
+        assert_eq!(scanned.insert(10, 9, 10), None);
+        assert_eq!(scanned.insert(9, 8, 9), None);
+        assert_eq!(
+            scanned.reached_reference_point(8),
+            Some(Checkpoint {
+                block_hash: 10,
+                block_height: 10
+            })
+        );
+    }
+
+    #[test]
+    fn given_gap_checkpoint_advances_once_gap_is_filled() {
+        let mut scanned = ScannedBlocks::new(Some(Checkpoint {
+            block_hash: 10,
+            block_height: 10,
+        }));
+
+        assert_eq!(scanned.insert(13, 12, 13), None);
+        assert_eq!(scanned.insert(12, 11, 12), None);
+        assert_eq!(
+            scanned.insert(11, 10, 11),
+            Some(Checkpoint {
+                block_hash: 13,
+                block_height: 13
+            })
+        );
+    }
+
+    #[test]
+    fn given_orphaned_checkpoint_competing_chain_has_to_reach_reference_point() {
+        let mut scanned = ScannedBlocks::new(Some(Checkpoint {
+            block_hash: 10,
+            block_height: 10,
+        }));
+
+        assert_eq!(scanned.insert(111, 100, 11), None);
+        assert_eq!(scanned.insert(100, 9, 10), None);
+        assert_eq!(
+            scanned.reached_reference_point(9),
+            Some(Checkpoint {
+                block_hash: 111,
+                block_height: 11
+            })
+        );
+    }
+
+    #[test]
+    fn walking_back_stops_at_checkpoint_only() {
+        let scanned = ScannedBlocks::new(Some(Checkpoint {
+            block_hash: 10,
+            block_height: 10,
+        }));
+
+        assert!(scanned.already_scanned(&10));
+        assert!(!scanned.already_scanned(&100));
+        assert!(!scanned.already_scanned(&9));
+        assert!(!scanned.already_scanned(&11));
+    }
+
+    #[test]
+    fn walking_back_stops_at_blocks_connected_to_checkpoint() {
+        let mut scanned = ScannedBlocks::new(Some(Checkpoint {
+            block_hash: 10,
+            block_height: 10,
+        }));
+
+        scanned.insert(12, 11, 12);
+        assert!(!scanned.already_scanned(&12));
+
+        scanned.insert(11, 10, 11);
+        assert!(scanned.already_scanned(&11));
+        assert!(scanned.already_scanned(&12));
+    }
+}
@@ -1,9 +1,9 @@
 use crate::{
-    ethereum::Erc20Token,
+    ethereum::{Erc20Token, Erc721Token},
     libp2p_comit_ext::{FromHeader, ToHeader},
     swap_protocols::{
         asset::AssetKind,
-        ledger::{Bitcoin, Ethereum, LedgerKind},
+        ledger::{Bitcoin, Ethereum, LedgerKind, Litecoin},
         rfc003::messages::Decision,
         SwapId, SwapProtocol,
     },
@@ -15,19 +15,13 @@ use serde::de::Error;
 impl FromHeader for LedgerKind {
     fn from_header(mut header: Header) -> Result<Self, serde_json::Error> {
         Ok(match header.value::<String>()?.as_str() {
-            "bitcoin" => LedgerKind::Bitcoin(Bitcoin::new(
-                match header.take_parameter::<String>("network")?.as_ref() {
-                    "mainnet" => bitcoin::Network::Bitcoin,
-                    "testnet" => bitcoin::Network::Testnet,
-                    "regtest" => bitcoin::Network::Regtest,
-                    _ => {
-                        return Err(serde_json::Error::custom(
-                            "unexpected bitcoin network variant",
-                        ))
-                    }
-                },
-            )),
+            "bitcoin" => LedgerKind::Bitcoin(Bitcoin::new(network_from_parameter(
+                header.take_parameter("network")?,
+            )?)),
             "ethereum" => LedgerKind::Ethereum(Ethereum::new(header.take_parameter("network")?)),
+            "litecoin" => LedgerKind::Litecoin(Litecoin::new(network_from_parameter(
+                header.take_parameter("network")?,
+            )?)),
             unknown => {
                 return Err(serde_json::Error::custom(format!(
                     "unknown ledger: {}",
@@ -41,21 +35,40 @@ impl FromHeader for LedgerKind {
 impl ToHeader for LedgerKind {
     fn to_header(&self) -> Result<Header, serde_json::Error> {
         Ok(match self {
-            LedgerKind::Bitcoin(bitcoin) => Header::with_str_value("bitcoin").with_parameter(
-                "network",
-                match bitcoin.network {
-                    bitcoin::Network::Bitcoin => "mainnet",
-                    bitcoin::Network::Testnet => "testnet",
-                    bitcoin::Network::Regtest => "regtest",
-                },
-            )?,
+            LedgerKind::Bitcoin(bitcoin) => Header::with_str_value("bitcoin")
+                .with_parameter("network", network_to_parameter(bitcoin.network))?,
             LedgerKind::Ethereum(ethereum) => {
                 Header::with_str_value("ethereum").with_parameter("network", ethereum.chain_id)?
             }
+            LedgerKind::Litecoin(litecoin) => Header::with_str_value("litecoin")
+                .with_parameter("network", network_to_parameter(litecoin.network))?,
         })
     }
 }
 
+/// Bitcoin and Litecoin name their networks the same way in the ledger
+/// header.
+fn network_from_parameter(network: String) -> Result<bitcoin::Network, serde_json::Error> {
+    Ok(match network.as_str() {
+        "mainnet" => bitcoin::Network::Bitcoin,
+        "testnet" => bitcoin::Network::Testnet,
+        "regtest" => bitcoin::Network::Regtest,
+        _ => {
+            return Err(serde_json::Error::custom(
+                "unexpected bitcoin network variant",
+            ))
+        }
+    })
+}
+
+fn network_to_parameter(network: bitcoin::Network) -> &'static str {
+    match network {
+        bitcoin::Network::Bitcoin => "mainnet",
+        bitcoin::Network::Testnet => "testnet",
+        bitcoin::Network::Regtest => "regtest",
+    }
+}
+
 impl FromHeader for SwapId {
     fn from_header(header: Header) -> Result<Self, serde_json::Error> {
         header.value::<SwapId>()
@@ -106,6 +119,11 @@ impl FromHeader for AssetKind {
                 header.take_parameter("address")?,
                 header.take_parameter("quantity")?,
             )),
+            "erc721" => AssetKind::Erc721(Erc721Token::new(
+                header.take_parameter("address")?,
+                header.take_parameter("token_id")?,
+            )),
+            "litecoin" => AssetKind::Litecoin(header.take_parameter("quantity")?),
             unknown => {
                 return Err(serde_json::Error::custom(format!(
                     "unknown asset: {}",
@@ -127,6 +145,12 @@ impl ToHeader for AssetKind {
             AssetKind::Erc20(erc20) => Header::with_str_value("erc20")
                 .with_parameter("address", erc20.token_contract)?
                 .with_parameter("quantity", erc20.quantity)?,
+            AssetKind::Erc721(erc721) => Header::with_str_value("erc721")
+                .with_parameter("address", erc721.token_contract)?
+                .with_parameter("token_id", erc721.token_id)?,
+            AssetKind::Litecoin(litecoin) => {
+                Header::with_str_value("litecoin").with_parameter("quantity", litecoin)?
+            }
         })
     }
 }
@@ -154,7 +178,7 @@ impl FromHeader for Decision {
 mod tests {
     use super::*;
     use crate::{
-        ethereum::{Address, Erc20Quantity, U256},
+        ethereum::{Address, Erc20Quantity, Erc721TokenId, U256},
         swap_protocols::{ledger::ethereum, HashFunction},
     };
     use bitcoin::Amount;
@@ -177,6 +201,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn erc721_token_header_roundtrip() -> Result<(), serde_json::Error> {
+        let token = Erc721Token::new(Address::zero(), Erc721TokenId(U256::from(42)));
+        let header = AssetKind::from(token).to_header()?;
+
+        assert_eq!(
+            header,
+            Header::with_str_value("erc721")
+                .with_parameter("token_id", "42")?
+                .with_parameter("address", "0x0000000000000000000000000000000000000000")?
+        );
+        assert_eq!(AssetKind::from_header(header)?, AssetKind::Erc721(token));
+
+        Ok(())
+    }
+
     #[test]
     fn swap_protocol_to_header() {
         // From comit-network/RFCs/RFC-003-SWAP-Basic.md SWAP REQUEST example.
@@ -275,4 +315,32 @@ mod tests {
         assert_eq!(serialized_headers, headers);
         assert_eq!(constructed_ledgerkinds, ledgerkinds);
     }
+
+    #[test]
+    fn litecoin_ledger_to_header_roundtrip() {
+        let ledger = LedgerKind::Litecoin(Litecoin::new(bitcoin::Network::Testnet));
+        let header = ledger.to_header().unwrap();
+
+        assert_eq!(
+            header,
+            Header::with_str_value("litecoin")
+                .with_parameter("network", "testnet")
+                .unwrap()
+        );
+        assert_eq!(LedgerKind::from_header(header).unwrap(), ledger);
+    }
+
+    #[test]
+    fn litecoin_quantity_to_header_roundtrip() {
+        let quantity = AssetKind::from(crate::litecoin::Amount::from_litoshi(100_000_000));
+        let header = quantity.to_header().unwrap();
+
+        assert_eq!(
+            header,
+            Header::with_str_value("litecoin")
+                .with_parameter("quantity", "100000000")
+                .unwrap()
+        );
+        assert_eq!(AssetKind::from_header(header).unwrap(), quantity);
+    }
 }
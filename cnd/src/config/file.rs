@@ -1,4 +1,4 @@
-use crate::config::{Bitcoin, Data, Ethereum, Network, Socket};
+use crate::config::{AutoRedeem, AutoRefund, Bitcoin, Data, Ethereum, Litecoin, Network, Socket};
 use config as config_rs;
 use log::LevelFilter;
 use std::{ffi::OsStr, path::Path};
@@ -16,6 +16,9 @@ pub struct File {
     pub logging: Option<Logging>,
     pub bitcoin: Option<Bitcoin>,
     pub ethereum: Option<Ethereum>,
+    pub litecoin: Option<Litecoin>,
+    pub auto_refund: Option<AutoRefund>,
+    pub auto_redeem: Option<AutoRedeem>,
 }
 
 impl File {
@@ -27,6 +30,9 @@ impl File {
             logging: Option::None,
             bitcoin: Option::None,
             ethereum: Option::None,
+            litecoin: Option::None,
+            auto_refund: Option::None,
+            auto_redeem: Option::None,
         }
     }
 
@@ -79,7 +85,10 @@ pub enum None {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::Settings;
+    use crate::{
+        config::{BitcoinBackend, Settings},
+        swap_protocols::ledger::ethereum::ChainId,
+    };
     use log::LevelFilter;
     use spectral::prelude::*;
     use std::{
@@ -170,9 +179,26 @@ structured = false
 [bitcoin]
 network = "mainnet"
 node_url = "http://example.com/"
+zmq_url = "tcp://127.0.0.1:28332"
+min_confirmations = 3
 
 [ethereum]
 node_url = "http://example.com/"
+chain_id = 1
+min_confirmations = 12
+gas_safety_margin = 50
+
+[litecoin]
+network = "testnet"
+node_url = "http://example.com/"
+
+[auto_refund]
+bitcoin_fee_per_wu = 10
+ethereum_gas_price = 20000000000
+
+[auto_redeem]
+bitcoin_fee_per_wu = 10
+ethereum_gas_price = 20000000000
 "#;
 
         let file = File {
@@ -197,10 +223,34 @@ node_url = "http://example.com/"
             }),
             bitcoin: Some(Bitcoin {
                 network: bitcoin::Network::Bitcoin,
+                backend: BitcoinBackend::Bitcoind,
                 node_url: "http://example.com".parse().unwrap(),
+                zmq_url: Some("tcp://127.0.0.1:28332".parse().unwrap()),
+                min_confirmations: 3,
+                confirmation_target: 6,
+                rpc_auth: None,
+                watch_only_descriptor: None,
             }),
             ethereum: Some(Ethereum {
                 node_url: "http://example.com".parse().unwrap(),
+                chain_id: ChainId::mainnet(),
+                min_confirmations: 12,
+                gas_safety_margin: 50,
+                chains: vec![],
+            }),
+            litecoin: Some(Litecoin {
+                network: bitcoin::Network::Testnet,
+                node_url: "http://example.com".parse().unwrap(),
+                min_confirmations: 1,
+            }),
+            auto_refund: Some(AutoRefund {
+                bitcoin_fee_per_wu: 10,
+                ethereum_gas_price: 20_000_000_000,
+            }),
+            auto_redeem: Some(AutoRedeem {
+                bitcoin_fee_per_wu: 10,
+                bitcoin_payout_address: None,
+                ethereum_gas_price: 20_000_000_000,
             }),
         };
 
@@ -2,6 +2,7 @@ pub mod file;
 mod serde_bitcoin_network;
 pub mod settings;
 
+use crate::swap_protocols::ledger::ethereum::ChainId;
 use libp2p::Multiaddr;
 use serde::{Deserialize, Serialize};
 use std::{net::IpAddr, path::PathBuf};
@@ -28,14 +29,162 @@ pub struct Socket {
 pub struct Bitcoin {
     #[serde(with = "crate::config::serde_bitcoin_network")]
     pub network: bitcoin::Network,
+    /// Where `node_url` points to, bitcoind unless configured otherwise.
+    #[serde(default)]
+    pub backend: BitcoinBackend,
+    /// The REST interface of bitcoind (its JSON-RPC interface if `rpc_auth`
+    /// is set), the base url of an Esplora API or, for Electrum,
+    /// `tcp://host:port`.
     #[serde(with = "url_serde")]
     pub node_url: reqwest::Url,
+    /// The `zmqpubrawblock` endpoint of bitcoind, e.g.
+    /// `tcp://127.0.0.1:28332`. If set, new blocks are pushed to us instead
+    /// of being polled for.
+    #[serde(default, with = "url_serde", skip_serializing_if = "Option::is_none")]
+    pub zmq_url: Option<reqwest::Url>,
+    /// How deep an HTLC transaction has to be buried before we act on it.
+    #[serde(default = "default_min_confirmations")]
+    pub min_confirmations: u32,
+    /// Within how many blocks the transactions cnd signs should be included
+    /// if the client leaves it to cnd to estimate the fee.
+    #[serde(default = "default_confirmation_target")]
+    pub confirmation_target: u32,
+    /// Credentials for the JSON-RPC interface of bitcoind. If set, bitcoind
+    /// is asked through JSON-RPC instead of its unauthenticated REST
+    /// interface.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_auth: Option<BitcoindRpcAuth>,
+    /// An output descriptor, e.g. `wsh(multi(2,xpub.../0/*,xpub.../0/*))`,
+    /// of a wallet cnd builds PSBTs for to fund HTLCs. Its outputs are
+    /// looked up through `scantxoutset`, which needs `rpc_auth`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watch_only_descriptor: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BitcoinBackend {
+    Bitcoind,
+    Esplora,
+    Electrum,
+}
+
+impl Default for BitcoinBackend {
+    fn default() -> Self {
+        BitcoinBackend::Bitcoind
+    }
+}
+
+/// Either the `rpcuser`/`rpcpassword` pair from `bitcoin.conf` or the path to
+/// the `.cookie` file in the data directory of bitcoind.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum BitcoindRpcAuth {
+    UserPassword { username: String, password: String },
+    CookieFile { cookie_file: PathBuf },
+}
+
+impl From<BitcoindRpcAuth> for crate::btsieve::bitcoin::RpcAuth {
+    fn from(auth: BitcoindRpcAuth) -> Self {
+        match auth {
+            BitcoindRpcAuth::UserPassword { username, password } => {
+                Self::UserPassword { username, password }
+            }
+            BitcoindRpcAuth::CookieFile { cookie_file } => Self::CookieFile(cookie_file),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Ethereum {
+    /// JSON-RPC endpoint of the node. With a `ws://` url, new blocks are
+    /// pushed to us through an `eth_subscribe("newHeads")` subscription
+    /// instead of being polled for.
     #[serde(with = "url_serde")]
     pub node_url: reqwest::Url,
+    /// The chain of the node at `node_url`.
+    #[serde(default = "default_chain_id")]
+    pub chain_id: ChainId,
+    /// How deep an HTLC transaction has to be buried before we act on it.
+    #[serde(default = "default_min_confirmations")]
+    pub min_confirmations: u32,
+    /// By how many percent the gas limit the node estimates for an HTLC
+    /// transaction is raised, in case the transaction uses more gas once it
+    /// is mined.
+    #[serde(default = "default_gas_safety_margin")]
+    pub gas_safety_margin: u32,
+    /// Nodes of further Ethereum chains. Swaps on a chain that has neither
+    /// this nor a node here are declined.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chains: Vec<EthereumChain>,
+}
+
+/// litecoind is only used to watch the HTLCs, cnd neither signs nor
+/// broadcasts Litecoin transactions.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Litecoin {
+    #[serde(with = "crate::config::serde_bitcoin_network")]
+    pub network: bitcoin::Network,
+    /// The REST interface of litecoind.
+    #[serde(with = "url_serde")]
+    pub node_url: reqwest::Url,
+    /// How deep an HTLC transaction has to be buried before we act on it.
+    #[serde(default = "default_min_confirmations")]
+    pub min_confirmations: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EthereumChain {
+    pub chain_id: ChainId,
+    /// JSON-RPC endpoint of a node of the chain `chain_id`.
+    #[serde(with = "url_serde")]
+    pub node_url: reqwest::Url,
+}
+
+/// Opts into refunding the HTLCs we funded as soon as they expire, without
+/// waiting for the refund action to be invoked.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AutoRefund {
+    /// The fee per weight unit of Bitcoin refund transactions in satoshis.
+    pub bitcoin_fee_per_wu: u64,
+    /// The gas price of Ethereum refund transactions in wei.
+    pub ethereum_gas_price: u64,
+}
+
+/// Opts into redeeming the HTLC on the alpha ledger as soon as the secret was
+/// revealed by the redeem of the beta HTLC, without waiting for the redeem
+/// action to be invoked.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AutoRedeem {
+    /// The fee per weight unit of Bitcoin redeem transactions in satoshis.
+    pub bitcoin_fee_per_wu: u64,
+    /// Where redeemed bitcoin are sent to, the wallet of cnd if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitcoin_payout_address: Option<bitcoin::Address>,
+    /// The gas price of Ethereum redeem transactions in wei.
+    pub ethereum_gas_price: u64,
+}
+
+/// A transaction is considered to be confirmed as soon as it is included in a
+/// block unless configured otherwise.
+fn default_min_confirmations() -> u32 {
+    1
+}
+
+/// Bitcoin Core aims at the same number of blocks unless configured
+/// otherwise.
+fn default_confirmation_target() -> u32 {
+    6
+}
+
+/// Token contracts can use more gas depending on their state, which can
+/// change between the estimate and the transaction being mined.
+fn default_gas_safety_margin() -> u32 {
+    20
+}
+
+fn default_chain_id() -> ChainId {
+    ChainId::regtest()
 }
 
 #[cfg(test)]
@@ -75,6 +224,64 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn auto_refund_deserializes_correctly() {
+        let file_contents = r#"
+            bitcoin_fee_per_wu = 10
+            ethereum_gas_price = 20000000000
+            "#;
+
+        let auto_refund = toml::from_str::<AutoRefund>(file_contents);
+
+        assert_eq!(
+            auto_refund,
+            Ok(AutoRefund {
+                bitcoin_fee_per_wu: 10,
+                ethereum_gas_price: 20_000_000_000,
+            })
+        );
+    }
+
+    #[test]
+    fn bitcoin_payout_address_of_auto_redeem_is_optional() {
+        let file_contents = vec![
+            r#"
+            bitcoin_fee_per_wu = 10
+            ethereum_gas_price = 20000000000
+            "#,
+            r#"
+            bitcoin_fee_per_wu = 10
+            bitcoin_payout_address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+            ethereum_gas_price = 20000000000
+            "#,
+        ];
+
+        let expected = vec![
+            AutoRedeem {
+                bitcoin_fee_per_wu: 10,
+                bitcoin_payout_address: None,
+                ethereum_gas_price: 20_000_000_000,
+            },
+            AutoRedeem {
+                bitcoin_fee_per_wu: 10,
+                bitcoin_payout_address: Some(
+                    "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+                        .parse()
+                        .unwrap(),
+                ),
+                ethereum_gas_price: 20_000_000_000,
+            },
+        ];
+
+        let actual = file_contents
+            .into_iter()
+            .map(toml::from_str)
+            .collect::<Result<Vec<AutoRedeem>, toml::de::Error>>()
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn bitcoin_deserializes_correctly() {
         let file_contents = vec![
@@ -89,21 +296,111 @@ mod tests {
             r#"
             network = "regtest"
             node_url = "http://example.com:8545"
+            zmq_url = "tcp://127.0.0.1:28332"
+            min_confirmations = 6
+            confirmation_target = 2
+            "#,
+            r#"
+            network = "testnet"
+            backend = "esplora"
+            node_url = "https://blockstream.info/testnet/api/"
+            "#,
+            r#"
+            network = "mainnet"
+            backend = "electrum"
+            node_url = "tcp://127.0.0.1:50001"
+            "#,
+            r#"
+            network = "regtest"
+            node_url = "http://127.0.0.1:18443"
+            [rpc_auth]
+            username = "bitcoin"
+            password = "t68ej4"
+            "#,
+            r#"
+            network = "regtest"
+            node_url = "http://127.0.0.1:18443"
+            watch_only_descriptor = "wpkh(tpubD6NzVbkrYhZ4WaWSyoBvQwbpLkojyoTZPRsgXELWz3Popb3qkjcJyJUGLnL4qHHoQvao8ESaAstxYSnhyswJ76uZPStJRJCTKvosUCJZL5B/0/*)"
+            [rpc_auth]
+            cookie_file = "/home/bitcoin/.bitcoin/regtest/.cookie"
             "#,
         ];
 
         let expected = vec![
             Bitcoin {
                 network: bitcoin::Network::Bitcoin,
+                backend: BitcoinBackend::Bitcoind,
                 node_url: Url::parse("http://example.com:8545").unwrap(),
+                zmq_url: None,
+                min_confirmations: 1,
+                confirmation_target: 6,
+                rpc_auth: None,
+                watch_only_descriptor: None,
             },
             Bitcoin {
                 network: bitcoin::Network::Testnet,
+                backend: BitcoinBackend::Bitcoind,
                 node_url: Url::parse("http://example.com:8545").unwrap(),
+                zmq_url: None,
+                min_confirmations: 1,
+                confirmation_target: 6,
+                rpc_auth: None,
+                watch_only_descriptor: None,
             },
             Bitcoin {
                 network: bitcoin::Network::Regtest,
+                backend: BitcoinBackend::Bitcoind,
                 node_url: Url::parse("http://example.com:8545").unwrap(),
+                zmq_url: Some(Url::parse("tcp://127.0.0.1:28332").unwrap()),
+                min_confirmations: 6,
+                confirmation_target: 2,
+                rpc_auth: None,
+                watch_only_descriptor: None,
+            },
+            Bitcoin {
+                network: bitcoin::Network::Testnet,
+                backend: BitcoinBackend::Esplora,
+                node_url: Url::parse("https://blockstream.info/testnet/api/").unwrap(),
+                zmq_url: None,
+                min_confirmations: 1,
+                confirmation_target: 6,
+                rpc_auth: None,
+                watch_only_descriptor: None,
+            },
+            Bitcoin {
+                network: bitcoin::Network::Bitcoin,
+                backend: BitcoinBackend::Electrum,
+                node_url: Url::parse("tcp://127.0.0.1:50001").unwrap(),
+                zmq_url: None,
+                min_confirmations: 1,
+                confirmation_target: 6,
+                rpc_auth: None,
+                watch_only_descriptor: None,
+            },
+            Bitcoin {
+                network: bitcoin::Network::Regtest,
+                backend: BitcoinBackend::Bitcoind,
+                node_url: Url::parse("http://127.0.0.1:18443").unwrap(),
+                zmq_url: None,
+                min_confirmations: 1,
+                confirmation_target: 6,
+                rpc_auth: Some(BitcoindRpcAuth::UserPassword {
+                    username: "bitcoin".to_owned(),
+                    password: "t68ej4".to_owned(),
+                }),
+                watch_only_descriptor: None,
+            },
+            Bitcoin {
+                network: bitcoin::Network::Regtest,
+                backend: BitcoinBackend::Bitcoind,
+                node_url: Url::parse("http://127.0.0.1:18443").unwrap(),
+                zmq_url: None,
+                min_confirmations: 1,
+                confirmation_target: 6,
+                rpc_auth: Some(BitcoindRpcAuth::CookieFile {
+                    cookie_file: PathBuf::from("/home/bitcoin/.bitcoin/regtest/.cookie"),
+                }),
+                watch_only_descriptor: Some("wpkh(tpubD6NzVbkrYhZ4WaWSyoBvQwbpLkojyoTZPRsgXELWz3Popb3qkjcJyJUGLnL4qHHoQvao8ESaAstxYSnhyswJ76uZPStJRJCTKvosUCJZL5B/0/*)".to_owned()),
             },
         ];
 
@@ -115,4 +412,83 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn litecoin_deserializes_correctly() {
+        let file_contents = vec![
+            r#"
+            network = "mainnet"
+            node_url = "http://example.com:9332"
+            "#,
+            r#"
+            network = "regtest"
+            node_url = "http://127.0.0.1:19443"
+            min_confirmations = 6
+            "#,
+        ];
+
+        let expected = vec![
+            Litecoin {
+                network: bitcoin::Network::Bitcoin,
+                node_url: Url::parse("http://example.com:9332").unwrap(),
+                min_confirmations: 1,
+            },
+            Litecoin {
+                network: bitcoin::Network::Regtest,
+                node_url: Url::parse("http://127.0.0.1:19443").unwrap(),
+                min_confirmations: 6,
+            },
+        ];
+
+        let actual = file_contents
+            .into_iter()
+            .map(toml::from_str)
+            .collect::<Result<Vec<Litecoin>, toml::de::Error>>()
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn ethereum_deserializes_correctly() {
+        let file_contents = vec![
+            r#"
+            node_url = "http://example.com:8545"
+            "#,
+            r#"
+            node_url = "http://example.com:8545"
+            [[chains]]
+            chain_id = 3
+            node_url = "http://example.com:8546"
+            "#,
+        ];
+
+        let expected = vec![
+            Ethereum {
+                node_url: Url::parse("http://example.com:8545").unwrap(),
+                chain_id: ChainId::regtest(),
+                min_confirmations: 1,
+                gas_safety_margin: 20,
+                chains: vec![],
+            },
+            Ethereum {
+                node_url: Url::parse("http://example.com:8545").unwrap(),
+                chain_id: ChainId::regtest(),
+                min_confirmations: 1,
+                gas_safety_margin: 20,
+                chains: vec![EthereumChain {
+                    chain_id: ChainId::ropsten(),
+                    node_url: Url::parse("http://example.com:8546").unwrap(),
+                }],
+            },
+        ];
+
+        let actual = file_contents
+            .into_iter()
+            .map(toml::from_str)
+            .collect::<Result<Vec<Ethereum>, toml::de::Error>>()
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
 }
@@ -1,4 +1,10 @@
-use crate::config::{file, Bitcoin, Data, Ethereum, File, Network, Socket};
+use crate::{
+    config::{
+        file, AutoRedeem, AutoRefund, Bitcoin, BitcoinBackend, Data, Ethereum, File, Litecoin,
+        Network, Socket,
+    },
+    swap_protocols::ledger::ethereum::ChainId,
+};
 use anyhow::Context;
 use log::LevelFilter;
 use reqwest::Url;
@@ -18,6 +24,9 @@ pub struct Settings {
     pub logging: Logging,
     pub bitcoin: Bitcoin,
     pub ethereum: Ethereum,
+    pub litecoin: Litecoin,
+    pub auto_refund: Option<AutoRefund>,
+    pub auto_redeem: Option<AutoRedeem>,
 }
 
 impl From<Settings> for File {
@@ -29,6 +38,9 @@ impl From<Settings> for File {
             logging: Logging { level, structured },
             bitcoin,
             ethereum,
+            litecoin,
+            auto_refund,
+            auto_redeem,
         } = settings;
 
         File {
@@ -50,6 +62,9 @@ impl From<Settings> for File {
             }),
             bitcoin: Some(bitcoin),
             ethereum: Some(ethereum),
+            litecoin: Some(litecoin),
+            auto_refund,
+            auto_redeem,
         }
     }
 }
@@ -109,6 +124,9 @@ impl Settings {
             logging,
             bitcoin,
             ethereum,
+            litecoin,
+            auto_refund,
+            auto_redeem,
         } = config_file;
 
         Ok(Self {
@@ -162,13 +180,31 @@ impl Settings {
             },
             bitcoin: bitcoin.unwrap_or_else(|| Bitcoin {
                 network: bitcoin::Network::Regtest,
+                backend: BitcoinBackend::Bitcoind,
                 node_url: Url::parse("http://localhost:18443")
                     .expect("static string to be a valid url"),
+                zmq_url: None,
+                min_confirmations: 1,
+                confirmation_target: 6,
+                rpc_auth: None,
+                watch_only_descriptor: None,
             }),
             ethereum: ethereum.unwrap_or_else(|| Ethereum {
                 node_url: Url::parse("http://localhost:8545")
                     .expect("static string to be a valid url"),
+                chain_id: ChainId::regtest(),
+                min_confirmations: 1,
+                gas_safety_margin: 20,
+                chains: vec![],
             }),
+            litecoin: litecoin.unwrap_or_else(|| Litecoin {
+                network: bitcoin::Network::Regtest,
+                node_url: Url::parse("http://localhost:19443")
+                    .expect("static string to be a valid url"),
+                min_confirmations: 1,
+            }),
+            auto_refund,
+            auto_redeem,
         })
     }
 }
@@ -297,4 +333,23 @@ mod tests {
                 listen: vec!["/ip4/0.0.0.0/tcp/9939".parse().unwrap()],
             })
     }
+
+    #[test]
+    fn litecoin_section_defaults_to_local_regtest_node() {
+        let config_file = File {
+            litecoin: None,
+            ..File::default()
+        };
+
+        let settings = Settings::from_config_file_and_defaults(config_file);
+
+        assert_that(&settings)
+            .is_ok()
+            .map(|settings| &settings.litecoin)
+            .is_equal_to(Litecoin {
+                network: bitcoin::Network::Regtest,
+                node_url: Url::parse("http://localhost:19443").unwrap(),
+                min_confirmations: 1,
+            })
+    }
 }
@@ -0,0 +1,53 @@
+use crate::{
+    db::{
+        custom_sql_types::Json,
+        schema::{self, bitcoin_wallet},
+        Sqlite,
+    },
+    diesel::{OptionalExtension, QueryDsl, RunQueryDsl},
+    wallet::bitcoin::State,
+};
+
+/// There is only one Bitcoin wallet, its state is always stored in this row.
+const WALLET_ID: i32 = 1;
+
+impl Sqlite {
+    pub async fn load_bitcoin_wallet_state(&self) -> anyhow::Result<Option<State>> {
+        use schema::bitcoin_wallet as wallet;
+
+        let state: Option<Json<State>> = self
+            .do_in_transaction(|connection| {
+                wallet::table
+                    .find(WALLET_ID)
+                    .select(wallet::state)
+                    .first(connection)
+                    .optional()
+            })
+            .await?;
+
+        Ok(state.map(|Json(state)| state))
+    }
+
+    pub async fn save_bitcoin_wallet_state(&self, state: &State) -> anyhow::Result<()> {
+        let insertable = InsertableBitcoinWallet {
+            id: WALLET_ID,
+            state: Json(state.clone()),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::replace_into(bitcoin_wallet::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[table_name = "bitcoin_wallet"]
+struct InsertableBitcoinWallet {
+    id: i32,
+    state: Json<State>,
+}
@@ -4,6 +4,7 @@ use diesel::{
     serialize::{self, Output, ToSql},
     sql_types,
 };
+use serde::{de::DeserializeOwned, Serialize};
 use std::{convert::TryFrom, fmt, ops::Deref, str::FromStr};
 
 /// Custom diesel new-type that works as long as T implements `Display` and
@@ -47,6 +48,49 @@ where
     }
 }
 
+/// Custom diesel new-type that stores T as a JSON string.
+///
+/// Used for ledger specific types like transactions that don't have a
+/// canonical string representation but implement `Serialize` and
+/// `Deserialize`.
+#[derive(Debug, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[sql_type = "sql_types::Text"]
+pub struct Json<T>(pub T);
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<DB, T> ToSql<sql_types::Text, DB> for Json<T>
+where
+    DB: Backend,
+    String: ToSql<sql_types::Text, DB>,
+    T: Serialize + fmt::Debug,
+{
+    fn to_sql<W: std::io::Write>(&self, out: &mut Output<'_, W, DB>) -> serialize::Result {
+        let s = serde_json::to_string(&self.0)?;
+        s.to_sql(out)
+    }
+}
+
+impl<DB, T> FromSql<sql_types::Text, DB> for Json<T>
+where
+    DB: Backend,
+    String: FromSql<sql_types::Text, DB>,
+    T: DeserializeOwned,
+{
+    fn from_sql(bytes: Option<&DB::RawValue>) -> deserialize::Result<Self> {
+        let s = String::from_sql(bytes)?;
+        let parsed = serde_json::from_str(s.as_ref())?;
+
+        Ok(Json(parsed))
+    }
+}
+
 // Custom diesel new type for enforcing storage of a u32
 #[derive(Debug, Clone, Copy, PartialEq, FromSqlRow, AsExpression)]
 #[sql_type = "sql_types::BigInt"]
@@ -1,17 +1,25 @@
 use crate::{
+    btsieve::{bitcoin::TransactionPattern, Checkpoint, ScanProgress},
     db::{
+        ledger_states::{LedgerStates, LoadLedgerStates},
         load_swaps::LoadAcceptedSwap,
         swap_types::{DetermineTypes, SwapTypes},
-        AssetKind, LedgerKind, Retrieve, Save, Sqlite, Swap,
+        ActionAttempt, AssetKind, LedgerKind, LoadActionAttempts, LoadSentTransactions, Retrieve,
+        Save, SentTransaction, Sqlite, Swap, SwapScanProgress,
     },
-    ethereum::{Erc20Token, EtherQuantity},
+    ethereum::{Erc20Token, Erc721Token, EtherQuantity},
+    litecoin::Amount as LitecoinAmount,
     quickcheck::Quickcheck,
+    seed::Seed,
     swap_protocols::{
-        ledger::{Bitcoin, Ethereum},
-        rfc003::{Accept, Request},
+        ledger::{Bitcoin, Ethereum, Litecoin},
+        rfc003::{actions::ActionKind, Accept, LedgerState, Request, Secret},
+        SwapId,
     },
+    timestamp::Timestamp,
+    wallet::bitcoin::{State as WalletState, Wallet},
 };
-use bitcoin::Amount as BitcoinAmount;
+use bitcoin::{hashes::sha256d, Amount as BitcoinAmount};
 use std::path::Path;
 
 macro_rules! db_roundtrip_test {
@@ -115,3 +123,312 @@ db_roundtrip_test!(Ethereum, Bitcoin, Erc20Token, BitcoinAmount, |role| {
         role,
     }
 });
+db_roundtrip_test!(Bitcoin, Ethereum, BitcoinAmount, Erc721Token, |role| {
+    SwapTypes {
+        alpha_ledger: LedgerKind::Bitcoin,
+        beta_ledger: LedgerKind::Ethereum,
+        alpha_asset: AssetKind::Bitcoin,
+        beta_asset: AssetKind::Erc721,
+        role,
+    }
+});
+db_roundtrip_test!(Ethereum, Bitcoin, Erc721Token, BitcoinAmount, |role| {
+    SwapTypes {
+        alpha_ledger: LedgerKind::Ethereum,
+        beta_ledger: LedgerKind::Bitcoin,
+        alpha_asset: AssetKind::Erc721,
+        beta_asset: AssetKind::Bitcoin,
+        role,
+    }
+});
+db_roundtrip_test!(Ethereum, Ethereum, EtherQuantity, EtherQuantity, |role| {
+    SwapTypes {
+        alpha_ledger: LedgerKind::Ethereum,
+        beta_ledger: LedgerKind::Ethereum,
+        alpha_asset: AssetKind::Ether,
+        beta_asset: AssetKind::Ether,
+        role,
+    }
+});
+db_roundtrip_test!(Ethereum, Ethereum, EtherQuantity, Erc20Token, |role| {
+    SwapTypes {
+        alpha_ledger: LedgerKind::Ethereum,
+        beta_ledger: LedgerKind::Ethereum,
+        alpha_asset: AssetKind::Ether,
+        beta_asset: AssetKind::Erc20,
+        role,
+    }
+});
+db_roundtrip_test!(Ethereum, Ethereum, Erc20Token, EtherQuantity, |role| {
+    SwapTypes {
+        alpha_ledger: LedgerKind::Ethereum,
+        beta_ledger: LedgerKind::Ethereum,
+        alpha_asset: AssetKind::Erc20,
+        beta_asset: AssetKind::Ether,
+        role,
+    }
+});
+db_roundtrip_test!(Ethereum, Ethereum, Erc20Token, Erc20Token, |role| {
+    SwapTypes {
+        alpha_ledger: LedgerKind::Ethereum,
+        beta_ledger: LedgerKind::Ethereum,
+        alpha_asset: AssetKind::Erc20,
+        beta_asset: AssetKind::Erc20,
+        role,
+    }
+});
+db_roundtrip_test!(Litecoin, Ethereum, LitecoinAmount, EtherQuantity, |role| {
+    SwapTypes {
+        alpha_ledger: LedgerKind::Litecoin,
+        beta_ledger: LedgerKind::Ethereum,
+        alpha_asset: AssetKind::Litecoin,
+        beta_asset: AssetKind::Ether,
+        role,
+    }
+});
+db_roundtrip_test!(Ethereum, Litecoin, EtherQuantity, LitecoinAmount, |role| {
+    SwapTypes {
+        alpha_ledger: LedgerKind::Ethereum,
+        beta_ledger: LedgerKind::Litecoin,
+        alpha_asset: AssetKind::Ether,
+        beta_asset: AssetKind::Litecoin,
+        role,
+    }
+});
+db_roundtrip_test!(Litecoin, Ethereum, LitecoinAmount, Erc20Token, |role| {
+    SwapTypes {
+        alpha_ledger: LedgerKind::Litecoin,
+        beta_ledger: LedgerKind::Ethereum,
+        alpha_asset: AssetKind::Litecoin,
+        beta_asset: AssetKind::Erc20,
+        role,
+    }
+});
+db_roundtrip_test!(Ethereum, Litecoin, Erc20Token, LitecoinAmount, |role| {
+    SwapTypes {
+        alpha_ledger: LedgerKind::Ethereum,
+        beta_ledger: LedgerKind::Litecoin,
+        alpha_asset: AssetKind::Erc20,
+        beta_asset: AssetKind::Litecoin,
+        role,
+    }
+});
+
+#[test]
+fn ledger_states_roundtrip() {
+    fn prop(
+        swap_id: Quickcheck<SwapId>,
+        ethereum_transaction: Quickcheck<crate::ethereum::Transaction>,
+        ethereum_htlc_location: Quickcheck<crate::ethereum::Address>,
+        secret: Quickcheck<[u8; 32]>,
+    ) -> anyhow::Result<bool> {
+        let db = Sqlite::new(&Path::new(":memory:"))?;
+
+        let bitcoin_transaction = bitcoin::Transaction {
+            version: 2,
+            lock_time: 0,
+            input: vec![],
+            output: vec![],
+        };
+        let bitcoin_htlc_location = bitcoin::OutPoint {
+            txid: bitcoin_transaction.txid(),
+            vout: 0,
+        };
+
+        let deployed = LedgerStates::<Bitcoin, Ethereum> {
+            swap_id: *swap_id,
+            alpha_ledger_state: LedgerState::Deployed {
+                htlc_location: bitcoin_htlc_location,
+                deploy_transaction: bitcoin_transaction.clone(),
+            },
+            beta_ledger_state: LedgerState::NotDeployed,
+            secret: None,
+        };
+        let redeemed = LedgerStates::<Bitcoin, Ethereum> {
+            swap_id: *swap_id,
+            alpha_ledger_state: LedgerState::Redeemed {
+                htlc_location: bitcoin_htlc_location,
+                deploy_transaction: bitcoin_transaction.clone(),
+                fund_transaction: bitcoin_transaction.clone(),
+                redeem_transaction: bitcoin_transaction,
+            },
+            beta_ledger_state: LedgerState::Funded {
+                htlc_location: *ethereum_htlc_location,
+                deploy_transaction: ethereum_transaction.0.clone(),
+                fund_transaction: ethereum_transaction.0,
+            },
+            secret: Some(Secret::from(*secret)),
+        };
+
+        let (loaded_deployed, loaded_redeemed) =
+            async_std::task::block_on::<_, Result<_, anyhow::Error>>(async {
+                db.save(deployed.clone()).await?;
+                let loaded_deployed = db.load_ledger_states(&swap_id).await?;
+
+                db.save(redeemed.clone()).await?;
+                let loaded_redeemed = db.load_ledger_states(&swap_id).await?;
+
+                Ok((loaded_deployed, loaded_redeemed))
+            })?;
+
+        Ok(deployed == loaded_deployed && redeemed == loaded_redeemed)
+    }
+
+    quickcheck::quickcheck(
+        prop as fn(
+            Quickcheck<SwapId>,
+            Quickcheck<crate::ethereum::Transaction>,
+            Quickcheck<crate::ethereum::Address>,
+            Quickcheck<[u8; 32]>,
+        ) -> anyhow::Result<bool>,
+    );
+}
+
+#[test]
+fn given_no_ledger_states_were_saved_both_ledgers_are_not_deployed() {
+    let db = Sqlite::new(&Path::new(":memory:")).unwrap();
+    let swap_id = SwapId::default();
+
+    let loaded: LedgerStates<Bitcoin, Ethereum> =
+        async_std::task::block_on(db.load_ledger_states(&swap_id)).unwrap();
+
+    assert_eq!(loaded, LedgerStates {
+        swap_id,
+        alpha_ledger_state: LedgerState::NotDeployed,
+        beta_ledger_state: LedgerState::NotDeployed,
+        secret: None,
+    });
+}
+
+#[test]
+fn scan_progress_is_stored_per_swap_and_pattern() {
+    let db = Sqlite::new(&Path::new(":memory:")).unwrap();
+    let swap_id = SwapId::default();
+    let deploy_pattern = TransactionPattern {
+        to_address: None,
+        from_outpoint: None,
+        unlock_script: Some(vec![vec![]]),
+    };
+    let redeem_pattern = TransactionPattern {
+        to_address: None,
+        from_outpoint: None,
+        unlock_script: Some(vec![vec![1u8]]),
+    };
+    let checkpoint = Checkpoint {
+        block_hash: sha256d::Hash::default(),
+        block_height: 42,
+    };
+
+    let deploy_progress =
+        SwapScanProgress::<sha256d::Hash>::new(db.clone(), swap_id, &deploy_pattern);
+    let redeem_progress =
+        SwapScanProgress::<sha256d::Hash>::new(db.clone(), swap_id, &redeem_pattern);
+    let other_swap_progress =
+        SwapScanProgress::<sha256d::Hash>::new(db, SwapId::default(), &deploy_pattern);
+
+    let (deploy_checkpoint, redeem_checkpoint, other_swap_checkpoint) =
+        async_std::task::block_on(async {
+            deploy_progress.save_checkpoint(checkpoint).await;
+
+            (
+                deploy_progress.load_checkpoint().await,
+                redeem_progress.load_checkpoint().await,
+                other_swap_progress.load_checkpoint().await,
+            )
+        });
+
+    assert_eq!(deploy_checkpoint, Some(checkpoint));
+    assert_eq!(redeem_checkpoint, None);
+    assert_eq!(other_swap_checkpoint, None);
+}
+
+#[test]
+fn bitcoin_wallet_state_roundtrip() {
+    let db = Sqlite::new(&Path::new(":memory:")).unwrap();
+    let wallet = Wallet::new(
+        &Seed::from([0u8; 32]),
+        bitcoin::Network::Regtest,
+        WalletState::default(),
+    );
+    let _ = wallet.new_address();
+
+    let (before_save, after_save) = async_std::task::block_on(async {
+        let before_save = db.load_bitcoin_wallet_state().await.unwrap();
+        db.save_bitcoin_wallet_state(&wallet.state()).await.unwrap();
+        let after_save = db.load_bitcoin_wallet_state().await.unwrap();
+
+        (before_save, after_save)
+    });
+
+    assert_eq!(before_save, None);
+    assert_eq!(after_save, Some(wallet.state()));
+}
+
+#[test]
+fn sent_transaction_is_replaced_when_action_is_executed_again() {
+    let db = Sqlite::new(&Path::new(":memory:")).unwrap();
+    let swap_id = SwapId::default();
+    let other_swap_id = SwapId::default();
+
+    let fund = |transaction_id: &str| SentTransaction {
+        swap_id,
+        action: ActionKind::Fund,
+        transaction_id: transaction_id.to_owned(),
+        raw_transaction: None,
+    };
+    let redeem = SentTransaction {
+        swap_id,
+        action: ActionKind::Redeem,
+        transaction_id: "0x02".to_owned(),
+        raw_transaction: Some("0200000000".to_owned()),
+    };
+
+    let (sent_transactions, other_sent_transactions) = async_std::task::block_on(async {
+        db.save(fund("0x00")).await.unwrap();
+        db.save(fund("0x01")).await.unwrap();
+        db.save(redeem.clone()).await.unwrap();
+
+        (
+            db.load_sent_transactions(&swap_id).await.unwrap(),
+            db.load_sent_transactions(&other_swap_id).await.unwrap(),
+        )
+    });
+
+    assert_eq!(sent_transactions.len(), 2);
+    assert!(sent_transactions.contains(&fund("0x01")));
+    assert!(sent_transactions.contains(&redeem));
+    assert_eq!(other_sent_transactions, vec![]);
+}
+
+#[test]
+fn every_action_attempt_is_kept_in_order() {
+    let db = Sqlite::new(&Path::new(":memory:")).unwrap();
+    let swap_id = SwapId::default();
+    let other_swap_id = SwapId::default();
+
+    let failed = ActionAttempt {
+        swap_id,
+        action: ActionKind::Refund,
+        attempted_at: Timestamp::from(1_000),
+        result: Err("HTLC is not expired yet".to_owned()),
+    };
+    let succeeded = ActionAttempt {
+        swap_id,
+        action: ActionKind::Refund,
+        attempted_at: Timestamp::from(1_060),
+        result: Ok("0x01".to_owned()),
+    };
+
+    let (action_attempts, other_action_attempts) = async_std::task::block_on(async {
+        db.save(failed.clone()).await.unwrap();
+        db.save(succeeded.clone()).await.unwrap();
+
+        (
+            db.load_action_attempts(&swap_id).await.unwrap(),
+            db.load_action_attempts(&other_swap_id).await.unwrap(),
+        )
+    });
+
+    assert_eq!(action_attempts, vec![failed, succeeded]);
+    assert_eq!(other_action_attempts, vec![]);
+}
@@ -0,0 +1,306 @@
+use crate::{
+    db::{
+        custom_sql_types::{Json, Text},
+        schema::{self, rfc003_ledger_states},
+        Save, Sqlite,
+    },
+    diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl},
+    swap_protocols::{
+        rfc003::{HtlcState, Ledger, LedgerState, Secret},
+        SwapId,
+    },
+};
+use async_trait::async_trait;
+use strum_macros::{Display, EnumString};
+
+/// The state of the alpha and beta ledger HTLCs of a swap.
+///
+/// The secret is only stored once it has been revealed by a redeem
+/// transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LedgerStates<AL: Ledger, BL: Ledger> {
+    pub swap_id: SwapId,
+    pub alpha_ledger_state: LedgerState<AL>,
+    pub beta_ledger_state: LedgerState<BL>,
+    pub secret: Option<Secret>,
+}
+
+/// Load the last known state of the HTLCs of a swap.
+///
+/// Swaps for which nothing has been observed on either ledger yet are returned
+/// as `NotDeployed` on both ledgers.
+#[async_trait]
+pub trait LoadLedgerStates<AL: Ledger, BL: Ledger>: Send + Sync + 'static {
+    async fn load_ledger_states(&self, swap_id: &SwapId) -> anyhow::Result<LedgerStates<AL, BL>>;
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq)]
+enum LedgerSide {
+    Alpha,
+    Beta,
+}
+
+#[async_trait]
+impl<AL, BL> Save<LedgerStates<AL, BL>> for Sqlite
+where
+    AL: Ledger,
+    BL: Ledger,
+{
+    async fn save(&self, ledger_states: LedgerStates<AL, BL>) -> anyhow::Result<()> {
+        let LedgerStates {
+            swap_id,
+            alpha_ledger_state,
+            beta_ledger_state,
+            secret,
+        } = ledger_states;
+
+        let alpha =
+            InsertableLedgerState::new(swap_id, LedgerSide::Alpha, alpha_ledger_state, secret);
+        let beta = InsertableLedgerState::new(swap_id, LedgerSide::Beta, beta_ledger_state, secret);
+
+        self.do_in_transaction(|connection| {
+            diesel::replace_into(rfc003_ledger_states::table)
+                .values(&alpha)
+                .execute(connection)?;
+            diesel::replace_into(rfc003_ledger_states::table)
+                .values(&beta)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<AL, BL> LoadLedgerStates<AL, BL> for Sqlite
+where
+    AL: Ledger,
+    BL: Ledger,
+{
+    async fn load_ledger_states(&self, key: &SwapId) -> anyhow::Result<LedgerStates<AL, BL>> {
+        let alpha = self.load_ledger_state::<AL>(key, LedgerSide::Alpha).await?;
+        let beta = self.load_ledger_state::<BL>(key, LedgerSide::Beta).await?;
+
+        let secret = alpha
+            .as_ref()
+            .and_then(|record| record.secret)
+            .or_else(|| beta.as_ref().and_then(|record| record.secret))
+            .map(|Text(secret)| secret);
+
+        Ok(LedgerStates {
+            swap_id: *key,
+            alpha_ledger_state: alpha
+                .map(QueryableLedgerState::into_ledger_state)
+                .transpose()?
+                .unwrap_or(LedgerState::NotDeployed),
+            beta_ledger_state: beta
+                .map(QueryableLedgerState::into_ledger_state)
+                .transpose()?
+                .unwrap_or(LedgerState::NotDeployed),
+            secret,
+        })
+    }
+}
+
+impl Sqlite {
+    async fn load_ledger_state<L: Ledger>(
+        &self,
+        key: &SwapId,
+        side: LedgerSide,
+    ) -> anyhow::Result<Option<QueryableLedgerState<L>>> {
+        use schema::rfc003_ledger_states as ledger_states;
+
+        let record = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                ledger_states::table
+                    .filter(ledger_states::swap_id.eq(key))
+                    .filter(ledger_states::ledger.eq(Text(side)))
+                    .select((
+                        ledger_states::htlc_state,
+                        ledger_states::htlc_location,
+                        ledger_states::deploy_transaction,
+                        ledger_states::fund_transaction,
+                        ledger_states::redeem_transaction,
+                        ledger_states::refund_transaction,
+                        ledger_states::secret,
+                    ))
+                    .first(connection)
+                    .optional()
+            })
+            .await?;
+
+        Ok(record)
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[table_name = "rfc003_ledger_states"]
+struct InsertableLedgerState<L: Ledger> {
+    swap_id: Text<SwapId>,
+    ledger: Text<LedgerSide>,
+    htlc_state: Text<HtlcState>,
+    htlc_location: Option<Json<L::HtlcLocation>>,
+    deploy_transaction: Option<Json<L::Transaction>>,
+    fund_transaction: Option<Json<L::Transaction>>,
+    redeem_transaction: Option<Json<L::Transaction>>,
+    refund_transaction: Option<Json<L::Transaction>>,
+    secret: Option<Text<Secret>>,
+}
+
+impl<L: Ledger> InsertableLedgerState<L> {
+    fn new(
+        swap_id: SwapId,
+        side: LedgerSide,
+        ledger_state: LedgerState<L>,
+        secret: Option<Secret>,
+    ) -> Self {
+        let htlc_state = HtlcState::from(&ledger_state);
+        let mut insertable = InsertableLedgerState {
+            swap_id: Text(swap_id),
+            ledger: Text(side),
+            htlc_state: Text(htlc_state),
+            htlc_location: None,
+            deploy_transaction: None,
+            fund_transaction: None,
+            redeem_transaction: None,
+            refund_transaction: None,
+            secret: None,
+        };
+
+        match ledger_state {
+            LedgerState::NotDeployed => {}
+            LedgerState::Deployed {
+                htlc_location,
+                deploy_transaction,
+            } => {
+                insertable.htlc_location = Some(Json(htlc_location));
+                insertable.deploy_transaction = Some(Json(deploy_transaction));
+            }
+            LedgerState::Funded {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+            }
+            | LedgerState::IncorrectlyFunded {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+            } => {
+                insertable.htlc_location = Some(Json(htlc_location));
+                insertable.deploy_transaction = Some(Json(deploy_transaction));
+                insertable.fund_transaction = Some(Json(fund_transaction));
+            }
+            LedgerState::Redeemed {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+                redeem_transaction,
+            } => {
+                insertable.htlc_location = Some(Json(htlc_location));
+                insertable.deploy_transaction = Some(Json(deploy_transaction));
+                insertable.fund_transaction = Some(Json(fund_transaction));
+                insertable.redeem_transaction = Some(Json(redeem_transaction));
+                insertable.secret = secret.map(Text);
+            }
+            LedgerState::Refunded {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+                refund_transaction,
+            } => {
+                insertable.htlc_location = Some(Json(htlc_location));
+                insertable.deploy_transaction = Some(Json(deploy_transaction));
+                insertable.fund_transaction = Some(Json(fund_transaction));
+                insertable.refund_transaction = Some(Json(refund_transaction));
+            }
+        }
+
+        insertable
+    }
+}
+
+#[derive(Queryable, Debug, Clone)]
+struct QueryableLedgerState<L: Ledger> {
+    htlc_state: Text<HtlcState>,
+    htlc_location: Option<Json<L::HtlcLocation>>,
+    deploy_transaction: Option<Json<L::Transaction>>,
+    fund_transaction: Option<Json<L::Transaction>>,
+    redeem_transaction: Option<Json<L::Transaction>>,
+    refund_transaction: Option<Json<L::Transaction>>,
+    secret: Option<Text<Secret>>,
+}
+
+impl<L: Ledger> QueryableLedgerState<L> {
+    fn into_ledger_state(self) -> anyhow::Result<LedgerState<L>> {
+        let htlc_state = *self.htlc_state;
+        let missing = |column: &str| {
+            anyhow::anyhow!(
+                "ledger state {} is missing column {} in the database",
+                htlc_state,
+                column
+            )
+        };
+
+        if let HtlcState::NotDeployed = htlc_state {
+            return Ok(LedgerState::NotDeployed);
+        }
+
+        let htlc_location = self
+            .htlc_location
+            .ok_or_else(|| missing("htlc_location"))?
+            .0;
+        let deploy_transaction = self
+            .deploy_transaction
+            .ok_or_else(|| missing("deploy_transaction"))?
+            .0;
+
+        if let HtlcState::Deployed = htlc_state {
+            return Ok(LedgerState::Deployed {
+                htlc_location,
+                deploy_transaction,
+            });
+        }
+
+        let fund_transaction = self
+            .fund_transaction
+            .ok_or_else(|| missing("fund_transaction"))?
+            .0;
+
+        let ledger_state = match htlc_state {
+            HtlcState::Funded => LedgerState::Funded {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+            },
+            HtlcState::IncorrectlyFunded => LedgerState::IncorrectlyFunded {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+            },
+            HtlcState::Redeemed => LedgerState::Redeemed {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+                redeem_transaction: self
+                    .redeem_transaction
+                    .ok_or_else(|| missing("redeem_transaction"))?
+                    .0,
+            },
+            HtlcState::Refunded => LedgerState::Refunded {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+                refund_transaction: self
+                    .refund_transaction
+                    .ok_or_else(|| missing("refund_transaction"))?
+                    .0,
+            },
+            HtlcState::NotDeployed | HtlcState::Deployed => unreachable!("handled above"),
+        };
+
+        Ok(ledger_state)
+    }
+}
@@ -4,10 +4,11 @@ use crate::{
         new_types::{DecimalU256, EthereumAddress, Satoshis},
         schema, Sqlite,
     },
-    ethereum::{Erc20Quantity, Erc20Token, EtherQuantity, U256},
+    ethereum::{Erc20Quantity, Erc20Token, Erc721Token, Erc721TokenId, EtherQuantity, U256},
+    litecoin,
     swap_protocols::{
         asset::Asset,
-        ledger::{ethereum::ChainId, Bitcoin, Ethereum},
+        ledger::{ethereum::ChainId, Bitcoin, Ethereum, Litecoin},
         rfc003::{
             messages::{Accept, Request},
             Ledger, SecretHash,
@@ -425,3 +426,989 @@ impl LoadAcceptedSwap<Ethereum, Bitcoin, Erc20Token, bitcoin::Amount> for Sqlite
         ))
     }
 }
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+struct BitcoinEthereumBitcoinErc721AcceptedSwap {
+    // Request fields.
+    swap_id: Text<SwapId>,
+    bitcoin_network: Text<bitcoin::Network>,
+    ethereum_chain_id: U32,
+    bitcoin_amount: Text<Satoshis>,
+    erc721_token_contract: Text<EthereumAddress>,
+    erc721_token_id: Text<DecimalU256>,
+    hash_function: Text<HashFunction>,
+    bitcoin_refund_identity: Text<bitcoin::PublicKey>,
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    bitcoin_expiry: U32,
+    ethereum_expiry: U32,
+    secret_hash: Text<SecretHash>,
+    // Accept fields.
+    bitcoin_redeem_identity: Text<bitcoin::PublicKey>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+
+    at: NaiveDateTime,
+}
+
+#[async_trait]
+impl LoadAcceptedSwap<Bitcoin, Ethereum, bitcoin::Amount, Erc721Token> for Sqlite {
+    async fn load_accepted_swap(
+        &self,
+        key: &SwapId,
+    ) -> anyhow::Result<AcceptedSwap<Bitcoin, Ethereum, bitcoin::Amount, Erc721Token>> {
+        use schema::{
+            rfc003_bitcoin_ethereum_accept_messages as accept_messages,
+            rfc003_bitcoin_ethereum_bitcoin_erc721_request_messages as request_messages,
+        };
+
+        diesel::allow_tables_to_appear_in_same_query!(request_messages, accept_messages);
+
+        let record: BitcoinEthereumBitcoinErc721AcceptedSwap = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                request_messages::table
+                    .inner_join(
+                        accept_messages::table
+                            .on(request_messages::swap_id.eq(accept_messages::swap_id)),
+                    )
+                    .select((
+                        request_messages::swap_id,
+                        request_messages::bitcoin_network,
+                        request_messages::ethereum_chain_id,
+                        request_messages::bitcoin_amount,
+                        request_messages::erc721_token_contract,
+                        request_messages::erc721_token_id,
+                        request_messages::hash_function,
+                        request_messages::bitcoin_refund_identity,
+                        request_messages::ethereum_redeem_identity,
+                        request_messages::bitcoin_expiry,
+                        request_messages::ethereum_expiry,
+                        request_messages::secret_hash,
+                        accept_messages::bitcoin_redeem_identity,
+                        accept_messages::ethereum_refund_identity,
+                        accept_messages::at,
+                    ))
+                    .filter(accept_messages::swap_id.eq(key))
+                    .first(connection)
+            })
+            .await?;
+
+        Ok((
+            Request {
+                swap_id: *record.swap_id,
+                alpha_ledger: Bitcoin {
+                    network: *record.bitcoin_network,
+                },
+                beta_ledger: Ethereum {
+                    chain_id: ChainId::new(record.ethereum_chain_id.into()),
+                },
+                alpha_asset: bitcoin::Amount::from_sat(u64::from(*record.bitcoin_amount)),
+                beta_asset: Erc721Token::new(
+                    (record.erc721_token_contract.0).0,
+                    Erc721TokenId((record.erc721_token_id.0).0),
+                ),
+                hash_function: *record.hash_function,
+                alpha_ledger_refund_identity: crate::bitcoin::PublicKey::from(
+                    *record.bitcoin_refund_identity,
+                ),
+                beta_ledger_redeem_identity: (record.ethereum_redeem_identity.0).0,
+                alpha_expiry: Timestamp::from(u32::from(record.bitcoin_expiry)),
+                beta_expiry: Timestamp::from(u32::from(record.ethereum_expiry)),
+                secret_hash: *record.secret_hash,
+            },
+            Accept {
+                swap_id: *record.swap_id,
+                alpha_ledger_redeem_identity: crate::bitcoin::PublicKey::from(
+                    *record.bitcoin_redeem_identity,
+                ),
+                beta_ledger_refund_identity: (record.ethereum_refund_identity.0).0,
+            },
+            record.at,
+        ))
+    }
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+struct EthereumBitcoinErc721BitcoinAcceptedSwap {
+    // Request fields.
+    swap_id: Text<SwapId>,
+    ethereum_chain_id: U32,
+    bitcoin_network: Text<bitcoin::Network>,
+    erc721_token_contract: Text<EthereumAddress>,
+    erc721_token_id: Text<DecimalU256>,
+    bitcoin_amount: Text<Satoshis>,
+    hash_function: Text<HashFunction>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+    bitcoin_redeem_identity: Text<bitcoin::PublicKey>,
+    ethereum_expiry: U32,
+    bitcoin_expiry: U32,
+    secret_hash: Text<SecretHash>,
+    // Accept fields.
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    bitcoin_refund_identity: Text<bitcoin::PublicKey>,
+
+    at: NaiveDateTime,
+}
+
+#[async_trait]
+impl LoadAcceptedSwap<Ethereum, Bitcoin, Erc721Token, bitcoin::Amount> for Sqlite {
+    async fn load_accepted_swap(
+        &self,
+        key: &SwapId,
+    ) -> anyhow::Result<AcceptedSwap<Ethereum, Bitcoin, Erc721Token, bitcoin::Amount>> {
+        use schema::{
+            rfc003_ethereum_bitcoin_accept_messages as accept_messages,
+            rfc003_ethereum_bitcoin_erc721_bitcoin_request_messages as request_messages,
+        };
+
+        diesel::allow_tables_to_appear_in_same_query!(request_messages, accept_messages);
+
+        let record: EthereumBitcoinErc721BitcoinAcceptedSwap = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                request_messages::table
+                    .inner_join(
+                        accept_messages::table
+                            .on(request_messages::swap_id.eq(accept_messages::swap_id)),
+                    )
+                    .select((
+                        request_messages::swap_id,
+                        request_messages::ethereum_chain_id,
+                        request_messages::bitcoin_network,
+                        request_messages::erc721_token_contract,
+                        request_messages::erc721_token_id,
+                        request_messages::bitcoin_amount,
+                        request_messages::hash_function,
+                        request_messages::ethereum_refund_identity,
+                        request_messages::bitcoin_redeem_identity,
+                        request_messages::ethereum_expiry,
+                        request_messages::bitcoin_expiry,
+                        request_messages::secret_hash,
+                        accept_messages::ethereum_redeem_identity,
+                        accept_messages::bitcoin_refund_identity,
+                        accept_messages::at,
+                    ))
+                    .filter(accept_messages::swap_id.eq(key))
+                    .first(connection)
+            })
+            .await?;
+
+        Ok((
+            Request {
+                swap_id: *record.swap_id,
+                alpha_ledger: Ethereum {
+                    chain_id: ChainId::new(record.ethereum_chain_id.into()),
+                },
+                beta_ledger: Bitcoin {
+                    network: *record.bitcoin_network,
+                },
+                alpha_asset: Erc721Token::new(
+                    (record.erc721_token_contract.0).0,
+                    Erc721TokenId((record.erc721_token_id.0).0),
+                ),
+                beta_asset: bitcoin::Amount::from_sat(u64::from(*record.bitcoin_amount)),
+                hash_function: *record.hash_function,
+                alpha_ledger_refund_identity: (record.ethereum_refund_identity.0).0,
+                beta_ledger_redeem_identity: crate::bitcoin::PublicKey::from(
+                    *record.bitcoin_redeem_identity,
+                ),
+                alpha_expiry: Timestamp::from(u32::from(record.ethereum_expiry)),
+                beta_expiry: Timestamp::from(u32::from(record.bitcoin_expiry)),
+                secret_hash: *record.secret_hash,
+            },
+            Accept {
+                swap_id: *record.swap_id,
+                alpha_ledger_redeem_identity: (record.ethereum_redeem_identity.0).0,
+                beta_ledger_refund_identity: crate::bitcoin::PublicKey::from(
+                    *record.bitcoin_refund_identity,
+                ),
+            },
+            record.at,
+        ))
+    }
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+struct EthereumEthereumEtherEtherAcceptedSwap {
+    // Request fields.
+    swap_id: Text<SwapId>,
+    alpha_ethereum_chain_id: U32,
+    beta_ethereum_chain_id: U32,
+    alpha_ether_amount: Text<DecimalU256>,
+    beta_ether_amount: Text<DecimalU256>,
+    hash_function: Text<HashFunction>,
+    alpha_ethereum_refund_identity: Text<EthereumAddress>,
+    beta_ethereum_redeem_identity: Text<EthereumAddress>,
+    alpha_expiry: U32,
+    beta_expiry: U32,
+    secret_hash: Text<SecretHash>,
+    // Accept fields.
+    alpha_ethereum_redeem_identity: Text<EthereumAddress>,
+    beta_ethereum_refund_identity: Text<EthereumAddress>,
+
+    at: NaiveDateTime,
+}
+
+#[async_trait]
+impl LoadAcceptedSwap<Ethereum, Ethereum, EtherQuantity, EtherQuantity> for Sqlite {
+    async fn load_accepted_swap(
+        &self,
+        key: &SwapId,
+    ) -> anyhow::Result<AcceptedSwap<Ethereum, Ethereum, EtherQuantity, EtherQuantity>> {
+        use schema::{
+            rfc003_ethereum_ethereum_accept_messages as accept_messages,
+            rfc003_ethereum_ethereum_ether_ether_request_messages as request_messages,
+        };
+
+        diesel::allow_tables_to_appear_in_same_query!(request_messages, accept_messages);
+
+        let record: EthereumEthereumEtherEtherAcceptedSwap = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                request_messages::table
+                    .inner_join(
+                        accept_messages::table
+                            .on(request_messages::swap_id.eq(accept_messages::swap_id)),
+                    )
+                    .select((
+                        request_messages::swap_id,
+                        request_messages::alpha_ethereum_chain_id,
+                        request_messages::beta_ethereum_chain_id,
+                        request_messages::alpha_ether_amount,
+                        request_messages::beta_ether_amount,
+                        request_messages::hash_function,
+                        request_messages::alpha_ethereum_refund_identity,
+                        request_messages::beta_ethereum_redeem_identity,
+                        request_messages::alpha_expiry,
+                        request_messages::beta_expiry,
+                        request_messages::secret_hash,
+                        accept_messages::alpha_ethereum_redeem_identity,
+                        accept_messages::beta_ethereum_refund_identity,
+                        accept_messages::at,
+                    ))
+                    .filter(accept_messages::swap_id.eq(key))
+                    .first(connection)
+            })
+            .await?;
+
+        Ok((
+            Request {
+                swap_id: *record.swap_id,
+                alpha_ledger: Ethereum {
+                    chain_id: ChainId::new(record.alpha_ethereum_chain_id.into()),
+                },
+                beta_ledger: Ethereum {
+                    chain_id: ChainId::new(record.beta_ethereum_chain_id.into()),
+                },
+                alpha_asset: EtherQuantity::from_wei(U256::from(*record.alpha_ether_amount)),
+                beta_asset: EtherQuantity::from_wei(U256::from(*record.beta_ether_amount)),
+                hash_function: *record.hash_function,
+                alpha_ledger_refund_identity: (record.alpha_ethereum_refund_identity.0).0,
+                beta_ledger_redeem_identity: (record.beta_ethereum_redeem_identity.0).0,
+                alpha_expiry: Timestamp::from(u32::from(record.alpha_expiry)),
+                beta_expiry: Timestamp::from(u32::from(record.beta_expiry)),
+                secret_hash: *record.secret_hash,
+            },
+            Accept {
+                swap_id: *record.swap_id,
+                alpha_ledger_redeem_identity: (record.alpha_ethereum_redeem_identity.0).0,
+                beta_ledger_refund_identity: (record.beta_ethereum_refund_identity.0).0,
+            },
+            record.at,
+        ))
+    }
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+struct EthereumEthereumEtherErc20AcceptedSwap {
+    // Request fields.
+    swap_id: Text<SwapId>,
+    alpha_ethereum_chain_id: U32,
+    beta_ethereum_chain_id: U32,
+    alpha_ether_amount: Text<DecimalU256>,
+    beta_erc20_token_contract: Text<EthereumAddress>,
+    beta_erc20_amount: Text<DecimalU256>,
+    hash_function: Text<HashFunction>,
+    alpha_ethereum_refund_identity: Text<EthereumAddress>,
+    beta_ethereum_redeem_identity: Text<EthereumAddress>,
+    alpha_expiry: U32,
+    beta_expiry: U32,
+    secret_hash: Text<SecretHash>,
+    // Accept fields.
+    alpha_ethereum_redeem_identity: Text<EthereumAddress>,
+    beta_ethereum_refund_identity: Text<EthereumAddress>,
+
+    at: NaiveDateTime,
+}
+
+#[async_trait]
+impl LoadAcceptedSwap<Ethereum, Ethereum, EtherQuantity, Erc20Token> for Sqlite {
+    async fn load_accepted_swap(
+        &self,
+        key: &SwapId,
+    ) -> anyhow::Result<AcceptedSwap<Ethereum, Ethereum, EtherQuantity, Erc20Token>> {
+        use schema::{
+            rfc003_ethereum_ethereum_accept_messages as accept_messages,
+            rfc003_ethereum_ethereum_ether_erc20_request_messages as request_messages,
+        };
+
+        diesel::allow_tables_to_appear_in_same_query!(request_messages, accept_messages);
+
+        let record: EthereumEthereumEtherErc20AcceptedSwap = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                request_messages::table
+                    .inner_join(
+                        accept_messages::table
+                            .on(request_messages::swap_id.eq(accept_messages::swap_id)),
+                    )
+                    .select((
+                        request_messages::swap_id,
+                        request_messages::alpha_ethereum_chain_id,
+                        request_messages::beta_ethereum_chain_id,
+                        request_messages::alpha_ether_amount,
+                        request_messages::beta_erc20_token_contract,
+                        request_messages::beta_erc20_amount,
+                        request_messages::hash_function,
+                        request_messages::alpha_ethereum_refund_identity,
+                        request_messages::beta_ethereum_redeem_identity,
+                        request_messages::alpha_expiry,
+                        request_messages::beta_expiry,
+                        request_messages::secret_hash,
+                        accept_messages::alpha_ethereum_redeem_identity,
+                        accept_messages::beta_ethereum_refund_identity,
+                        accept_messages::at,
+                    ))
+                    .filter(accept_messages::swap_id.eq(key))
+                    .first(connection)
+            })
+            .await?;
+
+        Ok((
+            Request {
+                swap_id: *record.swap_id,
+                alpha_ledger: Ethereum {
+                    chain_id: ChainId::new(record.alpha_ethereum_chain_id.into()),
+                },
+                beta_ledger: Ethereum {
+                    chain_id: ChainId::new(record.beta_ethereum_chain_id.into()),
+                },
+                alpha_asset: EtherQuantity::from_wei(U256::from(*record.alpha_ether_amount)),
+                beta_asset: Erc20Token::new(
+                    (record.beta_erc20_token_contract.0).0,
+                    Erc20Quantity((record.beta_erc20_amount.0).0),
+                ),
+                hash_function: *record.hash_function,
+                alpha_ledger_refund_identity: (record.alpha_ethereum_refund_identity.0).0,
+                beta_ledger_redeem_identity: (record.beta_ethereum_redeem_identity.0).0,
+                alpha_expiry: Timestamp::from(u32::from(record.alpha_expiry)),
+                beta_expiry: Timestamp::from(u32::from(record.beta_expiry)),
+                secret_hash: *record.secret_hash,
+            },
+            Accept {
+                swap_id: *record.swap_id,
+                alpha_ledger_redeem_identity: (record.alpha_ethereum_redeem_identity.0).0,
+                beta_ledger_refund_identity: (record.beta_ethereum_refund_identity.0).0,
+            },
+            record.at,
+        ))
+    }
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+struct EthereumEthereumErc20EtherAcceptedSwap {
+    // Request fields.
+    swap_id: Text<SwapId>,
+    alpha_ethereum_chain_id: U32,
+    beta_ethereum_chain_id: U32,
+    alpha_erc20_token_contract: Text<EthereumAddress>,
+    alpha_erc20_amount: Text<DecimalU256>,
+    beta_ether_amount: Text<DecimalU256>,
+    hash_function: Text<HashFunction>,
+    alpha_ethereum_refund_identity: Text<EthereumAddress>,
+    beta_ethereum_redeem_identity: Text<EthereumAddress>,
+    alpha_expiry: U32,
+    beta_expiry: U32,
+    secret_hash: Text<SecretHash>,
+    // Accept fields.
+    alpha_ethereum_redeem_identity: Text<EthereumAddress>,
+    beta_ethereum_refund_identity: Text<EthereumAddress>,
+
+    at: NaiveDateTime,
+}
+
+#[async_trait]
+impl LoadAcceptedSwap<Ethereum, Ethereum, Erc20Token, EtherQuantity> for Sqlite {
+    async fn load_accepted_swap(
+        &self,
+        key: &SwapId,
+    ) -> anyhow::Result<AcceptedSwap<Ethereum, Ethereum, Erc20Token, EtherQuantity>> {
+        use schema::{
+            rfc003_ethereum_ethereum_accept_messages as accept_messages,
+            rfc003_ethereum_ethereum_erc20_ether_request_messages as request_messages,
+        };
+
+        diesel::allow_tables_to_appear_in_same_query!(request_messages, accept_messages);
+
+        let record: EthereumEthereumErc20EtherAcceptedSwap = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                request_messages::table
+                    .inner_join(
+                        accept_messages::table
+                            .on(request_messages::swap_id.eq(accept_messages::swap_id)),
+                    )
+                    .select((
+                        request_messages::swap_id,
+                        request_messages::alpha_ethereum_chain_id,
+                        request_messages::beta_ethereum_chain_id,
+                        request_messages::alpha_erc20_token_contract,
+                        request_messages::alpha_erc20_amount,
+                        request_messages::beta_ether_amount,
+                        request_messages::hash_function,
+                        request_messages::alpha_ethereum_refund_identity,
+                        request_messages::beta_ethereum_redeem_identity,
+                        request_messages::alpha_expiry,
+                        request_messages::beta_expiry,
+                        request_messages::secret_hash,
+                        accept_messages::alpha_ethereum_redeem_identity,
+                        accept_messages::beta_ethereum_refund_identity,
+                        accept_messages::at,
+                    ))
+                    .filter(accept_messages::swap_id.eq(key))
+                    .first(connection)
+            })
+            .await?;
+
+        Ok((
+            Request {
+                swap_id: *record.swap_id,
+                alpha_ledger: Ethereum {
+                    chain_id: ChainId::new(record.alpha_ethereum_chain_id.into()),
+                },
+                beta_ledger: Ethereum {
+                    chain_id: ChainId::new(record.beta_ethereum_chain_id.into()),
+                },
+                alpha_asset: Erc20Token::new(
+                    (record.alpha_erc20_token_contract.0).0,
+                    Erc20Quantity((record.alpha_erc20_amount.0).0),
+                ),
+                beta_asset: EtherQuantity::from_wei(U256::from(*record.beta_ether_amount)),
+                hash_function: *record.hash_function,
+                alpha_ledger_refund_identity: (record.alpha_ethereum_refund_identity.0).0,
+                beta_ledger_redeem_identity: (record.beta_ethereum_redeem_identity.0).0,
+                alpha_expiry: Timestamp::from(u32::from(record.alpha_expiry)),
+                beta_expiry: Timestamp::from(u32::from(record.beta_expiry)),
+                secret_hash: *record.secret_hash,
+            },
+            Accept {
+                swap_id: *record.swap_id,
+                alpha_ledger_redeem_identity: (record.alpha_ethereum_redeem_identity.0).0,
+                beta_ledger_refund_identity: (record.beta_ethereum_refund_identity.0).0,
+            },
+            record.at,
+        ))
+    }
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+struct EthereumEthereumErc20Erc20AcceptedSwap {
+    // Request fields.
+    swap_id: Text<SwapId>,
+    alpha_ethereum_chain_id: U32,
+    beta_ethereum_chain_id: U32,
+    alpha_erc20_token_contract: Text<EthereumAddress>,
+    alpha_erc20_amount: Text<DecimalU256>,
+    beta_erc20_token_contract: Text<EthereumAddress>,
+    beta_erc20_amount: Text<DecimalU256>,
+    hash_function: Text<HashFunction>,
+    alpha_ethereum_refund_identity: Text<EthereumAddress>,
+    beta_ethereum_redeem_identity: Text<EthereumAddress>,
+    alpha_expiry: U32,
+    beta_expiry: U32,
+    secret_hash: Text<SecretHash>,
+    // Accept fields.
+    alpha_ethereum_redeem_identity: Text<EthereumAddress>,
+    beta_ethereum_refund_identity: Text<EthereumAddress>,
+
+    at: NaiveDateTime,
+}
+
+#[async_trait]
+impl LoadAcceptedSwap<Ethereum, Ethereum, Erc20Token, Erc20Token> for Sqlite {
+    async fn load_accepted_swap(
+        &self,
+        key: &SwapId,
+    ) -> anyhow::Result<AcceptedSwap<Ethereum, Ethereum, Erc20Token, Erc20Token>> {
+        use schema::{
+            rfc003_ethereum_ethereum_accept_messages as accept_messages,
+            rfc003_ethereum_ethereum_erc20_erc20_request_messages as request_messages,
+        };
+
+        diesel::allow_tables_to_appear_in_same_query!(request_messages, accept_messages);
+
+        let record: EthereumEthereumErc20Erc20AcceptedSwap = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                request_messages::table
+                    .inner_join(
+                        accept_messages::table
+                            .on(request_messages::swap_id.eq(accept_messages::swap_id)),
+                    )
+                    .select((
+                        request_messages::swap_id,
+                        request_messages::alpha_ethereum_chain_id,
+                        request_messages::beta_ethereum_chain_id,
+                        request_messages::alpha_erc20_token_contract,
+                        request_messages::alpha_erc20_amount,
+                        request_messages::beta_erc20_token_contract,
+                        request_messages::beta_erc20_amount,
+                        request_messages::hash_function,
+                        request_messages::alpha_ethereum_refund_identity,
+                        request_messages::beta_ethereum_redeem_identity,
+                        request_messages::alpha_expiry,
+                        request_messages::beta_expiry,
+                        request_messages::secret_hash,
+                        accept_messages::alpha_ethereum_redeem_identity,
+                        accept_messages::beta_ethereum_refund_identity,
+                        accept_messages::at,
+                    ))
+                    .filter(accept_messages::swap_id.eq(key))
+                    .first(connection)
+            })
+            .await?;
+
+        Ok((
+            Request {
+                swap_id: *record.swap_id,
+                alpha_ledger: Ethereum {
+                    chain_id: ChainId::new(record.alpha_ethereum_chain_id.into()),
+                },
+                beta_ledger: Ethereum {
+                    chain_id: ChainId::new(record.beta_ethereum_chain_id.into()),
+                },
+                alpha_asset: Erc20Token::new(
+                    (record.alpha_erc20_token_contract.0).0,
+                    Erc20Quantity((record.alpha_erc20_amount.0).0),
+                ),
+                beta_asset: Erc20Token::new(
+                    (record.beta_erc20_token_contract.0).0,
+                    Erc20Quantity((record.beta_erc20_amount.0).0),
+                ),
+                hash_function: *record.hash_function,
+                alpha_ledger_refund_identity: (record.alpha_ethereum_refund_identity.0).0,
+                beta_ledger_redeem_identity: (record.beta_ethereum_redeem_identity.0).0,
+                alpha_expiry: Timestamp::from(u32::from(record.alpha_expiry)),
+                beta_expiry: Timestamp::from(u32::from(record.beta_expiry)),
+                secret_hash: *record.secret_hash,
+            },
+            Accept {
+                swap_id: *record.swap_id,
+                alpha_ledger_redeem_identity: (record.alpha_ethereum_redeem_identity.0).0,
+                beta_ledger_refund_identity: (record.beta_ethereum_refund_identity.0).0,
+            },
+            record.at,
+        ))
+    }
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+struct LitecoinEthereumLitecoinEtherAcceptedSwap {
+    // Request fields.
+    swap_id: Text<SwapId>,
+    litecoin_network: Text<bitcoin::Network>,
+    ethereum_chain_id: U32,
+    litecoin_amount: Text<Satoshis>,
+    ether_amount: Text<DecimalU256>,
+    hash_function: Text<HashFunction>,
+    litecoin_refund_identity: Text<bitcoin::PublicKey>,
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    litecoin_expiry: U32,
+    ethereum_expiry: U32,
+    secret_hash: Text<SecretHash>,
+    // Accept fields.
+    litecoin_redeem_identity: Text<bitcoin::PublicKey>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+
+    at: NaiveDateTime,
+}
+
+#[async_trait]
+impl LoadAcceptedSwap<Litecoin, Ethereum, litecoin::Amount, EtherQuantity> for Sqlite {
+    async fn load_accepted_swap(
+        &self,
+        key: &SwapId,
+    ) -> anyhow::Result<
+        AcceptedSwap<Litecoin, Ethereum, litecoin::Amount, crate::ethereum::EtherQuantity>,
+    > {
+        use schema::{
+            rfc003_litecoin_ethereum_accept_messages as accept_messages,
+            rfc003_litecoin_ethereum_litecoin_ether_request_messages as request_messages,
+        };
+
+        diesel::allow_tables_to_appear_in_same_query!(request_messages, accept_messages);
+
+        let record: LitecoinEthereumLitecoinEtherAcceptedSwap = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                request_messages::table
+                    .inner_join(
+                        accept_messages::table
+                            .on(request_messages::swap_id.eq(accept_messages::swap_id)),
+                    )
+                    .select((
+                        request_messages::swap_id,
+                        request_messages::litecoin_network,
+                        request_messages::ethereum_chain_id,
+                        request_messages::litecoin_amount,
+                        request_messages::ether_amount,
+                        request_messages::hash_function,
+                        request_messages::litecoin_refund_identity,
+                        request_messages::ethereum_redeem_identity,
+                        request_messages::litecoin_expiry,
+                        request_messages::ethereum_expiry,
+                        request_messages::secret_hash,
+                        accept_messages::litecoin_redeem_identity,
+                        accept_messages::ethereum_refund_identity,
+                        accept_messages::at,
+                    ))
+                    .filter(accept_messages::swap_id.eq(key))
+                    .first(connection)
+            })
+            .await?;
+
+        Ok((
+            Request {
+                swap_id: *record.swap_id,
+                alpha_ledger: Litecoin {
+                    network: *record.litecoin_network,
+                },
+                beta_ledger: Ethereum {
+                    chain_id: ChainId::new(record.ethereum_chain_id.into()),
+                },
+                alpha_asset: litecoin::Amount::from_litoshi(u64::from(*record.litecoin_amount)),
+                beta_asset: EtherQuantity::from_wei(U256::from(*record.ether_amount)),
+                hash_function: *record.hash_function,
+                alpha_ledger_refund_identity: crate::bitcoin::PublicKey::from(
+                    *record.litecoin_refund_identity,
+                ),
+                beta_ledger_redeem_identity: (record.ethereum_redeem_identity.0).0,
+                alpha_expiry: Timestamp::from(u32::from(record.litecoin_expiry)),
+                beta_expiry: Timestamp::from(u32::from(record.ethereum_expiry)),
+                secret_hash: *record.secret_hash,
+            },
+            Accept {
+                swap_id: *record.swap_id,
+                alpha_ledger_redeem_identity: crate::bitcoin::PublicKey::from(
+                    *record.litecoin_redeem_identity,
+                ),
+                beta_ledger_refund_identity: (record.ethereum_refund_identity.0).0,
+            },
+            record.at,
+        ))
+    }
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+struct EthereumLitecoinEtherLitecoinAcceptedSwap {
+    // Request fields.
+    swap_id: Text<SwapId>,
+    ethereum_chain_id: U32,
+    litecoin_network: Text<bitcoin::Network>,
+    ether_amount: Text<DecimalU256>,
+    litecoin_amount: Text<Satoshis>,
+    hash_function: Text<HashFunction>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+    litecoin_redeem_identity: Text<bitcoin::PublicKey>,
+    ethereum_expiry: U32,
+    litecoin_expiry: U32,
+    secret_hash: Text<SecretHash>,
+    // Accept fields.
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    litecoin_refund_identity: Text<bitcoin::PublicKey>,
+
+    at: NaiveDateTime,
+}
+
+#[async_trait]
+impl LoadAcceptedSwap<Ethereum, Litecoin, EtherQuantity, litecoin::Amount> for Sqlite {
+    async fn load_accepted_swap(
+        &self,
+        key: &SwapId,
+    ) -> anyhow::Result<AcceptedSwap<Ethereum, Litecoin, EtherQuantity, litecoin::Amount>> {
+        use schema::{
+            rfc003_ethereum_litecoin_accept_messages as accept_messages,
+            rfc003_ethereum_litecoin_ether_litecoin_request_messages as request_messages,
+        };
+
+        diesel::allow_tables_to_appear_in_same_query!(request_messages, accept_messages);
+
+        let record: EthereumLitecoinEtherLitecoinAcceptedSwap = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                request_messages::table
+                    .inner_join(
+                        accept_messages::table
+                            .on(request_messages::swap_id.eq(accept_messages::swap_id)),
+                    )
+                    .select((
+                        request_messages::swap_id,
+                        request_messages::ethereum_chain_id,
+                        request_messages::litecoin_network,
+                        request_messages::ether_amount,
+                        request_messages::litecoin_amount,
+                        request_messages::hash_function,
+                        request_messages::ethereum_refund_identity,
+                        request_messages::litecoin_redeem_identity,
+                        request_messages::ethereum_expiry,
+                        request_messages::litecoin_expiry,
+                        request_messages::secret_hash,
+                        accept_messages::ethereum_redeem_identity,
+                        accept_messages::litecoin_refund_identity,
+                        accept_messages::at,
+                    ))
+                    .filter(accept_messages::swap_id.eq(key))
+                    .first(connection)
+            })
+            .await?;
+
+        Ok((
+            Request {
+                swap_id: *record.swap_id,
+                alpha_ledger: Ethereum {
+                    chain_id: ChainId::new(record.ethereum_chain_id.into()),
+                },
+                beta_ledger: Litecoin {
+                    network: *record.litecoin_network,
+                },
+                alpha_asset: EtherQuantity::from_wei(U256::from(*record.ether_amount)),
+                beta_asset: litecoin::Amount::from_litoshi(u64::from(*record.litecoin_amount)),
+                hash_function: *record.hash_function,
+                alpha_ledger_refund_identity: (record.ethereum_refund_identity.0).0,
+                beta_ledger_redeem_identity: crate::bitcoin::PublicKey::from(
+                    *record.litecoin_redeem_identity,
+                ),
+                alpha_expiry: Timestamp::from(u32::from(record.ethereum_expiry)),
+                beta_expiry: Timestamp::from(u32::from(record.litecoin_expiry)),
+                secret_hash: *record.secret_hash,
+            },
+            Accept {
+                swap_id: *record.swap_id,
+                alpha_ledger_redeem_identity: (record.ethereum_redeem_identity.0).0,
+                beta_ledger_refund_identity: crate::bitcoin::PublicKey::from(
+                    *record.litecoin_refund_identity,
+                ),
+            },
+            record.at,
+        ))
+    }
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+struct LitecoinEthereumLitecoinErc20AcceptedSwap {
+    // Request fields.
+    swap_id: Text<SwapId>,
+    litecoin_network: Text<bitcoin::Network>,
+    ethereum_chain_id: U32,
+    litecoin_amount: Text<Satoshis>,
+    erc20_token_contract: Text<EthereumAddress>,
+    erc20_amount: Text<DecimalU256>,
+    hash_function: Text<HashFunction>,
+    litecoin_refund_identity: Text<bitcoin::PublicKey>,
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    litecoin_expiry: U32,
+    ethereum_expiry: U32,
+    secret_hash: Text<SecretHash>,
+    // Accept fields.
+    litecoin_redeem_identity: Text<bitcoin::PublicKey>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+
+    at: NaiveDateTime,
+}
+
+#[async_trait]
+impl LoadAcceptedSwap<Litecoin, Ethereum, litecoin::Amount, Erc20Token> for Sqlite {
+    async fn load_accepted_swap(
+        &self,
+        key: &SwapId,
+    ) -> anyhow::Result<AcceptedSwap<Litecoin, Ethereum, litecoin::Amount, Erc20Token>> {
+        use schema::{
+            rfc003_litecoin_ethereum_accept_messages as accept_messages,
+            rfc003_litecoin_ethereum_litecoin_erc20_request_messages as request_messages,
+        };
+
+        diesel::allow_tables_to_appear_in_same_query!(request_messages, accept_messages);
+
+        let record: LitecoinEthereumLitecoinErc20AcceptedSwap = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                request_messages::table
+                    .inner_join(
+                        accept_messages::table
+                            .on(request_messages::swap_id.eq(accept_messages::swap_id)),
+                    )
+                    .select((
+                        request_messages::swap_id,
+                        request_messages::litecoin_network,
+                        request_messages::ethereum_chain_id,
+                        request_messages::litecoin_amount,
+                        request_messages::erc20_token_contract,
+                        request_messages::erc20_amount,
+                        request_messages::hash_function,
+                        request_messages::litecoin_refund_identity,
+                        request_messages::ethereum_redeem_identity,
+                        request_messages::litecoin_expiry,
+                        request_messages::ethereum_expiry,
+                        request_messages::secret_hash,
+                        accept_messages::litecoin_redeem_identity,
+                        accept_messages::ethereum_refund_identity,
+                        accept_messages::at,
+                    ))
+                    .filter(accept_messages::swap_id.eq(key))
+                    .first(connection)
+            })
+            .await?;
+
+        Ok((
+            Request {
+                swap_id: *record.swap_id,
+                alpha_ledger: Litecoin {
+                    network: *record.litecoin_network,
+                },
+                beta_ledger: Ethereum {
+                    chain_id: ChainId::new(record.ethereum_chain_id.into()),
+                },
+                alpha_asset: litecoin::Amount::from_litoshi(u64::from(*record.litecoin_amount)),
+                beta_asset: Erc20Token::new(
+                    (record.erc20_token_contract.0).0,
+                    Erc20Quantity((record.erc20_amount.0).0),
+                ),
+                hash_function: *record.hash_function,
+                alpha_ledger_refund_identity: crate::bitcoin::PublicKey::from(
+                    *record.litecoin_refund_identity,
+                ),
+                beta_ledger_redeem_identity: (record.ethereum_redeem_identity.0).0,
+                alpha_expiry: Timestamp::from(u32::from(record.litecoin_expiry)),
+                beta_expiry: Timestamp::from(u32::from(record.ethereum_expiry)),
+                secret_hash: *record.secret_hash,
+            },
+            Accept {
+                swap_id: *record.swap_id,
+                alpha_ledger_redeem_identity: crate::bitcoin::PublicKey::from(
+                    *record.litecoin_redeem_identity,
+                ),
+                beta_ledger_refund_identity: (record.ethereum_refund_identity.0).0,
+            },
+            record.at,
+        ))
+    }
+}
+
+#[derive(Queryable, Debug, Clone, PartialEq)]
+struct EthereumLitecoinErc20LitecoinAcceptedSwap {
+    // Request fields.
+    swap_id: Text<SwapId>,
+    ethereum_chain_id: U32,
+    litecoin_network: Text<bitcoin::Network>,
+    erc20_token_contract: Text<EthereumAddress>,
+    erc20_amount: Text<DecimalU256>,
+    litecoin_amount: Text<Satoshis>,
+    hash_function: Text<HashFunction>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+    litecoin_redeem_identity: Text<bitcoin::PublicKey>,
+    ethereum_expiry: U32,
+    litecoin_expiry: U32,
+    secret_hash: Text<SecretHash>,
+    // Accept fields.
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    litecoin_refund_identity: Text<bitcoin::PublicKey>,
+
+    at: NaiveDateTime,
+}
+
+#[async_trait]
+impl LoadAcceptedSwap<Ethereum, Litecoin, Erc20Token, litecoin::Amount> for Sqlite {
+    async fn load_accepted_swap(
+        &self,
+        key: &SwapId,
+    ) -> anyhow::Result<AcceptedSwap<Ethereum, Litecoin, Erc20Token, litecoin::Amount>> {
+        use schema::{
+            rfc003_ethereum_litecoin_accept_messages as accept_messages,
+            rfc003_ethereum_litecoin_erc20_litecoin_request_messages as request_messages,
+        };
+
+        diesel::allow_tables_to_appear_in_same_query!(request_messages, accept_messages);
+
+        let record: EthereumLitecoinErc20LitecoinAcceptedSwap = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                request_messages::table
+                    .inner_join(
+                        accept_messages::table
+                            .on(request_messages::swap_id.eq(accept_messages::swap_id)),
+                    )
+                    .select((
+                        request_messages::swap_id,
+                        request_messages::ethereum_chain_id,
+                        request_messages::litecoin_network,
+                        request_messages::erc20_token_contract,
+                        request_messages::erc20_amount,
+                        request_messages::litecoin_amount,
+                        request_messages::hash_function,
+                        request_messages::ethereum_refund_identity,
+                        request_messages::litecoin_redeem_identity,
+                        request_messages::ethereum_expiry,
+                        request_messages::litecoin_expiry,
+                        request_messages::secret_hash,
+                        accept_messages::ethereum_redeem_identity,
+                        accept_messages::litecoin_refund_identity,
+                        accept_messages::at,
+                    ))
+                    .filter(accept_messages::swap_id.eq(key))
+                    .first(connection)
+            })
+            .await?;
+
+        Ok((
+            Request {
+                swap_id: *record.swap_id,
+                alpha_ledger: Ethereum {
+                    chain_id: ChainId::new(record.ethereum_chain_id.into()),
+                },
+                beta_ledger: Litecoin {
+                    network: *record.litecoin_network,
+                },
+                alpha_asset: Erc20Token::new(
+                    (record.erc20_token_contract.0).0,
+                    Erc20Quantity((record.erc20_amount.0).0),
+                ),
+                beta_asset: litecoin::Amount::from_litoshi(u64::from(*record.litecoin_amount)),
+                hash_function: *record.hash_function,
+                alpha_ledger_refund_identity: (record.ethereum_refund_identity.0).0,
+                beta_ledger_redeem_identity: crate::bitcoin::PublicKey::from(
+                    *record.litecoin_redeem_identity,
+                ),
+                alpha_expiry: Timestamp::from(u32::from(record.ethereum_expiry)),
+                beta_expiry: Timestamp::from(u32::from(record.litecoin_expiry)),
+                secret_hash: *record.secret_hash,
+            },
+            Accept {
+                swap_id: *record.swap_id,
+                alpha_ledger_redeem_identity: (record.ethereum_redeem_identity.0).0,
+                beta_ledger_refund_identity: crate::bitcoin::PublicKey::from(
+                    *record.litecoin_refund_identity,
+                ),
+            },
+            record.at,
+        ))
+    }
+}
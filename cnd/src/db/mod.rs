@@ -1,10 +1,14 @@
+mod bitcoin_wallet;
 mod custom_sql_types;
 #[cfg(test)]
 mod integration_tests;
+mod ledger_states;
 mod load_swaps;
 mod new_types;
 mod save;
+mod scan_progress;
 mod schema;
+mod sent_transactions;
 #[cfg(test)]
 mod serialization_format_stability_tests;
 mod swap;
@@ -14,8 +18,11 @@ pub mod with_swap_types;
 embed_migrations!("./migrations");
 
 pub use self::{
+    ledger_states::{LedgerStates, LoadLedgerStates},
     load_swaps::{AcceptedSwap, LoadAcceptedSwap},
     save::*,
+    scan_progress::SwapScanProgress,
+    sent_transactions::{ActionAttempt, LoadActionAttempts, LoadSentTransactions, SentTransaction},
     swap::*,
     swap_types::*,
 };
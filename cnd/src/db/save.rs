@@ -5,9 +5,10 @@ use crate::{
         schema::{self, *},
         Sqlite, Swap,
     },
-    ethereum::{Erc20Token, EtherQuantity},
+    ethereum::{Erc20Token, Erc721Token, EtherQuantity},
+    litecoin,
     swap_protocols::{
-        ledger::{Bitcoin, Ethereum},
+        ledger::{Bitcoin, Ethereum, Litecoin},
         rfc003::{Accept, Decline, Request, SecretHash},
         HashFunction, Role, SwapId,
     },
@@ -26,10 +27,23 @@ pub trait Save<T>: Send + Sync + 'static {
 pub trait Saver:
     Save<Request<Bitcoin, Ethereum, bitcoin::Amount, EtherQuantity>>
     + Save<Request<Bitcoin, Ethereum, bitcoin::Amount, Erc20Token>>
+    + Save<Request<Bitcoin, Ethereum, bitcoin::Amount, Erc721Token>>
     + Save<Request<Ethereum, Bitcoin, EtherQuantity, bitcoin::Amount>>
     + Save<Request<Ethereum, Bitcoin, Erc20Token, bitcoin::Amount>>
+    + Save<Request<Ethereum, Bitcoin, Erc721Token, bitcoin::Amount>>
+    + Save<Request<Ethereum, Ethereum, EtherQuantity, EtherQuantity>>
+    + Save<Request<Ethereum, Ethereum, EtherQuantity, Erc20Token>>
+    + Save<Request<Ethereum, Ethereum, Erc20Token, EtherQuantity>>
+    + Save<Request<Ethereum, Ethereum, Erc20Token, Erc20Token>>
+    + Save<Request<Litecoin, Ethereum, litecoin::Amount, EtherQuantity>>
+    + Save<Request<Litecoin, Ethereum, litecoin::Amount, Erc20Token>>
+    + Save<Request<Ethereum, Litecoin, EtherQuantity, litecoin::Amount>>
+    + Save<Request<Ethereum, Litecoin, Erc20Token, litecoin::Amount>>
     + Save<Accept<Bitcoin, Ethereum>>
     + Save<Accept<Ethereum, Bitcoin>>
+    + Save<Accept<Ethereum, Ethereum>>
+    + Save<Accept<Litecoin, Ethereum>>
+    + Save<Accept<Ethereum, Litecoin>>
     + Save<Decline>
     + Save<Swap>
 {
@@ -195,27 +209,652 @@ impl Save<Request<Bitcoin, Ethereum, bitcoin::Amount, Erc20Token>> for Sqlite {
     }
 }
 
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_bitcoin_ethereum_bitcoin_erc721_request_messages"]
+struct InsertableBitcoinEthereumBitcoinErc721RequestMessage {
+    swap_id: Text<SwapId>,
+    bitcoin_network: Text<bitcoin::Network>,
+    ethereum_chain_id: U32,
+    bitcoin_amount: Text<Satoshis>,
+    erc721_token_id: Text<DecimalU256>,
+    erc721_token_contract: Text<EthereumAddress>,
+    hash_function: Text<HashFunction>,
+    bitcoin_refund_identity: Text<bitcoin::PublicKey>,
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    bitcoin_expiry: U32,
+    ethereum_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[async_trait]
+impl Save<Request<Bitcoin, Ethereum, bitcoin::Amount, Erc721Token>> for Sqlite {
+    async fn save(
+        &self,
+        message: Request<Bitcoin, Ethereum, bitcoin::Amount, Erc721Token>,
+    ) -> anyhow::Result<()> {
+        let Request {
+            swap_id,
+            alpha_ledger,
+            alpha_asset,
+            beta_ledger,
+            beta_asset,
+            hash_function,
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+            alpha_expiry,
+            beta_expiry,
+            secret_hash,
+        } = message;
+
+        let insertable = InsertableBitcoinEthereumBitcoinErc721RequestMessage {
+            swap_id: Text(swap_id),
+            bitcoin_network: Text(alpha_ledger.network),
+            ethereum_chain_id: U32(beta_ledger.chain_id.into()),
+            bitcoin_amount: Text(Satoshis(alpha_asset.as_sat())),
+            erc721_token_id: Text(DecimalU256(beta_asset.token_id.0)),
+            erc721_token_contract: Text(EthereumAddress(beta_asset.token_contract)),
+            hash_function: Text(hash_function),
+            bitcoin_refund_identity: Text(alpha_ledger_refund_identity.into_inner()),
+            ethereum_redeem_identity: Text(EthereumAddress(beta_ledger_redeem_identity)),
+            bitcoin_expiry: U32(alpha_expiry.into()),
+            ethereum_expiry: U32(beta_expiry.into()),
+            secret_hash: Text(secret_hash),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_bitcoin_ethereum_bitcoin_erc721_request_messages::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
 #[derive(Insertable, Debug, Copy, Clone)]
 #[table_name = "rfc003_ethereum_bitcoin_ether_bitcoin_request_messages"]
 struct InsertableEthereumBitcoinEtherBitcoinRequestMessage {
     swap_id: Text<SwapId>,
     ethereum_chain_id: U32,
-    bitcoin_network: Text<bitcoin::Network>,
+    bitcoin_network: Text<bitcoin::Network>,
+    ether_amount: Text<DecimalU256>,
+    bitcoin_amount: Text<Satoshis>,
+    hash_function: Text<HashFunction>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+    bitcoin_redeem_identity: Text<bitcoin::PublicKey>,
+    ethereum_expiry: U32,
+    bitcoin_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[async_trait]
+impl Save<Request<Ethereum, Bitcoin, EtherQuantity, bitcoin::Amount>> for Sqlite {
+    async fn save(
+        &self,
+        message: Request<Ethereum, Bitcoin, EtherQuantity, bitcoin::Amount>,
+    ) -> anyhow::Result<()> {
+        let Request {
+            swap_id,
+            alpha_ledger,
+            alpha_asset,
+            beta_ledger,
+            beta_asset,
+            hash_function,
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+            alpha_expiry,
+            beta_expiry,
+            secret_hash,
+        } = message;
+
+        let insertable = InsertableEthereumBitcoinEtherBitcoinRequestMessage {
+            swap_id: Text(swap_id),
+            ethereum_chain_id: U32(alpha_ledger.chain_id.into()),
+            bitcoin_network: Text(beta_ledger.network),
+            ether_amount: Text(DecimalU256(alpha_asset.wei())),
+            bitcoin_amount: Text(Satoshis(beta_asset.as_sat())),
+            hash_function: Text(hash_function),
+            ethereum_refund_identity: Text(EthereumAddress(alpha_ledger_refund_identity)),
+            bitcoin_redeem_identity: Text(beta_ledger_redeem_identity.into_inner()),
+            ethereum_expiry: U32(alpha_expiry.into()),
+            bitcoin_expiry: U32(beta_expiry.into()),
+            secret_hash: Text(secret_hash),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_ethereum_bitcoin_ether_bitcoin_request_messages::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages"]
+struct InsertableEthereumBitcoinErc20BitcoinRequestMessage {
+    swap_id: Text<SwapId>,
+    ethereum_chain_id: U32,
+    bitcoin_network: Text<bitcoin::Network>,
+    erc20_amount: Text<DecimalU256>,
+    erc20_token_contract: Text<EthereumAddress>,
+    bitcoin_amount: Text<Satoshis>,
+    hash_function: Text<HashFunction>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+    bitcoin_redeem_identity: Text<bitcoin::PublicKey>,
+    ethereum_expiry: U32,
+    bitcoin_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[async_trait]
+impl Save<Request<Ethereum, Bitcoin, Erc20Token, bitcoin::Amount>> for Sqlite {
+    async fn save(
+        &self,
+        message: Request<Ethereum, Bitcoin, Erc20Token, bitcoin::Amount>,
+    ) -> anyhow::Result<()> {
+        let Request {
+            swap_id,
+            alpha_ledger,
+            alpha_asset,
+            beta_ledger,
+            beta_asset,
+            hash_function,
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+            alpha_expiry,
+            beta_expiry,
+            secret_hash,
+        } = message;
+
+        let insertable = InsertableEthereumBitcoinErc20BitcoinRequestMessage {
+            swap_id: Text(swap_id),
+            ethereum_chain_id: U32(alpha_ledger.chain_id.into()),
+            bitcoin_network: Text(beta_ledger.network),
+            erc20_amount: Text(DecimalU256(alpha_asset.quantity.0)),
+            erc20_token_contract: Text(EthereumAddress(alpha_asset.token_contract)),
+            bitcoin_amount: Text(Satoshis(beta_asset.as_sat())),
+            hash_function: Text(hash_function),
+            ethereum_refund_identity: Text(EthereumAddress(alpha_ledger_refund_identity)),
+            bitcoin_redeem_identity: Text(beta_ledger_redeem_identity.into_inner()),
+            ethereum_expiry: U32(alpha_expiry.into()),
+            bitcoin_expiry: U32(beta_expiry.into()),
+            secret_hash: Text(secret_hash),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_ethereum_bitcoin_erc721_bitcoin_request_messages"]
+struct InsertableEthereumBitcoinErc721BitcoinRequestMessage {
+    swap_id: Text<SwapId>,
+    ethereum_chain_id: U32,
+    bitcoin_network: Text<bitcoin::Network>,
+    erc721_token_id: Text<DecimalU256>,
+    erc721_token_contract: Text<EthereumAddress>,
+    bitcoin_amount: Text<Satoshis>,
+    hash_function: Text<HashFunction>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+    bitcoin_redeem_identity: Text<bitcoin::PublicKey>,
+    ethereum_expiry: U32,
+    bitcoin_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[async_trait]
+impl Save<Request<Ethereum, Bitcoin, Erc721Token, bitcoin::Amount>> for Sqlite {
+    async fn save(
+        &self,
+        message: Request<Ethereum, Bitcoin, Erc721Token, bitcoin::Amount>,
+    ) -> anyhow::Result<()> {
+        let Request {
+            swap_id,
+            alpha_ledger,
+            alpha_asset,
+            beta_ledger,
+            beta_asset,
+            hash_function,
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+            alpha_expiry,
+            beta_expiry,
+            secret_hash,
+        } = message;
+
+        let insertable = InsertableEthereumBitcoinErc721BitcoinRequestMessage {
+            swap_id: Text(swap_id),
+            ethereum_chain_id: U32(alpha_ledger.chain_id.into()),
+            bitcoin_network: Text(beta_ledger.network),
+            erc721_token_id: Text(DecimalU256(alpha_asset.token_id.0)),
+            erc721_token_contract: Text(EthereumAddress(alpha_asset.token_contract)),
+            bitcoin_amount: Text(Satoshis(beta_asset.as_sat())),
+            hash_function: Text(hash_function),
+            ethereum_refund_identity: Text(EthereumAddress(alpha_ledger_refund_identity)),
+            bitcoin_redeem_identity: Text(beta_ledger_redeem_identity.into_inner()),
+            ethereum_expiry: U32(alpha_expiry.into()),
+            bitcoin_expiry: U32(beta_expiry.into()),
+            secret_hash: Text(secret_hash),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_ethereum_bitcoin_erc721_bitcoin_request_messages::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_ethereum_ethereum_ether_ether_request_messages"]
+struct InsertableEthereumEthereumEtherEtherRequestMessage {
+    swap_id: Text<SwapId>,
+    alpha_ethereum_chain_id: U32,
+    beta_ethereum_chain_id: U32,
+    alpha_ether_amount: Text<DecimalU256>,
+    beta_ether_amount: Text<DecimalU256>,
+    hash_function: Text<HashFunction>,
+    alpha_ethereum_refund_identity: Text<EthereumAddress>,
+    beta_ethereum_redeem_identity: Text<EthereumAddress>,
+    alpha_expiry: U32,
+    beta_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[async_trait]
+impl Save<Request<Ethereum, Ethereum, EtherQuantity, EtherQuantity>> for Sqlite {
+    async fn save(
+        &self,
+        message: Request<Ethereum, Ethereum, EtherQuantity, EtherQuantity>,
+    ) -> anyhow::Result<()> {
+        let Request {
+            swap_id,
+            alpha_ledger,
+            alpha_asset,
+            beta_ledger,
+            beta_asset,
+            hash_function,
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+            alpha_expiry,
+            beta_expiry,
+            secret_hash,
+        } = message;
+
+        let insertable = InsertableEthereumEthereumEtherEtherRequestMessage {
+            swap_id: Text(swap_id),
+            alpha_ethereum_chain_id: U32(alpha_ledger.chain_id.into()),
+            beta_ethereum_chain_id: U32(beta_ledger.chain_id.into()),
+            alpha_ether_amount: Text(DecimalU256(alpha_asset.wei())),
+            beta_ether_amount: Text(DecimalU256(beta_asset.wei())),
+            hash_function: Text(hash_function),
+            alpha_ethereum_refund_identity: Text(EthereumAddress(alpha_ledger_refund_identity)),
+            beta_ethereum_redeem_identity: Text(EthereumAddress(beta_ledger_redeem_identity)),
+            alpha_expiry: U32(alpha_expiry.into()),
+            beta_expiry: U32(beta_expiry.into()),
+            secret_hash: Text(secret_hash),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_ethereum_ethereum_ether_ether_request_messages::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_ethereum_ethereum_ether_erc20_request_messages"]
+struct InsertableEthereumEthereumEtherErc20RequestMessage {
+    swap_id: Text<SwapId>,
+    alpha_ethereum_chain_id: U32,
+    beta_ethereum_chain_id: U32,
+    alpha_ether_amount: Text<DecimalU256>,
+    beta_erc20_amount: Text<DecimalU256>,
+    beta_erc20_token_contract: Text<EthereumAddress>,
+    hash_function: Text<HashFunction>,
+    alpha_ethereum_refund_identity: Text<EthereumAddress>,
+    beta_ethereum_redeem_identity: Text<EthereumAddress>,
+    alpha_expiry: U32,
+    beta_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[async_trait]
+impl Save<Request<Ethereum, Ethereum, EtherQuantity, Erc20Token>> for Sqlite {
+    async fn save(
+        &self,
+        message: Request<Ethereum, Ethereum, EtherQuantity, Erc20Token>,
+    ) -> anyhow::Result<()> {
+        let Request {
+            swap_id,
+            alpha_ledger,
+            alpha_asset,
+            beta_ledger,
+            beta_asset,
+            hash_function,
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+            alpha_expiry,
+            beta_expiry,
+            secret_hash,
+        } = message;
+
+        let insertable = InsertableEthereumEthereumEtherErc20RequestMessage {
+            swap_id: Text(swap_id),
+            alpha_ethereum_chain_id: U32(alpha_ledger.chain_id.into()),
+            beta_ethereum_chain_id: U32(beta_ledger.chain_id.into()),
+            alpha_ether_amount: Text(DecimalU256(alpha_asset.wei())),
+            beta_erc20_amount: Text(DecimalU256(beta_asset.quantity.0)),
+            beta_erc20_token_contract: Text(EthereumAddress(beta_asset.token_contract)),
+            hash_function: Text(hash_function),
+            alpha_ethereum_refund_identity: Text(EthereumAddress(alpha_ledger_refund_identity)),
+            beta_ethereum_redeem_identity: Text(EthereumAddress(beta_ledger_redeem_identity)),
+            alpha_expiry: U32(alpha_expiry.into()),
+            beta_expiry: U32(beta_expiry.into()),
+            secret_hash: Text(secret_hash),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_ethereum_ethereum_ether_erc20_request_messages::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_ethereum_ethereum_erc20_ether_request_messages"]
+struct InsertableEthereumEthereumErc20EtherRequestMessage {
+    swap_id: Text<SwapId>,
+    alpha_ethereum_chain_id: U32,
+    beta_ethereum_chain_id: U32,
+    alpha_erc20_amount: Text<DecimalU256>,
+    alpha_erc20_token_contract: Text<EthereumAddress>,
+    beta_ether_amount: Text<DecimalU256>,
+    hash_function: Text<HashFunction>,
+    alpha_ethereum_refund_identity: Text<EthereumAddress>,
+    beta_ethereum_redeem_identity: Text<EthereumAddress>,
+    alpha_expiry: U32,
+    beta_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[async_trait]
+impl Save<Request<Ethereum, Ethereum, Erc20Token, EtherQuantity>> for Sqlite {
+    async fn save(
+        &self,
+        message: Request<Ethereum, Ethereum, Erc20Token, EtherQuantity>,
+    ) -> anyhow::Result<()> {
+        let Request {
+            swap_id,
+            alpha_ledger,
+            alpha_asset,
+            beta_ledger,
+            beta_asset,
+            hash_function,
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+            alpha_expiry,
+            beta_expiry,
+            secret_hash,
+        } = message;
+
+        let insertable = InsertableEthereumEthereumErc20EtherRequestMessage {
+            swap_id: Text(swap_id),
+            alpha_ethereum_chain_id: U32(alpha_ledger.chain_id.into()),
+            beta_ethereum_chain_id: U32(beta_ledger.chain_id.into()),
+            alpha_erc20_amount: Text(DecimalU256(alpha_asset.quantity.0)),
+            alpha_erc20_token_contract: Text(EthereumAddress(alpha_asset.token_contract)),
+            beta_ether_amount: Text(DecimalU256(beta_asset.wei())),
+            hash_function: Text(hash_function),
+            alpha_ethereum_refund_identity: Text(EthereumAddress(alpha_ledger_refund_identity)),
+            beta_ethereum_redeem_identity: Text(EthereumAddress(beta_ledger_redeem_identity)),
+            alpha_expiry: U32(alpha_expiry.into()),
+            beta_expiry: U32(beta_expiry.into()),
+            secret_hash: Text(secret_hash),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_ethereum_ethereum_erc20_ether_request_messages::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_ethereum_ethereum_erc20_erc20_request_messages"]
+struct InsertableEthereumEthereumErc20Erc20RequestMessage {
+    swap_id: Text<SwapId>,
+    alpha_ethereum_chain_id: U32,
+    beta_ethereum_chain_id: U32,
+    alpha_erc20_amount: Text<DecimalU256>,
+    alpha_erc20_token_contract: Text<EthereumAddress>,
+    beta_erc20_amount: Text<DecimalU256>,
+    beta_erc20_token_contract: Text<EthereumAddress>,
+    hash_function: Text<HashFunction>,
+    alpha_ethereum_refund_identity: Text<EthereumAddress>,
+    beta_ethereum_redeem_identity: Text<EthereumAddress>,
+    alpha_expiry: U32,
+    beta_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[async_trait]
+impl Save<Request<Ethereum, Ethereum, Erc20Token, Erc20Token>> for Sqlite {
+    async fn save(
+        &self,
+        message: Request<Ethereum, Ethereum, Erc20Token, Erc20Token>,
+    ) -> anyhow::Result<()> {
+        let Request {
+            swap_id,
+            alpha_ledger,
+            alpha_asset,
+            beta_ledger,
+            beta_asset,
+            hash_function,
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+            alpha_expiry,
+            beta_expiry,
+            secret_hash,
+        } = message;
+
+        let insertable = InsertableEthereumEthereumErc20Erc20RequestMessage {
+            swap_id: Text(swap_id),
+            alpha_ethereum_chain_id: U32(alpha_ledger.chain_id.into()),
+            beta_ethereum_chain_id: U32(beta_ledger.chain_id.into()),
+            alpha_erc20_amount: Text(DecimalU256(alpha_asset.quantity.0)),
+            alpha_erc20_token_contract: Text(EthereumAddress(alpha_asset.token_contract)),
+            beta_erc20_amount: Text(DecimalU256(beta_asset.quantity.0)),
+            beta_erc20_token_contract: Text(EthereumAddress(beta_asset.token_contract)),
+            hash_function: Text(hash_function),
+            alpha_ethereum_refund_identity: Text(EthereumAddress(alpha_ledger_refund_identity)),
+            beta_ethereum_redeem_identity: Text(EthereumAddress(beta_ledger_redeem_identity)),
+            alpha_expiry: U32(alpha_expiry.into()),
+            beta_expiry: U32(beta_expiry.into()),
+            secret_hash: Text(secret_hash),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_ethereum_ethereum_erc20_erc20_request_messages::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[table_name = "rfc003_litecoin_ethereum_litecoin_ether_request_messages"]
+struct InsertableLitecoinEthereumLitecoinEtherRequestMessage {
+    swap_id: Text<SwapId>,
+    litecoin_network: Text<bitcoin::Network>,
+    ethereum_chain_id: U32,
+    litecoin_amount: Text<Satoshis>,
     ether_amount: Text<DecimalU256>,
-    bitcoin_amount: Text<Satoshis>,
+    hash_function: Text<HashFunction>,
+    litecoin_refund_identity: Text<bitcoin::PublicKey>,
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    litecoin_expiry: U32,
+    ethereum_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[async_trait]
+impl Save<Request<Litecoin, Ethereum, litecoin::Amount, EtherQuantity>> for Sqlite {
+    async fn save(
+        &self,
+        message: Request<Litecoin, Ethereum, litecoin::Amount, EtherQuantity>,
+    ) -> anyhow::Result<()> {
+        let Request {
+            swap_id,
+            alpha_ledger,
+            alpha_asset,
+            beta_ledger,
+            beta_asset,
+            hash_function,
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+            alpha_expiry,
+            beta_expiry,
+            secret_hash,
+        } = message;
+
+        let insertable = InsertableLitecoinEthereumLitecoinEtherRequestMessage {
+            swap_id: Text(swap_id),
+            litecoin_network: Text(alpha_ledger.network),
+            ethereum_chain_id: U32(beta_ledger.chain_id.into()),
+            litecoin_amount: Text(Satoshis(alpha_asset.as_litoshi())),
+            ether_amount: Text(DecimalU256(beta_asset.wei())),
+            hash_function: Text(hash_function),
+            litecoin_refund_identity: Text(alpha_ledger_refund_identity.into_inner()),
+            ethereum_redeem_identity: Text(EthereumAddress(beta_ledger_redeem_identity)),
+            litecoin_expiry: U32(alpha_expiry.into()),
+            ethereum_expiry: U32(beta_expiry.into()),
+            secret_hash: Text(secret_hash),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_litecoin_ethereum_litecoin_ether_request_messages::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_litecoin_ethereum_litecoin_erc20_request_messages"]
+struct InsertableLitecoinEthereumLitecoinErc20RequestMessage {
+    swap_id: Text<SwapId>,
+    litecoin_network: Text<bitcoin::Network>,
+    ethereum_chain_id: U32,
+    litecoin_amount: Text<Satoshis>,
+    erc20_amount: Text<DecimalU256>,
+    erc20_token_contract: Text<EthereumAddress>,
+    hash_function: Text<HashFunction>,
+    litecoin_refund_identity: Text<bitcoin::PublicKey>,
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    litecoin_expiry: U32,
+    ethereum_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[async_trait]
+impl Save<Request<Litecoin, Ethereum, litecoin::Amount, Erc20Token>> for Sqlite {
+    async fn save(
+        &self,
+        message: Request<Litecoin, Ethereum, litecoin::Amount, Erc20Token>,
+    ) -> anyhow::Result<()> {
+        let Request {
+            swap_id,
+            alpha_ledger,
+            alpha_asset,
+            beta_ledger,
+            beta_asset,
+            hash_function,
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+            alpha_expiry,
+            beta_expiry,
+            secret_hash,
+        } = message;
+
+        let insertable = InsertableLitecoinEthereumLitecoinErc20RequestMessage {
+            swap_id: Text(swap_id),
+            litecoin_network: Text(alpha_ledger.network),
+            ethereum_chain_id: U32(beta_ledger.chain_id.into()),
+            litecoin_amount: Text(Satoshis(alpha_asset.as_litoshi())),
+            erc20_amount: Text(DecimalU256(beta_asset.quantity.0)),
+            erc20_token_contract: Text(EthereumAddress(beta_asset.token_contract)),
+            hash_function: Text(hash_function),
+            litecoin_refund_identity: Text(alpha_ledger_refund_identity.into_inner()),
+            ethereum_redeem_identity: Text(EthereumAddress(beta_ledger_redeem_identity)),
+            litecoin_expiry: U32(alpha_expiry.into()),
+            ethereum_expiry: U32(beta_expiry.into()),
+            secret_hash: Text(secret_hash),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_litecoin_ethereum_litecoin_erc20_request_messages::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_ethereum_litecoin_ether_litecoin_request_messages"]
+struct InsertableEthereumLitecoinEtherLitecoinRequestMessage {
+    swap_id: Text<SwapId>,
+    ethereum_chain_id: U32,
+    litecoin_network: Text<bitcoin::Network>,
+    ether_amount: Text<DecimalU256>,
+    litecoin_amount: Text<Satoshis>,
     hash_function: Text<HashFunction>,
     ethereum_refund_identity: Text<EthereumAddress>,
-    bitcoin_redeem_identity: Text<bitcoin::PublicKey>,
+    litecoin_redeem_identity: Text<bitcoin::PublicKey>,
     ethereum_expiry: U32,
-    bitcoin_expiry: U32,
+    litecoin_expiry: U32,
     secret_hash: Text<SecretHash>,
 }
 
 #[async_trait]
-impl Save<Request<Ethereum, Bitcoin, EtherQuantity, bitcoin::Amount>> for Sqlite {
+impl Save<Request<Ethereum, Litecoin, EtherQuantity, litecoin::Amount>> for Sqlite {
     async fn save(
         &self,
-        message: Request<Ethereum, Bitcoin, EtherQuantity, bitcoin::Amount>,
+        message: Request<Ethereum, Litecoin, EtherQuantity, litecoin::Amount>,
     ) -> anyhow::Result<()> {
         let Request {
             swap_id,
@@ -231,22 +870,22 @@ impl Save<Request<Ethereum, Bitcoin, EtherQuantity, bitcoin::Amount>> for Sqlite
             secret_hash,
         } = message;
 
-        let insertable = InsertableEthereumBitcoinEtherBitcoinRequestMessage {
+        let insertable = InsertableEthereumLitecoinEtherLitecoinRequestMessage {
             swap_id: Text(swap_id),
             ethereum_chain_id: U32(alpha_ledger.chain_id.into()),
-            bitcoin_network: Text(beta_ledger.network),
+            litecoin_network: Text(beta_ledger.network),
             ether_amount: Text(DecimalU256(alpha_asset.wei())),
-            bitcoin_amount: Text(Satoshis(beta_asset.as_sat())),
+            litecoin_amount: Text(Satoshis(beta_asset.as_litoshi())),
             hash_function: Text(hash_function),
             ethereum_refund_identity: Text(EthereumAddress(alpha_ledger_refund_identity)),
-            bitcoin_redeem_identity: Text(beta_ledger_redeem_identity.into_inner()),
+            litecoin_redeem_identity: Text(beta_ledger_redeem_identity.into_inner()),
             ethereum_expiry: U32(alpha_expiry.into()),
-            bitcoin_expiry: U32(beta_expiry.into()),
+            litecoin_expiry: U32(beta_expiry.into()),
             secret_hash: Text(secret_hash),
         };
 
         self.do_in_transaction(|connection| {
-            diesel::insert_into(rfc003_ethereum_bitcoin_ether_bitcoin_request_messages::table)
+            diesel::insert_into(rfc003_ethereum_litecoin_ether_litecoin_request_messages::table)
                 .values(&insertable)
                 .execute(connection)
         })
@@ -255,28 +894,29 @@ impl Save<Request<Ethereum, Bitcoin, EtherQuantity, bitcoin::Amount>> for Sqlite
         Ok(())
     }
 }
+
 #[derive(Insertable, Debug, Copy, Clone)]
-#[table_name = "rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages"]
-struct InsertableEthereumBitcoinErc20BitcoinRequestMessage {
+#[table_name = "rfc003_ethereum_litecoin_erc20_litecoin_request_messages"]
+struct InsertableEthereumLitecoinErc20LitecoinRequestMessage {
     swap_id: Text<SwapId>,
     ethereum_chain_id: U32,
-    bitcoin_network: Text<bitcoin::Network>,
+    litecoin_network: Text<bitcoin::Network>,
     erc20_amount: Text<DecimalU256>,
     erc20_token_contract: Text<EthereumAddress>,
-    bitcoin_amount: Text<Satoshis>,
+    litecoin_amount: Text<Satoshis>,
     hash_function: Text<HashFunction>,
     ethereum_refund_identity: Text<EthereumAddress>,
-    bitcoin_redeem_identity: Text<bitcoin::PublicKey>,
+    litecoin_redeem_identity: Text<bitcoin::PublicKey>,
     ethereum_expiry: U32,
-    bitcoin_expiry: U32,
+    litecoin_expiry: U32,
     secret_hash: Text<SecretHash>,
 }
 
 #[async_trait]
-impl Save<Request<Ethereum, Bitcoin, Erc20Token, bitcoin::Amount>> for Sqlite {
+impl Save<Request<Ethereum, Litecoin, Erc20Token, litecoin::Amount>> for Sqlite {
     async fn save(
         &self,
-        message: Request<Ethereum, Bitcoin, Erc20Token, bitcoin::Amount>,
+        message: Request<Ethereum, Litecoin, Erc20Token, litecoin::Amount>,
     ) -> anyhow::Result<()> {
         let Request {
             swap_id,
@@ -292,23 +932,23 @@ impl Save<Request<Ethereum, Bitcoin, Erc20Token, bitcoin::Amount>> for Sqlite {
             secret_hash,
         } = message;
 
-        let insertable = InsertableEthereumBitcoinErc20BitcoinRequestMessage {
+        let insertable = InsertableEthereumLitecoinErc20LitecoinRequestMessage {
             swap_id: Text(swap_id),
             ethereum_chain_id: U32(alpha_ledger.chain_id.into()),
-            bitcoin_network: Text(beta_ledger.network),
+            litecoin_network: Text(beta_ledger.network),
             erc20_amount: Text(DecimalU256(alpha_asset.quantity.0)),
             erc20_token_contract: Text(EthereumAddress(alpha_asset.token_contract)),
-            bitcoin_amount: Text(Satoshis(beta_asset.as_sat())),
+            litecoin_amount: Text(Satoshis(beta_asset.as_litoshi())),
             hash_function: Text(hash_function),
             ethereum_refund_identity: Text(EthereumAddress(alpha_ledger_refund_identity)),
-            bitcoin_redeem_identity: Text(beta_ledger_redeem_identity.into_inner()),
+            litecoin_redeem_identity: Text(beta_ledger_redeem_identity.into_inner()),
             ethereum_expiry: U32(alpha_expiry.into()),
-            bitcoin_expiry: U32(beta_expiry.into()),
+            litecoin_expiry: U32(beta_expiry.into()),
             secret_hash: Text(secret_hash),
         };
 
         self.do_in_transaction(|connection| {
-            diesel::insert_into(rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages::table)
+            diesel::insert_into(rfc003_ethereum_litecoin_erc20_litecoin_request_messages::table)
                 .values(&insertable)
                 .execute(connection)
         })
@@ -384,6 +1024,108 @@ impl Save<Accept<Bitcoin, Ethereum>> for Sqlite {
         Ok(())
     }
 }
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_ethereum_ethereum_accept_messages"]
+struct InsertableEthereumEthereumAcceptMessage {
+    swap_id: Text<SwapId>,
+    alpha_ethereum_redeem_identity: Text<EthereumAddress>,
+    beta_ethereum_refund_identity: Text<EthereumAddress>,
+}
+
+#[async_trait]
+impl Save<Accept<Ethereum, Ethereum>> for Sqlite {
+    async fn save(&self, message: Accept<Ethereum, Ethereum>) -> anyhow::Result<()> {
+        let Accept {
+            swap_id,
+            alpha_ledger_redeem_identity,
+            beta_ledger_refund_identity,
+        } = message;
+
+        let insertable = InsertableEthereumEthereumAcceptMessage {
+            swap_id: Text(swap_id),
+            alpha_ethereum_redeem_identity: Text(EthereumAddress(alpha_ledger_redeem_identity)),
+            beta_ethereum_refund_identity: Text(EthereumAddress(beta_ledger_refund_identity)),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_ethereum_ethereum_accept_messages::table)
+                .values(&insertable)
+                .execute(&*connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_ethereum_litecoin_accept_messages"]
+struct InsertableEthereumLitecoinAcceptMessage {
+    swap_id: Text<SwapId>,
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    litecoin_refund_identity: Text<bitcoin::PublicKey>,
+}
+
+#[async_trait]
+impl Save<Accept<Ethereum, Litecoin>> for Sqlite {
+    async fn save(&self, message: Accept<Ethereum, Litecoin>) -> anyhow::Result<()> {
+        let Accept {
+            swap_id,
+            alpha_ledger_redeem_identity,
+            beta_ledger_refund_identity,
+        } = message;
+
+        let insertable = InsertableEthereumLitecoinAcceptMessage {
+            swap_id: Text(swap_id),
+            ethereum_redeem_identity: Text(EthereumAddress(alpha_ledger_redeem_identity)),
+            litecoin_refund_identity: Text(beta_ledger_refund_identity.into_inner()),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_ethereum_litecoin_accept_messages::table)
+                .values(&insertable)
+                .execute(&*connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Copy, Clone)]
+#[table_name = "rfc003_litecoin_ethereum_accept_messages"]
+struct InsertableLitecoinEthereumAcceptMessage {
+    swap_id: Text<SwapId>,
+    litecoin_redeem_identity: Text<bitcoin::PublicKey>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+}
+
+#[async_trait]
+impl Save<Accept<Litecoin, Ethereum>> for Sqlite {
+    async fn save(&self, message: Accept<Litecoin, Ethereum>) -> anyhow::Result<()> {
+        let Accept {
+            swap_id,
+            alpha_ledger_redeem_identity,
+            beta_ledger_refund_identity,
+        } = message;
+
+        let insertable = InsertableLitecoinEthereumAcceptMessage {
+            swap_id: Text(swap_id),
+            litecoin_redeem_identity: Text(alpha_ledger_redeem_identity.into_inner()),
+            ethereum_refund_identity: Text(EthereumAddress(beta_ledger_refund_identity)),
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_litecoin_ethereum_accept_messages::table)
+                .values(&insertable)
+                .execute(&*connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
 #[derive(Insertable, Debug, Clone)]
 #[table_name = "rfc003_decline_messages"]
 struct InsertableDeclineMessage {
@@ -0,0 +1,145 @@
+use crate::{
+    btsieve::{Checkpoint, ScanProgress},
+    db::{
+        custom_sql_types::{Json, Text},
+        schema::{self, rfc003_scan_progress},
+        Sqlite,
+    },
+    diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl},
+    swap_protocols::SwapId,
+};
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{convert::TryFrom, fmt::Debug, marker::PhantomData};
+
+/// Stores how far the blockchain was scanned for one transaction pattern of a
+/// swap.
+///
+/// Patterns are identified by their JSON representation, i.e. a swap that
+/// watches for the same pattern again after a restart picks up the checkpoint
+/// of the previous scan.
+#[derive(Debug, Clone)]
+pub struct SwapScanProgress<H> {
+    db: Sqlite,
+    swap_id: SwapId,
+    pattern: String,
+    block_hash: PhantomData<H>,
+}
+
+impl<H> SwapScanProgress<H> {
+    pub fn new<P: Serialize>(db: Sqlite, swap_id: SwapId, pattern: &P) -> Self {
+        let pattern =
+            serde_json::to_string(pattern).expect("transaction patterns can be serialized");
+
+        Self {
+            db,
+            swap_id,
+            pattern,
+            block_hash: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<H> ScanProgress<H> for SwapScanProgress<H>
+where
+    H: Serialize + DeserializeOwned + Debug + Send + Sync + 'static,
+{
+    async fn load_checkpoint(&self) -> Option<Checkpoint<H>> {
+        match self.db.load_checkpoint(&self.swap_id, &self.pattern).await {
+            Ok(checkpoint) => checkpoint,
+            Err(e) => {
+                log::warn!(
+                    "Could not load scan progress of swap {}, scanning from scratch: {:?}",
+                    self.swap_id,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    async fn save_checkpoint(&self, checkpoint: Checkpoint<H>) {
+        if let Err(e) = self
+            .db
+            .save_checkpoint(self.swap_id, &self.pattern, checkpoint)
+            .await
+        {
+            log::warn!(
+                "Could not save scan progress of swap {}: {:?}",
+                self.swap_id,
+                e
+            );
+        }
+    }
+}
+
+impl Sqlite {
+    async fn load_checkpoint<H>(
+        &self,
+        key: &SwapId,
+        pattern: &str,
+    ) -> anyhow::Result<Option<Checkpoint<H>>>
+    where
+        H: DeserializeOwned,
+    {
+        use schema::rfc003_scan_progress as scan_progress;
+
+        let record: Option<(Json<H>, i64)> = self
+            .do_in_transaction(|connection| {
+                let key = Text(key);
+
+                scan_progress::table
+                    .filter(scan_progress::swap_id.eq(key))
+                    .filter(scan_progress::pattern.eq(pattern))
+                    .select((scan_progress::block_hash, scan_progress::block_height))
+                    .first(connection)
+                    .optional()
+            })
+            .await?;
+
+        record
+            .map(|(Json(block_hash), block_height)| {
+                Ok(Checkpoint {
+                    block_hash,
+                    block_height: u64::try_from(block_height)?,
+                })
+            })
+            .transpose()
+    }
+
+    async fn save_checkpoint<H>(
+        &self,
+        swap_id: SwapId,
+        pattern: &str,
+        checkpoint: Checkpoint<H>,
+    ) -> anyhow::Result<()>
+    where
+        H: Serialize + Debug,
+    {
+        let insertable = InsertableScanProgress {
+            swap_id: Text(swap_id),
+            pattern: pattern.to_owned(),
+            block_hash: Json(checkpoint.block_hash),
+            block_height: i64::try_from(checkpoint.block_height)?,
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::replace_into(rfc003_scan_progress::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[table_name = "rfc003_scan_progress"]
+struct InsertableScanProgress<H> {
+    swap_id: Text<SwapId>,
+    pattern: String,
+    block_hash: Json<H>,
+    block_height: i64,
+}
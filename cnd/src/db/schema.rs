@@ -68,6 +68,184 @@ table! {
    }
 }
 
+table! {
+   rfc003_bitcoin_ethereum_bitcoin_erc721_request_messages {
+       id -> Integer,
+       swap_id -> Text,
+       bitcoin_network -> Text,
+       ethereum_chain_id -> BigInt,
+       bitcoin_amount -> Text,
+       erc721_token_id -> Text,
+       erc721_token_contract -> Text,
+       hash_function -> Text,
+       bitcoin_refund_identity -> Text,
+       ethereum_redeem_identity -> Text,
+       bitcoin_expiry -> BigInt,
+       ethereum_expiry -> BigInt,
+       secret_hash -> Text,
+   }
+}
+
+table! {
+   rfc003_ethereum_bitcoin_erc721_bitcoin_request_messages {
+       id -> Integer,
+       swap_id -> Text,
+       ethereum_chain_id -> BigInt,
+       bitcoin_network -> Text,
+       erc721_token_id -> Text,
+       erc721_token_contract -> Text,
+       bitcoin_amount -> Text,
+       hash_function -> Text,
+       ethereum_refund_identity -> Text,
+       bitcoin_redeem_identity -> Text,
+       ethereum_expiry -> BigInt,
+       bitcoin_expiry -> BigInt,
+       secret_hash -> Text,
+   }
+}
+
+table! {
+   rfc003_ethereum_ethereum_ether_ether_request_messages {
+       id -> Integer,
+       swap_id -> Text,
+       alpha_ethereum_chain_id -> BigInt,
+       beta_ethereum_chain_id -> BigInt,
+       alpha_ether_amount -> Text,
+       beta_ether_amount -> Text,
+       hash_function -> Text,
+       alpha_ethereum_refund_identity -> Text,
+       beta_ethereum_redeem_identity -> Text,
+       alpha_expiry -> BigInt,
+       beta_expiry -> BigInt,
+       secret_hash -> Text,
+   }
+}
+
+table! {
+   rfc003_ethereum_ethereum_ether_erc20_request_messages {
+       id -> Integer,
+       swap_id -> Text,
+       alpha_ethereum_chain_id -> BigInt,
+       beta_ethereum_chain_id -> BigInt,
+       alpha_ether_amount -> Text,
+       beta_erc20_amount -> Text,
+       beta_erc20_token_contract -> Text,
+       hash_function -> Text,
+       alpha_ethereum_refund_identity -> Text,
+       beta_ethereum_redeem_identity -> Text,
+       alpha_expiry -> BigInt,
+       beta_expiry -> BigInt,
+       secret_hash -> Text,
+   }
+}
+
+table! {
+   rfc003_ethereum_ethereum_erc20_ether_request_messages {
+       id -> Integer,
+       swap_id -> Text,
+       alpha_ethereum_chain_id -> BigInt,
+       beta_ethereum_chain_id -> BigInt,
+       alpha_erc20_amount -> Text,
+       alpha_erc20_token_contract -> Text,
+       beta_ether_amount -> Text,
+       hash_function -> Text,
+       alpha_ethereum_refund_identity -> Text,
+       beta_ethereum_redeem_identity -> Text,
+       alpha_expiry -> BigInt,
+       beta_expiry -> BigInt,
+       secret_hash -> Text,
+   }
+}
+
+table! {
+   rfc003_ethereum_ethereum_erc20_erc20_request_messages {
+       id -> Integer,
+       swap_id -> Text,
+       alpha_ethereum_chain_id -> BigInt,
+       beta_ethereum_chain_id -> BigInt,
+       alpha_erc20_amount -> Text,
+       alpha_erc20_token_contract -> Text,
+       beta_erc20_amount -> Text,
+       beta_erc20_token_contract -> Text,
+       hash_function -> Text,
+       alpha_ethereum_refund_identity -> Text,
+       beta_ethereum_redeem_identity -> Text,
+       alpha_expiry -> BigInt,
+       beta_expiry -> BigInt,
+       secret_hash -> Text,
+   }
+}
+
+table! {
+   rfc003_litecoin_ethereum_litecoin_ether_request_messages {
+       id -> Integer,
+       swap_id -> Text,
+       litecoin_network -> Text,
+       ethereum_chain_id -> BigInt,
+       litecoin_amount -> Text,
+       ether_amount -> Text,
+       hash_function -> Text,
+       litecoin_refund_identity -> Text,
+       ethereum_redeem_identity -> Text,
+       litecoin_expiry -> BigInt,
+       ethereum_expiry -> BigInt,
+       secret_hash -> Text,
+   }
+}
+
+table! {
+   rfc003_ethereum_litecoin_ether_litecoin_request_messages {
+       id -> Integer,
+       swap_id -> Text,
+       ethereum_chain_id -> BigInt,
+       litecoin_network -> Text,
+       ether_amount -> Text,
+       litecoin_amount -> Text,
+       hash_function -> Text,
+       ethereum_refund_identity -> Text,
+       litecoin_redeem_identity -> Text,
+       ethereum_expiry -> BigInt,
+       litecoin_expiry -> BigInt,
+       secret_hash -> Text,
+   }
+}
+
+table! {
+   rfc003_litecoin_ethereum_litecoin_erc20_request_messages {
+       id -> Integer,
+       swap_id -> Text,
+       litecoin_network -> Text,
+       ethereum_chain_id -> BigInt,
+       litecoin_amount -> Text,
+       erc20_amount -> Text,
+       erc20_token_contract -> Text,
+       hash_function -> Text,
+       litecoin_refund_identity -> Text,
+       ethereum_redeem_identity -> Text,
+       litecoin_expiry -> BigInt,
+       ethereum_expiry -> BigInt,
+       secret_hash -> Text,
+   }
+}
+
+table! {
+   rfc003_ethereum_litecoin_erc20_litecoin_request_messages {
+       id -> Integer,
+       swap_id -> Text,
+       ethereum_chain_id -> BigInt,
+       litecoin_network -> Text,
+       erc20_amount -> Text,
+       erc20_token_contract -> Text,
+       litecoin_amount -> Text,
+       hash_function -> Text,
+       ethereum_refund_identity -> Text,
+       litecoin_redeem_identity -> Text,
+       ethereum_expiry -> BigInt,
+       litecoin_expiry -> BigInt,
+       secret_hash -> Text,
+   }
+}
+
 table! {
    rfc003_ethereum_bitcoin_accept_messages {
        id -> Integer,
@@ -88,6 +266,36 @@ table! {
    }
 }
 
+table! {
+   rfc003_ethereum_ethereum_accept_messages {
+       id -> Integer,
+       swap_id -> Text,
+       alpha_ethereum_redeem_identity -> Text,
+       beta_ethereum_refund_identity -> Text,
+       at -> Timestamp,
+   }
+}
+
+table! {
+   rfc003_litecoin_ethereum_accept_messages {
+       id -> Integer,
+       swap_id -> Text,
+       litecoin_redeem_identity -> Text,
+       ethereum_refund_identity -> Text,
+       at -> Timestamp,
+   }
+}
+
+table! {
+   rfc003_ethereum_litecoin_accept_messages {
+       id -> Integer,
+       swap_id -> Text,
+       ethereum_redeem_identity -> Text,
+       litecoin_refund_identity -> Text,
+       at -> Timestamp,
+   }
+}
+
 table! {
    rfc003_decline_messages {
        id -> Integer,
@@ -104,3 +312,56 @@ table! {
        counterparty -> Text,
    }
 }
+
+table! {
+   rfc003_ledger_states {
+       id -> Integer,
+       swap_id -> Text,
+       ledger -> Text,
+       htlc_state -> Text,
+       htlc_location -> Nullable<Text>,
+       deploy_transaction -> Nullable<Text>,
+       fund_transaction -> Nullable<Text>,
+       redeem_transaction -> Nullable<Text>,
+       refund_transaction -> Nullable<Text>,
+       secret -> Nullable<Text>,
+   }
+}
+
+table! {
+   rfc003_scan_progress {
+       id -> Integer,
+       swap_id -> Text,
+       pattern -> Text,
+       block_hash -> Text,
+       block_height -> BigInt,
+   }
+}
+
+table! {
+   bitcoin_wallet {
+       id -> Integer,
+       state -> Text,
+   }
+}
+
+table! {
+   rfc003_sent_transactions {
+       id -> Integer,
+       swap_id -> Text,
+       action -> Text,
+       transaction_id -> Text,
+       raw_transaction -> Nullable<Text>,
+   }
+}
+
+table! {
+   rfc003_action_attempts {
+       id -> Integer,
+       swap_id -> Text,
+       action -> Text,
+       attempted_at -> BigInt,
+       transaction_id -> Nullable<Text>,
+       error -> Nullable<Text>,
+   }
+}
@@ -0,0 +1,206 @@
+use crate::{
+    db::{
+        custom_sql_types::{Text, U32},
+        schema::{self, rfc003_action_attempts, rfc003_sent_transactions},
+        Save, Sqlite,
+    },
+    diesel::{ExpressionMethods, QueryDsl, RunQueryDsl},
+    swap_protocols::{rfc003::actions::ActionKind, SwapId},
+    timestamp::Timestamp,
+};
+use async_trait::async_trait;
+
+/// A transaction cnd broadcast itself while executing an action of a swap.
+///
+/// Executing the same action again, e.g. with a higher fee, replaces the
+/// previous record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SentTransaction {
+    pub swap_id: SwapId,
+    pub action: ActionKind,
+    pub transaction_id: String,
+    /// The hex encoded signed transaction, needed to broadcast it again and
+    /// to bump the fee of Bitcoin transactions later on. Ethereum
+    /// transactions are replaced by the account itself.
+    pub raw_transaction: Option<String>,
+}
+
+/// Load the transactions cnd broadcast for a swap, at most one per action.
+#[async_trait]
+pub trait LoadSentTransactions: Send + Sync + 'static {
+    async fn load_sent_transactions(
+        &self,
+        swap_id: &SwapId,
+    ) -> anyhow::Result<Vec<SentTransaction>>;
+}
+
+/// An attempt of cnd to execute an action of a swap on its own, e.g. to
+/// refund an expired HTLC.
+///
+/// Unlike sent transactions every attempt is kept, failed ones included.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActionAttempt {
+    pub swap_id: SwapId,
+    pub action: ActionKind,
+    pub attempted_at: Timestamp,
+    /// The id of the sent transaction or why none could be sent.
+    pub result: Result<String, String>,
+}
+
+/// Load the attempts to execute actions of a swap, oldest first.
+#[async_trait]
+pub trait LoadActionAttempts: Send + Sync + 'static {
+    async fn load_action_attempts(&self, swap_id: &SwapId) -> anyhow::Result<Vec<ActionAttempt>>;
+}
+
+#[async_trait]
+impl Save<SentTransaction> for Sqlite {
+    async fn save(&self, sent_transaction: SentTransaction) -> anyhow::Result<()> {
+        let insertable = InsertableSentTransaction {
+            swap_id: Text(sent_transaction.swap_id),
+            action: Text(sent_transaction.action),
+            transaction_id: sent_transaction.transaction_id,
+            raw_transaction: sent_transaction.raw_transaction,
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::replace_into(rfc003_sent_transactions::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LoadSentTransactions for Sqlite {
+    async fn load_sent_transactions(
+        &self,
+        swap_id: &SwapId,
+    ) -> anyhow::Result<Vec<SentTransaction>> {
+        use schema::rfc003_sent_transactions as sent_transactions;
+
+        let records: Vec<(Text<SwapId>, Text<ActionKind>, String, Option<String>)> = self
+            .do_in_transaction(|connection| {
+                let key = Text(swap_id);
+
+                sent_transactions::table
+                    .filter(sent_transactions::swap_id.eq(key))
+                    .select((
+                        sent_transactions::swap_id,
+                        sent_transactions::action,
+                        sent_transactions::transaction_id,
+                        sent_transactions::raw_transaction,
+                    ))
+                    .load(connection)
+            })
+            .await?;
+
+        Ok(records
+            .into_iter()
+            .map(
+                |(Text(swap_id), Text(action), transaction_id, raw_transaction)| SentTransaction {
+                    swap_id,
+                    action,
+                    transaction_id,
+                    raw_transaction,
+                },
+            )
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Save<ActionAttempt> for Sqlite {
+    async fn save(&self, action_attempt: ActionAttempt) -> anyhow::Result<()> {
+        let (transaction_id, error) = match action_attempt.result {
+            Ok(transaction_id) => (Some(transaction_id), None),
+            Err(error) => (None, Some(error)),
+        };
+        let insertable = InsertableActionAttempt {
+            swap_id: Text(action_attempt.swap_id),
+            action: Text(action_attempt.action),
+            attempted_at: U32(action_attempt.attempted_at.into()),
+            transaction_id,
+            error,
+        };
+
+        self.do_in_transaction(|connection| {
+            diesel::insert_into(rfc003_action_attempts::table)
+                .values(&insertable)
+                .execute(connection)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LoadActionAttempts for Sqlite {
+    async fn load_action_attempts(&self, swap_id: &SwapId) -> anyhow::Result<Vec<ActionAttempt>> {
+        use schema::rfc003_action_attempts as action_attempts;
+
+        let records: Vec<(
+            Text<SwapId>,
+            Text<ActionKind>,
+            U32,
+            Option<String>,
+            Option<String>,
+        )> = self
+            .do_in_transaction(|connection| {
+                let key = Text(swap_id);
+
+                action_attempts::table
+                    .filter(action_attempts::swap_id.eq(key))
+                    .order(action_attempts::id)
+                    .select((
+                        action_attempts::swap_id,
+                        action_attempts::action,
+                        action_attempts::attempted_at,
+                        action_attempts::transaction_id,
+                        action_attempts::error,
+                    ))
+                    .load(connection)
+            })
+            .await?;
+
+        Ok(records
+            .into_iter()
+            .map(
+                |(Text(swap_id), Text(action), U32(attempted_at), transaction_id, error)| {
+                    ActionAttempt {
+                        swap_id,
+                        action,
+                        attempted_at: Timestamp::from(attempted_at),
+                        result: match (transaction_id, error) {
+                            (Some(transaction_id), _) => Ok(transaction_id),
+                            (None, error) => Err(error.unwrap_or_default()),
+                        },
+                    }
+                },
+            )
+            .collect())
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[table_name = "rfc003_sent_transactions"]
+struct InsertableSentTransaction {
+    swap_id: Text<SwapId>,
+    action: Text<ActionKind>,
+    transaction_id: String,
+    raw_transaction: Option<String>,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[table_name = "rfc003_action_attempts"]
+struct InsertableActionAttempt {
+    swap_id: Text<SwapId>,
+    action: Text<ActionKind>,
+    attempted_at: U32,
+    transaction_id: Option<String>,
+    error: Option<String>,
+}
@@ -4,7 +4,10 @@
 /// make sure we don't change the format accidentally!
 use crate::{
     db::new_types::{DecimalU256, EthereumAddress, Satoshis},
-    swap_protocols::{rfc003::SecretHash, HashFunction, SwapId},
+    swap_protocols::{
+        rfc003::{HtlcState, Secret, SecretHash},
+        HashFunction, SwapId,
+    },
 };
 use std::{fmt, str::FromStr};
 
@@ -33,7 +36,9 @@ fn bitcoin_amount() {
 #[test]
 fn hash_function() {
     roundtrip_test::<HashFunction>("SHA-256");
-    assert_num_variants::<HashFunction>(1)
+    roundtrip_test::<HashFunction>("Keccak-256");
+    roundtrip_test::<HashFunction>("HASH160");
+    assert_num_variants::<HashFunction>(3)
 }
 
 #[test]
@@ -53,6 +58,23 @@ fn secrethash() {
     roundtrip_test::<SecretHash>(
         "68917b35bacf71dbadf37628b3b7f290f6d88877d7b2269008d893ae7bd4f9ee",
     );
+    roundtrip_test::<SecretHash>("68917b35bacf71dbadf37628b3b7f290f6d88877");
+}
+
+#[test]
+fn secret() {
+    roundtrip_test::<Secret>("68d627971643a6f97f27c58957826fcba853ec2077fd10ec6b93d8e61deb4cec");
+}
+
+#[test]
+fn htlc_state() {
+    roundtrip_test::<HtlcState>("NotDeployed");
+    roundtrip_test::<HtlcState>("Deployed");
+    roundtrip_test::<HtlcState>("Funded");
+    roundtrip_test::<HtlcState>("Redeemed");
+    roundtrip_test::<HtlcState>("Refunded");
+    roundtrip_test::<HtlcState>("IncorrectlyFunded");
+    assert_num_variants::<HtlcState>(6)
 }
 
 /// Given a string representation of a value T, this function will assert
@@ -72,6 +72,136 @@ impl DetermineTypes for Sqlite {
             });
         }
 
+        if self
+            .rfc003_bitcoin_ethereum_bitcoin_erc721_request_messages_has_swap(key)
+            .await?
+        {
+            return Ok(SwapTypes {
+                alpha_ledger: LedgerKind::Bitcoin,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Bitcoin,
+                beta_asset: AssetKind::Erc721,
+                role,
+            });
+        }
+
+        if self
+            .rfc003_ethereum_bitcoin_erc721_bitcoin_request_messages_has_swap(key)
+            .await?
+        {
+            return Ok(SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Bitcoin,
+                alpha_asset: AssetKind::Erc721,
+                beta_asset: AssetKind::Bitcoin,
+                role,
+            });
+        }
+
+        if self
+            .rfc003_ethereum_ethereum_ether_ether_request_messages_has_swap(key)
+            .await?
+        {
+            return Ok(SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Ether,
+                beta_asset: AssetKind::Ether,
+                role,
+            });
+        }
+
+        if self
+            .rfc003_ethereum_ethereum_ether_erc20_request_messages_has_swap(key)
+            .await?
+        {
+            return Ok(SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Ether,
+                beta_asset: AssetKind::Erc20,
+                role,
+            });
+        }
+
+        if self
+            .rfc003_ethereum_ethereum_erc20_ether_request_messages_has_swap(key)
+            .await?
+        {
+            return Ok(SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Erc20,
+                beta_asset: AssetKind::Ether,
+                role,
+            });
+        }
+
+        if self
+            .rfc003_ethereum_ethereum_erc20_erc20_request_messages_has_swap(key)
+            .await?
+        {
+            return Ok(SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Erc20,
+                beta_asset: AssetKind::Erc20,
+                role,
+            });
+        }
+
+        if self
+            .rfc003_litecoin_ethereum_litecoin_ether_request_messages_has_swap(key)
+            .await?
+        {
+            return Ok(SwapTypes {
+                alpha_ledger: LedgerKind::Litecoin,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Litecoin,
+                beta_asset: AssetKind::Ether,
+                role,
+            });
+        }
+
+        if self
+            .rfc003_ethereum_litecoin_ether_litecoin_request_messages_has_swap(key)
+            .await?
+        {
+            return Ok(SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Litecoin,
+                alpha_asset: AssetKind::Ether,
+                beta_asset: AssetKind::Litecoin,
+                role,
+            });
+        }
+
+        if self
+            .rfc003_litecoin_ethereum_litecoin_erc20_request_messages_has_swap(key)
+            .await?
+        {
+            return Ok(SwapTypes {
+                alpha_ledger: LedgerKind::Litecoin,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Litecoin,
+                beta_asset: AssetKind::Erc20,
+                role,
+            });
+        }
+
+        if self
+            .rfc003_ethereum_litecoin_erc20_litecoin_request_messages_has_swap(key)
+            .await?
+        {
+            return Ok(SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Litecoin,
+                alpha_asset: AssetKind::Erc20,
+                beta_asset: AssetKind::Litecoin,
+                role,
+            });
+        }
+
         unreachable!("we got role for swap so the swap_id must exist")
     }
 }
@@ -103,6 +233,16 @@ impl Sqlite {
     impl_has_swap!(rfc003_ethereum_bitcoin_ether_bitcoin_request_messages);
     impl_has_swap!(rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages);
     impl_has_swap!(rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages);
+    impl_has_swap!(rfc003_bitcoin_ethereum_bitcoin_erc721_request_messages);
+    impl_has_swap!(rfc003_ethereum_bitcoin_erc721_bitcoin_request_messages);
+    impl_has_swap!(rfc003_ethereum_ethereum_ether_ether_request_messages);
+    impl_has_swap!(rfc003_ethereum_ethereum_ether_erc20_request_messages);
+    impl_has_swap!(rfc003_ethereum_ethereum_erc20_ether_request_messages);
+    impl_has_swap!(rfc003_ethereum_ethereum_erc20_erc20_request_messages);
+    impl_has_swap!(rfc003_litecoin_ethereum_litecoin_ether_request_messages);
+    impl_has_swap!(rfc003_ethereum_litecoin_ether_litecoin_request_messages);
+    impl_has_swap!(rfc003_litecoin_ethereum_litecoin_erc20_request_messages);
+    impl_has_swap!(rfc003_ethereum_litecoin_erc20_litecoin_request_messages);
 }
 
 #[derive(Queryable, Debug, Clone, PartialEq)]
@@ -123,6 +263,7 @@ pub struct SwapTypes {
 pub enum LedgerKind {
     Bitcoin,
     Ethereum,
+    Litecoin,
 }
 
 impl From<ledger::LedgerKind> for LedgerKind {
@@ -130,6 +271,7 @@ impl From<ledger::LedgerKind> for LedgerKind {
         match ledger {
             ledger::LedgerKind::Bitcoin(_) => LedgerKind::Bitcoin,
             ledger::LedgerKind::Ethereum(_) => LedgerKind::Ethereum,
+            ledger::LedgerKind::Litecoin(_) => LedgerKind::Litecoin,
         }
     }
 }
@@ -139,6 +281,8 @@ pub enum AssetKind {
     Bitcoin,
     Ether,
     Erc20,
+    Erc721,
+    Litecoin,
 }
 
 impl From<asset::AssetKind> for AssetKind {
@@ -147,6 +291,8 @@ impl From<asset::AssetKind> for AssetKind {
             asset::AssetKind::Bitcoin(_) => AssetKind::Bitcoin,
             asset::AssetKind::Ether(_) => AssetKind::Ether,
             asset::AssetKind::Erc20(_) => AssetKind::Erc20,
+            asset::AssetKind::Erc721(_) => AssetKind::Erc721,
+            asset::AssetKind::Litecoin(_) => AssetKind::Litecoin,
         }
     }
 }
@@ -26,8 +26,9 @@ macro_rules! with_swap_types {
     ($swap_types:expr, $fn:expr) => {{
         use crate::{
             db::{AssetKind, LedgerKind, SwapTypes},
-            ethereum::{Erc20Token, EtherQuantity},
-            swap_protocols::ledger::{Bitcoin, Ethereum},
+            ethereum::{Erc20Token, Erc721Token, EtherQuantity},
+            litecoin,
+            swap_protocols::ledger::{Bitcoin, Ethereum, Litecoin},
         };
         use bitcoin::Amount;
         let swap_types: SwapTypes = $swap_types;
@@ -74,6 +75,26 @@ macro_rules! with_swap_types {
 
                 _match_role!(role, $fn)
             }
+            SwapTypes {
+                alpha_ledger: LedgerKind::Bitcoin,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Bitcoin,
+                beta_asset: AssetKind::Erc721,
+                ..
+            } => {
+                #[allow(dead_code)]
+                type AL = Bitcoin;
+                #[allow(dead_code)]
+                type BL = Ethereum;
+                #[allow(dead_code)]
+                type AA = Amount;
+                #[allow(dead_code)]
+                type BA = Erc721Token;
+                #[allow(dead_code)]
+                type AcceptBody = crate::http_api::routes::rfc003::accept::OnlyRefund<BL>;
+
+                _match_role!(role, $fn)
+            }
             SwapTypes {
                 alpha_ledger: LedgerKind::Ethereum,
                 beta_ledger: LedgerKind::Bitcoin,
@@ -114,6 +135,186 @@ macro_rules! with_swap_types {
 
                 _match_role!(role, $fn)
             }
+            SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Bitcoin,
+                alpha_asset: AssetKind::Erc721,
+                beta_asset: AssetKind::Bitcoin,
+                ..
+            } => {
+                #[allow(dead_code)]
+                type AL = Ethereum;
+                #[allow(dead_code)]
+                type BL = Bitcoin;
+                #[allow(dead_code)]
+                type AA = Erc721Token;
+                #[allow(dead_code)]
+                type BA = Amount;
+                #[allow(dead_code)]
+                type AcceptBody = crate::http_api::routes::rfc003::accept::OnlyRedeem<AL>;
+
+                _match_role!(role, $fn)
+            }
+            SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Ether,
+                beta_asset: AssetKind::Ether,
+                ..
+            } => {
+                #[allow(dead_code)]
+                type AL = Ethereum;
+                #[allow(dead_code)]
+                type BL = Ethereum;
+                #[allow(dead_code)]
+                type AA = EtherQuantity;
+                #[allow(dead_code)]
+                type BA = EtherQuantity;
+                #[allow(dead_code)]
+                type AcceptBody = crate::http_api::routes::rfc003::accept::RedeemAndRefund<AL, BL>;
+
+                _match_role!(role, $fn)
+            }
+            SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Ether,
+                beta_asset: AssetKind::Erc20,
+                ..
+            } => {
+                #[allow(dead_code)]
+                type AL = Ethereum;
+                #[allow(dead_code)]
+                type BL = Ethereum;
+                #[allow(dead_code)]
+                type AA = EtherQuantity;
+                #[allow(dead_code)]
+                type BA = Erc20Token;
+                #[allow(dead_code)]
+                type AcceptBody = crate::http_api::routes::rfc003::accept::RedeemAndRefund<AL, BL>;
+
+                _match_role!(role, $fn)
+            }
+            SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Erc20,
+                beta_asset: AssetKind::Ether,
+                ..
+            } => {
+                #[allow(dead_code)]
+                type AL = Ethereum;
+                #[allow(dead_code)]
+                type BL = Ethereum;
+                #[allow(dead_code)]
+                type AA = Erc20Token;
+                #[allow(dead_code)]
+                type BA = EtherQuantity;
+                #[allow(dead_code)]
+                type AcceptBody = crate::http_api::routes::rfc003::accept::RedeemAndRefund<AL, BL>;
+
+                _match_role!(role, $fn)
+            }
+            SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Erc20,
+                beta_asset: AssetKind::Erc20,
+                ..
+            } => {
+                #[allow(dead_code)]
+                type AL = Ethereum;
+                #[allow(dead_code)]
+                type BL = Ethereum;
+                #[allow(dead_code)]
+                type AA = Erc20Token;
+                #[allow(dead_code)]
+                type BA = Erc20Token;
+                #[allow(dead_code)]
+                type AcceptBody = crate::http_api::routes::rfc003::accept::RedeemAndRefund<AL, BL>;
+
+                _match_role!(role, $fn)
+            }
+            SwapTypes {
+                alpha_ledger: LedgerKind::Litecoin,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Litecoin,
+                beta_asset: AssetKind::Ether,
+                ..
+            } => {
+                #[allow(dead_code)]
+                type AL = Litecoin;
+                #[allow(dead_code)]
+                type BL = Ethereum;
+                #[allow(dead_code)]
+                type AA = litecoin::Amount;
+                #[allow(dead_code)]
+                type BA = EtherQuantity;
+                #[allow(dead_code)]
+                type AcceptBody = crate::http_api::routes::rfc003::accept::OnlyRefund<BL>;
+
+                _match_role!(role, $fn)
+            }
+            SwapTypes {
+                alpha_ledger: LedgerKind::Litecoin,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Litecoin,
+                beta_asset: AssetKind::Erc20,
+                ..
+            } => {
+                #[allow(dead_code)]
+                type AL = Litecoin;
+                #[allow(dead_code)]
+                type BL = Ethereum;
+                #[allow(dead_code)]
+                type AA = litecoin::Amount;
+                #[allow(dead_code)]
+                type BA = Erc20Token;
+                #[allow(dead_code)]
+                type AcceptBody = crate::http_api::routes::rfc003::accept::OnlyRefund<BL>;
+
+                _match_role!(role, $fn)
+            }
+            SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Litecoin,
+                alpha_asset: AssetKind::Ether,
+                beta_asset: AssetKind::Litecoin,
+                ..
+            } => {
+                #[allow(dead_code)]
+                type AL = Ethereum;
+                #[allow(dead_code)]
+                type BL = Litecoin;
+                #[allow(dead_code)]
+                type AA = EtherQuantity;
+                #[allow(dead_code)]
+                type BA = litecoin::Amount;
+                #[allow(dead_code)]
+                type AcceptBody = crate::http_api::routes::rfc003::accept::OnlyRedeem<AL>;
+
+                _match_role!(role, $fn)
+            }
+            SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Litecoin,
+                alpha_asset: AssetKind::Erc20,
+                beta_asset: AssetKind::Litecoin,
+                ..
+            } => {
+                #[allow(dead_code)]
+                type AL = Ethereum;
+                #[allow(dead_code)]
+                type BL = Litecoin;
+                #[allow(dead_code)]
+                type AA = Erc20Token;
+                #[allow(dead_code)]
+                type BA = litecoin::Amount;
+                #[allow(dead_code)]
+                type AcceptBody = crate::http_api::routes::rfc003::accept::OnlyRedeem<AL>;
+
+                _match_role!(role, $fn)
+            }
             _ => unimplemented!(),
         }
     }};
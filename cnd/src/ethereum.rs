@@ -2,20 +2,23 @@
 #![forbid(unsafe_code)]
 
 pub use self::{
-    contract_address::*, erc20_quantity::*, erc20_token::*, ether_quantity::*, u256_ext::*,
+    contract_address::*, erc20_quantity::*, erc20_token::*, erc721_token::*, erc721_token_id::*,
+    ether_quantity::*, u256_ext::*,
 };
 pub use ::web3::types::{
-    Address, Block, BlockId, BlockNumber, Bytes, Log, Transaction, TransactionReceipt,
-    TransactionRequest, H160, H2048, H256, U128, U256,
+    Address, Block, BlockId, BlockNumber, Bytes, FilterBuilder, Log, Transaction,
+    TransactionReceipt, TransactionRequest, H160, H2048, H256, U128, U256,
 };
 
 pub mod web3 {
-    pub use ::web3::{transports, Error, Web3};
+    pub use ::web3::{helpers, transports, Error, RequestId, Transport, Web3};
 }
 
 mod contract_address;
 mod erc20_quantity;
 mod erc20_token;
+mod erc721_token;
+mod erc721_token_id;
 mod ether_quantity;
 mod u256_ext;
 
@@ -0,0 +1,24 @@
+use crate::ethereum::{erc721_token_id::Erc721TokenId, Address};
+use std::fmt;
+
+/// A single non-fungible token of an ERC721 contract.
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct Erc721Token {
+    pub token_contract: Address,
+    pub token_id: Erc721TokenId,
+}
+
+impl fmt::Display for Erc721Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.token_id)
+    }
+}
+
+impl Erc721Token {
+    pub fn new(token_contract: Address, token_id: Erc721TokenId) -> Self {
+        Erc721Token {
+            token_contract,
+            token_id,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::ethereum::{
+    u256_ext::{FromDecimalStr, ToBigInt},
+    U256,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct Erc721TokenId(pub U256);
+
+impl fmt::Display for Erc721TokenId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.to_bigint())
+    }
+}
+
+impl<'de> Deserialize<'de> for Erc721TokenId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'vde> de::Visitor<'vde> for Visitor {
+            type Value = Erc721TokenId;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                formatter.write_str("A string representing an ERC721 token id")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Erc721TokenId, E>
+            where
+                E: de::Error,
+            {
+                U256::from_decimal_str(v)
+                    .map(Erc721TokenId)
+                    .map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+impl Serialize for Erc721TokenId {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
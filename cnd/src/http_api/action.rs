@@ -1,17 +1,34 @@
 use crate::{
+    btsieve::{
+        bitcoin::{
+            BitcoinConnector, BitcoinFeeEstimator, BitcoinLedgerConnector, FeeEstimate,
+            FeeEstimator,
+        },
+        ethereum::{EthereumGasEstimator, GasEstimator},
+    },
+    db::{Save, SentTransaction},
     http_api::{
         ethereum_network, problem, Http, MissingQueryParameters, UnexpectedQueryParameters,
     },
     swap_protocols::{
         actions::{
-            bitcoin::{SendToAddress, SpendOutput},
-            ethereum,
+            bitcoin::{spend_all_to, SendToAddress, SpendOutput},
+            ethereum, litecoin,
         },
-        ledger, SwapId,
+        ledger,
+        rfc003::actions::ActionKind,
+        SwapId,
     },
     timestamp::Timestamp,
+    wallet::{
+        bitcoin::{BitcoinWallet, Error as BitcoinWalletError, Wallet},
+        ethereum::{Account, EthereumAccount, UnsignedTransaction},
+        psbt::{self, BitcoinWatchOnlyWallet, ParseUtxoError, Utxo, WatchOnlyWallet},
+    },
 };
 use anyhow::Context;
+use async_trait::async_trait;
+use bitcoin::{hashes::sha256d, util::psbt::PartiallySignedTransaction};
 use blockchain_contracts::bitcoin::witness;
 use http_api_problem::HttpApiProblem;
 use serde::{Deserialize, Serialize};
@@ -29,13 +46,53 @@ pub trait ListRequiredFields {
 #[derive(Clone, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum ActionExecutionParameters {
-    BitcoinAddressAndFee {
-        address: bitcoin::Address,
+    /// The address is parsed against the ledger of the swap. Without
+    /// `fee_per_wu` the fee is estimated by the Bitcoin backend, cnd cannot
+    /// estimate Litecoin fees though.
+    AddressAndFee {
+        address: String,
+        fee_per_wu: Option<String>,
+    },
+    /// Funds the HTLC through an unsigned PSBT spending `utxos`, or the
+    /// outputs of the watch-only wallet if left out.
+    BitcoinPsbt {
+        change_address: bitcoin::Address,
         fee_per_wu: String,
+        #[serde(default)]
+        utxos: Option<String>,
+    },
+    BitcoinFee {
+        fee_per_wu: String,
+    },
+    EthereumGasPrice {
+        gas_price: String,
     },
     None {},
 }
 
+/// Whether cnd should perform the action itself instead of returning
+/// instructions for the client.
+#[derive(Clone, Copy, Default, Deserialize, Debug, PartialEq)]
+pub struct ExecuteAction {
+    #[serde(default)]
+    pub execute: bool,
+}
+
+/// The query parameters of the `bump_fee` route, the fee is estimated by the
+/// Bitcoin backend if `fee_per_wu` is left out.
+#[derive(Clone, Default, Deserialize, Debug, PartialEq)]
+pub struct BumpFeeParameters {
+    #[serde(default)]
+    pub fee_per_wu: Option<String>,
+}
+
+/// The body of the route that broadcasts the PSBT of a fund action once it was
+/// finalized, `psbt` is base64 encoded.
+#[derive(Clone, Deserialize, Debug, PartialEq)]
+pub struct PsbtBody {
+    pub psbt: String,
+}
+
 /// `network` field here for backward compatibility, to be removed with #1580
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -51,6 +108,12 @@ pub enum ActionResponseBody {
         network: Http<bitcoin::Network>,
         #[serde(skip_serializing_if = "Option::is_none")]
         min_median_block_time: Option<Timestamp>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        fee_estimate: Option<FeeEstimate>,
+    },
+    BitcoinSignPsbt {
+        psbt: String,
+        network: Http<bitcoin::Network>,
     },
     EthereumDeployContract {
         data: crate::ethereum::Bytes,
@@ -58,6 +121,9 @@ pub enum ActionResponseBody {
         gas_limit: crate::ethereum::U256,
         network: ethereum_network::Network,
         chain_id: ledger::ethereum::ChainId,
+        /// The gas price the node suggests, or the `gas_price` cnd was given.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        gas_price: Option<crate::ethereum::U256>,
     },
     EthereumCallContract {
         contract_address: crate::ethereum::Address,
@@ -68,6 +134,31 @@ pub enum ActionResponseBody {
         network: ethereum_network::Network,
         #[serde(skip_serializing_if = "Option::is_none")]
         min_block_timestamp: Option<Timestamp>,
+        /// The gas price the node suggests, or the `gas_price` cnd was given.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        gas_price: Option<crate::ethereum::U256>,
+    },
+    EthereumTransactionSent {
+        transaction_hash: crate::ethereum::H256,
+        chain_id: ledger::ethereum::ChainId,
+        network: ethereum_network::Network,
+    },
+    BitcoinTransactionSent {
+        transaction_id: bitcoin::hashes::sha256d::Hash,
+        network: Http<bitcoin::Network>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        fee_estimate: Option<FeeEstimate>,
+    },
+    LitecoinSendAmountToAddress {
+        to: crate::litecoin::Address,
+        amount: String,
+        network: Http<bitcoin::Network>,
+    },
+    LitecoinBroadcastSignedTransaction {
+        hex: String,
+        network: Http<bitcoin::Network>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min_median_block_time: Option<Timestamp>,
     },
     None,
 }
@@ -76,46 +167,670 @@ impl ActionResponseBody {
     fn bitcoin_broadcast_signed_transaction(
         transaction: &bitcoin::Transaction,
         network: bitcoin::Network,
+        fee_estimate: Option<FeeEstimate>,
     ) -> Self {
-        let min_median_block_time = if transaction.lock_time == 0 {
-            None
-        } else {
-            // The first time a tx with lock_time can be broadcasted is when
-            // mediantime == locktime + 1
-            let min_median_block_time = transaction.lock_time + 1;
-            Some(Timestamp::from(min_median_block_time))
-        };
-
         ActionResponseBody::BitcoinBroadcastSignedTransaction {
             hex: bitcoin::consensus::encode::serialize_hex(transaction),
             network: Http(network),
-            min_median_block_time,
+            min_median_block_time: min_median_block_time(transaction),
+            fee_estimate,
         }
     }
+
+    fn litecoin_broadcast_signed_transaction(
+        transaction: &bitcoin::Transaction,
+        network: bitcoin::Network,
+    ) -> Self {
+        ActionResponseBody::LitecoinBroadcastSignedTransaction {
+            hex: bitcoin::consensus::encode::serialize_hex(transaction),
+            network: Http(network),
+            min_median_block_time: min_median_block_time(transaction),
+        }
+    }
+}
+
+fn min_median_block_time(transaction: &bitcoin::Transaction) -> Option<Timestamp> {
+    if transaction.lock_time == 0 {
+        None
+    } else {
+        // The first time a tx with lock_time can be broadcasted is when
+        // mediantime == locktime + 1
+        let min_median_block_time = transaction.lock_time + 1;
+        Some(Timestamp::from(min_median_block_time))
+    }
 }
 
+#[async_trait]
 pub trait IntoResponsePayload {
-    fn into_response_payload(
+    async fn into_response_payload(
         self,
         parameters: ActionExecutionParameters,
+        bitcoin_wallet: &Wallet,
+        ethereum_account: &Account,
+        bitcoin_fee_estimator: &FeeEstimator,
+        bitcoin_watch_only_wallet: &WatchOnlyWallet,
+        ethereum_gas_estimator: &GasEstimator,
     ) -> anyhow::Result<ActionResponseBody>;
 }
 
+/// Speeds up the transaction cnd sent for a redeem or refund action that is
+/// still available, i.e. whose transaction did not make it into a block yet.
+#[async_trait]
+pub trait BumpFee {
+    /// Returns the response together with the record that replaces
+    /// `sent_transaction` if the transaction itself was replaced.
+    async fn bump_fee(
+        self,
+        sent_transaction: SentTransaction,
+        parameters: BumpFeeParameters,
+        bitcoin_wallet: &Wallet,
+        bitcoin_connector: &BitcoinConnector,
+        bitcoin_fee_estimator: &FeeEstimator,
+    ) -> anyhow::Result<(ActionResponseBody, Option<SentTransaction>)>;
+}
+
+fn parse_fee_per_wu(fee_per_wu: &str) -> anyhow::Result<usize> {
+    let fee_per_wu = fee_per_wu.parse::<usize>().with_context(|| {
+        HttpApiProblem::new("Invalid query parameter.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail("Query parameter fee-per-byte is not a valid unsigned integer.")
+    })?;
+
+    Ok(fee_per_wu)
+}
+
+fn parse_bitcoin_address(
+    address: &str,
+    network: bitcoin::Network,
+) -> anyhow::Result<bitcoin::Address> {
+    match address.parse::<bitcoin::Address>() {
+        Ok(address) if is_address_of(address.network, network) => Ok(address),
+        _ => Err(anyhow::Error::from(invalid_address("Bitcoin", network))),
+    }
+}
+
+fn parse_litecoin_address(
+    address: &str,
+    network: bitcoin::Network,
+) -> anyhow::Result<crate::litecoin::Address> {
+    match address.parse::<crate::litecoin::Address>() {
+        Ok(address) if is_address_of(address.network, network) => Ok(address),
+        _ => Err(anyhow::Error::from(invalid_address("Litecoin", network))),
+    }
+}
+
+/// Base58 addresses of testnet and regtest cannot be told apart, they are
+/// parsed as testnet addresses.
+fn is_address_of(address_network: bitcoin::Network, network: bitcoin::Network) -> bool {
+    address_network == network
+        || (address_network == bitcoin::Network::Testnet && network == bitcoin::Network::Regtest)
+}
+
+fn invalid_address(ledger: &str, network: bitcoin::Network) -> HttpApiProblem {
+    HttpApiProblem::new("Invalid query parameter.")
+        .set_status(StatusCode::BAD_REQUEST)
+        .set_detail(format!(
+            "Query parameter address is not a {} address of network {}.",
+            ledger, network
+        ))
+}
+
+fn parse_gas_price(gas_price: &str) -> anyhow::Result<crate::ethereum::U256> {
+    let gas_price = crate::ethereum::U256::from_dec_str(gas_price).map_err(|_| {
+        HttpApiProblem::new("Invalid query parameter.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail("Query parameter gas_price is not a valid unsigned integer.")
+    })?;
+
+    Ok(gas_price)
+}
+
+async fn estimate_fee(bitcoin_fee_estimator: &FeeEstimator) -> anyhow::Result<FeeEstimate> {
+    let fee_estimate = bitcoin_fee_estimator.estimate_fee().await.map_err(|e| {
+        log::error!("Could not estimate Bitcoin fee: {:?}", e);
+        HttpApiProblem::new("Fee estimation failed.")
+            .set_status(StatusCode::INTERNAL_SERVER_ERROR)
+            .set_detail(format!(
+                "Provide the query parameter fee_per_wu instead. {}",
+                e
+            ))
+    })?;
+
+    Ok(fee_estimate)
+}
+
+async fn send_ethereum_transaction(
+    ethereum_account: &Account,
+    transaction: UnsignedTransaction,
+    gas_price: crate::ethereum::U256,
+) -> anyhow::Result<(ActionResponseBody, String, Option<String>)> {
+    let chain_id = transaction.chain_id;
+
+    let (transaction_hash, signed) = ethereum_account.send(transaction, gas_price).await?;
+
+    Ok((
+        ActionResponseBody::EthereumTransactionSent {
+            transaction_hash,
+            chain_id,
+            network: chain_id.try_into()?,
+        },
+        format!("{:#x}", transaction_hash),
+        Some(hex::encode(signed.0)),
+    ))
+}
+
+/// Performs the action `payload` describes with the wallet and account of cnd
+/// and records the transaction id against the swap.
+pub async fn execute_action<D, P>(
+    dependencies: &D,
+    swap_id: SwapId,
+    action_kind: ActionKind,
+    payload: P,
+    parameters: ActionExecutionParameters,
+) -> anyhow::Result<(ActionResponseBody, String)>
+where
+    D: BitcoinWallet
+        + EthereumAccount
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + BitcoinWatchOnlyWallet
+        + EthereumGasEstimator
+        + Save<SentTransaction>,
+    P: IntoResponsePayload + Send,
+{
+    let gas_price = match &parameters {
+        ActionExecutionParameters::EthereumGasPrice { gas_price } => {
+            Some(parse_gas_price(gas_price)?)
+        }
+        _ => None,
+    };
+    let body = payload
+        .into_response_payload(
+            parameters,
+            dependencies.bitcoin_wallet(),
+            dependencies.ethereum_account(),
+            dependencies.bitcoin_fee_estimator(),
+            dependencies.bitcoin_watch_only_wallet(),
+            dependencies.ethereum_gas_estimator(),
+        )
+        .await?;
+    let (body, transaction_id, raw_transaction) = execute(
+        body,
+        gas_price,
+        dependencies.bitcoin_wallet(),
+        dependencies.bitcoin_connector(),
+        dependencies.ethereum_account(),
+    )
+    .await?;
+
+    Save::save(dependencies, SentTransaction {
+        swap_id,
+        action: action_kind,
+        transaction_id: transaction_id.clone(),
+        raw_transaction,
+    })
+    .await?;
+
+    Ok((body, transaction_id))
+}
+
+/// Broadcasts a transaction cnd sent for an action of a swap on `ledger` again,
+/// in case the node dropped it or cnd was restarted before it was mined.
+pub async fn rebroadcast<D>(
+    dependencies: &D,
+    ledger: ledger::LedgerKind,
+    sent_transaction: &SentTransaction,
+) -> anyhow::Result<()>
+where
+    D: BitcoinLedgerConnector + EthereumAccount,
+{
+    let raw_transaction = sent_transaction.raw_transaction.as_ref().ok_or_else(|| {
+        anyhow::anyhow!(
+            "transaction {} was recorded without its content",
+            sent_transaction.transaction_id
+        )
+    })?;
+    let raw_transaction = hex::decode(raw_transaction)?;
+
+    match ledger {
+        ledger::LedgerKind::Bitcoin(_) => {
+            let transaction: bitcoin::Transaction =
+                bitcoin::consensus::encode::deserialize(&raw_transaction)?;
+            dependencies
+                .bitcoin_connector()
+                .send_raw_transaction(&transaction)
+                .await
+                .map_err(|e| anyhow::anyhow!("node did not accept transaction: {:?}", e))?;
+        }
+        ledger::LedgerKind::Ethereum(ethereum) => {
+            dependencies
+                .ethereum_account()
+                .rebroadcast(ethereum.chain_id, crate::ethereum::Bytes(raw_transaction))
+                .await?;
+        }
+        ledger::LedgerKind::Litecoin(_) => {
+            anyhow::bail!("cnd cannot broadcast Litecoin transactions")
+        }
+    }
+
+    Ok(())
+}
+
+/// Broadcasts the transaction `body` describes and returns the response for
+/// the executed action together with the id of the transaction and, for
+/// Bitcoin, the transaction itself.
+///
+/// Ethereum transactions are signed and sent by the account at `gas_price`.
+/// The outputs of the wallet a broadcast Bitcoin transaction spends are
+/// locked, if the broadcast fails they are no longer reserved for it.
+async fn execute(
+    body: ActionResponseBody,
+    gas_price: Option<crate::ethereum::U256>,
+    bitcoin_wallet: &Wallet,
+    bitcoin_connector: &BitcoinConnector,
+    ethereum_account: &Account,
+) -> anyhow::Result<(ActionResponseBody, String, Option<String>)> {
+    match (body, gas_price) {
+        (
+            ActionResponseBody::BitcoinBroadcastSignedTransaction {
+                hex,
+                network,
+                fee_estimate,
+                ..
+            },
+            _,
+        ) => {
+            let transaction: bitcoin::Transaction =
+                bitcoin::consensus::encode::deserialize(&hex::decode(&hex)?)?;
+
+            let transaction_id = match broadcast(bitcoin_connector, &transaction).await {
+                Ok(transaction_id) => transaction_id,
+                Err(e) => {
+                    bitcoin_wallet.release_reserved_outputs(&transaction);
+                    return Err(e);
+                }
+            };
+            bitcoin_wallet.lock_spent_outputs(&transaction);
+
+            Ok((
+                ActionResponseBody::BitcoinTransactionSent {
+                    transaction_id,
+                    network,
+                    fee_estimate,
+                },
+                transaction_id.to_string(),
+                Some(hex),
+            ))
+        }
+        (
+            ActionResponseBody::EthereumDeployContract {
+                data,
+                amount,
+                gas_limit,
+                chain_id,
+                ..
+            },
+            Some(gas_price),
+        ) => {
+            let transaction = UnsignedTransaction {
+                to: None,
+                value: amount.wei(),
+                data,
+                gas_limit,
+                chain_id,
+            };
+
+            send_ethereum_transaction(ethereum_account, transaction, gas_price).await
+        }
+        (
+            ActionResponseBody::EthereumCallContract {
+                contract_address,
+                data,
+                gas_limit,
+                chain_id,
+                min_block_timestamp,
+                ..
+            },
+            Some(gas_price),
+        ) => {
+            // The contract only allows a refund once a block past the expiry
+            // was mined.
+            if let Some(min_block_timestamp) = min_block_timestamp {
+                if Timestamp::now() <= min_block_timestamp {
+                    return Err(anyhow::Error::from(
+                        HttpApiProblem::new("Too early.")
+                            .set_status(StatusCode::BAD_REQUEST)
+                            .set_detail(format!(
+                                "The transaction can only be mined in a block after {}.",
+                                u32::from(min_block_timestamp)
+                            )),
+                    ));
+                }
+            }
+
+            let transaction = UnsignedTransaction {
+                to: Some(contract_address),
+                value: crate::ethereum::U256::zero(),
+                data: data.unwrap_or_else(|| crate::ethereum::Bytes(vec![])),
+                gas_limit,
+                chain_id,
+            };
+
+            send_ethereum_transaction(ethereum_account, transaction, gas_price).await
+        }
+        (ActionResponseBody::BitcoinSendAmountToAddress { .. }, _) => {
+            Err(anyhow::Error::from(MissingQueryParameters {
+                action: "bitcoin::SendToAddress",
+                parameters: &[FEE_PER_WU],
+            }))
+        }
+        (ActionResponseBody::EthereumDeployContract { .. }, None) => {
+            Err(anyhow::Error::from(MissingQueryParameters {
+                action: "ethereum::ContractDeploy",
+                parameters: &[GAS_PRICE],
+            }))
+        }
+        (ActionResponseBody::EthereumCallContract { .. }, None) => {
+            Err(anyhow::Error::from(MissingQueryParameters {
+                action: "ethereum::SendTransaction",
+                parameters: &[GAS_PRICE],
+            }))
+        }
+        // cnd neither has a Litecoin wallet nor a way to broadcast to the
+        // Litecoin network.
+        (body, _) => Err(anyhow::anyhow!("{:?} cannot be executed", body)),
+    }
+}
+
+async fn broadcast(
+    bitcoin_connector: &BitcoinConnector,
+    transaction: &bitcoin::Transaction,
+) -> anyhow::Result<sha256d::Hash> {
+    let transaction_id = bitcoin_connector
+        .send_raw_transaction(transaction)
+        .await
+        .map_err(|e| {
+            log::error!("Could not broadcast Bitcoin transaction: {:?}", e);
+            HttpApiProblem::new("Broadcast failed.")
+                .set_status(StatusCode::INTERNAL_SERVER_ERROR)
+                .set_detail(format!("{:?}", e))
+        })?;
+
+    Ok(transaction_id)
+}
+
+const FEE_PER_WU: problem::MissingQueryParameter = problem::MissingQueryParameter {
+    name: "fee_per_wu",
+    data_type: "uint",
+    description: "The fee per weight unit you want to pay for the transaction in satoshis.",
+};
+
+const UTXOS: problem::MissingQueryParameter = problem::MissingQueryParameter {
+    name: "utxos",
+    data_type: "string",
+    description:
+        "The outputs to fund the HTLC from as txid:vout:value:script_pubkey, separated by commas.",
+};
+
+const LITECOIN_ADDRESS: problem::MissingQueryParameter = problem::MissingQueryParameter {
+    name: "address",
+    data_type: "string",
+    description: "The Litecoin address the output is sent to.",
+};
+
+const LITECOIN_FEE_PER_WU: problem::MissingQueryParameter = problem::MissingQueryParameter {
+    name: "fee_per_wu",
+    data_type: "uint",
+    description: "The fee per weight unit you want to pay for the transaction in litoshis.",
+};
+
+const GAS_PRICE: problem::MissingQueryParameter = problem::MissingQueryParameter {
+    name: "gas_price",
+    data_type: "uint",
+    description: "The gas price you want to pay for the transaction in wei.",
+};
+
+#[async_trait]
 impl IntoResponsePayload for SendToAddress {
-    fn into_response_payload(
+    async fn into_response_payload(
         self,
         query_params: ActionExecutionParameters,
+        bitcoin_wallet: &Wallet,
+        _: &Account,
+        _: &FeeEstimator,
+        bitcoin_watch_only_wallet: &WatchOnlyWallet,
+        _: &GasEstimator,
     ) -> anyhow::Result<ActionResponseBody> {
         match query_params {
             ActionExecutionParameters::None {} => Ok(self.into()),
+            ActionExecutionParameters::BitcoinPsbt {
+                change_address,
+                fee_per_wu,
+                utxos,
+            } => {
+                let fee_per_wu = parse_fee_per_wu(&fee_per_wu)?;
+                let utxos = match utxos {
+                    Some(utxos) => parse_utxos(&utxos)?,
+                    None => watch_only_utxos(bitcoin_watch_only_wallet).await?,
+                };
+
+                let SendToAddress {
+                    to,
+                    amount,
+                    network,
+                } = self;
+                let mut psbt = psbt::fund(
+                    &to,
+                    amount,
+                    network,
+                    utxos,
+                    &change_address,
+                    fee_per_wu as u64,
+                    bitcoin_watch_only_wallet.descriptor(),
+                )
+                .map_err(|e| {
+                    log::error!("Could not build PSBT to fund Bitcoin HTLC: {:?}", e);
+                    wallet_problem(e)
+                })?;
+                bitcoin_watch_only_wallet
+                    .add_previous_transactions(&mut psbt)
+                    .await;
+
+                Ok(ActionResponseBody::BitcoinSignPsbt {
+                    psbt: base64::encode(&bitcoin::consensus::encode::serialize(&psbt)),
+                    network: Http(network),
+                })
+            }
+            ActionExecutionParameters::BitcoinFee { fee_per_wu } => {
+                let fee_per_wu = parse_fee_per_wu(&fee_per_wu)?;
+
+                let SendToAddress {
+                    to,
+                    amount,
+                    network,
+                } = self;
+                let transaction = bitcoin_wallet
+                    .fund(&to, amount, network, fee_per_wu as u64)
+                    .map_err(|e| {
+                        log::error!("Could not fund Bitcoin HTLC from wallet: {:?}", e);
+                        wallet_problem(e)
+                    })?;
+
+                Ok(ActionResponseBody::bitcoin_broadcast_signed_transaction(
+                    &transaction,
+                    network,
+                    None,
+                ))
+            }
             _ => Err(anyhow::Error::from(UnexpectedQueryParameters {
                 action: "bitcoin::SendToAddress",
-                parameters: &["address", "fee_per_wu"],
+                parameters: &["address"],
             })),
         }
     }
 }
 
+fn parse_utxos(utxos: &str) -> anyhow::Result<Vec<Utxo>> {
+    let utxos = utxos
+        .split(',')
+        .map(str::parse)
+        .collect::<Result<Vec<Utxo>, ParseUtxoError>>()
+        .map_err(|e| {
+            HttpApiProblem::new("Invalid query parameter.")
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail(format!("Query parameter utxos is invalid, {}.", e))
+        })?;
+
+    Ok(utxos)
+}
+
+async fn watch_only_utxos(
+    bitcoin_watch_only_wallet: &WatchOnlyWallet,
+) -> anyhow::Result<Vec<Utxo>> {
+    let utxos = bitcoin_watch_only_wallet.utxos().await.map_err(|e| {
+        log::error!("Could not look up outputs of watch-only wallet: {:?}", e);
+        HttpApiProblem::new("Output lookup failed.")
+            .set_status(StatusCode::INTERNAL_SERVER_ERROR)
+            .set_detail(format!("Provide the query parameter utxos instead. {}", e))
+    })?;
+
+    utxos.ok_or_else(|| {
+        anyhow::Error::from(MissingQueryParameters {
+            action: "bitcoin::SendToAddress",
+            parameters: &[UTXOS],
+        })
+    })
+}
+
+/// Takes back the PSBT built for a fund action once the signers finalized it
+/// and returns the transaction to broadcast.
+pub trait AcceptPsbt {
+    fn accept_psbt(
+        self,
+        psbt: PartiallySignedTransaction,
+    ) -> anyhow::Result<(bitcoin::Transaction, bitcoin::Network)>;
+}
+
+impl AcceptPsbt for SendToAddress {
+    fn accept_psbt(
+        self,
+        psbt: PartiallySignedTransaction,
+    ) -> anyhow::Result<(bitcoin::Transaction, bitcoin::Network)> {
+        let transaction = psbt::finalized_transaction(psbt).ok_or_else(|| {
+            HttpApiProblem::new("PSBT is not finalized.")
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail("Every input needs a final script sig or witness.")
+        })?;
+
+        let script_pubkey = self.to.script_pubkey();
+        let pays_htlc = transaction.output.iter().any(|output| {
+            output.script_pubkey == script_pubkey && output.value >= self.amount.as_sat()
+        });
+        if !pays_htlc {
+            return Err(anyhow::Error::from(
+                HttpApiProblem::new("PSBT does not fund the HTLC.")
+                    .set_status(StatusCode::BAD_REQUEST)
+                    .set_detail(format!(
+                        "The transaction has to pay {} to {}.",
+                        self.amount, self.to
+                    )),
+            ));
+        }
+
+        Ok((transaction, self.network))
+    }
+}
+
+impl AcceptPsbt for litecoin::SendToAddress {
+    fn accept_psbt(
+        self,
+        _: PartiallySignedTransaction,
+    ) -> anyhow::Result<(bitcoin::Transaction, bitcoin::Network)> {
+        Err(anyhow::Error::from(not_funded_on_bitcoin()))
+    }
+}
+
+impl AcceptPsbt for ethereum::DeployContract {
+    fn accept_psbt(
+        self,
+        _: PartiallySignedTransaction,
+    ) -> anyhow::Result<(bitcoin::Transaction, bitcoin::Network)> {
+        Err(anyhow::Error::from(not_funded_on_bitcoin()))
+    }
+}
+
+impl AcceptPsbt for ethereum::CallContract {
+    fn accept_psbt(
+        self,
+        _: PartiallySignedTransaction,
+    ) -> anyhow::Result<(bitcoin::Transaction, bitcoin::Network)> {
+        Err(anyhow::Error::from(not_funded_on_bitcoin()))
+    }
+}
+
+fn not_funded_on_bitcoin() -> HttpApiProblem {
+    HttpApiProblem::new("HTLC is not on Bitcoin.")
+        .set_status(StatusCode::BAD_REQUEST)
+        .set_detail("Only Bitcoin HTLCs can be funded through a PSBT.")
+}
+
+/// Broadcasts the transaction of the finalized, base64 encoded `psbt` that
+/// funds the HTLC of the fund action `payload` and records it against the
+/// swap.
+pub async fn broadcast_psbt<D, P>(
+    dependencies: &D,
+    swap_id: SwapId,
+    payload: P,
+    psbt: &str,
+) -> anyhow::Result<ActionResponseBody>
+where
+    D: BitcoinLedgerConnector + Save<SentTransaction>,
+    P: AcceptPsbt,
+{
+    let psbt = base64::decode(psbt)
+        .ok()
+        .and_then(|bytes| bitcoin::consensus::encode::deserialize(&bytes).ok())
+        .ok_or_else(|| {
+            HttpApiProblem::new("Invalid body.")
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail("psbt is not a base64 encoded PSBT.")
+        })?;
+    let (transaction, network) = payload.accept_psbt(psbt)?;
+
+    let transaction_id = broadcast(dependencies.bitcoin_connector(), &transaction).await?;
+
+    Save::save(dependencies, SentTransaction {
+        swap_id,
+        action: ActionKind::Fund,
+        transaction_id: transaction_id.to_string(),
+        raw_transaction: Some(bitcoin::consensus::encode::serialize_hex(&transaction)),
+    })
+    .await?;
+
+    Ok(ActionResponseBody::BitcoinTransactionSent {
+        transaction_id,
+        network: Http(network),
+        fee_estimate: None,
+    })
+}
+
+fn wallet_problem(e: BitcoinWalletError) -> HttpApiProblem {
+    match e {
+        BitcoinWalletError::InsufficientFunds { .. } => HttpApiProblem::new("Insufficient funds.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail(e.to_string()),
+        BitcoinWalletError::OverflowingFee => HttpApiProblem::new("Fee is too high.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail(e.to_string()),
+        BitcoinWalletError::NetworkMismatch { .. } => HttpApiProblem::new("Network mismatch.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail(e.to_string()),
+        BitcoinWalletError::UnknownInputSize(_) => HttpApiProblem::new("Unknown input size.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail(e.to_string()),
+    }
+}
+
 impl From<SendToAddress> for ActionResponseBody {
     fn from(action: SendToAddress) -> Self {
         let SendToAddress {
@@ -137,71 +852,361 @@ impl ListRequiredFields for SendToAddress {
     }
 }
 
+#[async_trait]
 impl IntoResponsePayload for SpendOutput {
-    fn into_response_payload(
+    async fn into_response_payload(
         self,
         query_params: ActionExecutionParameters,
+        bitcoin_wallet: &Wallet,
+        _: &Account,
+        bitcoin_fee_estimator: &FeeEstimator,
+        _: &WatchOnlyWallet,
+        _: &GasEstimator,
     ) -> anyhow::Result<ActionResponseBody> {
-        match query_params {
-            ActionExecutionParameters::BitcoinAddressAndFee {
+        let (address, fee_per_wu, fee_estimate) = match query_params {
+            ActionExecutionParameters::AddressAndFee {
                 address,
-                fee_per_wu,
+                fee_per_wu: Some(fee_per_wu),
+            } => (
+                parse_bitcoin_address(&address, self.network)?,
+                parse_fee_per_wu(&fee_per_wu)?,
+                None,
+            ),
+            ActionExecutionParameters::AddressAndFee {
+                address,
+                fee_per_wu: None,
             } => {
-                let fee_per_wu = fee_per_wu.parse::<usize>().with_context(|| {
-                    HttpApiProblem::new("Invalid query parameter.")
-                        .set_status(StatusCode::BAD_REQUEST)
-                        .set_detail("Query parameter fee-per-byte is not a valid unsigned integer.")
-                })?;
+                let address = parse_bitcoin_address(&address, self.network)?;
+                let fee_estimate = estimate_fee(bitcoin_fee_estimator).await?;
+                (
+                    address,
+                    fee_estimate.fee_per_wu as usize,
+                    Some(fee_estimate),
+                )
+            }
+            // Without an address the output goes to our own wallet.
+            ActionExecutionParameters::BitcoinFee { fee_per_wu } => (
+                bitcoin_wallet.receive_address(),
+                parse_fee_per_wu(&fee_per_wu)?,
+                None,
+            ),
+            _ => {
+                return Err(anyhow::Error::from(MissingQueryParameters {
+                    action: "bitcoin::SpendOutput",
+                    parameters: &[FEE_PER_WU],
+                }))
+            }
+        };
 
-                let network = self.network;
-                let transaction =
-                    self.spend_to(address)
-                        .sign_with_rate(&*crate::SECP, fee_per_wu)
-                        .map_err(|e| {
-                            log::error!("Could not sign Bitcoin transaction: {:?}", e);
-                            match e {
-                                witness::Error::FeeHigherThanInputValue => HttpApiProblem::new(
-                                    "Fee is too high.",
-                                )
-                                .set_status(StatusCode::BAD_REQUEST)
-                                .set_detail(
-                                    "The Fee per byte/WU provided makes the total fee higher than the spendable input value.",
-                                ),
-                                witness::Error::OverflowingFee => HttpApiProblem::new(
-                                    "Fee is too high.",
-                                )
-                                    .set_status(StatusCode::BAD_REQUEST)
-                                    .set_detail(
-                                        "The Fee per byte/WU provided makes the total fee higher than the system supports.",
-                                    )
-                            }
-                        })?;
+        let network = self.network;
+        let transaction = sign_spend_output(self, address, fee_per_wu)?;
 
-                Ok(ActionResponseBody::bitcoin_broadcast_signed_transaction(
-                    &transaction,
-                    network,
+        Ok(ActionResponseBody::bitcoin_broadcast_signed_transaction(
+            &transaction,
+            network,
+            fee_estimate,
+        ))
+    }
+}
+
+fn sign_spend_output(
+    spend_output: SpendOutput,
+    address: bitcoin::Address,
+    fee_per_wu: usize,
+) -> anyhow::Result<bitcoin::Transaction> {
+    sign_with_rate(spend_output.spend_to(address), fee_per_wu)
+}
+
+fn sign_with_rate(
+    primed_transaction: witness::PrimedTransaction,
+    fee_per_wu: usize,
+) -> anyhow::Result<bitcoin::Transaction> {
+    let transaction = primed_transaction
+        .sign_with_rate(&*crate::SECP, fee_per_wu)
+        .map_err(|e| {
+            log::error!("Could not sign Bitcoin transaction: {:?}", e);
+            match e {
+                witness::Error::FeeHigherThanInputValue => HttpApiProblem::new("Fee is too high.")
+                    .set_status(StatusCode::BAD_REQUEST)
+                    .set_detail(
+                        "The Fee per byte/WU provided makes the total fee higher than the spendable input value.",
+                    ),
+                witness::Error::OverflowingFee => HttpApiProblem::new("Fee is too high.")
+                    .set_status(StatusCode::BAD_REQUEST)
+                    .set_detail(
+                        "The Fee per byte/WU provided makes the total fee higher than the system supports.",
+                    ),
+            }
+        })?;
+
+    Ok(transaction)
+}
+
+/// The body of the route that redeems or refunds the Bitcoin HTLCs of several
+/// swaps in one transaction. Without `address` the output goes to the wallet
+/// of cnd, without `fee_per_wu` the fee is estimated.
+#[derive(Clone, Deserialize, Debug, PartialEq)]
+pub struct BatchSpendBody {
+    pub swap_ids: Vec<SwapId>,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub fee_per_wu: Option<String>,
+}
+
+/// The Bitcoin HTLC a redeem or refund action spends, `None` for other
+/// ledgers.
+pub trait IntoSpendOutput {
+    fn into_spend_output(self) -> Option<SpendOutput>;
+}
+
+impl IntoSpendOutput for SpendOutput {
+    fn into_spend_output(self) -> Option<SpendOutput> {
+        Some(self)
+    }
+}
+
+impl IntoSpendOutput for litecoin::SpendOutput {
+    fn into_spend_output(self) -> Option<SpendOutput> {
+        None
+    }
+}
+
+impl IntoSpendOutput for ethereum::CallContract {
+    fn into_spend_output(self) -> Option<SpendOutput> {
+        None
+    }
+}
+
+/// Spends the HTLCs of `spends` in one transaction paying to `address`. With
+/// `execute` the transaction is broadcast and recorded against every swap,
+/// otherwise it is returned for the client to broadcast.
+pub async fn batch_spend<D>(
+    dependencies: &D,
+    spends: Vec<(SwapId, ActionKind, SpendOutput)>,
+    address: Option<String>,
+    fee_per_wu: Option<String>,
+    execute: bool,
+) -> anyhow::Result<ActionResponseBody>
+where
+    D: BitcoinWallet + BitcoinLedgerConnector + BitcoinFeeEstimator + Save<SentTransaction>,
+{
+    let network = match spends.first() {
+        Some((_, _, spend_output)) => spend_output.network,
+        None => {
+            return Err(anyhow::Error::from(
+                HttpApiProblem::new("Nothing to spend.")
+                    .set_status(StatusCode::BAD_REQUEST)
+                    .set_detail("swap_ids is empty."),
+            ))
+        }
+    };
+    if let Some((swap_id, ..)) = spends
+        .iter()
+        .find(|(_, _, spend_output)| spend_output.network != network)
+    {
+        return Err(anyhow::Error::from(
+            HttpApiProblem::new("Network mismatch.")
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail(format!(
+                    "The HTLC of swap {} is not on {}.",
+                    swap_id, network
+                )),
+        ));
+    }
+
+    let (fee_per_wu, fee_estimate) = match fee_per_wu {
+        Some(fee_per_wu) => (parse_fee_per_wu(&fee_per_wu)?, None),
+        None => {
+            let fee_estimate = estimate_fee(dependencies.bitcoin_fee_estimator()).await?;
+            (fee_estimate.fee_per_wu as usize, Some(fee_estimate))
+        }
+    };
+    let address = match address {
+        Some(address) => parse_bitcoin_address(&address, network)?,
+        None => dependencies.bitcoin_wallet().receive_address(),
+    };
+
+    let (swaps, spend_outputs): (Vec<_>, Vec<_>) = spends
+        .into_iter()
+        .map(|(swap_id, action_kind, spend_output)| ((swap_id, action_kind), spend_output))
+        .unzip();
+    let transaction = sign_with_rate(spend_all_to(spend_outputs, address), fee_per_wu)?;
+
+    if !execute {
+        return Ok(ActionResponseBody::bitcoin_broadcast_signed_transaction(
+            &transaction,
+            network,
+            fee_estimate,
+        ));
+    }
+
+    let transaction_id = broadcast(dependencies.bitcoin_connector(), &transaction).await?;
+    let raw_transaction = bitcoin::consensus::encode::serialize_hex(&transaction);
+
+    for (swap_id, action_kind) in swaps {
+        Save::save(dependencies, SentTransaction {
+            swap_id,
+            action: action_kind,
+            transaction_id: transaction_id.to_string(),
+            raw_transaction: Some(raw_transaction.clone()),
+        })
+        .await?;
+    }
+
+    Ok(ActionResponseBody::BitcoinTransactionSent {
+        transaction_id,
+        network: Http(network),
+        fee_estimate,
+    })
+}
+
+/// Replaces the stuck transaction with one that pays a higher fee to the same
+/// address if it signals replaceability (RBF). Otherwise a child transaction
+/// spending its output pays for both (CPFP), which needs the output to belong
+/// to the wallet of cnd.
+#[async_trait]
+impl BumpFee for SpendOutput {
+    async fn bump_fee(
+        self,
+        sent_transaction: SentTransaction,
+        parameters: BumpFeeParameters,
+        bitcoin_wallet: &Wallet,
+        bitcoin_connector: &BitcoinConnector,
+        bitcoin_fee_estimator: &FeeEstimator,
+    ) -> anyhow::Result<(ActionResponseBody, Option<SentTransaction>)> {
+        let hex = sent_transaction.raw_transaction.as_ref().ok_or_else(|| {
+            HttpApiProblem::new("Fee cannot be bumped.")
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail(format!(
+                    "Transaction {} was sent before cnd recorded the transactions it sends.",
+                    sent_transaction.transaction_id
                 ))
+        })?;
+        let stuck: bitcoin::Transaction =
+            bitcoin::consensus::encode::deserialize(&hex::decode(hex)?)?;
+        // A replacement of the HTLC output alone would conflict with the
+        // spends of the other swaps in the batch.
+        if stuck.input.len() > 1 {
+            return Err(anyhow::Error::from(
+                HttpApiProblem::new("Fee cannot be bumped.")
+                    .set_status(StatusCode::BAD_REQUEST)
+                    .set_detail(format!(
+                        "Transaction {} spends the HTLCs of several swaps.",
+                        stuck.txid()
+                    )),
+            ));
+        }
+
+        let (fee_per_wu, fee_estimate) = match parameters.fee_per_wu {
+            Some(fee_per_wu) => (parse_fee_per_wu(&fee_per_wu)?, None),
+            None => {
+                let fee_estimate = estimate_fee(bitcoin_fee_estimator).await?;
+                (fee_estimate.fee_per_wu as usize, Some(fee_estimate))
             }
-            _ => Err(anyhow::Error::from(MissingQueryParameters {
-                action: "bitcoin::SpendOutput",
-                parameters: &[
-                    problem::MissingQueryParameter {
-                        name: "address",
-                        data_type: "string",
-                        description: "The bitcoin address to where the funds should be sent.",
-                    },
-                    problem::MissingQueryParameter {
-                        name: "fee_per_wu",
-                        data_type: "uint",
-                        description:
-                        "The fee per weight unit you want to pay for the transaction in satoshis.",
-                    },
-                ]
-            }))
+        };
+        let network = self.network;
+
+        if signals_replaceability(&stuck) {
+            let address = stuck
+                .output
+                .first()
+                .and_then(|output| bitcoin::Address::from_script(&output.script_pubkey, network))
+                .ok_or_else(|| {
+                    anyhow::anyhow!("cannot decode address of transaction {}", stuck.txid())
+                })?;
+
+            let replacement = sign_spend_output(self, address, fee_per_wu)?;
+            if !pays_for_replacement(&stuck, &replacement) {
+                return Err(anyhow::Error::from(
+                    HttpApiProblem::new("Fee is too low.")
+                        .set_status(StatusCode::BAD_REQUEST)
+                        .set_detail(format!(
+                            "A replacement of transaction {} at {} satoshis per WU does not pay at least {} satoshi per vbyte more than it.",
+                            stuck.txid(),
+                            fee_per_wu,
+                            INCREMENTAL_RELAY_FEE_PER_VBYTE
+                        )),
+                ));
+            }
+
+            let transaction_id = broadcast(bitcoin_connector, &replacement).await?;
+            let raw_transaction = bitcoin::consensus::encode::serialize_hex(&replacement);
+
+            return Ok((
+                ActionResponseBody::BitcoinTransactionSent {
+                    transaction_id,
+                    network: Http(network),
+                    fee_estimate,
+                },
+                Some(SentTransaction {
+                    transaction_id: transaction_id.to_string(),
+                    raw_transaction: Some(raw_transaction),
+                    ..sent_transaction
+                }),
+            ));
         }
+
+        let child = bitcoin_wallet
+            .child_pays_for_parent(&stuck, fee_per_wu as u64)
+            .map_err(|e| {
+                log::error!("Could not build child transaction: {:?}", e);
+                wallet_problem(e)
+            })?
+            .ok_or_else(|| {
+                HttpApiProblem::new("Fee cannot be bumped.")
+                    .set_status(StatusCode::BAD_REQUEST)
+                    .set_detail(format!(
+                        "Transaction {} is not replaceable and does not pay to the wallet of cnd.",
+                        stuck.txid()
+                    ))
+            })?;
+
+        let transaction_id = broadcast(bitcoin_connector, &child).await?;
+        bitcoin_wallet.lock_spent_outputs(&child);
+
+        Ok((
+            ActionResponseBody::BitcoinTransactionSent {
+                transaction_id,
+                network: Http(network),
+                fee_estimate,
+            },
+            None,
+        ))
     }
 }
 
+/// A transaction is replaceable if any of its inputs has a sequence below
+/// `0xFFFF_FFFE` (BIP125).
+fn signals_replaceability(transaction: &bitcoin::Transaction) -> bool {
+    transaction
+        .input
+        .iter()
+        .any(|input| input.sequence < 0xFFFF_FFFE)
+}
+
+/// The minimum fee rate by which a replacement has to outbid the transaction it
+/// replaces, the default `-incrementalrelayfee` of Bitcoin Core.
+const INCREMENTAL_RELAY_FEE_PER_VBYTE: u64 = 1;
+
+/// Whether nodes accept `replacement` in place of `stuck`: it has to pay a
+/// higher absolute fee (BIP125 rule 3) and the additional fee has to pay for
+/// relaying the replacement at the incremental relay fee (rule 4).
+///
+/// Both spend the same HTLC output only, hence the additional fee is by how
+/// much the outputs of the replacement are lower.
+fn pays_for_replacement(stuck: &bitcoin::Transaction, replacement: &bitcoin::Transaction) -> bool {
+    let stuck_value = stuck.output.iter().map(|output| output.value).sum::<u64>();
+    let replacement_value = replacement
+        .output
+        .iter()
+        .map(|output| output.value)
+        .sum::<u64>();
+    let replacement_vsize = (replacement.get_weight() as u64 + 3) / 4;
+
+    stuck_value >= replacement_value + INCREMENTAL_RELAY_FEE_PER_VBYTE * replacement_vsize
+}
+
 impl ListRequiredFields for SpendOutput {
     fn list_required_fields() -> Vec<siren::Field> {
         vec![
@@ -229,10 +1234,137 @@ impl ListRequiredFields for SpendOutput {
     }
 }
 
+/// cnd has no Litecoin wallet, hence the HTLC is funded by the client.
+#[async_trait]
+impl IntoResponsePayload for litecoin::SendToAddress {
+    async fn into_response_payload(
+        self,
+        query_params: ActionExecutionParameters,
+        _: &Wallet,
+        _: &Account,
+        _: &FeeEstimator,
+        _: &WatchOnlyWallet,
+        _: &GasEstimator,
+    ) -> anyhow::Result<ActionResponseBody> {
+        match query_params {
+            ActionExecutionParameters::None {} => Ok(self.into()),
+            _ => Err(anyhow::Error::from(UnexpectedQueryParameters {
+                action: "litecoin::SendToAddress",
+                parameters: &["address", "fee_per_wu"],
+            })),
+        }
+    }
+}
+
+impl From<litecoin::SendToAddress> for ActionResponseBody {
+    fn from(action: litecoin::SendToAddress) -> Self {
+        let litecoin::SendToAddress {
+            to,
+            amount,
+            network,
+        } = action;
+        ActionResponseBody::LitecoinSendAmountToAddress {
+            to,
+            amount: amount.as_litoshi().to_string(),
+            network: Http(network),
+        }
+    }
+}
+
+impl ListRequiredFields for litecoin::SendToAddress {
+    fn list_required_fields() -> Vec<siren::Field> {
+        vec![]
+    }
+}
+
+/// The transaction is signed by cnd but has to be broadcast by the client.
+#[async_trait]
+impl IntoResponsePayload for litecoin::SpendOutput {
+    async fn into_response_payload(
+        self,
+        query_params: ActionExecutionParameters,
+        _: &Wallet,
+        _: &Account,
+        _: &FeeEstimator,
+        _: &WatchOnlyWallet,
+        _: &GasEstimator,
+    ) -> anyhow::Result<ActionResponseBody> {
+        let (address, fee_per_wu) = match query_params {
+            ActionExecutionParameters::AddressAndFee {
+                address,
+                fee_per_wu: Some(fee_per_wu),
+            } => (parse_litecoin_address(&address, self.network)?, fee_per_wu),
+            _ => {
+                return Err(anyhow::Error::from(MissingQueryParameters {
+                    action: "litecoin::SpendOutput",
+                    parameters: &[LITECOIN_ADDRESS, LITECOIN_FEE_PER_WU],
+                }))
+            }
+        };
+        let fee_per_wu = parse_fee_per_wu(&fee_per_wu)?;
+
+        let network = self.network;
+        let transaction = sign_with_rate(self.spend_to(address), fee_per_wu)?;
+
+        Ok(ActionResponseBody::litecoin_broadcast_signed_transaction(
+            &transaction,
+            network,
+        ))
+    }
+}
+
+#[async_trait]
+impl BumpFee for litecoin::SpendOutput {
+    async fn bump_fee(
+        self,
+        sent_transaction: SentTransaction,
+        _: BumpFeeParameters,
+        _: &Wallet,
+        _: &BitcoinConnector,
+        _: &FeeEstimator,
+    ) -> anyhow::Result<(ActionResponseBody, Option<SentTransaction>)> {
+        Err(anyhow::Error::from(
+            HttpApiProblem::new("Fee cannot be bumped.")
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail(format!(
+                    "Litecoin transaction {} was not sent by cnd.",
+                    sent_transaction.transaction_id
+                )),
+        ))
+    }
+}
+
+impl ListRequiredFields for litecoin::SpendOutput {
+    fn list_required_fields() -> Vec<siren::Field> {
+        vec![
+            siren::Field {
+                name: "address".to_owned(),
+                class: vec!["litecoin".to_owned(), "address".to_owned()],
+                _type: Some("text".to_owned()),
+                value: None,
+                title: None,
+            },
+            siren::Field {
+                name: "fee_per_wu".to_owned(),
+                class: vec!["litecoin".to_owned(), "feePerWU".to_owned()],
+                _type: Some("number".to_owned()),
+                value: None,
+                title: None,
+            },
+        ]
+    }
+}
+
+#[async_trait]
 impl IntoResponsePayload for ethereum::DeployContract {
-    fn into_response_payload(
+    async fn into_response_payload(
         self,
         query_params: ActionExecutionParameters,
+        _: &Wallet,
+        ethereum_account: &Account,
+        _: &FeeEstimator,
+        _: &WatchOnlyWallet,
+        ethereum_gas_estimator: &GasEstimator,
     ) -> anyhow::Result<ActionResponseBody> {
         let ethereum::DeployContract {
             data,
@@ -241,13 +1373,43 @@ impl IntoResponsePayload for ethereum::DeployContract {
             chain_id,
         } = self;
         match query_params {
-            ActionExecutionParameters::None {} => Ok(ActionResponseBody::EthereumDeployContract {
-                data,
-                amount,
-                gas_limit,
-                chain_id,
-                network: chain_id.try_into()?,
-            }),
+            ActionExecutionParameters::None {} => {
+                let gas_limit = ethereum_gas_estimator
+                    .gas_limit(chain_id, None, None, amount.wei(), &data, gas_limit)
+                    .await?;
+
+                Ok(ActionResponseBody::EthereumDeployContract {
+                    data,
+                    amount,
+                    gas_limit,
+                    chain_id,
+                    network: chain_id.try_into()?,
+                    gas_price: ethereum_gas_estimator.gas_price(chain_id).await?,
+                })
+            }
+            // The gas is estimated for the account of cnd, which sends the
+            // transaction if the action is executed.
+            ActionExecutionParameters::EthereumGasPrice { gas_price } => {
+                let gas_limit = ethereum_gas_estimator
+                    .gas_limit(
+                        chain_id,
+                        Some(ethereum_account.address()),
+                        None,
+                        amount.wei(),
+                        &data,
+                        gas_limit,
+                    )
+                    .await?;
+
+                Ok(ActionResponseBody::EthereumDeployContract {
+                    data,
+                    amount,
+                    gas_limit,
+                    chain_id,
+                    network: chain_id.try_into()?,
+                    gas_price: Some(parse_gas_price(&gas_price)?),
+                })
+            }
             _ => Err(anyhow::Error::from(UnexpectedQueryParameters {
                 action: "ethereum::ContractDeploy",
                 parameters: &["address", "fee_per_wu"],
@@ -262,10 +1424,16 @@ impl ListRequiredFields for ethereum::DeployContract {
     }
 }
 
+#[async_trait]
 impl IntoResponsePayload for ethereum::CallContract {
-    fn into_response_payload(
+    async fn into_response_payload(
         self,
         query_params: ActionExecutionParameters,
+        _: &Wallet,
+        ethereum_account: &Account,
+        _: &FeeEstimator,
+        _: &WatchOnlyWallet,
+        ethereum_gas_estimator: &GasEstimator,
     ) -> anyhow::Result<ActionResponseBody> {
         let ethereum::CallContract {
             to,
@@ -274,15 +1442,51 @@ impl IntoResponsePayload for ethereum::CallContract {
             chain_id,
             min_block_timestamp,
         } = self;
+        let call_data = data
+            .clone()
+            .unwrap_or_else(|| crate::ethereum::Bytes(vec![]));
+        let value = crate::ethereum::U256::zero();
+
         match query_params {
-            ActionExecutionParameters::None {} => Ok(ActionResponseBody::EthereumCallContract {
-                contract_address: to,
-                data,
-                gas_limit,
-                chain_id,
-                network: chain_id.try_into()?,
-                min_block_timestamp,
-            }),
+            ActionExecutionParameters::None {} => {
+                let gas_limit = ethereum_gas_estimator
+                    .gas_limit(chain_id, None, Some(to), value, &call_data, gas_limit)
+                    .await?;
+
+                Ok(ActionResponseBody::EthereumCallContract {
+                    contract_address: to,
+                    data,
+                    gas_limit,
+                    chain_id,
+                    network: chain_id.try_into()?,
+                    min_block_timestamp,
+                    gas_price: ethereum_gas_estimator.gas_price(chain_id).await?,
+                })
+            }
+            // The gas is estimated for the account of cnd, which sends the
+            // transaction if the action is executed.
+            ActionExecutionParameters::EthereumGasPrice { gas_price } => {
+                let gas_limit = ethereum_gas_estimator
+                    .gas_limit(
+                        chain_id,
+                        Some(ethereum_account.address()),
+                        Some(to),
+                        value,
+                        &call_data,
+                        gas_limit,
+                    )
+                    .await?;
+
+                Ok(ActionResponseBody::EthereumCallContract {
+                    contract_address: to,
+                    data,
+                    gas_limit,
+                    chain_id,
+                    network: chain_id.try_into()?,
+                    min_block_timestamp,
+                    gas_price: Some(parse_gas_price(&gas_price)?),
+                })
+            }
             _ => Err(anyhow::Error::from(UnexpectedQueryParameters {
                 action: "ethereum::SendTransaction",
                 parameters: &["address", "fee_per_wu"],
@@ -291,6 +1495,29 @@ impl IntoResponsePayload for ethereum::CallContract {
     }
 }
 
+/// The Ethereum account replaces stuck transactions with a higher gas price by
+/// itself.
+#[async_trait]
+impl BumpFee for ethereum::CallContract {
+    async fn bump_fee(
+        self,
+        sent_transaction: SentTransaction,
+        _: BumpFeeParameters,
+        _: &Wallet,
+        _: &BitcoinConnector,
+        _: &FeeEstimator,
+    ) -> anyhow::Result<(ActionResponseBody, Option<SentTransaction>)> {
+        Err(anyhow::Error::from(
+            HttpApiProblem::new("Fee cannot be bumped.")
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail(format!(
+                    "Ethereum transaction {} is replaced with a higher gas price automatically.",
+                    sent_transaction.transaction_id
+                )),
+        ))
+    }
+}
+
 impl ListRequiredFields for ethereum::CallContract {
     fn list_required_fields() -> Vec<siren::Field> {
         vec![]
@@ -303,10 +1530,16 @@ impl ListRequiredFields for Infallible {
     }
 }
 
+#[async_trait]
 impl IntoResponsePayload for Infallible {
-    fn into_response_payload(
+    async fn into_response_payload(
         self,
         _: ActionExecutionParameters,
+        _: &Wallet,
+        _: &Account,
+        _: &FeeEstimator,
+        _: &WatchOnlyWallet,
+        _: &GasEstimator,
     ) -> anyhow::Result<ActionResponseBody> {
         unreachable!("how did you manage to construct Infallible?")
     }
@@ -316,11 +1549,158 @@ impl IntoResponsePayload for Infallible {
 mod test {
     use super::*;
     use crate::{
-        ethereum::{Address as EthereumAddress, U256},
+        btsieve::{
+            bitcoin::{BitcoindConnector, BlockSource},
+            ethereum::Web3Connector,
+            BlockPoller,
+        },
+        ethereum::{web3::transports::EventLoopHandle, Address as EthereumAddress, U256},
+        seed::Seed,
         swap_protocols::ledger::ethereum::ChainId,
     };
     use bitcoin::Address as BitcoinAddress;
-    use std::str::FromStr;
+    use std::{collections::HashMap, str::FromStr};
+
+    /// The wallet and account of cnd in front of nodes that cannot be
+    /// reached, sending a transaction through them fails.
+    struct Dependencies {
+        bitcoin_wallet: Wallet,
+        ethereum_account: Account,
+        bitcoin_fee_estimator: FeeEstimator,
+        bitcoin_watch_only_wallet: WatchOnlyWallet,
+        ethereum_gas_estimator: GasEstimator,
+        _event_loop_handle: Option<EventLoopHandle>,
+    }
+
+    impl Dependencies {
+        fn new(runtime: &tokio::runtime::Runtime) -> Self {
+            let unreachable = "http://localhost:1".parse::<reqwest::Url>().unwrap();
+
+            let bitcoin_connector =
+                BitcoinConnector::Blocks(BlockPoller::new(BlockSource::Bitcoind(
+                    BitcoindConnector::new(unreachable.clone(), bitcoin::Network::Regtest).unwrap(),
+                )));
+            let (ethereum_connector, event_loop_handle) =
+                Web3Connector::new(unreachable, runtime.executor()).unwrap();
+            let ethereum_connectors = std::iter::once((ChainId::regtest(), ethereum_connector))
+                .collect::<HashMap<_, _>>();
+            let seed = Seed::from(*b"hello world, you are beautiful!!");
+
+            // A wallet that owns a single output of 1 BTC.
+            let state = serde_json::from_value(serde_json::json!({
+                "tip": null,
+                "next_receive_index": 1,
+                "next_change_index": 0,
+                "utxos": [{
+                    "outpoint": {
+                        "txid": "9d3d4f35d2b7b5b5cbd32b0bd5a4d7c5f6a1d3c2b1a09f8e7d6c5b4a39281706",
+                        "vout": 0
+                    },
+                    "value": 100_000_000,
+                    "keychain": "receive",
+                    "index": 0,
+                    "locked": false
+                }]
+            }))
+            .unwrap();
+
+            Self {
+                bitcoin_wallet: Wallet::new(&seed, bitcoin::Network::Regtest, state),
+                ethereum_account: Account::new(&seed, ethereum_connectors.clone()),
+                bitcoin_fee_estimator: FeeEstimator::new(bitcoin_connector.clone(), 6),
+                bitcoin_watch_only_wallet: WatchOnlyWallet::new(None, bitcoin_connector),
+                ethereum_gas_estimator: GasEstimator::new(ethereum_connectors, 0),
+                _event_loop_handle: event_loop_handle,
+            }
+        }
+
+        async fn response_payload<P: IntoResponsePayload>(
+            &self,
+            action: P,
+            parameters: ActionExecutionParameters,
+        ) -> anyhow::Result<ActionResponseBody> {
+            action
+                .into_response_payload(
+                    parameters,
+                    &self.bitcoin_wallet,
+                    &self.ethereum_account,
+                    &self.bitcoin_fee_estimator,
+                    &self.bitcoin_watch_only_wallet,
+                    &self.ethereum_gas_estimator,
+                )
+                .await
+        }
+    }
+
+    #[test]
+    fn given_fee_per_wu_fund_payload_does_not_lock_wallet_outputs() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let dependencies = Dependencies::new(&runtime);
+        let state = dependencies.bitcoin_wallet.state();
+        let action = SendToAddress {
+            to: Wallet::new(
+                &Seed::from([2u8; 32]),
+                bitcoin::Network::Regtest,
+                Default::default(),
+            )
+            .receive_address(),
+            amount: bitcoin::Amount::from_sat(50_000_000),
+            network: bitcoin::Network::Regtest,
+        };
+        let parameters = ActionExecutionParameters::BitcoinFee {
+            fee_per_wu: "10".to_owned(),
+        };
+
+        let first = async_std::task::block_on(
+            dependencies.response_payload(action.clone(), parameters.clone()),
+        )
+        .unwrap();
+        let second =
+            async_std::task::block_on(dependencies.response_payload(action, parameters)).unwrap();
+
+        match (first, second) {
+            (
+                ActionResponseBody::BitcoinBroadcastSignedTransaction { hex: first, .. },
+                ActionResponseBody::BitcoinBroadcastSignedTransaction { hex: second, .. },
+            ) => assert_eq!(first, second),
+            (first, second) => panic!(
+                "expected signed transactions, got {:?} and {:?}",
+                first, second
+            ),
+        }
+        assert_eq!(dependencies.bitcoin_wallet.state(), state);
+    }
+
+    #[test]
+    fn given_gas_price_deploy_payload_does_not_send_transaction() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let dependencies = Dependencies::new(&runtime);
+        let action = ethereum::DeployContract {
+            data: crate::ethereum::Bytes(vec![0x60, 0x00]),
+            amount: crate::ethereum::EtherQuantity::from_eth(1.0),
+            gas_limit: U256::from(100_000),
+            chain_id: ChainId::regtest(),
+        };
+        let parameters = ActionExecutionParameters::EthereumGasPrice {
+            gas_price: "20000000000".to_owned(),
+        };
+
+        // Sending through the unreachable node would fail.
+        let body =
+            async_std::task::block_on(dependencies.response_payload(action, parameters)).unwrap();
+
+        match body {
+            ActionResponseBody::EthereumDeployContract {
+                gas_limit,
+                gas_price,
+                ..
+            } => {
+                assert_eq!(gas_limit, U256::from(100_000));
+                assert_eq!(gas_price, Some(U256::from(20_000_000_000u64)));
+            }
+            _ => panic!("expected deploy contract payload, got {:?}", body),
+        }
+    }
 
     #[test]
     fn given_no_query_parameters_deserialize_to_none() {
@@ -337,13 +1717,136 @@ mod test {
         let res = serde_urlencoded::from_str::<ActionExecutionParameters>(s);
         assert_eq!(
             res,
-            Ok(ActionExecutionParameters::BitcoinAddressAndFee {
-                address: "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".parse().unwrap(),
-                fee_per_wu: "10.59".to_string(),
+            Ok(ActionExecutionParameters::AddressAndFee {
+                address: "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".to_string(),
+                fee_per_wu: Some("10.59".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn given_only_bitcoin_identity_deserialize_to_address_without_fee() {
+        let s = "address=1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+
+        let res = serde_urlencoded::from_str::<ActionExecutionParameters>(s);
+        assert_eq!(
+            res,
+            Ok(ActionExecutionParameters::AddressAndFee {
+                address: "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".to_string(),
+                fee_per_wu: None,
+            })
+        );
+    }
+
+    #[test]
+    fn given_litecoin_address_and_fee_deserialize_to_ditto() {
+        let s = "address=ltc1qvt5s0v2uhuna2sjnn84ldu8m2r4m3rcc3n0rm5&fee_per_wu=10";
+
+        let res = serde_urlencoded::from_str::<ActionExecutionParameters>(s);
+        assert_eq!(
+            res,
+            Ok(ActionExecutionParameters::AddressAndFee {
+                address: "ltc1qvt5s0v2uhuna2sjnn84ldu8m2r4m3rcc3n0rm5".to_string(),
+                fee_per_wu: Some("10".to_string()),
             })
         );
     }
 
+    #[test]
+    fn address_is_parsed_against_the_ledger_of_the_swap() {
+        let litecoin = "ltc1qvt5s0v2uhuna2sjnn84ldu8m2r4m3rcc3n0rm5";
+
+        assert!(parse_litecoin_address(litecoin, bitcoin::Network::Bitcoin).is_ok());
+        assert!(parse_bitcoin_address(litecoin, bitcoin::Network::Bitcoin).is_err());
+        assert!(parse_litecoin_address(litecoin, bitcoin::Network::Testnet).is_err());
+    }
+
+    #[test]
+    fn testnet_public_key_hash_address_is_valid_for_both_ledgers() {
+        let address = "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn";
+
+        let litecoin = parse_litecoin_address(address, bitcoin::Network::Regtest).unwrap();
+        let bitcoin = parse_bitcoin_address(address, bitcoin::Network::Regtest).unwrap();
+
+        assert_eq!(litecoin.script_pubkey(), bitcoin.script_pubkey());
+        assert!(parse_bitcoin_address(address, bitcoin::Network::Bitcoin).is_err());
+    }
+
+    #[test]
+    fn given_only_bitcoin_fee_deserialize_to_ditto() {
+        let s = "fee_per_wu=10";
+
+        let res = serde_urlencoded::from_str::<ActionExecutionParameters>(s);
+        assert_eq!(
+            res,
+            Ok(ActionExecutionParameters::BitcoinFee {
+                fee_per_wu: "10".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn given_change_address_and_fee_deserialize_to_psbt() {
+        let s = "change_address=1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2&fee_per_wu=10";
+
+        let res = serde_urlencoded::from_str::<ActionExecutionParameters>(s);
+        assert_eq!(
+            res,
+            Ok(ActionExecutionParameters::BitcoinPsbt {
+                change_address: "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2".parse().unwrap(),
+                fee_per_wu: "10".to_string(),
+                utxos: None,
+            })
+        );
+    }
+
+    #[test]
+    fn given_gas_price_deserialize_to_ethereum_gas_price() {
+        let s = "gas_price=20000000000";
+
+        let res = serde_urlencoded::from_str::<ActionExecutionParameters>(s);
+        assert_eq!(
+            res,
+            Ok(ActionExecutionParameters::EthereumGasPrice {
+                gas_price: "20000000000".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn execute_is_deserialized_next_to_the_action_parameters() {
+        let s = "fee_per_wu=10&execute=true";
+
+        let execute = serde_urlencoded::from_str::<ExecuteAction>(s);
+        let parameters = serde_urlencoded::from_str::<ActionExecutionParameters>(s);
+
+        assert_eq!(execute, Ok(ExecuteAction { execute: true }));
+        assert_eq!(
+            parameters,
+            Ok(ActionExecutionParameters::BitcoinFee {
+                fee_per_wu: "10".to_string(),
+            })
+        );
+        assert_eq!(
+            serde_urlencoded::from_str::<ExecuteAction>(""),
+            Ok(ExecuteAction { execute: false })
+        );
+    }
+
+    #[test]
+    fn batch_spend_body_leaves_address_and_fee_to_cnd() {
+        let body = serde_json::from_str::<BatchSpendBody>(
+            r#"{ "swap_ids": ["6f2a6b1c-cc1f-4a7b-8a5e-43b0a5b2b7d1"] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(body, BatchSpendBody {
+            swap_ids: vec!["6f2a6b1c-cc1f-4a7b-8a5e-43b0a5b2b7d1".parse().unwrap()],
+            address: None,
+            fee_per_wu: None,
+        });
+    }
+
     #[test]
     fn call_contract_serializes_correctly_to_json_with_none() {
         let addr = EthereumAddress::from_str("0A81e8be41b21f651a71aaB1A85c6813b8bBcCf8").unwrap();
@@ -355,6 +1858,7 @@ mod test {
             chain_id,
             network: chain_id.try_into().unwrap(),
             min_block_timestamp: None,
+            gas_price: None,
         };
         let serialized = serde_json::to_string(&contract).unwrap();
         assert_eq!(
@@ -363,6 +1867,26 @@ mod test {
         );
     }
 
+    #[test]
+    fn estimated_fee_is_reported_next_to_the_transaction_id() {
+        let body = ActionResponseBody::BitcoinTransactionSent {
+            transaction_id: "2a593b84b1943521be01f97a59fc7feba30e7e8527fb2ba20b0158ca09016d02"
+                .parse()
+                .unwrap(),
+            network: Http(bitcoin::Network::Regtest),
+            fee_estimate: Some(FeeEstimate {
+                fee_per_wu: 11,
+                confirmation_target: 6,
+            }),
+        };
+
+        let serialized = serde_json::to_string(&body).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"type":"bitcoin-transaction-sent","payload":{"transaction_id":"2a593b84b1943521be01f97a59fc7feba30e7e8527fb2ba20b0158ca09016d02","network":"regtest","fee_estimate":{"fee_per_wu":11,"confirmation_target":6}}}"#
+        );
+    }
+
     #[test]
     fn bitcoin_send_amount_to_address_serializes_correctly_to_json() {
         let to = BitcoinAddress::from_str("2N3pk6v15FrDiRNKYVuxnnugn1Yg7wfQRL9").unwrap();
@@ -400,4 +1924,45 @@ mod test {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn litecoin_send_amount_to_address_serializes_correctly_to_json() {
+        let body = ActionResponseBody::from(litecoin::SendToAddress {
+            to: "MGv9cSYnaRSTZNzYaN7bhbgmozoGkKBvCn".parse().unwrap(),
+            amount: crate::litecoin::Amount::from_litoshi(100_000_000),
+            network: bitcoin::Network::Bitcoin,
+        });
+
+        let serialized = serde_json::to_string(&body).unwrap();
+
+        assert_eq!(
+            serialized,
+            r#"{"type":"litecoin-send-amount-to-address","payload":{"to":"MGv9cSYnaRSTZNzYaN7bhbgmozoGkKBvCn","amount":"100000000","network":"mainnet"}}"#
+        );
+    }
+
+    #[test]
+    fn replacement_has_to_pay_for_its_own_relay() {
+        let spend = |value| bitcoin::Transaction {
+            version: 2,
+            lock_time: 0,
+            input: vec![bitcoin::TxIn {
+                previous_output: bitcoin::OutPoint::null(),
+                script_sig: Default::default(),
+                sequence: 0xFFFF_FFFD,
+                witness: vec![vec![0; 72], vec![0; 33]],
+            }],
+            output: vec![bitcoin::TxOut {
+                value,
+                script_pubkey: Default::default(),
+            }],
+        };
+        let stuck = spend(99_000_000);
+        // One satoshi per vbyte of the replacement on top of the stuck fee.
+        let highest_value = 99_000_000 - (stuck.get_weight() as u64 + 3) / 4;
+
+        assert!(!pays_for_replacement(&stuck, &spend(99_000_000)));
+        assert!(!pays_for_replacement(&stuck, &spend(highest_value + 1)));
+        assert!(pays_for_replacement(&stuck, &spend(highest_value)));
+    }
 }
@@ -16,6 +16,7 @@ pub const PATH: &str = "swaps";
 
 use crate::{
     ethereum::{self, Erc20Token},
+    litecoin,
     network::DialInformation,
     swap_protocols::{
         ledger::{self, ethereum::ChainId},
@@ -242,6 +243,7 @@ impl<'de> Deserialize<'de> for DialInformation {
 pub enum HttpLedger {
     Bitcoin(ledger::Bitcoin),
     Ethereum(ledger::Ethereum),
+    Litecoin(ledger::Litecoin),
 }
 
 /// An enum describing all the possible values of `alpha_asset` and
@@ -255,6 +257,8 @@ pub enum HttpAsset {
     Bitcoin(bitcoin::Amount),
     Ether(ethereum::EtherQuantity),
     Erc20(ethereum::Erc20Token),
+    Erc721(ethereum::Erc721Token),
+    Litecoin(litecoin::Amount),
 }
 
 /// The actual enum that is used by serde to deserialize the `alpha_ledger` and
@@ -269,6 +273,7 @@ pub enum HttpAsset {
 pub enum HttpLedgerParams {
     Bitcoin(BitcoinLedgerParams),
     Ethereum(EthereumLedgerParams),
+    Litecoin(LitecoinLedgerParams),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -276,6 +281,11 @@ pub struct BitcoinLedgerParams {
     network: Http<bitcoin::Network>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LitecoinLedgerParams {
+    network: Http<bitcoin::Network>,
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
 pub struct EthereumLedgerParams {
     chain_id: Option<ChainId>,
@@ -295,6 +305,8 @@ pub enum HttpAssetParams {
     Bitcoin(BitcoinAssetParams),
     Ether(EtherAssetParams),
     Erc20(Erc20AssetParams),
+    Erc721(Erc721AssetParams),
+    Litecoin(LitecoinAssetParams),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -313,6 +325,17 @@ pub struct Erc20AssetParams {
     token_contract: ethereum::Address,
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Erc721AssetParams {
+    token_id: ethereum::Erc721TokenId,
+    token_contract: ethereum::Address,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LitecoinAssetParams {
+    quantity: litecoin::Amount,
+}
+
 impl TryFrom<HttpLedgerParams> for HttpLedger {
     type Error = anyhow::Error;
 
@@ -320,6 +343,7 @@ impl TryFrom<HttpLedgerParams> for HttpLedger {
         Ok(match params {
             HttpLedgerParams::Bitcoin(params) => HttpLedger::Bitcoin(params.into()),
             HttpLedgerParams::Ethereum(params) => HttpLedger::Ethereum(params.try_into()?),
+            HttpLedgerParams::Litecoin(params) => HttpLedger::Litecoin(params.into()),
         })
     }
 }
@@ -329,6 +353,7 @@ impl From<HttpLedger> for HttpLedgerParams {
         match ledger {
             HttpLedger::Bitcoin(ledger) => HttpLedgerParams::Bitcoin(ledger.into()),
             HttpLedger::Ethereum(ledger) => HttpLedgerParams::Ethereum(ledger.into()),
+            HttpLedger::Litecoin(ledger) => HttpLedgerParams::Litecoin(ledger.into()),
         }
     }
 }
@@ -349,6 +374,22 @@ impl From<ledger::Bitcoin> for BitcoinLedgerParams {
     }
 }
 
+impl From<LitecoinLedgerParams> for ledger::Litecoin {
+    fn from(params: LitecoinLedgerParams) -> Self {
+        Self {
+            network: *params.network,
+        }
+    }
+}
+
+impl From<ledger::Litecoin> for LitecoinLedgerParams {
+    fn from(litecoin: ledger::Litecoin) -> Self {
+        Self {
+            network: Http(litecoin.network),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, thiserror::Error)]
 #[error("The Ethereum ledger requires either a network or a chain-id parameter.")]
 pub struct InvalidEthereumLedgerParams;
@@ -393,6 +434,8 @@ impl From<HttpAssetParams> for HttpAsset {
             HttpAssetParams::Bitcoin(params) => HttpAsset::Bitcoin(params.into()),
             HttpAssetParams::Ether(params) => HttpAsset::Ether(params.into()),
             HttpAssetParams::Erc20(params) => HttpAsset::Erc20(params.into()),
+            HttpAssetParams::Erc721(params) => HttpAsset::Erc721(params.into()),
+            HttpAssetParams::Litecoin(params) => HttpAsset::Litecoin(params.into()),
         }
     }
 }
@@ -403,6 +446,8 @@ impl From<HttpAsset> for HttpAssetParams {
             HttpAsset::Bitcoin(asset) => HttpAssetParams::Bitcoin(asset.into()),
             HttpAsset::Ether(asset) => HttpAssetParams::Ether(asset.into()),
             HttpAsset::Erc20(asset) => HttpAssetParams::Erc20(asset.into()),
+            HttpAsset::Erc721(asset) => HttpAssetParams::Erc721(asset.into()),
+            HttpAsset::Litecoin(asset) => HttpAssetParams::Litecoin(asset.into()),
         }
     }
 }
@@ -451,6 +496,36 @@ impl From<ethereum::Erc20Token> for Erc20AssetParams {
     }
 }
 
+impl From<Erc721AssetParams> for ethereum::Erc721Token {
+    fn from(params: Erc721AssetParams) -> Self {
+        Self {
+            token_contract: params.token_contract,
+            token_id: params.token_id,
+        }
+    }
+}
+
+impl From<ethereum::Erc721Token> for Erc721AssetParams {
+    fn from(erc721: ethereum::Erc721Token) -> Self {
+        Self {
+            token_id: erc721.token_id,
+            token_contract: erc721.token_contract,
+        }
+    }
+}
+
+impl From<LitecoinAssetParams> for litecoin::Amount {
+    fn from(params: LitecoinAssetParams) -> Self {
+        params.quantity
+    }
+}
+
+impl From<litecoin::Amount> for LitecoinAssetParams {
+    fn from(litecoin: litecoin::Amount) -> Self {
+        Self { quantity: litecoin }
+    }
+}
+
 impl From<ledger::Bitcoin> for HttpLedger {
     fn from(bitcoin: ledger::Bitcoin) -> Self {
         HttpLedger::Bitcoin(bitcoin)
@@ -463,6 +538,12 @@ impl From<ledger::Ethereum> for HttpLedger {
     }
 }
 
+impl From<ledger::Litecoin> for HttpLedger {
+    fn from(litecoin: ledger::Litecoin) -> Self {
+        HttpLedger::Litecoin(litecoin)
+    }
+}
+
 impl From<bitcoin::Amount> for HttpAsset {
     fn from(bitcoin: bitcoin::Amount) -> Self {
         HttpAsset::Bitcoin(bitcoin)
@@ -481,13 +562,27 @@ impl From<ethereum::Erc20Token> for HttpAsset {
     }
 }
 
+impl From<ethereum::Erc721Token> for HttpAsset {
+    fn from(erc721: ethereum::Erc721Token) -> Self {
+        HttpAsset::Erc721(erc721)
+    }
+}
+
+impl From<litecoin::Amount> for HttpAsset {
+    fn from(litecoin: litecoin::Amount) -> Self {
+        HttpAsset::Litecoin(litecoin)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        ethereum::{Erc20Quantity, Erc20Token, EtherQuantity, H160, H256, U256},
+        ethereum::{
+            Erc20Quantity, Erc20Token, Erc721Token, Erc721TokenId, EtherQuantity, H160, H256, U256,
+        },
         http_api::{Http, HttpAsset, HttpLedger},
         swap_protocols::{
-            ledger::{ethereum, Bitcoin, Ethereum},
+            ledger::{ethereum, Bitcoin, Ethereum, Litecoin},
             HashFunction, SwapId, SwapProtocol,
         },
     };
@@ -506,10 +601,17 @@ mod tests {
             "B97048628DB6B661D4C2aA833e95Dbe1A905B280".parse().unwrap(),
             Erc20Quantity(U256::from(100_000_000_000u64)),
         ));
+        let nft = HttpAsset::from(Erc721Token::new(
+            "B97048628DB6B661D4C2aA833e95Dbe1A905B280".parse().unwrap(),
+            Erc721TokenId(U256::from(42)),
+        ));
+        let litecoin = HttpAsset::from(crate::litecoin::Amount::from_litoshi(100_000_000));
 
         let bitcoin_serialized = serde_json::to_string(&bitcoin).unwrap();
         let ether_serialized = serde_json::to_string(&ether).unwrap();
         let pay_serialized = serde_json::to_string(&pay).unwrap();
+        let nft_serialized = serde_json::to_string(&nft).unwrap();
+        let litecoin_serialized = serde_json::to_string(&litecoin).unwrap();
 
         assert_eq!(
             &bitcoin_serialized,
@@ -523,6 +625,14 @@ mod tests {
             &pay_serialized,
             r#"{"name":"erc20","quantity":"100000000000","token_contract":"0xb97048628db6b661d4c2aa833e95dbe1a905b280"}"#
         );
+        assert_eq!(
+            &nft_serialized,
+            r#"{"name":"erc721","token_id":"42","token_contract":"0xb97048628db6b661d4c2aa833e95dbe1a905b280"}"#
+        );
+        assert_eq!(
+            &litecoin_serialized,
+            r#"{"name":"litecoin","quantity":"100000000"}"#
+        );
     }
 
     #[test]
@@ -548,6 +658,29 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn litecoin_http_ledger_serializes_correctly_to_json() {
+        let input = &[
+            HttpLedger::from(Litecoin::new(bitcoin::Network::Bitcoin)),
+            HttpLedger::from(Litecoin::new(bitcoin::Network::Testnet)),
+            HttpLedger::from(Litecoin::new(bitcoin::Network::Regtest)),
+        ];
+
+        let expected = &[
+            r#"{"name":"litecoin","network":"mainnet"}"#,
+            r#"{"name":"litecoin","network":"testnet"}"#,
+            r#"{"name":"litecoin","network":"regtest"}"#,
+        ];
+
+        let actual = input
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<String>, serde_json::Error>>()
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn ethereum_http_ledger_regtest_serializes_correctly_to_json() {
         let input = &[
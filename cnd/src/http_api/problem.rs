@@ -1,7 +1,9 @@
 use crate::{
+    btsieve::ethereum::UnsupportedEthereumChain,
     db,
     http_api::routes::rfc003::handlers::{
-        post_swap::UnsupportedSwap, InvalidAction, InvalidActionInvocation,
+        post_swap::{UnsupportedHashFunction, UnsupportedSwap},
+        InvalidAction, InvalidActionInvocation,
     },
 };
 use http_api_problem::HttpApiProblem;
@@ -100,6 +102,22 @@ pub fn from_anyhow(e: anyhow::Error) -> HttpApiProblem {
             .set_detail("The requested combination of ledgers and assets is not supported.");
     }
 
+    if e.is::<UnsupportedEthereumChain>() {
+        log::warn!("{:?}", e);
+
+        return HttpApiProblem::new("Ethereum chain not supported.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail(e.to_string());
+    }
+
+    if e.is::<UnsupportedHashFunction>() {
+        log::warn!("{:?}", e);
+
+        return HttpApiProblem::new("Hash function not supported.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail("The HTLCs of the requested ledgers cannot use this hash function.");
+    }
+
     log::error!("internal error occurred: {:#}", e);
 
     HttpApiProblem::with_title_and_type_from_status(StatusCode::INTERNAL_SERVER_ERROR)
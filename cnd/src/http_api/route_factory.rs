@@ -1,16 +1,24 @@
 use crate::{
+    btsieve::{
+        bitcoin::{BitcoinFeeEstimator, BitcoinLedgerConnector},
+        ethereum::{EthereumChains, EthereumGasEstimator},
+    },
     config::settings::AllowedOrigins,
-    db::{DetermineTypes, Retrieve, Saver},
-    ethereum::{Erc20Token, EtherQuantity},
-    http_api,
+    db::{DetermineTypes, LoadSentTransactions, Retrieve, Save, Saver, SentTransaction},
+    ethereum::{Erc20Token, Erc721Token, EtherQuantity},
+    http_api, litecoin,
     network::Network,
     seed::SwapSeed,
     swap_protocols::{
         self,
-        ledger::{Bitcoin, Ethereum},
-        rfc003::{events::HtlcEvents, state_store::StateStore},
+        ledger::{Bitcoin, Ethereum, Litecoin},
+        rfc003::{
+            events::{HtlcEvents, TransactionConfirmations},
+            state_store::StateStore,
+        },
         SwapId,
     },
+    wallet::{bitcoin::BitcoinWallet, ethereum::EthereumAccount, psbt::BitcoinWatchOnlyWallet},
 };
 use bitcoin::Amount;
 use libp2p::PeerId;
@@ -38,6 +46,20 @@ pub fn create<
         + HtlcEvents<Bitcoin, Amount>
         + HtlcEvents<Ethereum, EtherQuantity>
         + HtlcEvents<Ethereum, Erc20Token>
+        + HtlcEvents<Ethereum, Erc721Token>
+        + HtlcEvents<Litecoin, litecoin::Amount>
+        + TransactionConfirmations<Bitcoin>
+        + TransactionConfirmations<Ethereum>
+        + TransactionConfirmations<Litecoin>
+        + BitcoinWallet
+        + EthereumAccount
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + BitcoinWatchOnlyWallet
+        + EthereumGasEstimator
+        + EthereumChains
+        + LoadSentTransactions
+        + Save<SentTransaction>
         + Saver,
 >(
     peer_id: PeerId,
@@ -91,16 +113,67 @@ pub fn create<
         >())
         .and(warp::path::end())
         .and(warp::query::<http_api::action::ActionExecutionParameters>())
+        .and(warp::query::<http_api::action::ExecuteAction>())
         .and(dependencies.clone())
         .and(warp::body::json().or(empty_json_body).unify())
         .and_then(http_api::routes::rfc003::action);
 
+    let rfc003_batch_spend = warp::post2()
+        .and(rfc003)
+        .and(warp::path("batch_spend"))
+        .and(warp::path::end())
+        .and(warp::query::<http_api::action::ExecuteAction>())
+        .and(warp::body::json())
+        .and(dependencies.clone())
+        .and_then(http_api::routes::rfc003::batch_spend);
+
+    let rfc003_bump_fee = warp::post2()
+        .and(rfc003)
+        .and(warp::path::param::<SwapId>())
+        .and(warp::path("bump_fee"))
+        .and(warp::path::end())
+        .and(warp::query::<http_api::action::BumpFeeParameters>())
+        .and(dependencies.clone())
+        .and_then(http_api::routes::rfc003::bump_fee);
+
+    let rfc003_broadcast_psbt = warp::post2()
+        .and(rfc003)
+        .and(warp::path::param::<SwapId>())
+        .and(warp::path("fund"))
+        .and(warp::path("psbt"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(dependencies.clone())
+        .and_then(http_api::routes::rfc003::broadcast_psbt);
+
     let get_peers = warp::get2()
         .and(warp::path("peers"))
         .and(warp::path::end())
         .and(dependencies.clone())
         .and_then(http_api::routes::peers::get_peers);
 
+    let get_bitcoin_wallet = warp::get2()
+        .and(warp::path("wallet"))
+        .and(warp::path("bitcoin"))
+        .and(warp::path::end())
+        .and(dependencies.clone())
+        .and_then(http_api::routes::wallet::get_bitcoin_wallet);
+
+    let get_bitcoin_wallet_address = warp::get2()
+        .and(warp::path("wallet"))
+        .and(warp::path("bitcoin"))
+        .and(warp::path("address"))
+        .and(warp::path::end())
+        .and(dependencies.clone())
+        .and_then(http_api::routes::wallet::get_bitcoin_wallet_address);
+
+    let get_ethereum_account = warp::get2()
+        .and(warp::path("wallet"))
+        .and(warp::path("ethereum"))
+        .and(warp::path::end())
+        .and(dependencies.clone())
+        .and_then(http_api::routes::wallet::get_ethereum_account);
+
     let get_info_siren = warp::get2()
         .and(warp::path::end())
         .and(warp::header::exact("accept", "application/vnd.siren+json"))
@@ -117,9 +190,15 @@ pub fn create<
     preflight_cors_route
         .or(rfc003_get_swap)
         .or(rfc003_post_swap)
+        .or(rfc003_batch_spend)
+        .or(rfc003_bump_fee)
+        .or(rfc003_broadcast_psbt)
         .or(rfc003_action)
         .or(get_swaps)
         .or(get_peers)
+        .or(get_bitcoin_wallet)
+        .or(get_bitcoin_wallet_address)
+        .or(get_ethereum_account)
         .or(get_info_siren)
         .or(get_info)
         .recover(http_api::unpack_problem)
@@ -1,10 +1,20 @@
 use crate::{
     db::{DetermineTypes, Retrieve},
     http_api::swap_resource::{build_rfc003_siren_entity, IncludeState},
-    swap_protocols::rfc003::state_store::StateStore,
+    swap_protocols::{
+        ledger::{Bitcoin, Ethereum, Litecoin},
+        rfc003::{events::TransactionConfirmations, state_store::StateStore},
+    },
 };
 
-pub async fn handle_get_swaps<D: DetermineTypes + Retrieve + StateStore>(
+pub async fn handle_get_swaps<
+    D: DetermineTypes
+        + Retrieve
+        + StateStore
+        + TransactionConfirmations<Bitcoin>
+        + TransactionConfirmations<Ethereum>
+        + TransactionConfirmations<Litecoin>,
+>(
     dependencies: D,
 ) -> anyhow::Result<siren::Entity> {
     let mut entity = siren::Entity::default().with_class_member("swaps");
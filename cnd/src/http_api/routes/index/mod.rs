@@ -5,7 +5,10 @@ use crate::{
     db::{DetermineTypes, Retrieve},
     http_api::{problem, routes::into_rejection, Http},
     network::Network,
-    swap_protocols::rfc003::state_store::StateStore,
+    swap_protocols::{
+        ledger::{Bitcoin, Ethereum, Litecoin},
+        rfc003::{events::TransactionConfirmations, state_store::StateStore},
+    },
 };
 use futures::Future;
 use futures_core::future::{FutureExt, TryFutureExt};
@@ -55,7 +58,14 @@ pub fn get_info_siren<D: Network>(id: PeerId, dependencies: D) -> Result<impl Re
 }
 
 #[allow(clippy::needless_pass_by_value)]
-pub fn get_swaps<D: DetermineTypes + Retrieve + StateStore>(
+pub fn get_swaps<
+    D: DetermineTypes
+        + Retrieve
+        + StateStore
+        + TransactionConfirmations<Bitcoin>
+        + TransactionConfirmations<Ethereum>
+        + TransactionConfirmations<Litecoin>,
+>(
     dependencies: D,
 ) -> impl Future<Item = impl Reply, Error = Rejection> {
     handle_get_swaps(dependencies)
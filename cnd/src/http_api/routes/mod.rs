@@ -4,6 +4,7 @@ use warp::Rejection;
 pub mod index;
 pub mod peers;
 pub mod rfc003;
+pub mod wallet;
 
 pub fn into_rejection(problem: HttpApiProblem) -> Rejection {
     warp::reject::custom(problem)
@@ -1,7 +1,7 @@
 use crate::{
     http_api::action::ListRequiredFields,
     swap_protocols::{
-        ledger::{Bitcoin, Ethereum},
+        ledger::{Bitcoin, Ethereum, Litecoin},
         rfc003::{
             actions::Accept,
             messages::{self, IntoAcceptMessage},
@@ -47,6 +47,36 @@ impl IntoAcceptMessage<Ethereum, Bitcoin> for OnlyRedeem<Ethereum> {
     }
 }
 
+impl ListRequiredFields for Accept<Ethereum, Litecoin> {
+    fn list_required_fields() -> Vec<siren::Field> {
+        vec![siren::Field {
+            name: "alpha_ledger_redeem_identity".to_owned(),
+            class: vec!["ethereum".to_owned(), "address".to_owned()],
+            _type: Some("text".to_owned()),
+            value: None,
+            title: Some("Alpha ledger redeem identity".to_owned()),
+        }]
+    }
+}
+
+impl IntoAcceptMessage<Ethereum, Litecoin> for OnlyRedeem<Ethereum> {
+    fn into_accept_message(
+        self,
+        id: SwapId,
+        secret_source: &dyn SecretSource,
+    ) -> messages::Accept<Ethereum, Litecoin> {
+        let beta_ledger_refund_identity = crate::bitcoin::PublicKey::from_secret_key(
+            &*crate::SECP,
+            &secret_source.secp256k1_refund(),
+        );
+        messages::Accept {
+            swap_id: id,
+            alpha_ledger_redeem_identity: self.alpha_ledger_redeem_identity,
+            beta_ledger_refund_identity,
+        }
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct OnlyRefund<L: Ledger> {
     pub beta_ledger_refund_identity: L::Identity,
@@ -81,3 +111,74 @@ impl IntoAcceptMessage<Bitcoin, Ethereum> for OnlyRefund<Ethereum> {
         }
     }
 }
+
+impl ListRequiredFields for Accept<Litecoin, Ethereum> {
+    fn list_required_fields() -> Vec<siren::Field> {
+        vec![siren::Field {
+            name: "beta_ledger_refund_identity".to_owned(),
+            class: vec!["ethereum".to_owned(), "address".to_owned()],
+            _type: Some("text".to_owned()),
+            value: None,
+            title: Some("Beta ledger refund identity".to_owned()),
+        }]
+    }
+}
+
+impl IntoAcceptMessage<Litecoin, Ethereum> for OnlyRefund<Ethereum> {
+    fn into_accept_message(
+        self,
+        id: SwapId,
+        secret_source: &dyn SecretSource,
+    ) -> messages::Accept<Litecoin, Ethereum> {
+        let alpha_ledger_redeem_identity = crate::bitcoin::PublicKey::from_secret_key(
+            &*crate::SECP,
+            &secret_source.secp256k1_redeem(),
+        );
+        messages::Accept {
+            swap_id: id,
+            beta_ledger_refund_identity: self.beta_ledger_refund_identity,
+            alpha_ledger_redeem_identity,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct RedeemAndRefund<AL: Ledger, BL: Ledger> {
+    pub alpha_ledger_redeem_identity: AL::Identity,
+    pub beta_ledger_refund_identity: BL::Identity,
+}
+
+impl ListRequiredFields for Accept<Ethereum, Ethereum> {
+    fn list_required_fields() -> Vec<siren::Field> {
+        vec![
+            siren::Field {
+                name: "alpha_ledger_redeem_identity".to_owned(),
+                class: vec!["ethereum".to_owned(), "address".to_owned()],
+                _type: Some("text".to_owned()),
+                value: None,
+                title: Some("Alpha ledger redeem identity".to_owned()),
+            },
+            siren::Field {
+                name: "beta_ledger_refund_identity".to_owned(),
+                class: vec!["ethereum".to_owned(), "address".to_owned()],
+                _type: Some("text".to_owned()),
+                value: None,
+                title: Some("Beta ledger refund identity".to_owned()),
+            },
+        ]
+    }
+}
+
+impl IntoAcceptMessage<Ethereum, Ethereum> for RedeemAndRefund<Ethereum, Ethereum> {
+    fn into_accept_message(
+        self,
+        id: SwapId,
+        _: &dyn SecretSource,
+    ) -> messages::Accept<Ethereum, Ethereum> {
+        messages::Accept {
+            swap_id: id,
+            alpha_ledger_redeem_identity: self.alpha_ledger_redeem_identity,
+            beta_ledger_refund_identity: self.beta_ledger_refund_identity,
+        }
+    }
+}
@@ -1,33 +1,44 @@
 use crate::{
-    db::{DetermineTypes, Save, Saver},
-    ethereum::{Erc20Token, EtherQuantity},
+    btsieve::{
+        bitcoin::{BitcoinFeeEstimator, BitcoinLedgerConnector, FeeEstimator},
+        ethereum::{EthereumGasEstimator, GasEstimator},
+    },
+    db::{DetermineTypes, Save, Saver, SentTransaction},
+    ethereum::{Erc20Token, Erc721Token, EtherQuantity},
     http_api::{
         action::{
-            ActionExecutionParameters, ActionResponseBody, IntoResponsePayload, ListRequiredFields,
-            ToSirenAction,
+            execute_action, ActionExecutionParameters, ActionResponseBody, IntoResponsePayload,
+            ListRequiredFields, ToSirenAction,
         },
         route_factory::new_action_link,
         routes::rfc003::decline::{to_swap_decline_reason, DeclineBody},
     },
     libp2p_comit_ext::ToHeader,
+    litecoin,
     network::Network,
     seed::SwapSeed,
     swap_protocols::{
         self,
         actions::Actions,
-        ledger::{Bitcoin, Ethereum},
+        ledger::{Bitcoin, Ethereum, Litecoin},
         rfc003::{
             self,
             actions::{Action, ActionKind},
             bob::State,
-            events::HtlcEvents,
+            events::{HtlcEvents, TransactionConfirmations},
             messages::{Decision, IntoAcceptMessage},
             state_store::StateStore,
         },
         SwapId,
     },
+    wallet::{
+        bitcoin::{BitcoinWallet, Wallet},
+        ethereum::{Account, EthereumAccount},
+        psbt::{BitcoinWatchOnlyWallet, WatchOnlyWallet},
+    },
 };
 use anyhow::Context;
+use async_trait::async_trait;
 use bitcoin::Amount;
 use libp2p_comit::frame::Response;
 use std::fmt::Debug;
@@ -44,6 +55,18 @@ pub async fn handle_action<
         + HtlcEvents<Bitcoin, Amount>
         + HtlcEvents<Ethereum, EtherQuantity>
         + HtlcEvents<Ethereum, Erc20Token>
+        + HtlcEvents<Ethereum, Erc721Token>
+        + HtlcEvents<Litecoin, litecoin::Amount>
+        + TransactionConfirmations<Bitcoin>
+        + TransactionConfirmations<Ethereum>
+        + TransactionConfirmations<Litecoin>
+        + BitcoinWallet
+        + EthereumAccount
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + BitcoinWatchOnlyWallet
+        + EthereumGasEstimator
+        + Save<SentTransaction>
         + Executor
         + Clone,
 >(
@@ -52,6 +75,7 @@ pub async fn handle_action<
     action_kind: ActionKind,
     body: serde_json::Value,
     query_params: ActionExecutionParameters,
+    execute: bool,
     dependencies: D,
 ) -> anyhow::Result<ActionResponseBody> {
     let types = dependencies.determine_types(&swap_id).await?;
@@ -65,7 +89,7 @@ pub async fn handle_action<
         let action = state
             .actions()
             .into_iter()
-            .select_action(action_kind, method)?;
+            .select_action(action_kind, method, execute)?;
 
         match action {
             Action::Accept(_) => {
@@ -130,10 +154,35 @@ pub async fn handle_action<
 
                 Ok(ActionResponseBody::None)
             }
-            Action::Deploy(action) => action.into_response_payload(query_params),
-            Action::Fund(action) => action.into_response_payload(query_params),
-            Action::Redeem(action) => action.into_response_payload(query_params),
-            Action::Refund(action) => action.into_response_payload(query_params),
+            action @ Action::Deploy(_)
+            | action @ Action::Fund(_)
+            | action @ Action::Redeem(_)
+            | action @ Action::Refund(_) => {
+                if !execute {
+                    return action
+                        .into_response_payload(
+                            query_params,
+                            dependencies.bitcoin_wallet(),
+                            dependencies.ethereum_account(),
+                            dependencies.bitcoin_fee_estimator(),
+                            dependencies.bitcoin_watch_only_wallet(),
+                            dependencies.ethereum_gas_estimator(),
+                        )
+                        .await;
+                }
+
+                let (body, transaction_id) =
+                    execute_action(&dependencies, swap_id, action_kind, action, query_params)
+                        .await?;
+                log::info!(
+                    "Executed {} action of swap {} in transaction {}",
+                    action_kind,
+                    swap_id,
+                    transaction_id
+                );
+
+                Ok(body)
+            }
         }
     })
 }
@@ -158,6 +207,7 @@ trait SelectAction<Accept, Decline, Deploy, Fund, Redeem, Refund>:
         mut self,
         action_kind: ActionKind,
         method: http::Method,
+        execute: bool,
     ) -> anyhow::Result<Self::Item>
     where
         Self: Sized,
@@ -166,7 +216,15 @@ trait SelectAction<Accept, Decline, Deploy, Fund, Redeem, Refund>:
             .find(|action| ActionKind::from(action) == action_kind)
             .ok_or_else(|| anyhow::Error::from(InvalidAction { action_kind }))?;
 
-        if http::Method::from(action_kind) != method {
+        // Executing an action changes the state of a ledger, hence the client
+        // has to use POST for it.
+        let expected_method = if execute {
+            http::Method::POST
+        } else {
+            http::Method::from(action_kind)
+        };
+
+        if expected_method != method {
             return Err(anyhow::Error::from(InvalidActionInvocation {
                 action_kind,
                 method,
@@ -233,9 +291,10 @@ mod tests {
     fn action_not_available_should_return_409_conflict() {
         let given_actions = actions();
 
-        let result = given_actions
-            .into_iter()
-            .select_action(ActionKind::Accept, http::Method::POST);
+        let result =
+            given_actions
+                .into_iter()
+                .select_action(ActionKind::Accept, http::Method::POST, false);
 
         assert_that(&result)
             .is_inner_err::<InvalidAction>()
@@ -249,16 +308,18 @@ mod tests {
         let mut given_actions = actions();
         given_actions.extend(vec![Action::Accept(()), Action::Decline(())]);
 
-        let result = given_actions
-            .clone()
-            .into_iter()
-            .select_action(ActionKind::Accept, http::Method::POST);
+        let result = given_actions.clone().into_iter().select_action(
+            ActionKind::Accept,
+            http::Method::POST,
+            false,
+        );
 
         assert_that(&result).is_ok_containing(Action::Accept(()));
 
-        let result = given_actions
-            .into_iter()
-            .select_action(ActionKind::Decline, http::Method::POST);
+        let result =
+            given_actions
+                .into_iter()
+                .select_action(ActionKind::Decline, http::Method::POST, false);
 
         assert_that(&result).is_ok_containing(Action::Decline(()));
     }
@@ -268,10 +329,11 @@ mod tests {
         let mut given_actions = actions();
         given_actions.extend(vec![Action::Accept(()), Action::Decline(())]);
 
-        let result = given_actions
-            .clone()
-            .into_iter()
-            .select_action(ActionKind::Accept, http::Method::GET);
+        let result = given_actions.clone().into_iter().select_action(
+            ActionKind::Accept,
+            http::Method::GET,
+            false,
+        );
 
         assert_that(&result)
             .is_inner_err::<InvalidActionInvocation>()
@@ -280,9 +342,10 @@ mod tests {
                 method: http::Method::GET,
             });
 
-        let result = given_actions
-            .into_iter()
-            .select_action(ActionKind::Decline, http::Method::GET);
+        let result =
+            given_actions
+                .into_iter()
+                .select_action(ActionKind::Decline, http::Method::GET, false);
 
         assert_that(&result)
             .is_inner_err::<InvalidActionInvocation>()
@@ -302,10 +365,11 @@ mod tests {
             Action::Redeem(()),
         ]);
 
-        let result = given_actions
-            .clone()
-            .into_iter()
-            .select_action(ActionKind::Deploy, http::Method::POST);
+        let result = given_actions.clone().into_iter().select_action(
+            ActionKind::Deploy,
+            http::Method::POST,
+            false,
+        );
 
         assert_that(&result)
             .is_inner_err::<InvalidActionInvocation>()
@@ -314,10 +378,11 @@ mod tests {
                 method: http::Method::POST,
             });
 
-        let result = given_actions
-            .clone()
-            .into_iter()
-            .select_action(ActionKind::Fund, http::Method::POST);
+        let result = given_actions.clone().into_iter().select_action(
+            ActionKind::Fund,
+            http::Method::POST,
+            false,
+        );
 
         assert_that(&result)
             .is_inner_err::<InvalidActionInvocation>()
@@ -326,10 +391,11 @@ mod tests {
                 method: http::Method::POST,
             });
 
-        let result = given_actions
-            .clone()
-            .into_iter()
-            .select_action(ActionKind::Refund, http::Method::POST);
+        let result = given_actions.clone().into_iter().select_action(
+            ActionKind::Refund,
+            http::Method::POST,
+            false,
+        );
 
         assert_that(&result)
             .is_inner_err::<InvalidActionInvocation>()
@@ -338,9 +404,10 @@ mod tests {
                 method: http::Method::POST,
             });
 
-        let result = given_actions
-            .into_iter()
-            .select_action(ActionKind::Redeem, http::Method::POST);
+        let result =
+            given_actions
+                .into_iter()
+                .select_action(ActionKind::Redeem, http::Method::POST, false);
 
         assert_that(&result)
             .is_inner_err::<InvalidActionInvocation>()
@@ -349,6 +416,37 @@ mod tests {
                 method: http::Method::POST,
             });
     }
+
+    #[test]
+    fn executed_deploy_fund_refund_redeem_action_can_only_be_invoked_with_http_post() {
+        let mut given_actions = actions();
+        given_actions.extend(vec![
+            Action::Deploy(()),
+            Action::Fund(()),
+            Action::Refund(()),
+            Action::Redeem(()),
+        ]);
+
+        let result = given_actions.clone().into_iter().select_action(
+            ActionKind::Fund,
+            http::Method::POST,
+            true,
+        );
+
+        assert_that(&result).is_ok_containing(Action::Fund(()));
+
+        let result =
+            given_actions
+                .into_iter()
+                .select_action(ActionKind::Redeem, http::Method::GET, true);
+
+        assert_that(&result)
+            .is_inner_err::<InvalidActionInvocation>()
+            .is_equal_to(&InvalidActionInvocation {
+                action_kind: ActionKind::Redeem,
+                method: http::Method::GET,
+            });
+    }
 }
 
 impl From<ActionKind> for http::Method {
@@ -364,23 +462,75 @@ impl From<ActionKind> for http::Method {
     }
 }
 
+#[async_trait]
 impl<Accept, Decline, Deploy, Fund, Redeem, Refund> IntoResponsePayload
     for Action<Accept, Decline, Deploy, Fund, Redeem, Refund>
 where
-    Deploy: IntoResponsePayload,
-    Fund: IntoResponsePayload,
-    Redeem: IntoResponsePayload,
-    Refund: IntoResponsePayload,
+    Accept: Send,
+    Decline: Send,
+    Deploy: IntoResponsePayload + Send,
+    Fund: IntoResponsePayload + Send,
+    Redeem: IntoResponsePayload + Send,
+    Refund: IntoResponsePayload + Send,
 {
-    fn into_response_payload(
+    async fn into_response_payload(
         self,
         query_params: ActionExecutionParameters,
+        bitcoin_wallet: &Wallet,
+        ethereum_account: &Account,
+        bitcoin_fee_estimator: &FeeEstimator,
+        bitcoin_watch_only_wallet: &WatchOnlyWallet,
+        ethereum_gas_estimator: &GasEstimator,
     ) -> anyhow::Result<ActionResponseBody> {
         match self {
-            Action::Deploy(payload) => payload.into_response_payload(query_params),
-            Action::Fund(payload) => payload.into_response_payload(query_params),
-            Action::Redeem(payload) => payload.into_response_payload(query_params),
-            Action::Refund(payload) => payload.into_response_payload(query_params),
+            Action::Deploy(payload) => {
+                payload
+                    .into_response_payload(
+                        query_params,
+                        bitcoin_wallet,
+                        ethereum_account,
+                        bitcoin_fee_estimator,
+                        bitcoin_watch_only_wallet,
+                        ethereum_gas_estimator,
+                    )
+                    .await
+            }
+            Action::Fund(payload) => {
+                payload
+                    .into_response_payload(
+                        query_params,
+                        bitcoin_wallet,
+                        ethereum_account,
+                        bitcoin_fee_estimator,
+                        bitcoin_watch_only_wallet,
+                        ethereum_gas_estimator,
+                    )
+                    .await
+            }
+            Action::Redeem(payload) => {
+                payload
+                    .into_response_payload(
+                        query_params,
+                        bitcoin_wallet,
+                        ethereum_account,
+                        bitcoin_fee_estimator,
+                        bitcoin_watch_only_wallet,
+                        ethereum_gas_estimator,
+                    )
+                    .await
+            }
+            Action::Refund(payload) => {
+                payload
+                    .into_response_payload(
+                        query_params,
+                        bitcoin_wallet,
+                        ethereum_account,
+                        bitcoin_fee_estimator,
+                        bitcoin_watch_only_wallet,
+                        ethereum_gas_estimator,
+                    )
+                    .await
+            }
             Action::Accept(_) | Action::Decline(_) => Err(anyhow::anyhow!(
                 "IntoResponsePayload is not available for Accept/Decline"
             )),
@@ -0,0 +1,129 @@
+use crate::{
+    btsieve::bitcoin::{BitcoinFeeEstimator, BitcoinLedgerConnector},
+    db::{DetermineTypes, Save, SentTransaction},
+    http_api::action::{batch_spend, ActionResponseBody, BatchSpendBody, IntoSpendOutput},
+    swap_protocols::{
+        actions::Actions,
+        rfc003::{
+            actions::{Action, ActionKind},
+            state_store::StateStore,
+        },
+    },
+    wallet::bitcoin::BitcoinWallet,
+};
+use http_api_problem::HttpApiProblem;
+use warp::http::StatusCode;
+
+#[allow(clippy::cognitive_complexity)]
+pub async fn handle_batch_spend<D>(
+    body: BatchSpendBody,
+    execute: bool,
+    dependencies: D,
+) -> anyhow::Result<ActionResponseBody>
+where
+    D: StateStore
+        + DetermineTypes
+        + BitcoinWallet
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + Save<SentTransaction>,
+{
+    let BatchSpendBody {
+        swap_ids,
+        address,
+        fee_per_wu,
+    } = body;
+
+    let mut spends = Vec::new();
+    for swap_id in swap_ids {
+        // Spending the same HTLC twice makes the transaction invalid.
+        if spends.iter().any(|(spent, ..)| *spent == swap_id) {
+            continue;
+        }
+
+        let types = dependencies.determine_types(&swap_id).await?;
+
+        let spend = with_swap_types!(types, {
+            let state = StateStore::get::<ROLE>(&dependencies, &swap_id)?.ok_or_else(|| {
+                anyhow::anyhow!("state store did not contain an entry for {}", swap_id)
+            })?;
+
+            state.actions().into_iter().find_map(|action| match action {
+                Action::Redeem(redeem) => redeem
+                    .into_spend_output()
+                    .map(|spend_output| (ActionKind::Redeem, spend_output)),
+                Action::Refund(refund) => refund
+                    .into_spend_output()
+                    .map(|spend_output| (ActionKind::Refund, spend_output)),
+                _ => None,
+            })
+        });
+        let (action_kind, spend_output) = spend.ok_or_else(|| {
+            HttpApiProblem::new("Nothing to spend.")
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail(format!(
+                    "Swap {} has no Bitcoin HTLC to redeem or refund right now.",
+                    swap_id
+                ))
+        })?;
+
+        spends.push((swap_id, action_kind, spend_output));
+    }
+
+    let count = spends.len();
+    let body = batch_spend(&dependencies, spends, address, fee_per_wu, execute).await?;
+    if execute {
+        log::info!("Spent the HTLCs of {} swaps in one transaction", count);
+    }
+
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        swap_fixture::{serve_esplora, Dependencies},
+        swap_protocols::Role,
+    };
+    use std::sync::{Arc, Mutex};
+
+    fn dependencies(runtime: &tokio::runtime::Runtime) -> Dependencies {
+        let broadcast = Arc::new(Mutex::new(Vec::new()));
+
+        Dependencies::new(runtime, serve_esplora(broadcast), Role::Alice)
+    }
+
+    #[test]
+    fn given_address_of_the_network_of_the_htlcs_batch_spend_signs() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let dependencies = dependencies(&runtime);
+        let body = BatchSpendBody {
+            swap_ids: vec![dependencies.swap_id],
+            address: Some(dependencies.bitcoin_wallet.receive_address().to_string()),
+            fee_per_wu: Some("10".to_owned()),
+        };
+
+        let response = async_std::task::block_on(handle_batch_spend(body, false, dependencies));
+
+        assert!(response.is_ok());
+    }
+
+    #[test]
+    fn given_address_of_another_network_batch_spend_is_rejected() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let dependencies = dependencies(&runtime);
+        let body = BatchSpendBody {
+            swap_ids: vec![dependencies.swap_id],
+            address: Some("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_owned()),
+            fee_per_wu: Some("10".to_owned()),
+        };
+
+        let error = async_std::task::block_on(handle_batch_spend(body, false, dependencies))
+            .unwrap_err()
+            .downcast::<HttpApiProblem>()
+            .unwrap();
+
+        assert_eq!(error.status, Some(StatusCode::BAD_REQUEST));
+    }
+}
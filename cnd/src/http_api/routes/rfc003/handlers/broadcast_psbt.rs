@@ -0,0 +1,47 @@
+use crate::{
+    btsieve::bitcoin::BitcoinLedgerConnector,
+    db::{DetermineTypes, Save, SentTransaction},
+    http_api::action::{broadcast_psbt, ActionResponseBody, PsbtBody},
+    swap_protocols::{
+        actions::Actions,
+        rfc003::{actions::Action, state_store::StateStore},
+        SwapId,
+    },
+};
+use http_api_problem::HttpApiProblem;
+use warp::http::StatusCode;
+
+pub async fn handle_broadcast_psbt<D>(
+    swap_id: SwapId,
+    body: PsbtBody,
+    dependencies: D,
+) -> anyhow::Result<ActionResponseBody>
+where
+    D: StateStore + DetermineTypes + BitcoinLedgerConnector + Save<SentTransaction>,
+{
+    let types = dependencies.determine_types(&swap_id).await?;
+
+    with_swap_types!(types, {
+        let state = StateStore::get::<ROLE>(&dependencies, &swap_id)?.ok_or_else(|| {
+            anyhow::anyhow!("state store did not contain an entry for {}", swap_id)
+        })?;
+
+        let fund = state
+            .actions()
+            .into_iter()
+            .find_map(|action| match action {
+                Action::Fund(fund) => Some(fund),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                HttpApiProblem::new("Nothing to fund.")
+                    .set_status(StatusCode::BAD_REQUEST)
+                    .set_detail("The HTLC of this swap cannot be funded right now.")
+            })?;
+
+        let body = broadcast_psbt(&dependencies, swap_id, fund, &body.psbt).await?;
+        log::info!("Funded the HTLC of swap {} through a PSBT", swap_id);
+
+        Ok(body)
+    })
+}
@@ -0,0 +1,100 @@
+use crate::{
+    btsieve::bitcoin::{BitcoinFeeEstimator, BitcoinLedgerConnector},
+    db::{DetermineTypes, LoadSentTransactions, Save, SentTransaction},
+    http_api::action::{ActionResponseBody, BumpFee, BumpFeeParameters},
+    swap_protocols::{
+        actions::Actions,
+        rfc003::{
+            actions::{Action, ActionKind},
+            state_store::StateStore,
+        },
+        SwapId,
+    },
+    wallet::bitcoin::BitcoinWallet,
+};
+use http_api_problem::HttpApiProblem;
+use warp::http::StatusCode;
+
+#[allow(clippy::cognitive_complexity)]
+pub async fn handle_bump_fee<D>(
+    swap_id: SwapId,
+    parameters: BumpFeeParameters,
+    dependencies: D,
+) -> anyhow::Result<ActionResponseBody>
+where
+    D: StateStore
+        + DetermineTypes
+        + LoadSentTransactions
+        + BitcoinWallet
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + Save<SentTransaction>,
+{
+    let types = dependencies.determine_types(&swap_id).await?;
+    let sent_transactions = dependencies.load_sent_transactions(&swap_id).await?;
+
+    with_swap_types!(types, {
+        let state = StateStore::get::<ROLE>(&dependencies, &swap_id)?.ok_or_else(|| {
+            anyhow::anyhow!("state store did not contain an entry for {}", swap_id)
+        })?;
+
+        // As long as the redeem or refund action is still available, the
+        // transaction we sent for it did not make it into a block.
+        let bumpable = state.actions().into_iter().find_map(|action| {
+            let action_kind = match action {
+                Action::Redeem(_) => ActionKind::Redeem,
+                Action::Refund(_) => ActionKind::Refund,
+                _ => return None,
+            };
+            let sent_transaction = sent_transactions
+                .iter()
+                .find(|sent_transaction| sent_transaction.action == action_kind)?;
+
+            Some((action, sent_transaction.clone()))
+        });
+        let (action, sent_transaction) = bumpable.ok_or_else(|| {
+            HttpApiProblem::new("Nothing to bump.")
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail(
+                    "cnd did not send a redeem or refund transaction for this swap that is still unconfirmed.",
+                )
+        })?;
+
+        let (body, replacement) = match action {
+            Action::Redeem(redeem) => {
+                redeem
+                    .bump_fee(
+                        sent_transaction,
+                        parameters,
+                        dependencies.bitcoin_wallet(),
+                        dependencies.bitcoin_connector(),
+                        dependencies.bitcoin_fee_estimator(),
+                    )
+                    .await?
+            }
+            Action::Refund(refund) => {
+                refund
+                    .bump_fee(
+                        sent_transaction,
+                        parameters,
+                        dependencies.bitcoin_wallet(),
+                        dependencies.bitcoin_connector(),
+                        dependencies.bitcoin_fee_estimator(),
+                    )
+                    .await?
+            }
+            _ => unreachable!("only redeem and refund actions are bumped"),
+        };
+
+        if let Some(replacement) = replacement {
+            log::info!(
+                "Replaced transaction of swap {} with {}",
+                swap_id,
+                replacement.transaction_id
+            );
+            Save::save(&dependencies, replacement).await?;
+        }
+
+        Ok(body)
+    })
+}
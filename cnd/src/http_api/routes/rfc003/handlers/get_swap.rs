@@ -1,10 +1,21 @@
 use crate::{
     db::{DetermineTypes, Retrieve},
     http_api::swap_resource::{build_rfc003_siren_entity, IncludeState},
-    swap_protocols::{rfc003::state_store::StateStore, SwapId},
+    swap_protocols::{
+        ledger::{Bitcoin, Ethereum, Litecoin},
+        rfc003::{events::TransactionConfirmations, state_store::StateStore},
+        SwapId,
+    },
 };
 
-pub async fn handle_get_swap<D: Retrieve + StateStore + DetermineTypes>(
+pub async fn handle_get_swap<
+    D: Retrieve
+        + StateStore
+        + DetermineTypes
+        + TransactionConfirmations<Bitcoin>
+        + TransactionConfirmations<Ethereum>
+        + TransactionConfirmations<Litecoin>,
+>(
     dependencies: D,
     id: SwapId,
 ) -> anyhow::Result<siren::Entity> {
@@ -1,9 +1,15 @@
 mod action;
+mod batch_spend;
+mod broadcast_psbt;
+mod bump_fee;
 mod get_swap;
 pub mod post_swap;
 
 pub use self::{
     action::{handle_action, InvalidAction, InvalidActionInvocation},
+    batch_spend::handle_batch_spend,
+    broadcast_psbt::handle_broadcast_psbt,
+    bump_fee::handle_bump_fee,
     get_swap::handle_get_swap,
     post_swap::handle_post_swap,
 };
@@ -1,16 +1,21 @@
 use crate::{
+    btsieve::ethereum::{EthereumChains, UnsupportedEthereumChain},
     db::{Save, Saver, Swap},
-    ethereum::{self, Erc20Token, EtherQuantity},
+    ethereum::{self, Erc20Token, Erc721Token, EtherQuantity},
     http_api::{HttpAsset, HttpLedger},
+    litecoin,
     network::{DialInformation, Network},
     seed::SwapSeed,
     swap_protocols::{
         self,
         asset::Asset,
-        ledger::{self, Bitcoin, Ethereum},
+        ledger::{self, Bitcoin, Ethereum, Litecoin},
         rfc003::{
-            self, alice::State, events::HtlcEvents, state_store::StateStore, Accept, Decline,
-            Ledger, Request, SecretHash, SecretSource,
+            self,
+            alice::State,
+            events::{HtlcEvents, TransactionConfirmations},
+            state_store::StateStore,
+            Accept, Decline, Ledger, Request, SecretSource,
         },
         HashFunction, Role, SwapId,
     },
@@ -36,18 +41,33 @@ pub async fn handle_post_swap<
         + Saver
         + Network
         + Clone
+        + EthereumChains
         + HtlcEvents<Bitcoin, Amount>
         + HtlcEvents<Ethereum, EtherQuantity>
-        + HtlcEvents<Ethereum, Erc20Token>,
+        + HtlcEvents<Ethereum, Erc20Token>
+        + HtlcEvents<Ethereum, Erc721Token>
+        + HtlcEvents<Litecoin, litecoin::Amount>
+        + TransactionConfirmations<Bitcoin>
+        + TransactionConfirmations<Ethereum>
+        + TransactionConfirmations<Litecoin>,
 >(
     dependencies: D,
     body: serde_json::Value,
 ) -> anyhow::Result<SwapCreated> {
     let id = SwapId::default();
     let seed = dependencies.swap_seed(id);
-    let secret_hash = seed.secret().hash();
 
-    let body = serde_json::from_value(body)?;
+    let body: SwapRequestBody = serde_json::from_value(body)?;
+
+    for ledger in &[body.alpha_ledger, body.beta_ledger] {
+        if let HttpLedger::Ethereum(ethereum) = ledger {
+            if !dependencies.supports_ethereum_chain(ethereum.chain_id) {
+                return Err(anyhow::Error::from(UnsupportedEthereumChain {
+                    chain_id: ethereum.chain_id,
+                }));
+            }
+        }
+    }
 
     match body {
         SwapRequestBody {
@@ -57,6 +77,7 @@ pub async fn handle_post_swap<
             beta_asset: HttpAsset::Ether(beta_asset),
             alpha_expiry,
             beta_expiry,
+            hash_function,
             identities,
             peer,
         } => {
@@ -70,8 +91,9 @@ pub async fn handle_post_swap<
                 alpha_expiry,
                 beta_expiry,
                 identities,
-                secret_hash,
-            );
+                hash_function,
+                &seed,
+            )?;
             initiate_request(dependencies, id, peer, request).await?;
         }
         SwapRequestBody {
@@ -81,6 +103,7 @@ pub async fn handle_post_swap<
             beta_asset: HttpAsset::Bitcoin(beta_asset),
             alpha_expiry,
             beta_expiry,
+            hash_function,
             identities,
             peer,
         } => {
@@ -94,8 +117,9 @@ pub async fn handle_post_swap<
                 alpha_expiry,
                 beta_expiry,
                 identities,
-                secret_hash,
-            );
+                hash_function,
+                &seed,
+            )?;
             initiate_request(dependencies, id, peer, request).await?;
         }
         SwapRequestBody {
@@ -105,6 +129,7 @@ pub async fn handle_post_swap<
             beta_asset: HttpAsset::Erc20(beta_asset),
             alpha_expiry,
             beta_expiry,
+            hash_function,
             identities,
             peer,
         } => {
@@ -118,8 +143,9 @@ pub async fn handle_post_swap<
                 alpha_expiry,
                 beta_expiry,
                 identities,
-                secret_hash,
-            );
+                hash_function,
+                &seed,
+            )?;
             initiate_request(dependencies, id, peer, request).await?;
         }
         SwapRequestBody {
@@ -129,6 +155,163 @@ pub async fn handle_post_swap<
             beta_asset: HttpAsset::Bitcoin(beta_asset),
             alpha_expiry,
             beta_expiry,
+            hash_function,
+            identities,
+            peer,
+        } => {
+            let identities = identities.into_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                &seed,
+            )?;
+            initiate_request(dependencies, id, peer, request).await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Bitcoin(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Bitcoin(alpha_asset),
+            beta_asset: HttpAsset::Erc721(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            hash_function,
+            identities,
+            peer,
+        } => {
+            let identities = identities.into_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                &seed,
+            )?;
+            initiate_request(dependencies, id, peer, request).await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::Bitcoin(beta_ledger),
+            alpha_asset: HttpAsset::Erc721(alpha_asset),
+            beta_asset: HttpAsset::Bitcoin(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            hash_function,
+            identities,
+            peer,
+        } => {
+            let identities = identities.into_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                &seed,
+            )?;
+            initiate_request(dependencies, id, peer, request).await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Ether(alpha_asset),
+            beta_asset: HttpAsset::Ether(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            hash_function,
+            identities,
+            peer,
+        } => {
+            let identities = identities.into_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                &seed,
+            )?;
+            initiate_request(dependencies, id, peer, request).await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Ether(alpha_asset),
+            beta_asset: HttpAsset::Erc20(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            hash_function,
+            identities,
+            peer,
+        } => {
+            let identities = identities.into_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                &seed,
+            )?;
+            initiate_request(dependencies, id, peer, request).await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Erc20(alpha_asset),
+            beta_asset: HttpAsset::Ether(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            hash_function,
+            identities,
+            peer,
+        } => {
+            let identities = identities.into_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                &seed,
+            )?;
+            initiate_request(dependencies, id, peer, request).await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Erc20(alpha_asset),
+            beta_asset: HttpAsset::Erc20(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            hash_function,
             identities,
             peer,
         } => {
@@ -142,8 +325,113 @@ pub async fn handle_post_swap<
                 alpha_expiry,
                 beta_expiry,
                 identities,
-                secret_hash,
-            );
+                hash_function,
+                &seed,
+            )?;
+            initiate_request(dependencies, id, peer, request).await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Litecoin(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Litecoin(alpha_asset),
+            beta_asset: HttpAsset::Ether(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            hash_function,
+            identities,
+            peer,
+        } => {
+            let identities = identities.into_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                &seed,
+            )?;
+            initiate_request(dependencies, id, peer, request).await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::Litecoin(beta_ledger),
+            alpha_asset: HttpAsset::Ether(alpha_asset),
+            beta_asset: HttpAsset::Litecoin(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            hash_function,
+            identities,
+            peer,
+        } => {
+            let identities = identities.into_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                &seed,
+            )?;
+            initiate_request(dependencies, id, peer, request).await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Litecoin(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Litecoin(alpha_asset),
+            beta_asset: HttpAsset::Erc20(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            hash_function,
+            identities,
+            peer,
+        } => {
+            let identities = identities.into_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                &seed,
+            )?;
+            initiate_request(dependencies, id, peer, request).await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::Litecoin(beta_ledger),
+            alpha_asset: HttpAsset::Erc20(alpha_asset),
+            beta_asset: HttpAsset::Litecoin(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            hash_function,
+            identities,
+            peer,
+        } => {
+            let identities = identities.into_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                &seed,
+            )?;
             initiate_request(dependencies, id, peer, request).await?;
         }
         _ => {
@@ -169,27 +457,34 @@ fn new_request<AL, BL, AA, BA>(
     alpha_expiry: Option<Timestamp>,
     beta_expiry: Option<Timestamp>,
     identities: Identities<AL, BL>,
-    secret_hash: SecretHash,
-) -> rfc003::Request<AL, BL, AA, BA>
+    hash_function: Option<HashFunction>,
+    secret_source: &dyn SecretSource,
+) -> Result<rfc003::Request<AL, BL, AA, BA>, UnsupportedHashFunction>
 where
     AL: Ledger,
     BL: Ledger,
     AA: Asset,
     BA: Asset,
 {
-    rfc003::Request {
+    let hash_function = hash_function.unwrap_or(HashFunction::Sha256);
+    if !AL::HASH_FUNCTIONS.contains(&hash_function) || !BL::HASH_FUNCTIONS.contains(&hash_function)
+    {
+        return Err(UnsupportedHashFunction { hash_function });
+    }
+
+    Ok(rfc003::Request {
         swap_id: id,
         alpha_ledger,
         beta_ledger,
         alpha_asset,
         beta_asset,
-        hash_function: HashFunction::Sha256,
+        hash_function,
         alpha_ledger_refund_identity: identities.alpha_ledger_refund_identity,
         beta_ledger_redeem_identity: identities.beta_ledger_redeem_identity,
         alpha_expiry: alpha_expiry.unwrap_or_else(default_alpha_expiry),
         beta_expiry: beta_expiry.unwrap_or_else(default_beta_expiry),
-        secret_hash,
-    }
+        secret_hash: secret_source.secret().hash(hash_function),
+    })
 }
 
 /// An error type for describing that a particular combination of assets and
@@ -203,6 +498,14 @@ pub struct UnsupportedSwap {
     beta_ledger: HttpLedger,
 }
 
+/// An error type for describing that the HTLCs of the requested ledgers
+/// cannot check the secret with a particular hash function.
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("{hash_function} is not supported by the HTLCs of the requested ledgers")]
+pub struct UnsupportedHashFunction {
+    hash_function: HashFunction,
+}
+
 async fn initiate_request<D, AL, BL, AA, BA>(
     dependencies: D,
     id: SwapId,
@@ -220,6 +523,8 @@ where
         + Network
         + HtlcEvents<AL, AA>
         + HtlcEvents<BL, BA>
+        + TransactionConfirmations<AL>
+        + TransactionConfirmations<BL>
         + Clone,
     AL: Ledger,
     BL: Ledger,
@@ -289,6 +594,7 @@ struct SwapRequestBody {
     beta_ledger: HttpLedger,
     alpha_expiry: Option<Timestamp>,
     beta_expiry: Option<Timestamp>,
+    hash_function: Option<HashFunction>,
     #[serde(flatten)]
     identities: HttpIdentities,
     peer: DialInformation,
@@ -417,6 +723,108 @@ impl IntoIdentities<ledger::Ethereum, ledger::Bitcoin> for HttpIdentities {
     }
 }
 
+impl IntoIdentities<ledger::Litecoin, ledger::Ethereum> for HttpIdentities {
+    fn into_identities(
+        self,
+        secret_source: &dyn SecretSource,
+    ) -> anyhow::Result<Identities<ledger::Litecoin, ledger::Ethereum>> {
+        let HttpIdentities {
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+        } = self;
+
+        let beta_ledger_redeem_identity =
+            match (alpha_ledger_refund_identity, beta_ledger_redeem_identity) {
+                (None, Some(beta_ledger_redeem_identity)) => beta_ledger_redeem_identity,
+                (_, None) => {
+                    return Err(anyhow::Error::from(MissingIdentity {
+                        kind: IdentityKind::BetaLedgerRedeemIdentity,
+                    }))
+                }
+                (Some(_), _) => {
+                    return Err(anyhow::Error::from(UnexpectedIdentity {
+                        kind: IdentityKind::AlphaLedgerRefundIdentity,
+                    }))
+                }
+            };
+
+        let alpha_ledger_refund_identity = crate::bitcoin::PublicKey::from_secret_key(
+            &*crate::SECP,
+            &secret_source.secp256k1_refund(),
+        );
+
+        Ok(Identities {
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+        })
+    }
+}
+
+impl IntoIdentities<ledger::Ethereum, ledger::Litecoin> for HttpIdentities {
+    fn into_identities(
+        self,
+        secret_source: &dyn SecretSource,
+    ) -> anyhow::Result<Identities<ledger::Ethereum, ledger::Litecoin>> {
+        let HttpIdentities {
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+        } = self;
+
+        let alpha_ledger_refund_identity =
+            match (alpha_ledger_refund_identity, beta_ledger_redeem_identity) {
+                (Some(alpha_ledger_refund_identity), None) => alpha_ledger_refund_identity,
+                (_, Some(_)) => {
+                    return Err(anyhow::Error::from(UnexpectedIdentity {
+                        kind: IdentityKind::BetaLedgerRedeemIdentity,
+                    }))
+                }
+                (None, _) => {
+                    return Err(anyhow::Error::from(MissingIdentity {
+                        kind: IdentityKind::AlphaLedgerRefundIdentity,
+                    }))
+                }
+            };
+
+        let beta_ledger_redeem_identity = crate::bitcoin::PublicKey::from_secret_key(
+            &*crate::SECP,
+            &secret_source.secp256k1_redeem(),
+        );
+
+        Ok(Identities {
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+        })
+    }
+}
+
+impl IntoIdentities<ledger::Ethereum, ledger::Ethereum> for HttpIdentities {
+    fn into_identities(
+        self,
+        _: &dyn SecretSource,
+    ) -> anyhow::Result<Identities<ledger::Ethereum, ledger::Ethereum>> {
+        let HttpIdentities {
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+        } = self;
+
+        let alpha_ledger_refund_identity = alpha_ledger_refund_identity.ok_or_else(|| {
+            anyhow::Error::from(MissingIdentity {
+                kind: IdentityKind::AlphaLedgerRefundIdentity,
+            })
+        })?;
+        let beta_ledger_redeem_identity = beta_ledger_redeem_identity.ok_or_else(|| {
+            anyhow::Error::from(MissingIdentity {
+                kind: IdentityKind::BetaLedgerRedeemIdentity,
+            })
+        })?;
+
+        Ok(Identities {
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+        })
+    }
+}
+
 fn default_alpha_expiry() -> Timestamp {
     Timestamp::now().plus(60 * 60 * 24)
 }
@@ -428,7 +836,7 @@ fn default_beta_expiry() -> Timestamp {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{network::DialInformation, swap_protocols::ledger::ethereum::ChainId};
+    use crate::{network::DialInformation, seed::Seed, swap_protocols::ledger::ethereum::ChainId};
     use spectral::prelude::*;
 
     #[test]
@@ -533,4 +941,59 @@ mod tests {
                 chain_id: ChainId::new(3),
             }));
     }
+
+    #[test]
+    fn ethereum_to_ethereum_swap_requires_both_identities() {
+        let seed = Seed::from([0u8; 32]);
+        let address = "0x00a329c0648769a73afac7f9381e08fb43dbea72"
+            .parse::<ethereum::Address>()
+            .unwrap();
+
+        let both = HttpIdentities {
+            alpha_ledger_refund_identity: Some(address),
+            beta_ledger_redeem_identity: Some(address),
+        };
+        let only_refund = HttpIdentities {
+            alpha_ledger_refund_identity: Some(address),
+            beta_ledger_redeem_identity: None,
+        };
+
+        let both: anyhow::Result<Identities<ledger::Ethereum, ledger::Ethereum>> =
+            both.into_identities(&seed);
+        let only_refund: anyhow::Result<Identities<ledger::Ethereum, ledger::Ethereum>> =
+            only_refund.into_identities(&seed);
+
+        assert_that(&both).is_ok();
+        assert_that(&only_refund).is_err();
+    }
+
+    #[test]
+    fn bitcoin_htlcs_do_not_support_keccak256() {
+        let seed = Seed::from([0u8; 32]);
+        let identities: Identities<ledger::Bitcoin, ledger::Ethereum> = HttpIdentities {
+            alpha_ledger_refund_identity: None,
+            beta_ledger_redeem_identity: Some(ethereum::Address::repeat_byte(0x11)),
+        }
+        .into_identities(&seed)
+        .unwrap();
+
+        let request = |hash_function| {
+            new_request(
+                SwapId::default(),
+                ledger::Bitcoin::default(),
+                ledger::Ethereum::default(),
+                Amount::from_sat(100_000_000),
+                EtherQuantity::zero(),
+                None,
+                None,
+                identities.clone(),
+                hash_function,
+                &seed,
+            )
+        };
+
+        assert_that(&request(None)).is_ok();
+        assert_that(&request(Some(HashFunction::Hash160))).is_ok();
+        assert_that(&request(Some(HashFunction::Keccak256))).is_err();
+    }
 }
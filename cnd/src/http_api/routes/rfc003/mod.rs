@@ -4,23 +4,38 @@ pub mod handlers;
 mod swap_state;
 
 use crate::{
-    db::{DetermineTypes, Retrieve, Save, Swap},
-    ethereum::{Erc20Token, EtherQuantity},
+    btsieve::{
+        bitcoin::{BitcoinFeeEstimator, BitcoinLedgerConnector},
+        ethereum::{EthereumChains, EthereumGasEstimator},
+    },
+    db::{DetermineTypes, LoadSentTransactions, Retrieve, Save, SentTransaction, Swap},
+    ethereum::{Erc20Token, Erc721Token, EtherQuantity},
     http_api::{
-        action::ActionExecutionParameters,
+        action::{
+            ActionExecutionParameters, BatchSpendBody, BumpFeeParameters, ExecuteAction, PsbtBody,
+        },
         route_factory::swap_path,
         routes::{
             into_rejection,
-            rfc003::handlers::{handle_action, handle_get_swap, handle_post_swap},
+            rfc003::handlers::{
+                handle_action, handle_batch_spend, handle_broadcast_psbt, handle_bump_fee,
+                handle_get_swap, handle_post_swap,
+            },
         },
     },
+    litecoin,
     network::Network,
     seed::SwapSeed,
     swap_protocols::{
-        ledger::{Bitcoin, Ethereum},
-        rfc003::{actions::ActionKind, events::HtlcEvents, state_store::StateStore},
+        ledger::{Bitcoin, Ethereum, Litecoin},
+        rfc003::{
+            actions::ActionKind,
+            events::{HtlcEvents, TransactionConfirmations},
+            state_store::StateStore,
+        },
         SwapId,
     },
+    wallet::{bitcoin::BitcoinWallet, ethereum::EthereumAccount, psbt::BitcoinWatchOnlyWallet},
 };
 use bitcoin::Amount;
 use futures::Future;
@@ -43,9 +58,15 @@ pub fn post_swap<
         + Save<Swap>
         + SwapSeed
         + Saver
+        + EthereumChains
         + HtlcEvents<Bitcoin, Amount>
         + HtlcEvents<Ethereum, EtherQuantity>
-        + HtlcEvents<Ethereum, Erc20Token>,
+        + HtlcEvents<Ethereum, Erc20Token>
+        + HtlcEvents<Ethereum, Erc721Token>
+        + HtlcEvents<Litecoin, litecoin::Amount>
+        + TransactionConfirmations<Bitcoin>
+        + TransactionConfirmations<Ethereum>
+        + TransactionConfirmations<Litecoin>,
 >(
     dependencies: D,
     body: serde_json::Value,
@@ -64,7 +85,14 @@ pub fn post_swap<
 }
 
 #[allow(clippy::needless_pass_by_value)]
-pub fn get_swap<D: DetermineTypes + Retrieve + StateStore>(
+pub fn get_swap<
+    D: DetermineTypes
+        + Retrieve
+        + StateStore
+        + TransactionConfirmations<Bitcoin>
+        + TransactionConfirmations<Ethereum>
+        + TransactionConfirmations<Litecoin>,
+>(
     dependencies: D,
     id: SwapId,
 ) -> impl Future<Item = impl Reply, Error = Rejection> {
@@ -88,16 +116,96 @@ pub fn action<
         + Saver
         + HtlcEvents<Bitcoin, Amount>
         + HtlcEvents<Ethereum, EtherQuantity>
-        + HtlcEvents<Ethereum, Erc20Token>,
+        + HtlcEvents<Ethereum, Erc20Token>
+        + HtlcEvents<Ethereum, Erc721Token>
+        + HtlcEvents<Litecoin, litecoin::Amount>
+        + TransactionConfirmations<Bitcoin>
+        + TransactionConfirmations<Ethereum>
+        + TransactionConfirmations<Litecoin>
+        + BitcoinWallet
+        + EthereumAccount
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + BitcoinWatchOnlyWallet
+        + EthereumGasEstimator
+        + Save<SentTransaction>,
 >(
     method: http::Method,
     id: SwapId,
     action_kind: ActionKind,
     query_params: ActionExecutionParameters,
+    execute: ExecuteAction,
     dependencies: D,
     body: serde_json::Value,
 ) -> impl Future<Item = impl Reply, Error = Rejection> {
-    handle_action(method, id, action_kind, body, query_params, dependencies)
+    handle_action(
+        method,
+        id,
+        action_kind,
+        body,
+        query_params,
+        execute.execute,
+        dependencies,
+    )
+    .boxed()
+    .compat()
+    .map(|body| warp::reply::json(&body))
+    .map_err(problem::from_anyhow)
+    .map_err(into_rejection)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn bump_fee<
+    D: DetermineTypes
+        + StateStore
+        + LoadSentTransactions
+        + BitcoinWallet
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + Save<SentTransaction>,
+>(
+    id: SwapId,
+    parameters: BumpFeeParameters,
+    dependencies: D,
+) -> impl Future<Item = impl Reply, Error = Rejection> {
+    handle_bump_fee(id, parameters, dependencies)
+        .boxed()
+        .compat()
+        .map(|body| warp::reply::json(&body))
+        .map_err(problem::from_anyhow)
+        .map_err(into_rejection)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn broadcast_psbt<
+    D: DetermineTypes + StateStore + BitcoinLedgerConnector + Save<SentTransaction>,
+>(
+    id: SwapId,
+    body: PsbtBody,
+    dependencies: D,
+) -> impl Future<Item = impl Reply, Error = Rejection> {
+    handle_broadcast_psbt(id, body, dependencies)
+        .boxed()
+        .compat()
+        .map(|body| warp::reply::json(&body))
+        .map_err(problem::from_anyhow)
+        .map_err(into_rejection)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn batch_spend<
+    D: DetermineTypes
+        + StateStore
+        + BitcoinWallet
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + Save<SentTransaction>,
+>(
+    execute: ExecuteAction,
+    body: BatchSpendBody,
+    dependencies: D,
+) -> impl Future<Item = impl Reply, Error = Rejection> {
+    handle_batch_spend(body, execute.execute, dependencies)
         .boxed()
         .compat()
         .map(|body| warp::reply::json(&body))
@@ -45,6 +45,17 @@ pub struct LedgerState<H, T> {
     pub fund_tx: Option<Http<T>>,
     pub redeem_tx: Option<Http<T>>,
     pub refund_tx: Option<Http<T>>,
+    pub confirmations: Confirmations,
+}
+
+/// The number of confirmations of the HTLC transactions as far as we know
+/// them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct Confirmations {
+    pub deploy_tx: Option<u32>,
+    pub fund_tx: Option<u32>,
+    pub redeem_tx: Option<u32>,
+    pub refund_tx: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Copy, Serialize)]
@@ -111,6 +122,7 @@ impl<L: Ledger> From<rfc003::LedgerState<L>> for LedgerState<L::HtlcLocation, L:
                 fund_tx: None,
                 refund_tx: None,
                 redeem_tx: None,
+                confirmations: Confirmations::default(),
             },
             IncorrectlyFunded {
                 htlc_location,
@@ -123,6 +135,7 @@ impl<L: Ledger> From<rfc003::LedgerState<L>> for LedgerState<L::HtlcLocation, L:
                 fund_tx: Some(Http(fund_transaction)),
                 redeem_tx: None,
                 refund_tx: None,
+                confirmations: Confirmations::default(),
             },
             Funded {
                 htlc_location,
@@ -135,6 +148,7 @@ impl<L: Ledger> From<rfc003::LedgerState<L>> for LedgerState<L::HtlcLocation, L:
                 fund_tx: Some(Http(fund_transaction)),
                 refund_tx: None,
                 redeem_tx: None,
+                confirmations: Confirmations::default(),
             },
             Redeemed {
                 htlc_location,
@@ -148,6 +162,7 @@ impl<L: Ledger> From<rfc003::LedgerState<L>> for LedgerState<L::HtlcLocation, L:
                 fund_tx: Some(Http(fund_transaction)),
                 redeem_tx: Some(Http(redeem_transaction)),
                 refund_tx: None,
+                confirmations: Confirmations::default(),
             },
             Refunded {
                 htlc_location,
@@ -161,7 +176,28 @@ impl<L: Ledger> From<rfc003::LedgerState<L>> for LedgerState<L::HtlcLocation, L:
                 fund_tx: Some(Http(fund_transaction)),
                 refund_tx: Some(Http(refund_transaction)),
                 redeem_tx: None,
+                confirmations: Confirmations::default(),
+            },
+        }
+    }
+}
+
+impl<H, T> LedgerState<H, T> {
+    pub fn with_confirmations(self, confirmations_of: impl Fn(&T) -> Option<u32>) -> Self {
+        let confirmations = |transaction: &Option<Http<T>>| {
+            transaction
+                .as_ref()
+                .and_then(|Http(transaction)| confirmations_of(transaction))
+        };
+
+        Self {
+            confirmations: Confirmations {
+                deploy_tx: confirmations(&self.deploy_tx),
+                fund_tx: confirmations(&self.fund_tx),
+                redeem_tx: confirmations(&self.redeem_tx),
+                refund_tx: confirmations(&self.refund_tx),
             },
+            ..self
         }
     }
 }
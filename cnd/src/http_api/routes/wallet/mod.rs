@@ -0,0 +1,48 @@
+use crate::{
+    http_api::Http,
+    wallet::{bitcoin::BitcoinWallet, ethereum::EthereumAccount},
+};
+use serde::Serialize;
+use warp::{Rejection, Reply};
+
+#[derive(Serialize, Debug)]
+pub struct BitcoinWalletResource {
+    balance: Http<bitcoin::Amount>,
+    network: Http<bitcoin::Network>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BitcoinAddressResource {
+    address: bitcoin::Address,
+}
+
+#[derive(Serialize, Debug)]
+pub struct EthereumAccountResource {
+    address: crate::ethereum::Address,
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn get_bitcoin_wallet<D: BitcoinWallet>(dependencies: D) -> Result<impl Reply, Rejection> {
+    let wallet = dependencies.bitcoin_wallet();
+
+    Ok(warp::reply::json(&BitcoinWalletResource {
+        balance: Http(wallet.balance()),
+        network: Http(wallet.network()),
+    }))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn get_bitcoin_wallet_address<D: BitcoinWallet>(
+    dependencies: D,
+) -> Result<impl Reply, Rejection> {
+    let address = dependencies.bitcoin_wallet().receive_address();
+
+    Ok(warp::reply::json(&BitcoinAddressResource { address }))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn get_ethereum_account<D: EthereumAccount>(dependencies: D) -> Result<impl Reply, Rejection> {
+    let address = dependencies.ethereum_account().address();
+
+    Ok(warp::reply::json(&EthereumAccountResource { address }))
+}
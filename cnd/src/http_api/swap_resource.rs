@@ -9,10 +9,11 @@ use crate::{
         routes::rfc003::{LedgerState, SwapCommunication, SwapState},
         Http, HttpAsset, HttpLedger,
     },
+    litecoin,
     swap_protocols::{
         actions::Actions,
         ledger,
-        rfc003::{self, state_store::StateStore},
+        rfc003::{self, events::TransactionConfirmations, state_store::StateStore},
         HashFunction, SwapId, SwapProtocol,
     },
 };
@@ -39,6 +40,7 @@ pub struct SwapParameters {
     beta_ledger: HttpLedger,
     alpha_asset: HttpAsset,
     beta_asset: HttpAsset,
+    hash_function: HashFunction,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, PartialEq)]
@@ -63,6 +65,7 @@ macro_rules! impl_from_request_for_swap_parameters {
                     alpha_asset: HttpAsset::from(request.alpha_asset),
                     beta_ledger: HttpLedger::from(request.beta_ledger),
                     beta_asset: HttpAsset::from(request.beta_asset),
+                    hash_function: request.hash_function,
                 }
             }
         }
@@ -93,29 +96,103 @@ impl_from_request_for_swap_parameters!(
     ethereum::Erc20Token,
     bitcoin::Amount
 );
+impl_from_request_for_swap_parameters!(
+    ledger::Bitcoin,
+    ledger::Ethereum,
+    bitcoin::Amount,
+    ethereum::Erc721Token
+);
+impl_from_request_for_swap_parameters!(
+    ledger::Ethereum,
+    ledger::Bitcoin,
+    ethereum::Erc721Token,
+    bitcoin::Amount
+);
+impl_from_request_for_swap_parameters!(
+    ledger::Ethereum,
+    ledger::Ethereum,
+    ethereum::EtherQuantity,
+    ethereum::EtherQuantity
+);
+impl_from_request_for_swap_parameters!(
+    ledger::Ethereum,
+    ledger::Ethereum,
+    ethereum::EtherQuantity,
+    ethereum::Erc20Token
+);
+impl_from_request_for_swap_parameters!(
+    ledger::Ethereum,
+    ledger::Ethereum,
+    ethereum::Erc20Token,
+    ethereum::EtherQuantity
+);
+impl_from_request_for_swap_parameters!(
+    ledger::Ethereum,
+    ledger::Ethereum,
+    ethereum::Erc20Token,
+    ethereum::Erc20Token
+);
+impl_from_request_for_swap_parameters!(
+    ledger::Litecoin,
+    ledger::Ethereum,
+    litecoin::Amount,
+    ethereum::EtherQuantity
+);
+impl_from_request_for_swap_parameters!(
+    ledger::Ethereum,
+    ledger::Litecoin,
+    ethereum::EtherQuantity,
+    litecoin::Amount
+);
+impl_from_request_for_swap_parameters!(
+    ledger::Litecoin,
+    ledger::Ethereum,
+    litecoin::Amount,
+    ethereum::Erc20Token
+);
+impl_from_request_for_swap_parameters!(
+    ledger::Ethereum,
+    ledger::Litecoin,
+    ethereum::Erc20Token,
+    litecoin::Amount
+);
 
 pub enum IncludeState {
     Yes,
     No,
 }
 
-pub fn build_rfc003_siren_entity<S: StateStore>(
-    state_store: &S,
+pub fn build_rfc003_siren_entity<D>(
+    dependencies: &D,
     swap: Swap,
     types: SwapTypes,
     include_state: IncludeState,
-) -> anyhow::Result<siren::Entity> {
+) -> anyhow::Result<siren::Entity>
+where
+    D: StateStore
+        + TransactionConfirmations<ledger::Bitcoin>
+        + TransactionConfirmations<ledger::Ethereum>
+        + TransactionConfirmations<ledger::Litecoin>,
+{
     let id = swap.swap_id;
 
     with_swap_types!(types, {
-        let state = state_store
+        let state = dependencies
             .get::<ROLE>(&id)?
             .ok_or_else(|| anyhow::anyhow!("state store did not contain an entry for {}", id))?;
 
         let communication = SwapCommunication::from(state.swap_communication.clone());
-        let alpha_ledger = LedgerState::from(state.alpha_ledger_state.clone());
-        let beta_ledger = LedgerState::from(state.beta_ledger_state.clone());
-        let parameters = SwapParameters::from(state.clone().request());
+        let alpha_ledger =
+            LedgerState::from(state.alpha_ledger_state.clone()).with_confirmations(|transaction| {
+                TransactionConfirmations::<AL>::transaction_confirmations(dependencies, transaction)
+            });
+        let beta_ledger =
+            LedgerState::from(state.beta_ledger_state.clone()).with_confirmations(|transaction| {
+                TransactionConfirmations::<BL>::transaction_confirmations(dependencies, transaction)
+            });
+        let request = state.clone().request();
+        let protocol = SwapProtocol::Rfc003(request.hash_function);
+        let parameters = SwapParameters::from(request);
         let actions = state.clone().actions();
 
         let error = state.error;
@@ -129,7 +206,7 @@ pub fn build_rfc003_siren_entity<S: StateStore>(
         let swap = SwapResource {
             id: Http(id),
             status,
-            protocol: Http(SwapProtocol::Rfc003(HashFunction::Sha256)),
+            protocol: Http(protocol),
             parameters,
             role: swap.role.to_string(),
             counterparty: Http(swap.counterparty),
@@ -22,22 +22,28 @@ pub mod libp2p_comit_ext;
 #[macro_use]
 pub mod db;
 
+pub mod auto_redeem;
 pub mod bitcoin;
 pub mod btsieve;
 pub mod comit_api;
 pub mod config;
 pub mod ethereum;
 pub mod http_api;
+pub mod litecoin;
 pub mod load_swaps;
 pub mod logging;
 pub mod network;
 #[cfg(test)]
 pub mod quickcheck;
+pub mod refund_watchdog;
 pub mod seed;
 #[cfg(test)]
 pub mod spectral_ext;
+#[cfg(test)]
+pub mod swap_fixture;
 pub mod swap_protocols;
 pub mod timestamp;
+pub mod wallet;
 
 use crate::swap_protocols::{
     asset::Asset,
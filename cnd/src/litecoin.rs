@@ -0,0 +1,14 @@
+//! This module is the home of litecoin-specific types and functionality that
+//! is needed across several places in cnd.
+//!
+//! Litecoin shares its transaction format and script system with Bitcoin,
+//! hence transactions, scripts and public keys are the ones of the `bitcoin`
+//! crate. Only amounts and addresses differ and are defined here.
+//!
+//! Litecoin networks are represented through `bitcoin::Network`, with
+//! `Network::Bitcoin` standing for the Litecoin mainnet.
+
+pub use self::{address::*, amount::*};
+
+mod address;
+mod amount;
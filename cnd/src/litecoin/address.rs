@@ -0,0 +1,311 @@
+use bitcoin::{
+    bech32::{self, FromBase32, ToBase32},
+    hashes::{hash160, Hash},
+    util::{address::Payload, base58},
+    Network, Script,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
+
+const MAINNET_PUBKEY_HASH_PREFIX: u8 = 0x30;
+const MAINNET_SCRIPT_HASH_PREFIX: u8 = 0x32;
+/// Litecoin used the script hash prefix of Bitcoin before it introduced its
+/// own, such addresses are still accepted but never produced.
+const MAINNET_LEGACY_SCRIPT_HASH_PREFIX: u8 = 0x05;
+const TESTNET_PUBKEY_HASH_PREFIX: u8 = 0x6f;
+const TESTNET_SCRIPT_HASH_PREFIX: u8 = 0x3a;
+
+/// A Litecoin address.
+///
+/// The payload, i.e. the public key hash, script hash or witness program, is
+/// the same as for Bitcoin, only the encoding of the address differs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Address {
+    pub payload: Payload,
+    pub network: Network,
+}
+
+impl Address {
+    pub fn from_script(script: &Script, network: Network) -> Option<Self> {
+        bitcoin::Address::from_script(script, network).map(|address| Address {
+            payload: address.payload,
+            network,
+        })
+    }
+
+    pub fn script_pubkey(&self) -> Script {
+        self.to_bitcoin_address().script_pubkey()
+    }
+
+    /// The Bitcoin address with the same payload, to be used wherever the
+    /// script matters but not the encoding, e.g. when building transactions.
+    pub fn to_bitcoin_address(&self) -> bitcoin::Address {
+        bitcoin::Address {
+            payload: self.payload.clone(),
+            network: self.network,
+        }
+    }
+}
+
+fn bech32_hrp(network: Network) -> &'static str {
+    match network {
+        Network::Bitcoin => "ltc",
+        Network::Testnet => "tltc",
+        Network::Regtest => "rltc",
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mainnet = self.network == Network::Bitcoin;
+
+        match &self.payload {
+            Payload::PubkeyHash(hash) => {
+                let prefix = if mainnet {
+                    MAINNET_PUBKEY_HASH_PREFIX
+                } else {
+                    TESTNET_PUBKEY_HASH_PREFIX
+                };
+                base58_check(f, prefix, hash)
+            }
+            Payload::ScriptHash(hash) => {
+                let prefix = if mainnet {
+                    MAINNET_SCRIPT_HASH_PREFIX
+                } else {
+                    TESTNET_SCRIPT_HASH_PREFIX
+                };
+                base58_check(f, prefix, hash)
+            }
+            Payload::WitnessProgram { version, program } => {
+                let mut data = vec![*version];
+                data.extend(program.to_base32());
+                let address =
+                    bech32::encode(bech32_hrp(self.network), data).map_err(|_| fmt::Error)?;
+
+                f.write_str(&address)
+            }
+        }
+    }
+}
+
+fn base58_check(f: &mut fmt::Formatter<'_>, prefix: u8, hash: &hash160::Hash) -> fmt::Result {
+    let mut data = vec![prefix];
+    data.extend_from_slice(&hash[..]);
+
+    f.write_str(&base58::check_encode_slice(&data))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("invalid base58 encoding: {0}")]
+    Base58(#[from] base58::Error),
+    #[error("invalid bech32 encoding: {0}")]
+    Bech32(#[from] bech32::Error),
+    #[error("unknown address prefix {0:#04x}")]
+    UnknownPrefix(u8),
+    #[error("unknown human readable part {0}")]
+    UnknownHrp(String),
+    #[error("invalid address length {0}")]
+    InvalidLength(usize),
+    #[error("invalid witness program")]
+    InvalidWitnessProgram,
+}
+
+impl FromStr for Address {
+    type Err = Error;
+
+    /// Base58 addresses of testnet and regtest cannot be told apart, they are
+    /// parsed as testnet addresses.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hrp = s.rfind('1').map(|separator| s[..separator].to_lowercase());
+        let network = match hrp.as_ref().map(String::as_str) {
+            Some("ltc") => Some(Network::Bitcoin),
+            Some("tltc") => Some(Network::Testnet),
+            Some("rltc") => Some(Network::Regtest),
+            _ => None,
+        };
+
+        match network {
+            Some(network) => from_bech32(s, network),
+            None => from_base58(s),
+        }
+    }
+}
+
+fn from_bech32(s: &str, network: Network) -> Result<Address, Error> {
+    let (hrp, data) = bech32::decode(s)?;
+    if hrp != bech32_hrp(network) {
+        return Err(Error::UnknownHrp(hrp));
+    }
+
+    let (version, program) = data.split_first().ok_or(Error::InvalidWitnessProgram)?;
+    let program = Vec::<u8>::from_base32(program)?;
+    if version.to_u8() > 16 || program.len() < 2 || program.len() > 40 {
+        return Err(Error::InvalidWitnessProgram);
+    }
+    if version.to_u8() == 0 && program.len() != 20 && program.len() != 32 {
+        return Err(Error::InvalidWitnessProgram);
+    }
+
+    Ok(Address {
+        payload: Payload::WitnessProgram {
+            version: *version,
+            program,
+        },
+        network,
+    })
+}
+
+fn from_base58(s: &str) -> Result<Address, Error> {
+    let data = base58::from_check(s)?;
+    if data.len() != 21 {
+        return Err(Error::InvalidLength(data.len()));
+    }
+
+    let hash = hash160::Hash::from_slice(&data[1..]).expect("slice is 20 bytes long");
+    let (payload, network) = match data[0] {
+        MAINNET_PUBKEY_HASH_PREFIX => (Payload::PubkeyHash(hash), Network::Bitcoin),
+        MAINNET_SCRIPT_HASH_PREFIX | MAINNET_LEGACY_SCRIPT_HASH_PREFIX => {
+            (Payload::ScriptHash(hash), Network::Bitcoin)
+        }
+        TESTNET_PUBKEY_HASH_PREFIX => (Payload::PubkeyHash(hash), Network::Testnet),
+        TESTNET_SCRIPT_HASH_PREFIX => (Payload::ScriptHash(hash), Network::Testnet),
+        prefix => return Err(Error::UnknownPrefix(prefix)),
+    };
+
+    Ok(Address { payload, network })
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'vde> de::Visitor<'vde> for Visitor {
+            type Value = Address;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                formatter.write_str("a litecoin address")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Address, E>
+            where
+                E: de::Error,
+            {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::{bech32::u5, hashes::hex::FromHex};
+
+    fn pubkey_hash() -> hash160::Hash {
+        hash160::Hash::from_hex("62e907b15cbf27d5425399ebf6f0fb50ebb88f18").unwrap()
+    }
+
+    fn witness_program() -> Payload {
+        Payload::WitnessProgram {
+            version: u5::try_from_u8(0).unwrap(),
+            program: pubkey_hash()[..].to_vec(),
+        }
+    }
+
+    #[test]
+    fn encodes_addresses_for_every_network() {
+        let cases = vec![
+            (
+                Payload::PubkeyHash(pubkey_hash()),
+                Network::Bitcoin,
+                "LUEweDxDA4WhvWiNXXSxjM9CYzHPJv4QQF",
+            ),
+            (
+                Payload::ScriptHash(pubkey_hash()),
+                Network::Bitcoin,
+                "MGv9cSYnaRSTZNzYaN7bhbgmozoGkKBvCn",
+            ),
+            (
+                witness_program(),
+                Network::Bitcoin,
+                "ltc1qvt5s0v2uhuna2sjnn84ldu8m2r4m3rcc3n0rm5",
+            ),
+            (
+                Payload::PubkeyHash(pubkey_hash()),
+                Network::Testnet,
+                "mpXwg4jMtRhuSpVq4xS3HFHmCmWp9NyGKt",
+            ),
+            (
+                Payload::ScriptHash(pubkey_hash()),
+                Network::Testnet,
+                "QVcyVJw6Fs9U6r7Emin9abs4r2rpPhBE1A",
+            ),
+            (
+                witness_program(),
+                Network::Testnet,
+                "tltc1qvt5s0v2uhuna2sjnn84ldu8m2r4m3rccxpv2g7",
+            ),
+            (
+                witness_program(),
+                Network::Regtest,
+                "rltc1qvt5s0v2uhuna2sjnn84ldu8m2r4m3rccrddscq",
+            ),
+        ];
+
+        for (payload, network, expected) in cases {
+            let address = Address { payload, network };
+
+            assert_eq!(address.to_string(), expected);
+            assert_eq!(expected.parse::<Address>().unwrap(), address);
+        }
+    }
+
+    #[test]
+    fn accepts_legacy_mainnet_script_hash_addresses() {
+        let address = "3Ai1JZ8pdJb2ksieUV8FsxSNVJCpoPi8W6"
+            .parse::<Address>()
+            .unwrap();
+
+        assert_eq!(address, Address {
+            payload: Payload::ScriptHash(pubkey_hash()),
+            network: Network::Bitcoin,
+        });
+    }
+
+    #[test]
+    fn rejects_bitcoin_addresses() {
+        assert!("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"
+            .parse::<Address>()
+            .is_err());
+        assert!("bc1qu5t5yrh75zca6msxzszx5mm0egu2vepu09lwqh"
+            .parse::<Address>()
+            .is_err());
+    }
+
+    #[test]
+    fn script_pubkey_does_not_depend_on_the_encoding() {
+        let bitcoin_address: bitcoin::Address =
+            "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".parse().unwrap();
+        let litecoin_address: Address = "LUEweDxDA4WhvWiNXXSxjM9CYzHPJv4QQF".parse().unwrap();
+
+        assert_eq!(
+            litecoin_address.script_pubkey(),
+            bitcoin_address.script_pubkey()
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+/// The number of litoshis in one LTC.
+const LITOSHIS_PER_LTC: u64 = 100_000_000;
+
+/// An amount of litecoin, stored in litoshis.
+#[derive(Debug, Clone, Copy, Default, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct Amount(u64);
+
+impl Amount {
+    pub fn from_litoshi(litoshi: u64) -> Self {
+        Amount(litoshi)
+    }
+
+    pub fn as_litoshi(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{:08} LTC",
+            self.0 / LITOSHIS_PER_LTC,
+            self.0 % LITOSHIS_PER_LTC
+        )
+    }
+}
+
+/// Parses an amount in litoshis.
+impl FromStr for Amount {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u64::from_str(s).map(Amount)
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'vde> de::Visitor<'vde> for Visitor {
+            type Value = Amount;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                formatter.write_str("A string representing a litecoin quantity in litoshis")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Amount, E>
+            where
+                E: de::Error,
+            {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.0.to_string().as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_amount_in_ltc() {
+        assert_eq!(
+            Amount::from_litoshi(150_000_001).to_string(),
+            "1.50000001 LTC"
+        );
+    }
+
+    #[test]
+    fn serialize_amount_as_litoshis() {
+        let amount = Amount::from_litoshi(100_000_000);
+        let json = serde_json::to_string(&amount).unwrap();
+
+        assert_eq!(json, r#""100000000""#);
+        assert_eq!(serde_json::from_str::<Amount>(&json).unwrap(), amount);
+    }
+}
@@ -1,12 +1,16 @@
 #![allow(clippy::type_repetition_in_bounds)]
 use crate::{
-    db::{DetermineTypes, LoadAcceptedSwap, Retrieve},
-    ethereum::{Erc20Token, EtherQuantity},
+    db::{DetermineTypes, LoadAcceptedSwap, LoadLedgerStates, Retrieve},
+    ethereum::{Erc20Token, Erc721Token, EtherQuantity},
+    litecoin,
     seed::SwapSeed,
     swap_protocols::{
         self,
-        ledger::{Bitcoin, Ethereum},
-        rfc003::{events::HtlcEvents, state_store::StateStore},
+        ledger::{Bitcoin, Ethereum, Litecoin},
+        rfc003::{
+            events::{HtlcEvents, TransactionConfirmations},
+            state_store::StateStore,
+        },
     },
 };
 use bitcoin::Amount;
@@ -24,10 +28,30 @@ where
         + HtlcEvents<Bitcoin, Amount>
         + HtlcEvents<Ethereum, EtherQuantity>
         + HtlcEvents<Ethereum, Erc20Token>
+        + HtlcEvents<Ethereum, Erc721Token>
+        + HtlcEvents<Litecoin, litecoin::Amount>
+        + TransactionConfirmations<Bitcoin>
+        + TransactionConfirmations<Ethereum>
+        + TransactionConfirmations<Litecoin>
         + LoadAcceptedSwap<Bitcoin, Ethereum, bitcoin::Amount, EtherQuantity>
         + LoadAcceptedSwap<Ethereum, Bitcoin, EtherQuantity, bitcoin::Amount>
         + LoadAcceptedSwap<Bitcoin, Ethereum, bitcoin::Amount, Erc20Token>
-        + LoadAcceptedSwap<Ethereum, Bitcoin, Erc20Token, bitcoin::Amount>,
+        + LoadAcceptedSwap<Ethereum, Bitcoin, Erc20Token, bitcoin::Amount>
+        + LoadAcceptedSwap<Bitcoin, Ethereum, bitcoin::Amount, Erc721Token>
+        + LoadAcceptedSwap<Ethereum, Bitcoin, Erc721Token, bitcoin::Amount>
+        + LoadAcceptedSwap<Ethereum, Ethereum, EtherQuantity, EtherQuantity>
+        + LoadAcceptedSwap<Ethereum, Ethereum, EtherQuantity, Erc20Token>
+        + LoadAcceptedSwap<Ethereum, Ethereum, Erc20Token, EtherQuantity>
+        + LoadAcceptedSwap<Ethereum, Ethereum, Erc20Token, Erc20Token>
+        + LoadAcceptedSwap<Litecoin, Ethereum, litecoin::Amount, EtherQuantity>
+        + LoadAcceptedSwap<Ethereum, Litecoin, EtherQuantity, litecoin::Amount>
+        + LoadAcceptedSwap<Litecoin, Ethereum, litecoin::Amount, Erc20Token>
+        + LoadAcceptedSwap<Ethereum, Litecoin, Erc20Token, litecoin::Amount>
+        + LoadLedgerStates<Bitcoin, Ethereum>
+        + LoadLedgerStates<Ethereum, Bitcoin>
+        + LoadLedgerStates<Ethereum, Ethereum>
+        + LoadLedgerStates<Litecoin, Ethereum>
+        + LoadLedgerStates<Ethereum, Litecoin>,
 {
     log::debug!("loading swaps from database ...");
 
@@ -35,19 +59,46 @@ where
         let swap_id = swap.swap_id;
         log::debug!("got swap from database: {}", swap_id);
 
-        let types = DetermineTypes::determine_types(&dependencies, &swap_id).await?;
+        let types = match DetermineTypes::determine_types(&dependencies, &swap_id).await {
+            Ok(types) => types,
+            Err(e) => {
+                log::error!("failed to determine swap types: {}, continuing ...", e);
+                continue;
+            }
+        };
 
         with_swap_types!(types, {
             let accepted =
                 LoadAcceptedSwap::<AL, BL, AA, BA>::load_accepted_swap(&dependencies, &swap_id)
                     .await;
 
-            match accepted {
-                Ok((request, accept, _at)) => {
-                    swap_protocols::init_accepted_swap(&dependencies, request, accept, types.role)?;
+            let (request, accept, _at) = match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    log::error!("failed to load swap: {}, continuing ...", e);
+                    continue;
                 }
-                Err(e) => log::error!("failed to load swap: {}, continuing ...", e),
             };
+
+            let ledger_states =
+                match LoadLedgerStates::<AL, BL>::load_ledger_states(&dependencies, &swap_id).await
+                {
+                    Ok(ledger_states) => ledger_states,
+                    Err(e) => {
+                        log::error!("failed to load ledger states: {}, continuing ...", e);
+                        continue;
+                    }
+                };
+
+            if let Err(e) = swap_protocols::restore_accepted_swap(
+                &dependencies,
+                request,
+                accept,
+                types.role,
+                ledger_states,
+            ) {
+                log::error!("failed to restore swap: {}, continuing ...", e);
+            }
         });
     }
     Ok(())
@@ -3,14 +3,25 @@
 use crate::cli::Options;
 use anyhow::Context;
 use cnd::{
-    btsieve::{bitcoin::BitcoindConnector, ethereum::Web3Connector},
-    config::{self, Settings},
+    auto_redeem,
+    btsieve::{
+        bitcoin::{
+            BitcoinConnector, BitcoindConnector, BitcoindRpcConnector, BlockSource,
+            ElectrumConnector, EsploraConnector, FeeEstimator, ZmqBlockNotifications,
+        },
+        ethereum::{GasEstimator, Web3Connector},
+        litecoin::LitecoindConnector,
+        BlockPoller, Confirmations,
+    },
+    config::{self, BitcoinBackend, Settings},
     db::Sqlite,
     http_api::route_factory,
     load_swaps,
     network::{self, transport, Network},
+    refund_watchdog,
     seed::Seed,
-    swap_protocols::{rfc003::state_store::InMemoryStateStore, Facade},
+    swap_protocols::{rfc003::state_store::SqliteStateStore, Facade},
+    wallet::{bitcoin::Wallet, ethereum::Account, psbt::WatchOnlyWallet},
 };
 use futures::{stream, Future, Stream};
 use futures_core::{FutureExt, TryFutureExt};
@@ -20,6 +31,7 @@ use libp2p::{
 };
 use rand::rngs::OsRng;
 use std::{
+    collections::HashMap,
     net::SocketAddr,
     process,
     sync::{Arc, Mutex},
@@ -51,18 +63,91 @@ fn main() -> anyhow::Result<()> {
 
     let mut runtime = tokio::runtime::Runtime::new()?;
 
-    let bitcoin_connector = {
-        let config::Bitcoin { node_url, network } = settings.clone().bitcoin;
-        BitcoindConnector::new(node_url, network)?
-    };
+    let bitcoin_connector = bitcoin_connector(settings.bitcoin.clone())?;
+
+    // The event loops of the connectors have to be kept alive as long as cnd
+    // runs.
+    let mut event_loop_handles = Vec::new();
+    let mut ethereum_connectors = HashMap::new();
+    let ethereum = &settings.ethereum;
+    let mut nodes = vec![(ethereum.chain_id, ethereum.node_url.clone())];
+    nodes.extend(
+        ethereum
+            .chains
+            .iter()
+            .map(|chain| (chain.chain_id, chain.node_url.clone())),
+    );
+    for (chain_id, node_url) in nodes {
+        let (connector, event_loop_handle) = Web3Connector::new(node_url, runtime.executor())?;
+        if ethereum_connectors.insert(chain_id, connector).is_some() {
+            anyhow::bail!(
+                "more than one node is configured for Ethereum chain {}",
+                chain_id
+            );
+        }
+        event_loop_handles.push(event_loop_handle);
+    }
+
+    let ethereum_account = Account::new(&seed, ethereum_connectors.clone());
+    runtime.spawn(
+        {
+            let ethereum_account = ethereum_account.clone();
+            async move {
+                ethereum_account.replace_stuck_transactions().await;
+                Ok(())
+            }
+        }
+        .boxed()
+        .compat(),
+    );
+    let ethereum_gas_estimator = GasEstimator::new(
+        ethereum_connectors.clone(),
+        settings.ethereum.gas_safety_margin,
+    );
+    let ethereum_connectors = ethereum_connectors
+        .into_iter()
+        .map(|(chain_id, connector)| {
+            let poller = BlockPoller::new(connector.clone());
+            connector.wake_up_on_new_blocks(poller.wake_up());
 
-    let (ethereum_connector, _event_loop_handle) =
-        { Web3Connector::new(settings.clone().ethereum.node_url, runtime.executor())? };
+            (chain_id, poller)
+        })
+        .collect::<HashMap<_, _>>();
 
-    let state_store = Arc::new(InMemoryStateStore::default());
+    let litecoin_connector = BlockPoller::new(LitecoindConnector::new(
+        settings.litecoin.node_url.clone(),
+        settings.litecoin.network,
+    )?);
 
     let database = Sqlite::new_in_dir(&settings.data.dir)?;
 
+    let state_store = Arc::new(SqliteStateStore::new(database.clone()));
+
+    let bitcoin_wallet = {
+        let state = runtime
+            .block_on(database.load_bitcoin_wallet_state().boxed().compat())?
+            .unwrap_or_default();
+        Wallet::new(&seed, settings.bitcoin.network, state)
+    };
+    match &bitcoin_connector {
+        BitcoinConnector::Blocks(blockchain_connector) => {
+            let sync = bitcoin_wallet
+                .clone()
+                .sync(blockchain_connector.clone(), database.clone());
+            runtime.spawn(
+                async move {
+                    sync.await;
+                    Ok(())
+                }
+                    .boxed()
+                    .compat(),
+            );
+        }
+        BitcoinConnector::Electrum(_) => log::warn!(
+            "The Bitcoin wallet does not follow the blockchain with the electrum backend, its balance will not change"
+        ),
+    }
+
     let local_key_pair = derive_key_pair(&seed);
     let local_peer_id = PeerId::from(local_key_pair.clone().public());
     log::info!("Starting with peer_id: {}", local_peer_id);
@@ -70,7 +155,7 @@ fn main() -> anyhow::Result<()> {
     let transport = transport::build_comit_transport(local_key_pair);
     let behaviour = network::ComitNode::new(
         bitcoin_connector.clone(),
-        ethereum_connector.clone(),
+        ethereum_connectors.clone(),
         Arc::clone(&state_store),
         seed,
         database.clone(),
@@ -85,9 +170,27 @@ fn main() -> anyhow::Result<()> {
 
     let swarm = Arc::new(Mutex::new(swarm));
 
+    let bitcoin_fee_estimator = FeeEstimator::new(
+        bitcoin_connector.clone(),
+        settings.bitcoin.confirmation_target,
+    );
+    let bitcoin_watch_only_wallet = WatchOnlyWallet::new(
+        settings.bitcoin.watch_only_descriptor.clone(),
+        bitcoin_connector.clone(),
+    );
+
     let deps = Facade {
         bitcoin_connector,
-        ethereum_connector,
+        ethereum_connectors,
+        litecoin_connector,
+        bitcoin_confirmations: Confirmations::new(settings.bitcoin.min_confirmations),
+        ethereum_confirmations: Confirmations::new(settings.ethereum.min_confirmations),
+        litecoin_confirmations: Confirmations::new(settings.litecoin.min_confirmations),
+        bitcoin_fee_estimator,
+        bitcoin_wallet,
+        bitcoin_watch_only_wallet,
+        ethereum_account,
+        ethereum_gas_estimator,
         state_store: Arc::clone(&state_store),
         seed,
         swarm: Arc::clone(&swarm),
@@ -101,6 +204,30 @@ fn main() -> anyhow::Result<()> {
             .compat(),
     )?;
 
+    if let Some(auto_refund) = settings.auto_refund {
+        let refund_expired_htlcs = refund_watchdog::refund_expired_htlcs(deps.clone(), auto_refund);
+        runtime.spawn(
+            async move {
+                refund_expired_htlcs.await;
+                Ok(())
+            }
+            .boxed()
+            .compat(),
+        );
+    }
+
+    if let Some(auto_redeem) = settings.auto_redeem.clone() {
+        let redeem_revealed_htlcs = auto_redeem::redeem_revealed_htlcs(deps.clone(), auto_redeem);
+        runtime.spawn(
+            async move {
+                redeem_revealed_htlcs.await;
+                Ok(())
+            }
+            .boxed()
+            .compat(),
+        );
+    }
+
     spawn_warp_instance(&settings, local_peer_id, &mut runtime, deps);
 
     let swarm_worker = stream::poll_fn(move || swarm.lock().unwrap().poll())
@@ -127,6 +254,57 @@ fn version() {
     println!("{} {} ({})", name, version, short);
 }
 
+fn bitcoin_connector(settings: config::Bitcoin) -> anyhow::Result<BitcoinConnector> {
+    let config::Bitcoin {
+        node_url,
+        network,
+        backend,
+        zmq_url,
+        rpc_auth,
+        ..
+    } = settings;
+
+    let mut block_notifications = None;
+
+    let block_source = match backend {
+        BitcoinBackend::Bitcoind => {
+            if let Some(zmq_url) = zmq_url {
+                block_notifications = Some(ZmqBlockNotifications::subscribe(&zmq_url)?);
+            }
+
+            match (rpc_auth, block_notifications.clone()) {
+                (Some(rpc_auth), Some(block_notifications)) => BlockSource::BitcoindRpc(
+                    BitcoindRpcConnector::new(node_url, network, rpc_auth.into())
+                        .with_block_notifications(block_notifications),
+                ),
+                (Some(rpc_auth), None) => BlockSource::BitcoindRpc(BitcoindRpcConnector::new(
+                    node_url,
+                    network,
+                    rpc_auth.into(),
+                )),
+                (None, Some(block_notifications)) => BlockSource::Bitcoind(
+                    BitcoindConnector::new(node_url, network)?
+                        .with_block_notifications(block_notifications),
+                ),
+                (None, None) => BlockSource::Bitcoind(BitcoindConnector::new(node_url, network)?),
+            }
+        }
+        BitcoinBackend::Esplora => BlockSource::Esplora(EsploraConnector::new(node_url, network)?),
+        BitcoinBackend::Electrum => {
+            return Ok(BitcoinConnector::Electrum(ElectrumConnector::new(
+                &node_url,
+            )?))
+        }
+    };
+
+    let poller = BlockPoller::new(block_source);
+    if let Some(block_notifications) = block_notifications {
+        block_notifications.wake_up_on_new_blocks(poller.wake_up());
+    }
+
+    Ok(BitcoinConnector::Blocks(poller))
+}
+
 fn derive_key_pair(seed: &Seed) -> identity::Keypair {
     let bytes = seed.sha256_with_seed(&[b"NODE_ID"]);
     let key = ed25519::SecretKey::from_bytes(bytes).expect("we always pass 32 bytes");
@@ -1,16 +1,17 @@
 pub mod transport;
 
 use crate::{
-    btsieve::{bitcoin::BitcoindConnector, ethereum::Web3Connector},
+    btsieve::{bitcoin::BitcoinConnector, ethereum::Web3Connector, BlockPoller},
     db::{Save, Saver, Sqlite, Swap},
     libp2p_comit_ext::{FromHeader, ToHeader},
     seed::Seed,
     swap_protocols::{
         asset::{Asset, AssetKind},
+        ledger::ethereum::ChainId,
         rfc003::{
             self, bob,
             messages::{Decision, DeclineResponseBody, Request, SwapDeclineReason},
-            state_store::{InMemoryStateStore, StateStore},
+            state_store::{SqliteStateStore, StateStore},
             Ledger,
         },
         HashFunction, LedgerKind, Role, SwapId, SwapProtocol,
@@ -46,11 +47,11 @@ pub struct ComitNode<TSubstream> {
     mdns: Mdns<TSubstream>,
 
     #[behaviour(ignore)]
-    pub bitcoin_connector: BitcoindConnector,
+    pub bitcoin_connector: BitcoinConnector,
     #[behaviour(ignore)]
-    pub ethereum_connector: Web3Connector,
+    pub ethereum_connectors: HashMap<ChainId, BlockPoller<Web3Connector>>,
     #[behaviour(ignore)]
-    pub state_store: Arc<InMemoryStateStore>,
+    pub state_store: Arc<SqliteStateStore>,
     #[behaviour(ignore)]
     pub seed: Seed,
     #[behaviour(ignore)]
@@ -95,9 +96,9 @@ pub struct Reason {
 
 impl<TSubstream> ComitNode<TSubstream> {
     pub fn new(
-        bitcoin_connector: BitcoindConnector,
-        ethereum_connector: Web3Connector,
-        state_store: Arc<InMemoryStateStore>,
+        bitcoin_connector: BitcoinConnector,
+        ethereum_connectors: HashMap<ChainId, BlockPoller<Web3Connector>>,
+        state_store: Arc<SqliteStateStore>,
         seed: Seed,
         db: Sqlite,
         task_executor: TaskExecutor,
@@ -117,7 +118,7 @@ impl<TSubstream> ComitNode<TSubstream> {
             comit: Comit::new(known_headers),
             mdns: Mdns::new()?,
             bitcoin_connector,
-            ethereum_connector,
+            ethereum_connectors,
             state_store,
             seed,
             db,
@@ -139,7 +140,8 @@ impl<TSubstream> ComitNode<TSubstream> {
 async fn handle_request(
     db: Sqlite,
     seed: Seed,
-    state_store: Arc<InMemoryStateStore>,
+    state_store: Arc<SqliteStateStore>,
+    ethereum_chains: HashSet<ChainId>,
     counterparty: PeerId,
     mut request: ValidatedInboundRequest,
 ) -> Result<SwapId, Response> {
@@ -164,6 +166,20 @@ async fn handle_request(
                         .take_header("beta_asset")
                         .map(AssetKind::from_header));
 
+                    // Without a node the HTLCs on the chain cannot be watched.
+                    for ledger in &[alpha_ledger, beta_ledger] {
+                        if let LedgerKind::Ethereum(ethereum) = ledger {
+                            if !ethereum_chains.contains(&ethereum.chain_id) {
+                                log::warn!(
+                                    "swapping on Ethereum chain {} is not supported, no node is configured for it",
+                                    ethereum.chain_id
+                                );
+
+                                return Err(decline(SwapDeclineReason::UnsupportedSwap));
+                            }
+                        }
+                    }
+
                     match (alpha_ledger, beta_ledger, alpha_asset, beta_asset) {
                         (
                             LedgerKind::Bitcoin(alpha_ledger),
@@ -179,7 +195,7 @@ async fn handle_request(
                                 beta_asset,
                                 hash_function,
                                 body!(request.take_body_as()),
-                            );
+                            )?;
                             insert_state_for_bob(
                                 db.clone(),
                                 seed,
@@ -205,7 +221,7 @@ async fn handle_request(
                                 beta_asset,
                                 hash_function,
                                 body!(request.take_body_as()),
-                            );
+                            )?;
                             insert_state_for_bob(
                                 db.clone(),
                                 seed,
@@ -231,7 +247,7 @@ async fn handle_request(
                                 beta_asset,
                                 hash_function,
                                 body!(request.take_body_as()),
-                            );
+                            )?;
                             insert_state_for_bob(
                                 db.clone(),
                                 seed,
@@ -258,7 +274,267 @@ async fn handle_request(
                                 beta_asset,
                                 hash_function,
                                 body!(request.take_body_as()),
-                            );
+                            )?;
+                            insert_state_for_bob(
+                                db.clone(),
+                                seed,
+                                state_store.clone(),
+                                counterparty,
+                                request,
+                            )
+                            .await
+                            .expect("Could not save state to db");
+                            Ok(swap_id)
+                        }
+                        (
+                            LedgerKind::Bitcoin(alpha_ledger),
+                            LedgerKind::Ethereum(beta_ledger),
+                            AssetKind::Bitcoin(alpha_asset),
+                            AssetKind::Erc721(beta_asset),
+                        ) => {
+                            let request = rfc003_swap_request(
+                                swap_id,
+                                alpha_ledger,
+                                beta_ledger,
+                                alpha_asset,
+                                beta_asset,
+                                hash_function,
+                                body!(request.take_body_as()),
+                            )?;
+                            insert_state_for_bob(
+                                db.clone(),
+                                seed,
+                                state_store.clone(),
+                                counterparty,
+                                request,
+                            )
+                            .await
+                            .expect("Could not save state to db");
+                            Ok(swap_id)
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Bitcoin(beta_ledger),
+                            AssetKind::Erc721(alpha_asset),
+                            AssetKind::Bitcoin(beta_asset),
+                        ) => {
+                            let request = rfc003_swap_request(
+                                swap_id,
+                                alpha_ledger,
+                                beta_ledger,
+                                alpha_asset,
+                                beta_asset,
+                                hash_function,
+                                body!(request.take_body_as()),
+                            )?;
+                            insert_state_for_bob(
+                                db.clone(),
+                                seed,
+                                state_store.clone(),
+                                counterparty,
+                                request,
+                            )
+                            .await
+                            .expect("Could not save state to db");
+                            Ok(swap_id)
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Ethereum(beta_ledger),
+                            AssetKind::Ether(alpha_asset),
+                            AssetKind::Ether(beta_asset),
+                        ) => {
+                            let request = rfc003_swap_request(
+                                swap_id,
+                                alpha_ledger,
+                                beta_ledger,
+                                alpha_asset,
+                                beta_asset,
+                                hash_function,
+                                body!(request.take_body_as()),
+                            )?;
+                            insert_state_for_bob(
+                                db.clone(),
+                                seed,
+                                state_store.clone(),
+                                counterparty,
+                                request,
+                            )
+                            .await
+                            .expect("Could not save state to db");
+                            Ok(swap_id)
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Ethereum(beta_ledger),
+                            AssetKind::Ether(alpha_asset),
+                            AssetKind::Erc20(beta_asset),
+                        ) => {
+                            let request = rfc003_swap_request(
+                                swap_id,
+                                alpha_ledger,
+                                beta_ledger,
+                                alpha_asset,
+                                beta_asset,
+                                hash_function,
+                                body!(request.take_body_as()),
+                            )?;
+                            insert_state_for_bob(
+                                db.clone(),
+                                seed,
+                                state_store.clone(),
+                                counterparty,
+                                request,
+                            )
+                            .await
+                            .expect("Could not save state to db");
+                            Ok(swap_id)
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Ethereum(beta_ledger),
+                            AssetKind::Erc20(alpha_asset),
+                            AssetKind::Ether(beta_asset),
+                        ) => {
+                            let request = rfc003_swap_request(
+                                swap_id,
+                                alpha_ledger,
+                                beta_ledger,
+                                alpha_asset,
+                                beta_asset,
+                                hash_function,
+                                body!(request.take_body_as()),
+                            )?;
+                            insert_state_for_bob(
+                                db.clone(),
+                                seed,
+                                state_store.clone(),
+                                counterparty,
+                                request,
+                            )
+                            .await
+                            .expect("Could not save state to db");
+                            Ok(swap_id)
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Ethereum(beta_ledger),
+                            AssetKind::Erc20(alpha_asset),
+                            AssetKind::Erc20(beta_asset),
+                        ) => {
+                            let request = rfc003_swap_request(
+                                swap_id,
+                                alpha_ledger,
+                                beta_ledger,
+                                alpha_asset,
+                                beta_asset,
+                                hash_function,
+                                body!(request.take_body_as()),
+                            )?;
+                            insert_state_for_bob(
+                                db.clone(),
+                                seed,
+                                state_store.clone(),
+                                counterparty,
+                                request,
+                            )
+                            .await
+                            .expect("Could not save state to db");
+                            Ok(swap_id)
+                        }
+                        (
+                            LedgerKind::Litecoin(alpha_ledger),
+                            LedgerKind::Ethereum(beta_ledger),
+                            AssetKind::Litecoin(alpha_asset),
+                            AssetKind::Ether(beta_asset),
+                        ) => {
+                            let request = rfc003_swap_request(
+                                swap_id,
+                                alpha_ledger,
+                                beta_ledger,
+                                alpha_asset,
+                                beta_asset,
+                                hash_function,
+                                body!(request.take_body_as()),
+                            )?;
+                            insert_state_for_bob(
+                                db.clone(),
+                                seed,
+                                state_store.clone(),
+                                counterparty,
+                                request,
+                            )
+                            .await
+                            .expect("Could not save state to db");
+                            Ok(swap_id)
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Litecoin(beta_ledger),
+                            AssetKind::Ether(alpha_asset),
+                            AssetKind::Litecoin(beta_asset),
+                        ) => {
+                            let request = rfc003_swap_request(
+                                swap_id,
+                                alpha_ledger,
+                                beta_ledger,
+                                alpha_asset,
+                                beta_asset,
+                                hash_function,
+                                body!(request.take_body_as()),
+                            )?;
+                            insert_state_for_bob(
+                                db.clone(),
+                                seed,
+                                state_store.clone(),
+                                counterparty,
+                                request,
+                            )
+                            .await
+                            .expect("Could not save state to db");
+                            Ok(swap_id)
+                        }
+                        (
+                            LedgerKind::Litecoin(alpha_ledger),
+                            LedgerKind::Ethereum(beta_ledger),
+                            AssetKind::Litecoin(alpha_asset),
+                            AssetKind::Erc20(beta_asset),
+                        ) => {
+                            let request = rfc003_swap_request(
+                                swap_id,
+                                alpha_ledger,
+                                beta_ledger,
+                                alpha_asset,
+                                beta_asset,
+                                hash_function,
+                                body!(request.take_body_as()),
+                            )?;
+                            insert_state_for_bob(
+                                db.clone(),
+                                seed,
+                                state_store.clone(),
+                                counterparty,
+                                request,
+                            )
+                            .await
+                            .expect("Could not save state to db");
+                            Ok(swap_id)
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Litecoin(beta_ledger),
+                            AssetKind::Erc20(alpha_asset),
+                            AssetKind::Litecoin(beta_asset),
+                        ) => {
+                            let request = rfc003_swap_request(
+                                swap_id,
+                                alpha_ledger,
+                                beta_ledger,
+                                alpha_asset,
+                                beta_asset,
+                                hash_function,
+                                body!(request.take_body_as()),
+                            )?;
                             insert_state_for_bob(
                                 db.clone(),
                                 seed,
@@ -275,20 +551,7 @@ async fn handle_request(
                                     "swapping {:?} to {:?} from {:?} to {:?} is currently not supported", alpha_asset, beta_asset, alpha_ledger, beta_ledger
                                 );
 
-                            let decline_body = DeclineResponseBody {
-                                reason: Some(SwapDeclineReason::UnsupportedSwap),
-                            };
-
-                            Err(Response::empty()
-                                .with_header(
-                                    "decision",
-                                    Decision::Declined
-                                        .to_header()
-                                        .expect("Decision should not fail to serialize"),
-                                )
-                                .with_body(serde_json::to_value(decline_body).expect(
-                                    "decline body should always serialize into serde_json::Value",
-                                )))
+                            Err(decline(SwapDeclineReason::UnsupportedSwap))
                         }
                     }
                 }
@@ -316,7 +579,7 @@ async fn handle_request(
 async fn insert_state_for_bob<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset, DB>(
     db: DB,
     seed: Seed,
-    state_store: Arc<InMemoryStateStore>,
+    state_store: Arc<SqliteStateStore>,
     counterparty: PeerId,
     swap_request: Request<AL, BL, AA, BA>,
 ) -> anyhow::Result<()>
@@ -479,6 +742,7 @@ impl<TSubstream> NetworkBehaviourEventProcess<BehaviourOutEvent> for ComitNode<T
                         self.db.clone(),
                         self.seed,
                         self.state_store.clone(),
+                        self.ethereum_connectors.keys().copied().collect(),
                         peer_id,
                         request,
                     )
@@ -531,8 +795,22 @@ fn rfc003_swap_request<AL: rfc003::Ledger, BL: rfc003::Ledger, AA: Asset, BA: As
     beta_asset: BA,
     hash_function: HashFunction,
     body: rfc003::messages::RequestBody<AL, BL>,
-) -> rfc003::Request<AL, BL, AA, BA> {
-    rfc003::Request::<AL, BL, AA, BA> {
+) -> Result<rfc003::Request<AL, BL, AA, BA>, Response> {
+    if !AL::HASH_FUNCTIONS.contains(&hash_function)
+        || !BL::HASH_FUNCTIONS.contains(&hash_function)
+        || !body.secret_hash.fits(hash_function)
+    {
+        log::warn!(
+            "swapping with {} HTLCs from {:?} to {:?} is not supported",
+            hash_function,
+            alpha_ledger,
+            beta_ledger
+        );
+
+        return Err(decline(SwapDeclineReason::UnsupportedProtocol));
+    }
+
+    Ok(rfc003::Request::<AL, BL, AA, BA> {
         swap_id: id,
         alpha_asset,
         beta_asset,
@@ -544,7 +822,25 @@ fn rfc003_swap_request<AL: rfc003::Ledger, BL: rfc003::Ledger, AA: Asset, BA: As
         alpha_expiry: body.alpha_expiry,
         beta_expiry: body.beta_expiry,
         secret_hash: body.secret_hash,
-    }
+    })
+}
+
+fn decline(reason: SwapDeclineReason) -> Response {
+    let decline_body = DeclineResponseBody {
+        reason: Some(reason),
+    };
+
+    Response::empty()
+        .with_header(
+            "decision",
+            Decision::Declined
+                .to_header()
+                .expect("Decision should not fail to serialize"),
+        )
+        .with_body(
+            serde_json::to_value(decline_body)
+                .expect("decline body should always serialize into serde_json::Value"),
+        )
 }
 
 fn build_outbound_request<AL: rfc003::Ledger, BL: rfc003::Ledger, AA: Asset, BA: Asset>(
@@ -1,6 +1,7 @@
 use crate::{
     db::Swap,
     ethereum::Bytes,
+    litecoin,
     swap_protocols::{
         ledger::{self, ethereum::ChainId},
         rfc003::{Accept, Request, SecretHash},
@@ -83,6 +84,14 @@ impl Arbitrary for Quickcheck<bitcoin::Amount> {
     }
 }
 
+impl Arbitrary for Quickcheck<litecoin::Amount> {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let amount = litecoin::Amount::from_litoshi(g.next_u64());
+
+        Quickcheck(amount)
+    }
+}
+
 impl Arbitrary for Quickcheck<crate::ethereum::U256> {
     fn arbitrary<G: Gen>(g: &mut G) -> Self {
         let bytes = *Quickcheck::<[u8; 32]>::arbitrary(g);
@@ -134,9 +143,38 @@ impl Arbitrary for Quickcheck<crate::ethereum::Erc20Token> {
     }
 }
 
+impl Arbitrary for Quickcheck<crate::ethereum::Erc721TokenId> {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let u256 = *Quickcheck::<crate::ethereum::U256>::arbitrary(g);
+        let erc721_token_id = crate::ethereum::Erc721TokenId(u256);
+
+        Quickcheck(erc721_token_id)
+    }
+}
+
+impl Arbitrary for Quickcheck<crate::ethereum::Erc721Token> {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let token_contract = *Quickcheck::<crate::ethereum::Address>::arbitrary(g);
+        let token_id = *Quickcheck::<crate::ethereum::Erc721TokenId>::arbitrary(g);
+        let erc721_token = crate::ethereum::Erc721Token {
+            token_contract,
+            token_id,
+        };
+
+        Quickcheck(erc721_token)
+    }
+}
+
 impl Arbitrary for Quickcheck<HashFunction> {
-    fn arbitrary<G: Gen>(_g: &mut G) -> Self {
-        Quickcheck(HashFunction::Sha256)
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let hash_function = match g.next_u32() % 3 {
+            0 => HashFunction::Sha256,
+            1 => HashFunction::Keccak256,
+            2 => HashFunction::Hash160,
+            _ => unreachable!(),
+        };
+
+        Quickcheck(hash_function)
     }
 }
 
@@ -204,9 +242,13 @@ impl Arbitrary for Quickcheck<Timestamp> {
 
 impl Arbitrary for Quickcheck<SecretHash> {
     fn arbitrary<G: Gen>(g: &mut G) -> Self {
-        let bytes = *Quickcheck::<[u8; 32]>::arbitrary(g);
+        let secret_hash = if g.next_u32() % 2 == 0 {
+            SecretHash::from(*Quickcheck::<[u8; 32]>::arbitrary(g))
+        } else {
+            SecretHash::from(*Quickcheck::<[u8; 20]>::arbitrary(g))
+        };
 
-        Quickcheck(SecretHash::from(bytes))
+        Quickcheck(secret_hash)
     }
 }
 
@@ -262,6 +304,32 @@ impl Arbitrary
     }
 }
 
+impl Arbitrary
+    for Quickcheck<
+        Request<ledger::Ethereum, ledger::Bitcoin, crate::ethereum::Erc721Token, bitcoin::Amount>,
+    >
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Request {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            beta_ledger: ledger::Bitcoin {
+                network: *Quickcheck::<bitcoin::Network>::arbitrary(g),
+            },
+            alpha_asset: *Quickcheck::<crate::ethereum::Erc721Token>::arbitrary(g),
+            beta_asset: *Quickcheck::<bitcoin::Amount>::arbitrary(g),
+            hash_function: *Quickcheck::<HashFunction>::arbitrary(g),
+            alpha_ledger_refund_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            beta_ledger_redeem_identity: *Quickcheck::<crate::bitcoin::PublicKey>::arbitrary(g),
+            alpha_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            beta_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            secret_hash: *Quickcheck::<SecretHash>::arbitrary(g),
+        })
+    }
+}
+
 impl Arbitrary
     for Quickcheck<
         Request<ledger::Ethereum, ledger::Bitcoin, crate::ethereum::EtherQuantity, bitcoin::Amount>,
@@ -314,6 +382,270 @@ impl Arbitrary
     }
 }
 
+impl Arbitrary
+    for Quickcheck<
+        Request<ledger::Bitcoin, ledger::Ethereum, bitcoin::Amount, crate::ethereum::Erc721Token>,
+    >
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Request {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger: ledger::Bitcoin {
+                network: *Quickcheck::<bitcoin::Network>::arbitrary(g),
+            },
+            beta_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            alpha_asset: *Quickcheck::<bitcoin::Amount>::arbitrary(g),
+            beta_asset: *Quickcheck::<crate::ethereum::Erc721Token>::arbitrary(g),
+            hash_function: *Quickcheck::<HashFunction>::arbitrary(g),
+            alpha_ledger_refund_identity: *Quickcheck::<crate::bitcoin::PublicKey>::arbitrary(g),
+            beta_ledger_redeem_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            alpha_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            beta_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            secret_hash: *Quickcheck::<SecretHash>::arbitrary(g),
+        })
+    }
+}
+
+impl Arbitrary
+    for Quickcheck<
+        Request<
+            ledger::Ethereum,
+            ledger::Ethereum,
+            crate::ethereum::EtherQuantity,
+            crate::ethereum::EtherQuantity,
+        >,
+    >
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Request {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            beta_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            alpha_asset: *Quickcheck::<crate::ethereum::EtherQuantity>::arbitrary(g),
+            beta_asset: *Quickcheck::<crate::ethereum::EtherQuantity>::arbitrary(g),
+            hash_function: *Quickcheck::<HashFunction>::arbitrary(g),
+            alpha_ledger_refund_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            beta_ledger_redeem_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            alpha_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            beta_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            secret_hash: *Quickcheck::<SecretHash>::arbitrary(g),
+        })
+    }
+}
+
+impl Arbitrary
+    for Quickcheck<
+        Request<
+            ledger::Ethereum,
+            ledger::Ethereum,
+            crate::ethereum::EtherQuantity,
+            crate::ethereum::Erc20Token,
+        >,
+    >
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Request {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            beta_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            alpha_asset: *Quickcheck::<crate::ethereum::EtherQuantity>::arbitrary(g),
+            beta_asset: *Quickcheck::<crate::ethereum::Erc20Token>::arbitrary(g),
+            hash_function: *Quickcheck::<HashFunction>::arbitrary(g),
+            alpha_ledger_refund_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            beta_ledger_redeem_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            alpha_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            beta_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            secret_hash: *Quickcheck::<SecretHash>::arbitrary(g),
+        })
+    }
+}
+
+impl Arbitrary
+    for Quickcheck<
+        Request<
+            ledger::Ethereum,
+            ledger::Ethereum,
+            crate::ethereum::Erc20Token,
+            crate::ethereum::EtherQuantity,
+        >,
+    >
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Request {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            beta_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            alpha_asset: *Quickcheck::<crate::ethereum::Erc20Token>::arbitrary(g),
+            beta_asset: *Quickcheck::<crate::ethereum::EtherQuantity>::arbitrary(g),
+            hash_function: *Quickcheck::<HashFunction>::arbitrary(g),
+            alpha_ledger_refund_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            beta_ledger_redeem_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            alpha_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            beta_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            secret_hash: *Quickcheck::<SecretHash>::arbitrary(g),
+        })
+    }
+}
+
+impl Arbitrary
+    for Quickcheck<
+        Request<
+            ledger::Ethereum,
+            ledger::Ethereum,
+            crate::ethereum::Erc20Token,
+            crate::ethereum::Erc20Token,
+        >,
+    >
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Request {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            beta_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            alpha_asset: *Quickcheck::<crate::ethereum::Erc20Token>::arbitrary(g),
+            beta_asset: *Quickcheck::<crate::ethereum::Erc20Token>::arbitrary(g),
+            hash_function: *Quickcheck::<HashFunction>::arbitrary(g),
+            alpha_ledger_refund_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            beta_ledger_redeem_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            alpha_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            beta_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            secret_hash: *Quickcheck::<SecretHash>::arbitrary(g),
+        })
+    }
+}
+
+impl Arbitrary
+    for Quickcheck<
+        Request<
+            ledger::Litecoin,
+            ledger::Ethereum,
+            litecoin::Amount,
+            crate::ethereum::EtherQuantity,
+        >,
+    >
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Request {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger: ledger::Litecoin {
+                network: *Quickcheck::<bitcoin::Network>::arbitrary(g),
+            },
+            beta_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            alpha_asset: *Quickcheck::<litecoin::Amount>::arbitrary(g),
+            beta_asset: *Quickcheck::<crate::ethereum::EtherQuantity>::arbitrary(g),
+            hash_function: *Quickcheck::<HashFunction>::arbitrary(g),
+            alpha_ledger_refund_identity: *Quickcheck::<crate::bitcoin::PublicKey>::arbitrary(g),
+            beta_ledger_redeem_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            alpha_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            beta_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            secret_hash: *Quickcheck::<SecretHash>::arbitrary(g),
+        })
+    }
+}
+
+impl Arbitrary
+    for Quickcheck<
+        Request<ledger::Litecoin, ledger::Ethereum, litecoin::Amount, crate::ethereum::Erc20Token>,
+    >
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Request {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger: ledger::Litecoin {
+                network: *Quickcheck::<bitcoin::Network>::arbitrary(g),
+            },
+            beta_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            alpha_asset: *Quickcheck::<litecoin::Amount>::arbitrary(g),
+            beta_asset: *Quickcheck::<crate::ethereum::Erc20Token>::arbitrary(g),
+            hash_function: *Quickcheck::<HashFunction>::arbitrary(g),
+            alpha_ledger_refund_identity: *Quickcheck::<crate::bitcoin::PublicKey>::arbitrary(g),
+            beta_ledger_redeem_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            alpha_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            beta_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            secret_hash: *Quickcheck::<SecretHash>::arbitrary(g),
+        })
+    }
+}
+
+impl Arbitrary
+    for Quickcheck<
+        Request<
+            ledger::Ethereum,
+            ledger::Litecoin,
+            crate::ethereum::EtherQuantity,
+            litecoin::Amount,
+        >,
+    >
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Request {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            beta_ledger: ledger::Litecoin {
+                network: *Quickcheck::<bitcoin::Network>::arbitrary(g),
+            },
+            alpha_asset: *Quickcheck::<crate::ethereum::EtherQuantity>::arbitrary(g),
+            beta_asset: *Quickcheck::<litecoin::Amount>::arbitrary(g),
+            hash_function: *Quickcheck::<HashFunction>::arbitrary(g),
+            alpha_ledger_refund_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            beta_ledger_redeem_identity: *Quickcheck::<crate::bitcoin::PublicKey>::arbitrary(g),
+            alpha_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            beta_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            secret_hash: *Quickcheck::<SecretHash>::arbitrary(g),
+        })
+    }
+}
+
+impl Arbitrary
+    for Quickcheck<
+        Request<ledger::Ethereum, ledger::Litecoin, crate::ethereum::Erc20Token, litecoin::Amount>,
+    >
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Request {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger: ledger::Ethereum {
+                chain_id: *Quickcheck::<ChainId>::arbitrary(g),
+            },
+            beta_ledger: ledger::Litecoin {
+                network: *Quickcheck::<bitcoin::Network>::arbitrary(g),
+            },
+            alpha_asset: *Quickcheck::<crate::ethereum::Erc20Token>::arbitrary(g),
+            beta_asset: *Quickcheck::<litecoin::Amount>::arbitrary(g),
+            hash_function: *Quickcheck::<HashFunction>::arbitrary(g),
+            alpha_ledger_refund_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            beta_ledger_redeem_identity: *Quickcheck::<crate::bitcoin::PublicKey>::arbitrary(g),
+            alpha_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            beta_expiry: *Quickcheck::<Timestamp>::arbitrary(g),
+            secret_hash: *Quickcheck::<SecretHash>::arbitrary(g),
+        })
+    }
+}
+
 impl Arbitrary for Quickcheck<Accept<ledger::Bitcoin, ledger::Ethereum>> {
     fn arbitrary<G: Gen>(g: &mut G) -> Self {
         Quickcheck(Accept {
@@ -334,6 +666,36 @@ impl Arbitrary for Quickcheck<Accept<ledger::Ethereum, ledger::Bitcoin>> {
     }
 }
 
+impl Arbitrary for Quickcheck<Accept<ledger::Ethereum, ledger::Ethereum>> {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Accept {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger_redeem_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            beta_ledger_refund_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+        })
+    }
+}
+
+impl Arbitrary for Quickcheck<Accept<ledger::Litecoin, ledger::Ethereum>> {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Accept {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger_redeem_identity: *Quickcheck::<crate::bitcoin::PublicKey>::arbitrary(g),
+            beta_ledger_refund_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+        })
+    }
+}
+
+impl Arbitrary for Quickcheck<Accept<ledger::Ethereum, ledger::Litecoin>> {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        Quickcheck(Accept {
+            swap_id: *Quickcheck::<SwapId>::arbitrary(g),
+            alpha_ledger_redeem_identity: *Quickcheck::<crate::ethereum::Address>::arbitrary(g),
+            beta_ledger_refund_identity: *Quickcheck::<crate::bitcoin::PublicKey>::arbitrary(g),
+        })
+    }
+}
+
 impl Arbitrary for Quickcheck<Role> {
     fn arbitrary<G: Gen>(g: &mut G) -> Self {
         let role = match g.next_u32() % 2 {
@@ -0,0 +1,393 @@
+//! Refunds the HTLCs we funded once they expired, for nodes that opted in
+//! through the `auto_refund` section of the config file.
+
+use crate::{
+    btsieve::{
+        bitcoin::{BitcoinFeeEstimator, BitcoinLedgerConnector},
+        ethereum::EthereumGasEstimator,
+    },
+    config::AutoRefund,
+    db::{ActionAttempt, DetermineTypes, LoadSentTransactions, Retrieve, Save, SentTransaction},
+    http_api::action::{self, ActionExecutionParameters},
+    swap_protocols::{
+        actions::Actions,
+        ledger::{Bitcoin, Ethereum, LedgerKind},
+        rfc003::{
+            actions::{Action, ActionKind},
+            state_store::StateStore,
+            Ledger,
+        },
+        Role,
+    },
+    timestamp::Timestamp,
+    wallet::{bitcoin::BitcoinWallet, ethereum::EthereumAccount, psbt::BitcoinWatchOnlyWallet},
+};
+use async_trait::async_trait;
+use futures_core::compat::Future01CompatExt;
+use std::{
+    collections::HashMap,
+    ops::Add,
+    time::{Duration, Instant},
+};
+use tokio::timer::Delay;
+
+/// How often we look for expired HTLCs.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The time of a ledger as its consensus rules see it, the expiry of an HTLC
+/// is compared against this time and not against our clock.
+#[async_trait]
+pub trait LedgerTime<L: Ledger>: Send + Sync + 'static {
+    async fn ledger_time(&self, ledger: L) -> anyhow::Result<Timestamp>;
+}
+
+/// Refunds every HTLC we funded that is neither redeemed nor refunded once it
+/// expired, forever.
+///
+/// Whether a refund was already sent is looked up in the recorded
+/// transactions, a refund that is still not mined is broadcast again instead
+/// of sending another one.
+pub async fn refund_expired_htlcs<D>(dependencies: D, settings: AutoRefund)
+where
+    D: StateStore
+        + Retrieve
+        + DetermineTypes
+        + BitcoinWallet
+        + EthereumAccount
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + BitcoinWatchOnlyWallet
+        + EthereumGasEstimator
+        + Save<SentTransaction>
+        + Save<ActionAttempt>
+        + LoadSentTransactions
+        + LedgerTime<Bitcoin>
+        + LedgerTime<Ethereum>,
+{
+    loop {
+        if let Err(e) = check_swaps(&dependencies, settings).await {
+            log::warn!("Could not look for expired HTLCs: {:?}", e);
+        }
+
+        delay(CHECK_INTERVAL).await;
+    }
+}
+
+#[allow(clippy::cognitive_complexity)]
+async fn check_swaps<D>(dependencies: &D, settings: AutoRefund) -> anyhow::Result<()>
+where
+    D: StateStore
+        + Retrieve
+        + DetermineTypes
+        + BitcoinWallet
+        + EthereumAccount
+        + BitcoinLedgerConnector
+        + BitcoinFeeEstimator
+        + BitcoinWatchOnlyWallet
+        + EthereumGasEstimator
+        + Save<SentTransaction>
+        + Save<ActionAttempt>
+        + LoadSentTransactions
+        + LedgerTime<Bitcoin>
+        + LedgerTime<Ethereum>,
+{
+    // Each ledger is asked for its time at most once per round.
+    let mut bitcoin_time = None;
+    let mut ethereum_times = HashMap::new();
+
+    for swap in Retrieve::all(dependencies).await?.iter() {
+        let swap_id = swap.swap_id;
+        // A swap we cannot look at must not keep us from refunding the others.
+        let types = match DetermineTypes::determine_types(dependencies, &swap_id).await {
+            Ok(types) => types,
+            Err(e) => {
+                log::warn!("Could not determine the types of swap {}: {:?}", swap_id, e);
+                continue;
+            }
+        };
+
+        with_swap_types!(types, {
+            let state = match StateStore::get::<ROLE>(dependencies, &swap_id) {
+                Ok(Some(state)) => state,
+                Ok(None) => continue,
+                Err(e) => {
+                    log::warn!("Could not load the state of swap {}: {:?}", swap_id, e);
+                    continue;
+                }
+            };
+
+            // There only is a refund action while our HTLC is funded.
+            let refund = match state.actions().into_iter().find_map(|action| match action {
+                Action::Refund(refund) => Some(refund),
+                _ => None,
+            }) {
+                Some(refund) => refund,
+                None => continue,
+            };
+
+            let request = state.request();
+            let (expiry, ledger): (_, LedgerKind) = match types.role {
+                Role::Alice => (request.alpha_expiry, request.alpha_ledger.into()),
+                Role::Bob => (request.beta_expiry, request.beta_ledger.into()),
+            };
+
+            let ledger_time = match ledger {
+                LedgerKind::Bitcoin(bitcoin) => match bitcoin_time {
+                    Some(time) => time,
+                    None => {
+                        let time =
+                            match LedgerTime::<Bitcoin>::ledger_time(dependencies, bitcoin).await {
+                                Ok(time) => time,
+                                Err(e) => {
+                                    log::warn!("Could not get the time of Bitcoin: {:?}", e);
+                                    continue;
+                                }
+                            };
+                        bitcoin_time = Some(time);
+                        time
+                    }
+                },
+                LedgerKind::Ethereum(ethereum) => match ethereum_times.get(&ethereum.chain_id) {
+                    Some(time) => *time,
+                    None => {
+                        let time = match LedgerTime::<Ethereum>::ledger_time(dependencies, ethereum)
+                            .await
+                        {
+                            Ok(time) => time,
+                            Err(e) => {
+                                log::warn!("Could not get the time of Ethereum: {:?}", e);
+                                continue;
+                            }
+                        };
+                        ethereum_times.insert(ethereum.chain_id, time);
+                        time
+                    }
+                },
+                // cnd cannot broadcast Litecoin transactions.
+                LedgerKind::Litecoin(_) => continue,
+            };
+            if ledger_time <= expiry {
+                continue;
+            }
+
+            // The refund action is available until our refund is mined, it
+            // might have been dropped by the node in the meantime.
+            let sent_transactions =
+                match LoadSentTransactions::load_sent_transactions(dependencies, &swap_id).await {
+                    Ok(sent_transactions) => sent_transactions,
+                    Err(e) => {
+                        log::warn!(
+                            "Could not load the transactions sent for swap {}: {:?}",
+                            swap_id,
+                            e
+                        );
+                        continue;
+                    }
+                };
+            if let Some(sent_transaction) = sent_transactions
+                .iter()
+                .find(|sent_transaction| sent_transaction.action == ActionKind::Refund)
+            {
+                if let Err(e) = action::rebroadcast(dependencies, ledger, sent_transaction).await {
+                    // Usually because the node still knows the transaction.
+                    log::debug!(
+                        "Could not broadcast refund {} of swap {} again: {:?}",
+                        sent_transaction.transaction_id,
+                        swap_id,
+                        e
+                    );
+                }
+                continue;
+            }
+
+            let parameters = match ledger {
+                LedgerKind::Bitcoin(_) => ActionExecutionParameters::BitcoinFee {
+                    fee_per_wu: settings.bitcoin_fee_per_wu.to_string(),
+                },
+                LedgerKind::Ethereum(_) => ActionExecutionParameters::EthereumGasPrice {
+                    gas_price: settings.ethereum_gas_price.to_string(),
+                },
+                LedgerKind::Litecoin(_) => continue,
+            };
+
+            log::info!(
+                "Refunding the HTLC of swap {} which expired at {}",
+                swap_id,
+                u32::from(expiry)
+            );
+            let attempted_at = Timestamp::now();
+            let result = match action::execute_action(
+                dependencies,
+                swap_id,
+                ActionKind::Refund,
+                refund,
+                parameters,
+            )
+            .await
+            {
+                Ok((_, transaction_id)) => {
+                    log::info!(
+                        "Refunded the HTLC of swap {} in transaction {}",
+                        swap_id,
+                        transaction_id
+                    );
+                    Ok(transaction_id)
+                }
+                Err(e) => {
+                    log::warn!("Could not refund the HTLC of swap {}: {:?}", swap_id, e);
+                    Err(format!("{:#}", e))
+                }
+            };
+
+            let attempt = ActionAttempt {
+                swap_id,
+                action: ActionKind::Refund,
+                attempted_at,
+                result,
+            };
+            if let Err(e) = Save::save(dependencies, attempt).await {
+                log::warn!(
+                    "Could not record the refund attempt of swap {}: {:?}",
+                    swap_id,
+                    e
+                );
+            }
+        });
+    }
+
+    Ok(())
+}
+
+async fn delay(duration: Duration) {
+    Delay::new(Instant::now().add(duration))
+        .compat()
+        .await
+        .unwrap_or_else(|e| log::warn!("Failed to wait for delay: {:?}", e));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        swap_fixture::{serve_esplora, Dependencies, EXPIRY},
+        swap_protocols::SwapId,
+    };
+    use bitcoin::{OutPoint, Transaction, TxIn, TxOut};
+    use futures_core::{FutureExt, TryFutureExt};
+    use std::sync::{Arc, Mutex};
+
+    fn check_swaps_twice(runtime: &mut tokio::runtime::Runtime, dependencies: Arc<Dependencies>) {
+        let settings = AutoRefund {
+            bitcoin_fee_per_wu: 10,
+            ethereum_gas_price: 1,
+        };
+
+        runtime
+            .block_on(
+                async move {
+                    check_swaps(&*dependencies, settings).await?;
+                    check_swaps(&*dependencies, settings).await
+                }
+                .boxed()
+                .compat(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn given_expired_htlc_refund_is_sent_once_and_broadcast_again_while_unmined() {
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let broadcast = Arc::new(Mutex::new(Vec::new()));
+        let dependencies = Arc::new(Dependencies::new(
+            &runtime,
+            serve_esplora(broadcast.clone()),
+            Role::Alice,
+        ));
+
+        check_swaps_twice(&mut runtime, dependencies.clone());
+
+        let sent_transactions = dependencies.sent_transactions.lock().unwrap().clone();
+        assert_eq!(sent_transactions.len(), 1);
+        assert_eq!(sent_transactions[0].action, ActionKind::Refund);
+
+        // Broadcasting the recorded refund again is not another attempt.
+        let action_attempts = dependencies.action_attempts.lock().unwrap().clone();
+        assert_eq!(action_attempts.len(), 1);
+        assert_eq!(action_attempts[0].swap_id, dependencies.swap_id);
+        assert_eq!(action_attempts[0].action, ActionKind::Refund);
+        assert_eq!(
+            action_attempts[0].result,
+            Ok(sent_transactions[0].transaction_id.clone())
+        );
+
+        // The second round sent the recorded refund again.
+        let broadcast = broadcast.lock().unwrap().clone();
+        assert_eq!(broadcast.len(), 2);
+        assert_eq!(broadcast[0], broadcast[1]);
+        assert_eq!(
+            broadcast[0].txid().to_string(),
+            sent_transactions[0].transaction_id
+        );
+    }
+
+    #[test]
+    fn given_refund_was_recorded_before_a_restart_it_is_not_sent_again() {
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let broadcast = Arc::new(Mutex::new(Vec::new()));
+        let dependencies =
+            Dependencies::new(&runtime, serve_esplora(broadcast.clone()), Role::Alice);
+        let refund = Transaction {
+            version: 2,
+            lock_time: EXPIRY,
+            input: vec![TxIn {
+                previous_output: OutPoint::null(),
+                script_sig: Default::default(),
+                sequence: 0xFFFF_FFFD,
+                witness: Vec::new(),
+            }],
+            output: vec![TxOut {
+                value: 99_990_000,
+                script_pubkey: dependencies
+                    .bitcoin_wallet
+                    .receive_address()
+                    .script_pubkey(),
+            }],
+        };
+        let recorded = SentTransaction {
+            swap_id: dependencies.swap_id,
+            action: ActionKind::Refund,
+            transaction_id: refund.txid().to_string(),
+            raw_transaction: Some(bitcoin::consensus::encode::serialize_hex(&refund)),
+        };
+        dependencies
+            .sent_transactions
+            .lock()
+            .unwrap()
+            .push(recorded.clone());
+        let dependencies = Arc::new(dependencies);
+
+        check_swaps_twice(&mut runtime, dependencies.clone());
+
+        assert_eq!(*dependencies.sent_transactions.lock().unwrap(), vec![
+            recorded
+        ]);
+        assert_eq!(*broadcast.lock().unwrap(), vec![refund.clone(), refund]);
+    }
+
+    #[test]
+    fn given_swap_with_unknown_types_the_other_swaps_are_still_refunded() {
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let broadcast = Arc::new(Mutex::new(Vec::new()));
+        let mut dependencies =
+            Dependencies::new(&runtime, serve_esplora(broadcast.clone()), Role::Alice);
+        dependencies.unknown_swaps.push(SwapId::default());
+        let dependencies = Arc::new(dependencies);
+
+        check_swaps_twice(&mut runtime, dependencies.clone());
+
+        let sent_transactions = dependencies.sent_transactions.lock().unwrap().clone();
+        assert_eq!(sent_transactions.len(), 1);
+        assert_eq!(sent_transactions[0].swap_id, dependencies.swap_id);
+        assert_eq!(broadcast.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,349 @@
+//! A swap from Bitcoin to Ether with its HTLC on Bitcoin funded together with
+//! everything needed to act on it, for testing the tasks that redeem and
+//! refund on their own.
+
+use crate::{
+    btsieve::{
+        bitcoin::{
+            BitcoinConnector, BitcoinFeeEstimator, BitcoinLedgerConnector, BlockSource,
+            EsploraConnector, FeeEstimator,
+        },
+        ethereum::{EthereumGasEstimator, GasEstimator, Web3Connector},
+        BlockPoller,
+    },
+    db::{
+        ActionAttempt, AssetKind, DetermineTypes, LedgerKind, LoadSentTransactions, Retrieve, Save,
+        SentTransaction, Swap, SwapTypes,
+    },
+    ethereum::{web3::transports::EventLoopHandle, Address, EtherQuantity},
+    refund_watchdog::LedgerTime,
+    seed::Seed,
+    swap_protocols::{
+        ledger::{ethereum::ChainId, Bitcoin, Ethereum},
+        rfc003::{
+            alice, bob,
+            state_machine::SwapStates,
+            state_store::{self, InMemoryStateStore, StateStore},
+            Accept, ActorState, LedgerState, Request, SecretSource, SwapCommunication,
+        },
+        HashFunction, Role, SwapId,
+    },
+    timestamp::Timestamp,
+    wallet::{
+        bitcoin::{BitcoinWallet, Wallet},
+        ethereum::{Account, EthereumAccount},
+        psbt::{BitcoinWatchOnlyWallet, WatchOnlyWallet},
+    },
+};
+use async_trait::async_trait;
+use bitcoin::{consensus::encode::deserialize, Amount, OutPoint, Transaction, TxOut};
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// The expiry of the alpha HTLC, the beta HTLC expires an hour earlier.
+pub const EXPIRY: u32 = 2_000_000_000;
+
+/// Accepts every transaction posted to the Esplora API it serves and
+/// remembers it.
+pub fn serve_esplora(broadcast: Arc<Mutex<Vec<Transaction>>>) -> reqwest::Url {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/api", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut reader = BufReader::new(stream.unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                let line = line.to_lowercase();
+                if line.starts_with("content-length:") {
+                    content_length = line["content-length:".len()..].trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let transaction: Transaction = deserialize(&hex::decode(body).unwrap()).unwrap();
+            let txid = transaction.txid().to_string();
+            broadcast.lock().unwrap().push(transaction);
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                txid.len(),
+                txid
+            )
+            .unwrap();
+        }
+    });
+
+    url.parse().unwrap()
+}
+
+/// Our side of the swap, as Alice it expired 100 seconds ago and as Bob Alice
+/// revealed the secret.
+///
+/// The swaps in `unknown_swaps` are listed before it but their types cannot
+/// be determined.
+#[allow(missing_debug_implementations)]
+pub struct Dependencies {
+    pub swap_id: SwapId,
+    pub role: Role,
+    pub state_store: InMemoryStateStore,
+    pub unknown_swaps: Vec<SwapId>,
+    pub sent_transactions: Mutex<Vec<SentTransaction>>,
+    pub action_attempts: Mutex<Vec<ActionAttempt>>,
+    pub bitcoin_wallet: Wallet,
+    pub ethereum_account: Account,
+    pub bitcoin_connector: BitcoinConnector,
+    pub bitcoin_fee_estimator: FeeEstimator,
+    pub bitcoin_watch_only_wallet: WatchOnlyWallet,
+    pub ethereum_gas_estimator: GasEstimator,
+    _event_loop_handle: Option<EventLoopHandle>,
+}
+
+impl Dependencies {
+    pub fn new(runtime: &tokio::runtime::Runtime, esplora: reqwest::Url, role: Role) -> Self {
+        let seed = Seed::from(*b"hello world, you are beautiful!!");
+        let swap_id = SwapId::default();
+        let swap_seed = seed.swap_seed(swap_id);
+
+        let bitcoin_connector = BitcoinConnector::Blocks(BlockPoller::new(BlockSource::Esplora(
+            EsploraConnector::new(esplora, bitcoin::Network::Regtest).unwrap(),
+        )));
+        let (ethereum_connector, event_loop_handle) =
+            Web3Connector::new("http://localhost:1".parse().unwrap(), runtime.executor()).unwrap();
+        let ethereum_connectors =
+            std::iter::once((ChainId::regtest(), ethereum_connector)).collect::<HashMap<_, _>>();
+
+        // As Bob the secret comes from Alice, who we are not.
+        let alice_seed = match role {
+            Role::Alice => swap_seed,
+            Role::Bob => Seed::from([1u8; 32]),
+        };
+        let secret = alice_seed.secret();
+        let refund_identity = crate::bitcoin::PublicKey::from_secret_key(
+            &*crate::SECP,
+            &alice_seed.secp256k1_refund(),
+        );
+        let redeem_identity = crate::bitcoin::PublicKey::from_secret_key(
+            &*crate::SECP,
+            &swap_seed.secp256k1_redeem(),
+        );
+        let ethereum_address: Address = "8457037fcd80a8650c4692d7fcfc1d0a96b92867".parse().unwrap();
+        let request = Request {
+            swap_id,
+            alpha_ledger: Bitcoin::default(),
+            beta_ledger: Ethereum::default(),
+            alpha_asset: Amount::from_btc(1.0).unwrap(),
+            beta_asset: EtherQuantity::from_eth(10.0),
+            hash_function: HashFunction::Sha256,
+            alpha_ledger_refund_identity: refund_identity,
+            beta_ledger_redeem_identity: ethereum_address,
+            alpha_expiry: Timestamp::from(EXPIRY),
+            beta_expiry: Timestamp::from(EXPIRY - 3600),
+            secret_hash: secret.hash(HashFunction::Sha256),
+        };
+        let response = Accept {
+            swap_id,
+            beta_ledger_refund_identity: ethereum_address,
+            alpha_ledger_redeem_identity: redeem_identity,
+        };
+        let fund_transaction = Transaction {
+            version: 2,
+            lock_time: 0,
+            input: Vec::new(),
+            output: vec![TxOut {
+                value: 100_000_000,
+                script_pubkey: Default::default(),
+            }],
+        };
+        let swap_communication = SwapCommunication::Accepted { request, response };
+        let alpha_ledger_state = LedgerState::Funded {
+            htlc_location: OutPoint {
+                txid: fund_transaction.txid(),
+                vout: 0,
+            },
+            deploy_transaction: fund_transaction.clone(),
+            fund_transaction,
+        };
+        let state_store = InMemoryStateStore::default();
+        match role {
+            Role::Alice => state_store.insert(swap_id, alice::State {
+                swap_communication,
+                alpha_ledger_state,
+                beta_ledger_state: LedgerState::NotDeployed,
+                secret_source: Arc::new(swap_seed),
+                error: None,
+            }),
+            Role::Bob => state_store.insert(swap_id, bob::State {
+                swap_communication,
+                alpha_ledger_state,
+                beta_ledger_state: LedgerState::NotDeployed,
+                secret_source: Arc::new(swap_seed),
+                secret: Some(secret),
+                error: None,
+            }),
+        }
+
+        Self {
+            swap_id,
+            role,
+            state_store,
+            unknown_swaps: Vec::new(),
+            sent_transactions: Mutex::new(Vec::new()),
+            action_attempts: Mutex::new(Vec::new()),
+            bitcoin_wallet: Wallet::new(&seed, bitcoin::Network::Regtest, Default::default()),
+            ethereum_account: Account::new(&seed, ethereum_connectors.clone()),
+            bitcoin_connector: bitcoin_connector.clone(),
+            bitcoin_fee_estimator: FeeEstimator::new(bitcoin_connector.clone(), 6),
+            bitcoin_watch_only_wallet: WatchOnlyWallet::new(None, bitcoin_connector),
+            ethereum_gas_estimator: GasEstimator::new(ethereum_connectors, 0),
+            _event_loop_handle: event_loop_handle,
+        }
+    }
+}
+
+impl StateStore for Dependencies {
+    fn insert<A: ActorState>(&self, key: SwapId, value: A) {
+        self.state_store.insert(key, value)
+    }
+
+    fn get<A: ActorState>(&self, key: &SwapId) -> Result<Option<A>, state_store::Error> {
+        self.state_store.get(key)
+    }
+
+    fn update<A: ActorState>(&self, key: &SwapId, update: SwapStates<A::AL, A::BL, A::AA, A::BA>) {
+        self.state_store.update::<A>(key, update)
+    }
+}
+
+#[async_trait]
+impl Retrieve for Dependencies {
+    async fn get(&self, key: &SwapId) -> anyhow::Result<Swap> {
+        self.all()
+            .await?
+            .into_iter()
+            .find(|swap| swap.swap_id == *key)
+            .ok_or_else(|| anyhow::anyhow!("swap {} not found", key))
+    }
+
+    async fn all(&self) -> anyhow::Result<Vec<Swap>> {
+        Ok(self
+            .unknown_swaps
+            .iter()
+            .chain(std::iter::once(&self.swap_id))
+            .map(|swap_id| {
+                Swap::new(
+                    *swap_id,
+                    self.role,
+                    "QmfUfpC2frwFvcDzpspnfZitHt5wct6n4kpG5jzgRdsxkY"
+                        .parse()
+                        .unwrap(),
+                )
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl DetermineTypes for Dependencies {
+    async fn determine_types(&self, key: &SwapId) -> anyhow::Result<SwapTypes> {
+        if *key != self.swap_id {
+            anyhow::bail!("types of swap {} are unknown", key)
+        }
+
+        Ok(SwapTypes {
+            alpha_ledger: LedgerKind::Bitcoin,
+            beta_ledger: LedgerKind::Ethereum,
+            alpha_asset: AssetKind::Bitcoin,
+            beta_asset: AssetKind::Ether,
+            role: self.role,
+        })
+    }
+}
+
+#[async_trait]
+impl Save<SentTransaction> for Dependencies {
+    async fn save(&self, sent_transaction: SentTransaction) -> anyhow::Result<()> {
+        self.sent_transactions
+            .lock()
+            .unwrap()
+            .push(sent_transaction);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Save<ActionAttempt> for Dependencies {
+    async fn save(&self, action_attempt: ActionAttempt) -> anyhow::Result<()> {
+        self.action_attempts.lock().unwrap().push(action_attempt);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LoadSentTransactions for Dependencies {
+    async fn load_sent_transactions(&self, _: &SwapId) -> anyhow::Result<Vec<SentTransaction>> {
+        Ok(self.sent_transactions.lock().unwrap().clone())
+    }
+}
+
+#[async_trait]
+impl LedgerTime<Bitcoin> for Dependencies {
+    async fn ledger_time(&self, _: Bitcoin) -> anyhow::Result<Timestamp> {
+        Ok(Timestamp::from(EXPIRY + 100))
+    }
+}
+
+#[async_trait]
+impl LedgerTime<Ethereum> for Dependencies {
+    async fn ledger_time(&self, _: Ethereum) -> anyhow::Result<Timestamp> {
+        Ok(Timestamp::from(EXPIRY + 100))
+    }
+}
+
+impl BitcoinWallet for Dependencies {
+    fn bitcoin_wallet(&self) -> &Wallet {
+        &self.bitcoin_wallet
+    }
+}
+
+impl EthereumAccount for Dependencies {
+    fn ethereum_account(&self) -> &Account {
+        &self.ethereum_account
+    }
+}
+
+impl BitcoinLedgerConnector for Dependencies {
+    fn bitcoin_connector(&self) -> &BitcoinConnector {
+        &self.bitcoin_connector
+    }
+}
+
+impl BitcoinFeeEstimator for Dependencies {
+    fn bitcoin_fee_estimator(&self) -> &FeeEstimator {
+        &self.bitcoin_fee_estimator
+    }
+}
+
+impl BitcoinWatchOnlyWallet for Dependencies {
+    fn bitcoin_watch_only_wallet(&self) -> &WatchOnlyWallet {
+        &self.bitcoin_watch_only_wallet
+    }
+}
+
+impl EthereumGasEstimator for Dependencies {
+    fn ethereum_gas_estimator(&self) -> &GasEstimator {
+        &self.ethereum_gas_estimator
+    }
+}
@@ -36,6 +36,48 @@ pub mod bitcoin {
             }
         }
     }
+
+    /// Spends the HTLCs of several swaps in one transaction. Each input keeps
+    /// its own witness, the lock time of the transaction is the latest one
+    /// any of the inputs needs.
+    pub fn spend_all_to(spend_outputs: Vec<SpendOutput>, to_address: Address) -> PrimedTransaction {
+        PrimedTransaction {
+            inputs: spend_outputs
+                .into_iter()
+                .map(|spend_output| spend_output.output)
+                .collect(),
+            output_address: to_address,
+        }
+    }
+}
+
+pub mod litecoin {
+    use crate::litecoin::{Address, Amount};
+    use blockchain_contracts::bitcoin::witness::{PrimedInput, PrimedTransaction};
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SendToAddress {
+        pub to: Address,
+        pub amount: Amount,
+        pub network: bitcoin::Network,
+    }
+
+    /// Litecoin transactions are signed like Bitcoin ones, the value of
+    /// `output` is in litoshis.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SpendOutput {
+        pub output: PrimedInput,
+        pub network: bitcoin::Network,
+    }
+
+    impl SpendOutput {
+        pub fn spend_to(self, to_address: Address) -> PrimedTransaction {
+            PrimedTransaction {
+                inputs: vec![self.output],
+                output_address: to_address.to_bitcoin_address(),
+            }
+        }
+    }
 }
 
 pub mod ethereum {
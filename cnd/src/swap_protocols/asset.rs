@@ -1,4 +1,7 @@
-use crate::ethereum::{Erc20Token, EtherQuantity};
+use crate::{
+    ethereum::{Erc20Token, Erc721Token, EtherQuantity},
+    litecoin,
+};
 use bitcoin::Amount;
 use derivative::Derivative;
 use std::{
@@ -28,12 +31,18 @@ impl Asset for EtherQuantity {}
 
 impl Asset for Erc20Token {}
 
+impl Asset for Erc721Token {}
+
+impl Asset for litecoin::Amount {}
+
 #[derive(Clone, Copy, Derivative, PartialEq)]
 #[derivative(Debug = "transparent")]
 pub enum AssetKind {
     Bitcoin(Amount),
     Ether(EtherQuantity),
     Erc20(Erc20Token),
+    Erc721(Erc721Token),
+    Litecoin(litecoin::Amount),
 }
 
 impl From<Amount> for AssetKind {
@@ -53,3 +62,15 @@ impl From<Erc20Token> for AssetKind {
         AssetKind::Erc20(quantity)
     }
 }
+
+impl From<Erc721Token> for AssetKind {
+    fn from(token: Erc721Token) -> Self {
+        AssetKind::Erc721(token)
+    }
+}
+
+impl From<litecoin::Amount> for AssetKind {
+    fn from(amount: litecoin::Amount) -> Self {
+        AssetKind::Litecoin(amount)
+    }
+}
@@ -1,33 +1,55 @@
 use crate::{
-    btsieve::{bitcoin::BitcoindConnector, ethereum::Web3Connector},
+    btsieve::{
+        self,
+        bitcoin::{BitcoinConnector, BitcoinFeeEstimator, BitcoinLedgerConnector, FeeEstimator},
+        ethereum::{
+            EthereumChains, EthereumGasEstimator, GasEstimator, UnsupportedEthereumChain,
+            Web3Connector,
+        },
+        litecoin::LitecoindConnector,
+        BlockPoller, Confirmations, IncludingBlocks, LatestBlock,
+    },
     db::{
-        AcceptedSwap, DetermineTypes, LoadAcceptedSwap, Retrieve, Save, Saver, Sqlite, Swap,
-        SwapTypes,
+        AcceptedSwap, DetermineTypes, LedgerStates, LoadAcceptedSwap, LoadLedgerStates,
+        LoadSentTransactions, Retrieve, Save, Saver, SentTransaction, Sqlite, Swap, SwapTypes,
     },
+    litecoin,
     network::{DialInformation, Network, RequestError},
+    refund_watchdog::LedgerTime,
     seed::{Seed, SwapSeed},
     swap_protocols::{
         asset::Asset,
-        ledger::{Bitcoin, Ethereum},
+        ledger::{ethereum::ChainId, Bitcoin, Ethereum, Litecoin},
         rfc003::{
             self,
             events::{
-                Deployed, DeployedFuture, Funded, FundedFuture, HtlcEvents,
-                RedeemedOrRefundedFuture,
+                Deployed, DeployedFuture, Funded, FundedFuture, HtlcEvents, HtlcWatcher,
+                RedeemedOrRefundedFuture, RetractedFuture, TransactionConfirmations,
             },
             state_machine::{HtlcParams, SwapStates},
-            state_store::{self, InMemoryStateStore, StateStore},
+            state_store::{self, SqliteStateStore, StateStore},
             ActorState, Ledger,
         },
         SwapId,
     },
+    timestamp::Timestamp,
+    wallet::{
+        bitcoin::{BitcoinWallet, Wallet},
+        ethereum::{Account, EthereumAccount},
+        psbt::{BitcoinWatchOnlyWallet, WatchOnlyWallet},
+    },
 };
 use async_trait::async_trait;
-use bitcoin::Amount;
-use futures::{sync::oneshot::Sender, Future};
+use bitcoin::{hashes::sha256d, Amount};
+use futures::{
+    future::{self, Either},
+    sync::oneshot::Sender,
+    Future,
+};
+use futures_core::{compat::Future01CompatExt, FutureExt, TryFutureExt};
 use libp2p::PeerId;
 use libp2p_comit::frame::Response;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio::{executor, runtime::TaskExecutor};
 
 /// This is a facade that implements all the required traits and forwards them
@@ -35,9 +57,20 @@ use tokio::{executor, runtime::TaskExecutor};
 /// HTTP API controllers small and still access all the functionality we need.
 #[allow(missing_debug_implementations)]
 pub struct Facade<S> {
-    pub bitcoin_connector: BitcoindConnector,
-    pub ethereum_connector: Web3Connector,
-    pub state_store: Arc<InMemoryStateStore>,
+    pub bitcoin_connector: BitcoinConnector,
+    /// Connectors to the nodes of the Ethereum chains cnd supports, swaps on
+    /// any other chain are declined.
+    pub ethereum_connectors: HashMap<ChainId, BlockPoller<Web3Connector>>,
+    pub litecoin_connector: BlockPoller<LitecoindConnector>,
+    pub bitcoin_confirmations: Confirmations<sha256d::Hash>,
+    pub ethereum_confirmations: Confirmations<crate::ethereum::H256>,
+    pub litecoin_confirmations: Confirmations<sha256d::Hash>,
+    pub bitcoin_fee_estimator: FeeEstimator,
+    pub bitcoin_wallet: Wallet,
+    pub bitcoin_watch_only_wallet: WatchOnlyWallet,
+    pub ethereum_account: Account,
+    pub ethereum_gas_estimator: GasEstimator,
+    pub state_store: Arc<SqliteStateStore>,
     pub seed: Seed,
     pub swarm: Arc<S>, // S is the libp2p Swarm within a mutex.
     pub db: Sqlite,
@@ -48,7 +81,16 @@ impl<S> Clone for Facade<S> {
     fn clone(&self) -> Self {
         Self {
             bitcoin_connector: self.bitcoin_connector.clone(),
-            ethereum_connector: self.ethereum_connector.clone(),
+            ethereum_connectors: self.ethereum_connectors.clone(),
+            litecoin_connector: self.litecoin_connector.clone(),
+            bitcoin_confirmations: self.bitcoin_confirmations.clone(),
+            ethereum_confirmations: self.ethereum_confirmations.clone(),
+            litecoin_confirmations: self.litecoin_confirmations.clone(),
+            bitcoin_fee_estimator: self.bitcoin_fee_estimator.clone(),
+            bitcoin_wallet: self.bitcoin_wallet.clone(),
+            bitcoin_watch_only_wallet: self.bitcoin_watch_only_wallet.clone(),
+            ethereum_account: self.ethereum_account.clone(),
+            ethereum_gas_estimator: self.ethereum_gas_estimator.clone(),
             state_store: Arc::clone(&self.state_store),
             seed: self.seed,
             swarm: Arc::clone(&self.swarm),
@@ -112,6 +154,69 @@ where
     }
 }
 
+impl<S> BitcoinWallet for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn bitcoin_wallet(&self) -> &Wallet {
+        &self.bitcoin_wallet
+    }
+}
+
+impl<S> BitcoinWatchOnlyWallet for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn bitcoin_watch_only_wallet(&self) -> &WatchOnlyWallet {
+        &self.bitcoin_watch_only_wallet
+    }
+}
+
+impl<S> EthereumGasEstimator for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn ethereum_gas_estimator(&self) -> &GasEstimator {
+        &self.ethereum_gas_estimator
+    }
+}
+
+impl<S> EthereumChains for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn supports_ethereum_chain(&self, chain_id: ChainId) -> bool {
+        self.ethereum_connectors.contains_key(&chain_id)
+    }
+}
+
+impl<S> BitcoinLedgerConnector for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn bitcoin_connector(&self) -> &BitcoinConnector {
+        &self.bitcoin_connector
+    }
+}
+
+impl<S> BitcoinFeeEstimator for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn bitcoin_fee_estimator(&self) -> &FeeEstimator {
+        &self.bitcoin_fee_estimator
+    }
+}
+
+impl<S> EthereumAccount for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn ethereum_account(&self) -> &Account {
+        &self.ethereum_account
+    }
+}
+
 #[async_trait]
 impl<S> Retrieve for Facade<S>
 where
@@ -144,6 +249,31 @@ where
     }
 }
 
+#[async_trait]
+impl<S, AL, BL> LoadLedgerStates<AL, BL> for Facade<S>
+where
+    S: Send + Sync + 'static,
+    AL: Ledger,
+    BL: Ledger,
+{
+    async fn load_ledger_states(&self, swap_id: &SwapId) -> anyhow::Result<LedgerStates<AL, BL>> {
+        self.db.load_ledger_states(swap_id).await
+    }
+}
+
+#[async_trait]
+impl<S> LoadSentTransactions for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    async fn load_sent_transactions(
+        &self,
+        swap_id: &SwapId,
+    ) -> anyhow::Result<Vec<SentTransaction>> {
+        self.db.load_sent_transactions(swap_id).await
+    }
+}
+
 #[async_trait]
 impl<S> DetermineTypes for Facade<S>
 where
@@ -169,6 +299,151 @@ where
     }
 }
 
+impl<S> Facade<S> {
+    fn bitcoin_htlc_watcher(&self) -> HtlcWatcher<BitcoinConnector> {
+        HtlcWatcher {
+            connector: self.bitcoin_connector.clone(),
+            db: self.db.clone(),
+        }
+    }
+
+    /// Returns the connector to the node of the Ethereum chain `chain_id`.
+    fn ethereum_connector(&self, chain_id: ChainId) -> anyhow::Result<BlockPoller<Web3Connector>> {
+        self.ethereum_connectors
+            .get(&chain_id)
+            .cloned()
+            .ok_or_else(|| anyhow::Error::from(UnsupportedEthereumChain { chain_id }))
+    }
+
+    fn ethereum_htlc_watcher(
+        &self,
+        chain_id: ChainId,
+    ) -> Result<HtlcWatcher<BlockPoller<Web3Connector>>, rfc003::Error> {
+        let connector = self
+            .ethereum_connector(chain_id)
+            .map_err(|e| rfc003::Error::Internal(e.to_string()))?;
+
+        Ok(HtlcWatcher {
+            connector,
+            db: self.db.clone(),
+        })
+    }
+
+    /// Delays `event` until `transaction` has the number of confirmations
+    /// configured for Bitcoin.
+    fn bitcoin_confirmed<T>(
+        &self,
+        event: T,
+        transaction: &bitcoin::Transaction,
+    ) -> Box<dyn Future<Item = T, Error = rfc003::Error> + Send>
+    where
+        T: Send + 'static,
+    {
+        let connector = self.bitcoin_connector.clone();
+        let confirmations = self.bitcoin_confirmations.clone();
+        let transaction = transaction.clone();
+        let task_executor = self.task_executor.clone();
+
+        Box::new(
+            async move {
+                connector
+                    .clone()
+                    .transaction_confirmed(transaction.clone(), confirmations.clone())
+                    .await;
+                task_executor.spawn(
+                    connector
+                        .follow_confirmations(transaction, confirmations)
+                        .unit_error()
+                        .boxed()
+                        .compat(),
+                );
+                Ok(event)
+            }
+            .boxed()
+            .compat(),
+        )
+    }
+
+    fn litecoin_htlc_watcher(&self) -> HtlcWatcher<BlockPoller<LitecoindConnector>> {
+        HtlcWatcher {
+            connector: self.litecoin_connector.clone(),
+            db: self.db.clone(),
+        }
+    }
+
+    /// Delays `event` until `transaction` has the number of confirmations
+    /// configured for Litecoin.
+    fn litecoin_confirmed<T>(
+        &self,
+        event: T,
+        transaction: &bitcoin::Transaction,
+    ) -> Box<dyn Future<Item = T, Error = rfc003::Error> + Send>
+    where
+        T: Send + 'static,
+    {
+        let connector = self.litecoin_connector.clone();
+        let confirmations = self.litecoin_confirmations.clone();
+        let txid = transaction.txid();
+        let task_executor = self.task_executor.clone();
+
+        Box::new(
+            async move {
+                btsieve::bitcoin::transaction_confirmed(
+                    connector.clone(),
+                    txid,
+                    confirmations.clone(),
+                )
+                .await;
+                task_executor.spawn(
+                    btsieve::bitcoin::follow_confirmations(connector, txid, confirmations)
+                        .unit_error()
+                        .boxed()
+                        .compat(),
+                );
+                Ok(event)
+            }
+            .boxed()
+            .compat(),
+        )
+    }
+
+    /// Delays `event` until `transaction` has the number of confirmations
+    /// configured for Ethereum.
+    fn ethereum_confirmed<T>(
+        &self,
+        event: T,
+        connector: BlockPoller<Web3Connector>,
+        transaction: &crate::ethereum::Transaction,
+    ) -> Box<dyn Future<Item = T, Error = rfc003::Error> + Send>
+    where
+        T: Send + 'static,
+    {
+        let confirmations = self.ethereum_confirmations.clone();
+        let transaction = transaction.clone();
+        let task_executor = self.task_executor.clone();
+
+        Box::new(
+            async move {
+                btsieve::ethereum::transaction_confirmed(
+                    connector.clone(),
+                    transaction.clone(),
+                    confirmations.clone(),
+                )
+                .await;
+                task_executor.spawn(
+                    btsieve::ethereum::follow_confirmations(connector, transaction, confirmations)
+                        .unit_error()
+                        .boxed()
+                        .compat(),
+                );
+                Ok(event)
+            }
+            .boxed()
+            .compat(),
+        )
+    }
+}
+
 impl<S> HtlcEvents<Bitcoin, Amount> for Facade<S>
 where
     S: Send + Sync + 'static,
@@ -177,7 +452,16 @@ where
         &self,
         htlc_params: HtlcParams<Bitcoin, Amount>,
     ) -> Box<DeployedFuture<Bitcoin>> {
-        self.bitcoin_connector.htlc_deployed(htlc_params)
+        let facade = self.clone();
+
+        Box::new(
+            self.bitcoin_htlc_watcher()
+                .htlc_deployed(htlc_params)
+                .and_then(move |deployed| {
+                    let transaction = deployed.transaction.clone();
+                    facade.bitcoin_confirmed(deployed, &transaction)
+                }),
+        )
     }
 
     fn htlc_funded(
@@ -185,8 +469,16 @@ where
         htlc_params: HtlcParams<Bitcoin, Amount>,
         htlc_deployment: &Deployed<Bitcoin>,
     ) -> Box<FundedFuture<Bitcoin, Amount>> {
-        self.bitcoin_connector
-            .htlc_funded(htlc_params, htlc_deployment)
+        let facade = self.clone();
+
+        Box::new(
+            self.bitcoin_htlc_watcher()
+                .htlc_funded(htlc_params, htlc_deployment)
+                .and_then(move |funded| {
+                    let transaction = funded.transaction.clone();
+                    facade.bitcoin_confirmed(funded, &transaction)
+                }),
+        )
     }
 
     fn htlc_redeemed_or_refunded(
@@ -195,8 +487,31 @@ where
         htlc_deployment: &Deployed<Bitcoin>,
         htlc_funding: &Funded<Bitcoin, Amount>,
     ) -> Box<RedeemedOrRefundedFuture<Bitcoin>> {
-        self.bitcoin_connector
-            .htlc_redeemed_or_refunded(htlc_params, htlc_deployment, htlc_funding)
+        let facade = self.clone();
+
+        Box::new(
+            self.bitcoin_htlc_watcher()
+                .htlc_redeemed_or_refunded(htlc_params, htlc_deployment, htlc_funding)
+                .and_then(move |redeemed_or_refunded| {
+                    let transaction = match &redeemed_or_refunded {
+                        Either::A(redeemed) => redeemed.transaction.clone(),
+                        Either::B(refunded) => refunded.transaction.clone(),
+                    };
+                    facade.bitcoin_confirmed(redeemed_or_refunded, &transaction)
+                }),
+        )
+    }
+
+    fn transaction_retracted(
+        &self,
+        ledger: Bitcoin,
+        transaction: &bitcoin::Transaction,
+    ) -> Box<RetractedFuture> {
+        HtlcEvents::<Bitcoin, Amount>::transaction_retracted(
+            &self.bitcoin_htlc_watcher(),
+            ledger,
+            transaction,
+        )
     }
 }
 
@@ -204,10 +519,24 @@ impl<A, S> HtlcEvents<Ethereum, A> for Facade<S>
 where
     S: Send + Sync + 'static,
     A: Asset + Send + Sync + 'static,
-    Web3Connector: HtlcEvents<Ethereum, A>,
+    HtlcWatcher<BlockPoller<Web3Connector>>: HtlcEvents<Ethereum, A>,
 {
     fn htlc_deployed(&self, htlc_params: HtlcParams<Ethereum, A>) -> Box<DeployedFuture<Ethereum>> {
-        self.ethereum_connector.htlc_deployed(htlc_params)
+        let facade = self.clone();
+        let watcher = match self.ethereum_htlc_watcher(htlc_params.ledger.chain_id) {
+            Ok(watcher) => watcher,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let connector = watcher.connector.clone();
+
+        Box::new(
+            watcher
+                .htlc_deployed(htlc_params)
+                .and_then(move |deployed| {
+                    let transaction = deployed.transaction.clone();
+                    facade.ethereum_confirmed(deployed, connector, &transaction)
+                }),
+        )
     }
 
     fn htlc_funded(
@@ -215,8 +544,21 @@ where
         htlc_params: HtlcParams<Ethereum, A>,
         htlc_deployment: &Deployed<Ethereum>,
     ) -> Box<FundedFuture<Ethereum, A>> {
-        self.ethereum_connector
-            .htlc_funded(htlc_params, htlc_deployment)
+        let facade = self.clone();
+        let watcher = match self.ethereum_htlc_watcher(htlc_params.ledger.chain_id) {
+            Ok(watcher) => watcher,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let connector = watcher.connector.clone();
+
+        Box::new(
+            watcher
+                .htlc_funded(htlc_params, htlc_deployment)
+                .and_then(move |funded| {
+                    let transaction = funded.transaction.clone();
+                    facade.ethereum_confirmed(funded, connector, &transaction)
+                }),
+        )
     }
 
     fn htlc_redeemed_or_refunded(
@@ -225,14 +567,187 @@ where
         htlc_deployment: &Deployed<Ethereum>,
         htlc_funding: &Funded<Ethereum, A>,
     ) -> Box<RedeemedOrRefundedFuture<Ethereum>> {
-        self.ethereum_connector.htlc_redeemed_or_refunded(
-            htlc_params,
-            htlc_deployment,
-            htlc_funding,
+        let facade = self.clone();
+        let watcher = match self.ethereum_htlc_watcher(htlc_params.ledger.chain_id) {
+            Ok(watcher) => watcher,
+            Err(e) => return Box::new(future::err(e)),
+        };
+        let connector = watcher.connector.clone();
+
+        Box::new(
+            watcher
+                .htlc_redeemed_or_refunded(htlc_params, htlc_deployment, htlc_funding)
+                .and_then(move |redeemed_or_refunded| {
+                    let transaction = match &redeemed_or_refunded {
+                        Either::A(redeemed) => redeemed.transaction.clone(),
+                        Either::B(refunded) => refunded.transaction.clone(),
+                    };
+                    facade.ethereum_confirmed(redeemed_or_refunded, connector, &transaction)
+                }),
+        )
+    }
+
+    fn transaction_retracted(
+        &self,
+        ledger: Ethereum,
+        transaction: &crate::ethereum::Transaction,
+    ) -> Box<RetractedFuture> {
+        match self.ethereum_htlc_watcher(ledger.chain_id) {
+            Ok(watcher) => {
+                HtlcEvents::<Ethereum, A>::transaction_retracted(&watcher, ledger, transaction)
+            }
+            Err(e) => Box::new(future::err(e)),
+        }
+    }
+}
+
+impl<S> HtlcEvents<Litecoin, litecoin::Amount> for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn htlc_deployed(
+        &self,
+        htlc_params: HtlcParams<Litecoin, litecoin::Amount>,
+    ) -> Box<DeployedFuture<Litecoin>> {
+        let facade = self.clone();
+
+        Box::new(
+            self.litecoin_htlc_watcher()
+                .htlc_deployed(htlc_params)
+                .and_then(move |deployed| {
+                    let transaction = deployed.transaction.clone();
+                    facade.litecoin_confirmed(deployed, &transaction)
+                }),
+        )
+    }
+
+    fn htlc_funded(
+        &self,
+        htlc_params: HtlcParams<Litecoin, litecoin::Amount>,
+        htlc_deployment: &Deployed<Litecoin>,
+    ) -> Box<FundedFuture<Litecoin, litecoin::Amount>> {
+        let facade = self.clone();
+
+        Box::new(
+            self.litecoin_htlc_watcher()
+                .htlc_funded(htlc_params, htlc_deployment)
+                .and_then(move |funded| {
+                    let transaction = funded.transaction.clone();
+                    facade.litecoin_confirmed(funded, &transaction)
+                }),
+        )
+    }
+
+    fn htlc_redeemed_or_refunded(
+        &self,
+        htlc_params: HtlcParams<Litecoin, litecoin::Amount>,
+        htlc_deployment: &Deployed<Litecoin>,
+        htlc_funding: &Funded<Litecoin, litecoin::Amount>,
+    ) -> Box<RedeemedOrRefundedFuture<Litecoin>> {
+        let facade = self.clone();
+
+        Box::new(
+            self.litecoin_htlc_watcher()
+                .htlc_redeemed_or_refunded(htlc_params, htlc_deployment, htlc_funding)
+                .and_then(move |redeemed_or_refunded| {
+                    let transaction = match &redeemed_or_refunded {
+                        Either::A(redeemed) => redeemed.transaction.clone(),
+                        Either::B(refunded) => refunded.transaction.clone(),
+                    };
+                    facade.litecoin_confirmed(redeemed_or_refunded, &transaction)
+                }),
+        )
+    }
+
+    fn transaction_retracted(
+        &self,
+        ledger: Litecoin,
+        transaction: &bitcoin::Transaction,
+    ) -> Box<RetractedFuture> {
+        HtlcEvents::<Litecoin, litecoin::Amount>::transaction_retracted(
+            &self.litecoin_htlc_watcher(),
+            ledger,
+            transaction,
         )
     }
 }
 
+impl<S> TransactionConfirmations<Bitcoin> for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn transaction_confirmations(&self, transaction: &bitcoin::Transaction) -> Option<u32> {
+        self.bitcoin_confirmations.get(&transaction.txid())
+    }
+
+    fn forget_transaction(&self, transaction: &bitcoin::Transaction) {
+        self.bitcoin_confirmations.remove(&transaction.txid());
+        self.bitcoin_connector.forget_transaction(transaction);
+    }
+}
+
+impl<S> TransactionConfirmations<Ethereum> for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn transaction_confirmations(&self, transaction: &crate::ethereum::Transaction) -> Option<u32> {
+        self.ethereum_confirmations.get(&transaction.hash)
+    }
+
+    fn forget_transaction(&self, transaction: &crate::ethereum::Transaction) {
+        self.ethereum_confirmations.remove(&transaction.hash);
+    }
+}
+
+impl<S> TransactionConfirmations<Litecoin> for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn transaction_confirmations(&self, transaction: &bitcoin::Transaction) -> Option<u32> {
+        self.litecoin_confirmations.get(&transaction.txid())
+    }
+
+    fn forget_transaction(&self, transaction: &bitcoin::Transaction) {
+        self.litecoin_confirmations.remove(&transaction.txid());
+        self.litecoin_connector
+            .forget_including_block(&transaction.txid());
+    }
+}
+
+#[async_trait]
+impl<S> LedgerTime<Bitcoin> for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    async fn ledger_time(&self, _: Bitcoin) -> anyhow::Result<Timestamp> {
+        let median_time_past = self
+            .bitcoin_connector
+            .median_time_past()
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to get median time past: {:?}", e))?;
+
+        Ok(Timestamp::from(median_time_past))
+    }
+}
+
+#[async_trait]
+impl<S> LedgerTime<Ethereum> for Facade<S>
+where
+    S: Send + Sync + 'static,
+{
+    async fn ledger_time(&self, ledger: Ethereum) -> anyhow::Result<Timestamp> {
+        let latest_block = self
+            .ethereum_connector(ledger.chain_id)?
+            .latest_block()
+            .compat()
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to get latest block: {:?}", e))?
+            .ok_or_else(|| anyhow::anyhow!("node did not return the latest block"))?;
+
+        Ok(Timestamp::from(latest_block.timestamp.low_u32()))
+    }
+}
+
 impl<S> executor::Executor for Facade<S>
 where
     S: Send + Sync + 'static,
@@ -1,13 +1,14 @@
 use crate::{
+    db::LedgerStates,
     seed::SwapSeed,
     swap_protocols::{
         asset::Asset,
         rfc003::{
             alice, bob,
-            events::HtlcEvents,
-            state_machine::{self, SwapStates},
+            events::{HtlcEvents, TransactionConfirmations},
+            state_machine::{self, OngoingSwap, SwapStates},
             state_store::StateStore,
-            Accept, Ledger, Request,
+            Accept, ActorState, Ledger, Request,
         },
         Role, SwapId,
     },
@@ -23,7 +24,14 @@ pub fn init_accepted_swap<D, AL: Ledger, BL: Ledger, AA: Asset, BA: Asset>(
     role: Role,
 ) -> anyhow::Result<()>
 where
-    D: StateStore + Clone + SwapSeed + Executor + HtlcEvents<AL, AA> + HtlcEvents<BL, BA>,
+    D: StateStore
+        + Clone
+        + SwapSeed
+        + Executor
+        + HtlcEvents<AL, AA>
+        + HtlcEvents<BL, BA>
+        + TransactionConfirmations<AL>
+        + TransactionConfirmations<BL>,
 {
     let id = request.swap_id;
     let seed = SwapSeed::swap_seed(dependencies, id);
@@ -45,6 +53,79 @@ where
     spawn(dependencies, id, swap_execution, receiver, role)
 }
 
+/// Restores a swap that was loaded from the database.
+///
+/// The actor state is restored from the last known ledger states and the swap
+/// execution resumes from there. Swaps that already finished are only
+/// restored, nothing is spawned for them.
+#[allow(clippy::cognitive_complexity)]
+pub fn restore_accepted_swap<D, AL: Ledger, BL: Ledger, AA: Asset, BA: Asset>(
+    dependencies: &D,
+    request: Request<AL, BL, AA, BA>,
+    accept: Accept<AL, BL>,
+    role: Role,
+    ledger_states: LedgerStates<AL, BL>,
+) -> anyhow::Result<()>
+where
+    D: StateStore
+        + Clone
+        + SwapSeed
+        + Executor
+        + HtlcEvents<AL, AA>
+        + HtlcEvents<BL, BA>
+        + TransactionConfirmations<AL>
+        + TransactionConfirmations<BL>,
+{
+    let id = request.swap_id;
+    let seed = SwapSeed::swap_seed(dependencies, id);
+
+    let secret = match role {
+        Role::Alice => {
+            let mut state = alice::State::accepted(request.clone(), accept, seed);
+            restore_ledger_states(&mut state, &ledger_states);
+            let secret = state.secret();
+            StateStore::insert(dependencies, id, state);
+            secret
+        }
+        Role::Bob => {
+            let mut state = bob::State::accepted(request.clone(), accept, seed);
+            restore_ledger_states(&mut state, &ledger_states);
+            let secret = state.secret();
+            StateStore::insert(dependencies, id, state);
+            secret
+        }
+    };
+
+    let alpha_ledger = request.alpha_ledger;
+    let beta_ledger = request.beta_ledger;
+
+    let state = SwapStates::from_ledger_states(
+        OngoingSwap::new(request, accept),
+        ledger_states.alpha_ledger_state,
+        ledger_states.beta_ledger_state,
+        secret,
+    )?;
+
+    if let SwapStates::Final(_) = state {
+        log::debug!("Swap {} already finished, not resuming it", id);
+        return Ok(());
+    }
+
+    let (swap_execution, receiver) =
+        state_machine::resume_swap(dependencies.clone(), id, alpha_ledger, beta_ledger, state);
+
+    spawn(dependencies, id, swap_execution, receiver, role)
+}
+
+fn restore_ledger_states<A: ActorState>(state: &mut A, ledger_states: &LedgerStates<A::AL, A::BL>) {
+    *state.alpha_ledger_mut() = ledger_states.alpha_ledger_state.clone();
+    *state.beta_ledger_mut() = ledger_states.beta_ledger_state.clone();
+
+    if let Some(secret) = ledger_states.secret {
+        state.set_secret(secret);
+    }
+}
+
 fn spawn<D, AL: Ledger, BL: Ledger, AA: Asset, BA: Asset>(
     dependencies: &D,
     id: SwapId,
@@ -53,7 +134,7 @@ fn spawn<D, AL: Ledger, BL: Ledger, AA: Asset, BA: Asset>(
     role: Role,
 ) -> anyhow::Result<()>
 where
-    D: Executor + StateStore + Clone,
+    D: Executor + StateStore + Clone + TransactionConfirmations<AL> + TransactionConfirmations<BL>,
 {
     let mut dependencies = dependencies.clone();
 
@@ -62,12 +143,24 @@ where
     dependencies.spawn(Box::new(receiver.for_each({
         let dependencies = dependencies.clone();
         move |update| {
+            let finished = if let SwapStates::Final(_) = update {
+                true
+            } else {
+                false
+            };
+
             match role {
                 Role::Alice => {
-                    StateStore::update::<alice::State<AL, BL, AA, BA>>(&dependencies, &id, update)
+                    StateStore::update::<alice::State<AL, BL, AA, BA>>(&dependencies, &id, update);
+                    if finished {
+                        forget_transactions::<_, alice::State<AL, BL, AA, BA>>(&dependencies, &id);
+                    }
                 }
                 Role::Bob => {
-                    StateStore::update::<bob::State<AL, BL, AA, BA>>(&dependencies, &id, update)
+                    StateStore::update::<bob::State<AL, BL, AA, BA>>(&dependencies, &id, update);
+                    if finished {
+                        forget_transactions::<_, bob::State<AL, BL, AA, BA>>(&dependencies, &id);
+                    }
                 }
             }
             Ok(())
@@ -75,3 +168,21 @@ where
     })))?;
     Ok(())
 }
+
+/// Stops counting the confirmations of the transactions of a finished swap.
+fn forget_transactions<D, A: ActorState>(dependencies: &D, id: &SwapId)
+where
+    D: StateStore + TransactionConfirmations<A::AL> + TransactionConfirmations<A::BL>,
+{
+    let state = match StateStore::get::<A>(dependencies, id) {
+        Ok(Some(state)) => state,
+        _ => return,
+    };
+
+    for transaction in state.alpha_ledger().transactions() {
+        TransactionConfirmations::<A::AL>::forget_transaction(dependencies, transaction);
+    }
+    for transaction in state.beta_ledger().transactions() {
+        TransactionConfirmations::<A::BL>::forget_transaction(dependencies, transaction);
+    }
+}
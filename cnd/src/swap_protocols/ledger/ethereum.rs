@@ -3,6 +3,7 @@ use crate::{
     swap_protocols::ledger::{Ledger, LedgerKind},
 };
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// `network` is only kept for backward compatibility with client
 /// and must be removed with issue #1580
@@ -62,3 +63,9 @@ impl From<ChainId> for u32 {
         chain_id.0
     }
 }
+
+impl fmt::Display for ChainId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::swap_protocols::ledger::{Ledger, LedgerKind};
+use bitcoin::{Network, Transaction};
+
+/// Litecoin shares the transaction format of Bitcoin, its networks are
+/// represented through `bitcoin::Network`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Litecoin {
+    pub network: Network,
+}
+
+impl Litecoin {
+    pub fn new(network: Network) -> Self {
+        Litecoin { network }
+    }
+}
+
+impl Default for Litecoin {
+    fn default() -> Self {
+        Litecoin {
+            network: Network::Regtest,
+        }
+    }
+}
+
+impl Ledger for Litecoin {
+    type Identity = crate::bitcoin::PublicKey;
+    type Transaction = Transaction;
+}
+
+impl From<Litecoin> for LedgerKind {
+    fn from(litecoin: Litecoin) -> Self {
+        LedgerKind::Litecoin(litecoin)
+    }
+}
@@ -1,7 +1,8 @@
 mod bitcoin;
 pub mod ethereum;
+mod litecoin;
 
-pub use self::{bitcoin::Bitcoin, ethereum::Ethereum};
+pub use self::{bitcoin::Bitcoin, ethereum::Ethereum, litecoin::Litecoin};
 
 use derivative::Derivative;
 use serde::{de::DeserializeOwned, Serialize};
@@ -36,4 +37,5 @@ pub trait Ledger:
 pub enum LedgerKind {
     Bitcoin(Bitcoin),
     Ethereum(Ethereum),
+    Litecoin(Litecoin),
 }
@@ -31,6 +31,13 @@ pub enum HashFunction {
     #[strum(serialize = "SHA-256")]
     #[serde(rename = "SHA-256")]
     Sha256,
+    #[strum(serialize = "Keccak-256")]
+    #[serde(rename = "Keccak-256")]
+    Keccak256,
+    /// RIPEMD-160 of SHA-256, as computed by `OP_HASH160`.
+    #[strum(serialize = "HASH160")]
+    #[serde(rename = "HASH160")]
+    Hash160,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -3,13 +3,26 @@ use crate::swap_protocols::{
     ledger::Bitcoin,
     rfc003::{
         actions::{FundAction, RedeemAction, RefundAction},
+        bitcoin::Htlc,
         secret_source::SecretSource,
         state_machine::HtlcParams,
         Secret,
     },
 };
 use bitcoin::{Amount, OutPoint, Transaction};
-use blockchain_contracts::bitcoin::{rfc003::bitcoin_htlc::BitcoinHtlc, witness::PrimedInput};
+use blockchain_contracts::bitcoin::witness::{PrimedInput, UnlockParameters};
+
+/// Signals replaceability (BIP125) so the fee of a stuck redeem or refund
+/// transaction can be bumped. Like any sequence below `0xFFFF_FFFF` it keeps
+/// the lock time of refund transactions enabled.
+const SEQUENCE_ALLOW_NTIMELOCK_AND_RBF: u32 = 0xFFFF_FFFD;
+
+pub fn replaceable(unlock_parameters: UnlockParameters) -> UnlockParameters {
+    UnlockParameters {
+        sequence: SEQUENCE_ALLOW_NTIMELOCK_AND_RBF,
+        ..unlock_parameters
+    }
+}
 
 impl FundAction<Bitcoin, Amount> for (Bitcoin, Amount) {
     type FundActionOutput = SendToAddress;
@@ -34,13 +47,15 @@ impl RefundAction<Bitcoin, Amount> for (Bitcoin, Amount) {
         secret_source: &dyn SecretSource,
         fund_transaction: &Transaction,
     ) -> Self::RefundActionOutput {
-        let htlc = BitcoinHtlc::from(htlc_params.clone());
+        let htlc = Htlc::from(htlc_params.clone());
 
         SpendOutput {
             output: PrimedInput::new(
                 htlc_location,
                 Amount::from_sat(fund_transaction.output[htlc_location.vout as usize].value),
-                htlc.unlock_after_timeout(&*crate::SECP, secret_source.secp256k1_refund()),
+                replaceable(
+                    htlc.unlock_after_timeout(&*crate::SECP, secret_source.secp256k1_refund()),
+                ),
             ),
             network: htlc_params.ledger.network,
         }
@@ -56,17 +71,17 @@ impl RedeemAction<Bitcoin, Amount> for (Bitcoin, Amount) {
         secret_source: &dyn SecretSource,
         secret: Secret,
     ) -> Self::RedeemActionOutput {
-        let htlc = BitcoinHtlc::from(htlc_params.clone());
+        let htlc = Htlc::from(htlc_params.clone());
 
         SpendOutput {
             output: PrimedInput::new(
                 htlc_location,
                 htlc_params.asset,
-                htlc.unlock_with_secret(
+                replaceable(htlc.unlock_with_secret(
                     &*crate::SECP,
                     secret_source.secp256k1_redeem(),
                     secret.into_raw_secret(),
-                ),
+                )),
             ),
             network: htlc_params.ledger.network,
         }
@@ -0,0 +1,71 @@
+use crate::{
+    ethereum::{Bytes, Erc721Token},
+    swap_protocols::{
+        actions::ethereum::{CallContract, DeployContract},
+        ledger::{ethereum::ChainId, Ethereum},
+        rfc003::{ethereum::erc721_htlc::Erc721Htlc, state_machine::HtlcParams, Secret},
+    },
+    timestamp::Timestamp,
+};
+
+pub fn deploy_action(htlc_params: HtlcParams<Ethereum, Erc721Token>) -> DeployContract {
+    htlc_params.into()
+}
+
+/// Transfers the token from the refund identity, which has to own it, into the
+/// deployed HTLC.
+pub fn fund_action(
+    htlc_params: HtlcParams<Ethereum, Erc721Token>,
+    htlc_location: crate::ethereum::Address,
+) -> CallContract {
+    let chain_id = htlc_params.ledger.chain_id;
+    let gas_limit = Erc721Htlc::fund_tx_gas_limit();
+
+    let data = Erc721Htlc::transfer_from_tx_payload(
+        htlc_params.refund_identity,
+        htlc_location,
+        htlc_params.asset.token_id.0,
+    );
+
+    CallContract {
+        to: htlc_params.asset.token_contract,
+        data: Some(data),
+        gas_limit,
+        chain_id,
+        min_block_timestamp: None,
+    }
+}
+
+pub fn refund_action(
+    chain_id: ChainId,
+    expiry: Timestamp,
+    htlc_location: crate::ethereum::Address,
+) -> CallContract {
+    let data = Bytes::default();
+    let gas_limit = Erc721Htlc::tx_gas_limit();
+
+    CallContract {
+        to: htlc_location,
+        data: Some(data),
+        gas_limit,
+        chain_id,
+        min_block_timestamp: Some(expiry),
+    }
+}
+
+pub fn redeem_action(
+    htlc_location: crate::ethereum::Address,
+    secret: Secret,
+    chain_id: ChainId,
+) -> CallContract {
+    let data = Bytes::from(secret.as_raw_secret().to_vec());
+    let gas_limit = Erc721Htlc::tx_gas_limit();
+
+    CallContract {
+        to: htlc_location,
+        data: Some(data),
+        gas_limit,
+        chain_id,
+        min_block_timestamp: None,
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    litecoin,
+    swap_protocols::{
+        actions::litecoin::{SendToAddress, SpendOutput},
+        ledger::Litecoin,
+        rfc003::{
+            actions::{bitcoin::replaceable, FundAction, RedeemAction, RefundAction},
+            bitcoin::Htlc,
+            secret_source::SecretSource,
+            state_machine::HtlcParams,
+            Secret,
+        },
+    },
+};
+use bitcoin::{Amount, OutPoint, Transaction};
+use blockchain_contracts::bitcoin::witness::PrimedInput;
+
+impl FundAction<Litecoin, litecoin::Amount> for (Litecoin, litecoin::Amount) {
+    type FundActionOutput = SendToAddress;
+
+    fn fund_action(htlc_params: HtlcParams<Litecoin, litecoin::Amount>) -> Self::FundActionOutput {
+        let to = htlc_params.compute_address();
+
+        SendToAddress {
+            to,
+            amount: htlc_params.asset,
+            network: htlc_params.ledger.network,
+        }
+    }
+}
+
+impl RefundAction<Litecoin, litecoin::Amount> for (Litecoin, litecoin::Amount) {
+    type RefundActionOutput = SpendOutput;
+
+    fn refund_action(
+        htlc_params: HtlcParams<Litecoin, litecoin::Amount>,
+        htlc_location: OutPoint,
+        secret_source: &dyn SecretSource,
+        fund_transaction: &Transaction,
+    ) -> Self::RefundActionOutput {
+        let htlc = Htlc::from(htlc_params.clone());
+
+        SpendOutput {
+            output: PrimedInput::new(
+                htlc_location,
+                Amount::from_sat(fund_transaction.output[htlc_location.vout as usize].value),
+                replaceable(
+                    htlc.unlock_after_timeout(&*crate::SECP, secret_source.secp256k1_refund()),
+                ),
+            ),
+            network: htlc_params.ledger.network,
+        }
+    }
+}
+
+impl RedeemAction<Litecoin, litecoin::Amount> for (Litecoin, litecoin::Amount) {
+    type RedeemActionOutput = SpendOutput;
+
+    fn redeem_action(
+        htlc_params: HtlcParams<Litecoin, litecoin::Amount>,
+        htlc_location: OutPoint,
+        secret_source: &dyn SecretSource,
+        secret: Secret,
+    ) -> Self::RedeemActionOutput {
+        let htlc = Htlc::from(htlc_params.clone());
+
+        SpendOutput {
+            output: PrimedInput::new(
+                htlc_location,
+                Amount::from_sat(htlc_params.asset.as_litoshi()),
+                replaceable(htlc.unlock_with_secret(
+                    &*crate::SECP,
+                    secret_source.secp256k1_redeem(),
+                    secret.into_raw_secret(),
+                )),
+            ),
+            network: htlc_params.ledger.network,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 pub mod bitcoin;
 pub mod erc20;
+pub mod erc721;
 pub mod ether;
+pub mod litecoin;
 
 use crate::swap_protocols::{
     asset::Asset,
@@ -11,7 +11,10 @@ pub trait ActorState: Debug + Clone + Send + Sync + 'static {
     type BA: Asset;
 
     fn set_secret(&mut self, secret: Secret);
+    fn secret(&self) -> Option<Secret>;
     fn set_error(&mut self, error: rfc003::Error);
+    fn alpha_ledger(&self) -> &LedgerState<Self::AL>;
+    fn beta_ledger(&self) -> &LedgerState<Self::BL>;
     fn alpha_ledger_mut(&mut self) -> &mut LedgerState<Self::AL>;
     fn beta_ledger_mut(&mut self) -> &mut LedgerState<Self::BL>;
 }
@@ -128,3 +128,55 @@ where
         actions
     }
 }
+
+impl Actions for alice::State<Ethereum, Ethereum, Erc20Token, Erc20Token> {
+    #[allow(clippy::type_complexity)]
+    type ActionKind = Action<
+        Accept<Ethereum, Ethereum>,
+        Decline<Ethereum, Ethereum>,
+        ethereum::DeployContract,
+        ethereum::CallContract,
+        ethereum::CallContract,
+        ethereum::CallContract,
+    >;
+
+    fn actions(&self) -> Vec<Self::ActionKind> {
+        let (request, response) = match self.swap_communication {
+            SwapCommunication::Accepted {
+                ref request,
+                ref response,
+            } => (request, response),
+            _ => return vec![],
+        };
+        let alpha_state = &self.alpha_ledger_state;
+        let beta_state = &self.beta_ledger_state;
+
+        use self::LedgerState::*;
+
+        let mut actions = match alpha_state {
+            NotDeployed => vec![Action::Deploy(erc20::deploy_action(
+                HtlcParams::new_alpha_params(request, response),
+            ))],
+            Deployed { htlc_location, .. } => vec![Action::Fund(erc20::fund_action(
+                HtlcParams::new_alpha_params(request, response),
+                request.alpha_asset.token_contract,
+                *htlc_location,
+            ))],
+            Funded { htlc_location, .. } => vec![Action::Refund(erc20::refund_action(
+                request.alpha_ledger.chain_id,
+                request.alpha_expiry,
+                *htlc_location,
+            ))],
+            _ => vec![],
+        };
+
+        if let Funded { htlc_location, .. } = beta_state {
+            actions.push(Action::Redeem(erc20::redeem_action(
+                *htlc_location,
+                self.secret_source.secret(),
+                request.beta_ledger.chain_id,
+            )));
+        }
+        actions
+    }
+}
@@ -1,2 +1,3 @@
 mod erc20;
+mod erc721;
 mod generic_impl;
@@ -84,10 +84,22 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> ActorState for State<AL, BL,
         // ignored because Alice already knows the secret
     }
 
+    fn secret(&self) -> Option<Secret> {
+        Some(self.secret_source.secret())
+    }
+
     fn set_error(&mut self, error: rfc003::Error) {
         self.error = Some(error)
     }
 
+    fn alpha_ledger(&self) -> &LedgerState<AL> {
+        &self.alpha_ledger_state
+    }
+
+    fn beta_ledger(&self) -> &LedgerState<BL> {
+        &self.beta_ledger_state
+    }
+
     fn alpha_ledger_mut(&mut self) -> &mut LedgerState<AL> {
         &mut self.alpha_ledger_state
     }
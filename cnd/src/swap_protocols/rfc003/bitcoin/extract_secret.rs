@@ -1,12 +1,19 @@
-use crate::swap_protocols::rfc003::secret::{Secret, SecretHash};
+use crate::swap_protocols::{
+    rfc003::secret::{Secret, SecretHash},
+    HashFunction,
+};
 use bitcoin::Transaction;
 
-pub fn extract_secret(transaction: &Transaction, secret_hash: &SecretHash) -> Option<Secret> {
+pub fn extract_secret(
+    transaction: &Transaction,
+    hash_function: HashFunction,
+    secret_hash: &SecretHash,
+) -> Option<Secret> {
     transaction.input.iter().find_map(|txin| {
         txin.witness
             .iter()
             .find_map(|script_item| match Secret::from_vec(&script_item) {
-                Ok(secret) if secret.hash() == *secret_hash => Some(secret),
+                Ok(secret) if secret.hash(hash_function) == *secret_hash => Some(secret),
                 Ok(_) => None,
                 Err(_) => None,
             })
@@ -45,9 +52,27 @@ mod test {
         let secret = Secret::from(*b"This is our favourite passphrase");
         let transaction = setup(&secret);
 
-        assert_that!(extract_secret(&transaction, &secret.hash()))
-            .is_some()
-            .is_equal_to(&secret);
+        assert_that!(extract_secret(
+            &transaction,
+            HashFunction::Sha256,
+            &secret.hash(HashFunction::Sha256)
+        ))
+        .is_some()
+        .is_equal_to(&secret);
+    }
+
+    #[test]
+    fn extract_correct_secret_of_hash160_htlc() {
+        let secret = Secret::from(*b"This is our favourite passphrase");
+        let transaction = setup(&secret);
+
+        assert_that!(extract_secret(
+            &transaction,
+            HashFunction::Hash160,
+            &secret.hash(HashFunction::Hash160)
+        ))
+        .is_some()
+        .is_equal_to(&secret);
     }
 
     #[test]
@@ -60,7 +85,12 @@ mod test {
              bfbfbfbfbfbfbfbfbfbfbfbfbfbfbfbf",
         )
         .unwrap();
-        assert_that!(extract_secret(&transaction, &secret_hash)).is_none();
+        assert_that!(extract_secret(
+            &transaction,
+            HashFunction::Sha256,
+            &secret_hash
+        ))
+        .is_none();
     }
 
     #[test]
@@ -72,8 +102,12 @@ mod test {
                 .unwrap();
         let secret = Secret::from_vec(&hex_secret).unwrap();
 
-        assert_that!(extract_secret(&transaction, &secret.hash()))
-            .is_some()
-            .is_equal_to(&secret);
+        assert_that!(extract_secret(
+            &transaction,
+            HashFunction::Sha256,
+            &secret.hash(HashFunction::Sha256)
+        ))
+        .is_some()
+        .is_equal_to(&secret);
     }
 }
@@ -0,0 +1,220 @@
+use crate::swap_protocols::{rfc003::SecretHash, HashFunction};
+use bitcoin::{
+    blockdata::{opcodes::all::*, script::Builder},
+    hashes::hash160,
+    secp256k1::{PublicKey, Secp256k1, SecretKey, Signing},
+    Address, Network, Script,
+};
+use blockchain_contracts::bitcoin::{
+    rfc003::bitcoin_htlc::BitcoinHtlc,
+    witness::{UnlockParameters, Witness},
+};
+
+const SEQUENCE_ALLOW_NTIMELOCK_NO_RBF: u32 = 0xFFFF_FFFE;
+
+/// The HTLC of a swap on Bitcoin or Litecoin.
+///
+/// SHA-256 HTLCs are the ones of `blockchain_contracts`. Bitcoin script has no
+/// opcode for Keccak-256, hence the only other HTLC checks the secret with
+/// `OP_HASH160`.
+pub enum Htlc {
+    Sha256(BitcoinHtlc),
+    Hash160(Hash160Htlc),
+}
+
+impl Htlc {
+    pub fn new(
+        hash_function: HashFunction,
+        expiry: u32,
+        refund_identity: hash160::Hash,
+        redeem_identity: hash160::Hash,
+        secret_hash: SecretHash,
+    ) -> Self {
+        match hash_function {
+            HashFunction::Sha256 => Htlc::Sha256(BitcoinHtlc::new(
+                expiry,
+                refund_identity,
+                redeem_identity,
+                secret_hash.into(),
+            )),
+            HashFunction::Hash160 => Htlc::Hash160(Hash160Htlc::new(
+                expiry,
+                refund_identity,
+                redeem_identity,
+                secret_hash,
+            )),
+            HashFunction::Keccak256 => {
+                unreachable!("swaps with Keccak-256 HTLCs on Bitcoin are declined")
+            }
+        }
+    }
+
+    pub fn compute_address(&self, network: Network) -> Address {
+        match self {
+            Htlc::Sha256(htlc) => htlc.compute_address(network),
+            Htlc::Hash160(htlc) => htlc.compute_address(network),
+        }
+    }
+
+    pub fn unlock_with_secret<C: Signing>(
+        self,
+        secp: &Secp256k1<C>,
+        secret_key: SecretKey,
+        secret: [u8; 32],
+    ) -> UnlockParameters {
+        match self {
+            Htlc::Sha256(htlc) => htlc.unlock_with_secret(secp, secret_key, secret),
+            Htlc::Hash160(htlc) => htlc.unlock_with_secret(secp, secret_key, secret),
+        }
+    }
+
+    pub fn unlock_after_timeout<C: Signing>(
+        self,
+        secp: &Secp256k1<C>,
+        secret_key: SecretKey,
+    ) -> UnlockParameters {
+        match self {
+            Htlc::Sha256(htlc) => htlc.unlock_after_timeout(secp, secret_key),
+            Htlc::Hash160(htlc) => htlc.unlock_after_timeout(secp, secret_key),
+        }
+    }
+}
+
+/// The SHA-256 HTLC of `blockchain_contracts` with `OP_HASH160` in place of
+/// `OP_SHA256`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hash160Htlc {
+    script: Script,
+    expiry: u32,
+}
+
+impl Hash160Htlc {
+    pub fn new(
+        expiry: u32,
+        refund_identity: hash160::Hash,
+        redeem_identity: hash160::Hash,
+        secret_hash: SecretHash,
+    ) -> Self {
+        let script = Builder::new()
+            .push_opcode(OP_IF)
+            .push_opcode(OP_SIZE)
+            .push_int(32)
+            .push_opcode(OP_EQUALVERIFY)
+            .push_opcode(OP_HASH160)
+            .push_slice(secret_hash.as_bytes())
+            .push_opcode(OP_EQUALVERIFY)
+            .push_opcode(OP_DUP)
+            .push_opcode(OP_HASH160)
+            .push_slice(&redeem_identity[..])
+            .push_opcode(OP_ELSE)
+            .push_int(i64::from(expiry))
+            .push_opcode(OP_CLTV)
+            .push_opcode(OP_DROP)
+            .push_opcode(OP_DUP)
+            .push_opcode(OP_HASH160)
+            .push_slice(&refund_identity[..])
+            .push_opcode(OP_ENDIF)
+            .push_opcode(OP_EQUALVERIFY)
+            .push_opcode(OP_CHECKSIG)
+            .into_script();
+
+        Hash160Htlc { script, expiry }
+    }
+
+    pub fn compute_address(&self, network: Network) -> Address {
+        Address::p2wsh(&self.script, network)
+    }
+
+    pub fn unlock_with_secret<C: Signing>(
+        self,
+        secp: &Secp256k1<C>,
+        secret_key: SecretKey,
+        secret: [u8; 32],
+    ) -> UnlockParameters {
+        let public_key = PublicKey::from_secret_key(secp, &secret_key);
+
+        UnlockParameters {
+            witness: vec![
+                Witness::Signature(secret_key),
+                Witness::PublicKey(public_key),
+                Witness::Data(secret.to_vec()),
+                Witness::Bool(true),
+                Witness::PrevScript,
+            ],
+            sequence: SEQUENCE_ALLOW_NTIMELOCK_NO_RBF,
+            locktime: 0,
+            prev_script: self.script,
+        }
+    }
+
+    pub fn unlock_after_timeout<C: Signing>(
+        self,
+        secp: &Secp256k1<C>,
+        secret_key: SecretKey,
+    ) -> UnlockParameters {
+        let public_key = PublicKey::from_secret_key(secp, &secret_key);
+
+        UnlockParameters {
+            witness: vec![
+                Witness::Signature(secret_key),
+                Witness::PublicKey(public_key),
+                Witness::Bool(false),
+                Witness::PrevScript,
+            ],
+            sequence: SEQUENCE_ALLOW_NTIMELOCK_NO_RBF,
+            locktime: self.expiry,
+            prev_script: self.script,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swap_protocols::rfc003::Secret;
+    use bitcoin::hashes::Hash;
+
+    fn htlc(hash_function: HashFunction) -> Htlc {
+        let secret = Secret::from(*b"hello world, you are beautiful!!");
+
+        Htlc::new(
+            hash_function,
+            2_000_000_000,
+            hash160::Hash::hash(b"refund"),
+            hash160::Hash::hash(b"redeem"),
+            secret.hash(hash_function),
+        )
+    }
+
+    #[test]
+    fn hash160_htlc_checks_the_secret_with_op_hash160() {
+        let secret_hash = Secret::from(*b"hello world, you are beautiful!!")
+            .hash(HashFunction::Hash160)
+            .to_string();
+
+        match htlc(HashFunction::Hash160) {
+            Htlc::Hash160(htlc) => {
+                let script = hex::encode(htlc.script.as_bytes());
+                assert!(script.starts_with(&format!("6382012088a914{}88", secret_hash)));
+            }
+            Htlc::Sha256(_) => panic!("expected a HASH160 HTLC"),
+        }
+    }
+
+    #[test]
+    fn htlc_address_depends_on_the_hash_function() {
+        let sha256_address = htlc(HashFunction::Sha256).compute_address(Network::Regtest);
+        let hash160_address = htlc(HashFunction::Hash160).compute_address(Network::Regtest);
+
+        assert_ne!(sha256_address, hash160_address);
+    }
+
+    #[test]
+    fn refund_is_locked_until_expiry() {
+        let unlock_parameters = htlc(HashFunction::Hash160)
+            .unlock_after_timeout(&*crate::SECP, SecretKey::from_slice(&[1u8; 32]).unwrap());
+
+        assert_eq!(unlock_parameters.locktime, 2_000_000_000);
+        assert_eq!(unlock_parameters.witness.len(), 4);
+    }
+}
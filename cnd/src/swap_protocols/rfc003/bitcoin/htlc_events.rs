@@ -1,15 +1,14 @@
 use crate::{
-    btsieve::bitcoin::{
-        matching_transaction, BitcoindConnector, TransactionExt, TransactionPattern,
-    },
+    btsieve::bitcoin::{BitcoinConnector, TransactionExt, TransactionPattern},
+    db::SwapScanProgress,
     swap_protocols::{
         ledger::Bitcoin,
         rfc003::{
             self,
             bitcoin::extract_secret::extract_secret,
             events::{
-                Deployed, DeployedFuture, Funded, FundedFuture, HtlcEvents, Redeemed,
-                RedeemedOrRefundedFuture, Refunded,
+                Deployed, DeployedFuture, Funded, FundedFuture, HtlcEvents, HtlcWatcher, Redeemed,
+                RedeemedOrRefundedFuture, Refunded, RetractedFuture,
             },
             state_machine::HtlcParams,
         },
@@ -22,26 +21,26 @@ use futures::{
 };
 use futures_core::future::{FutureExt, TryFutureExt};
 
-impl HtlcEvents<Bitcoin, Amount> for BitcoindConnector {
+impl HtlcEvents<Bitcoin, Amount> for HtlcWatcher<BitcoinConnector> {
     fn htlc_deployed(
         &self,
         htlc_params: HtlcParams<Bitcoin, Amount>,
     ) -> Box<DeployedFuture<Bitcoin>> {
         let future = {
-            let connector = self.clone();
+            let connector = self.connector.clone();
             let pattern = TransactionPattern {
                 to_address: Some(htlc_params.compute_address()),
                 from_outpoint: None,
                 unlock_script: None,
             };
+            let scan_progress =
+                SwapScanProgress::new(self.db.clone(), htlc_params.swap_id, &pattern);
+            let script = htlc_params.compute_address().script_pubkey();
 
             async {
-                matching_transaction(
-                    connector,
-                    pattern,
-                    None,
-                )
-                .await
+                connector
+                    .matching_transaction(pattern, script, scan_progress)
+                    .await
             }
             .boxed()
             .compat()
@@ -88,36 +87,51 @@ impl HtlcEvents<Bitcoin, Amount> for BitcoindConnector {
         _htlc_funding: &Funded<Bitcoin, Amount>,
     ) -> Box<RedeemedOrRefundedFuture<Bitcoin>> {
         let refunded_future = {
-            let connector = self.clone();
+            let connector = self.connector.clone();
             let pattern = TransactionPattern {
                 to_address: None,
                 from_outpoint: Some(htlc_deployment.location),
                 unlock_script: Some(vec![vec![]]),
             };
+            let scan_progress =
+                SwapScanProgress::new(self.db.clone(), htlc_params.swap_id, &pattern);
+            let script = htlc_params.compute_address().script_pubkey();
 
-            async { matching_transaction(connector, pattern, None).await }
-                .boxed()
-                .compat()
-                .map_err(|_| rfc003::Error::Btsieve)
-                .and_then(|transaction| Ok(Refunded { transaction }))
+            async {
+                connector
+                    .matching_transaction(pattern, script, scan_progress)
+                    .await
+            }
+            .boxed()
+            .compat()
+            .map_err(|_| rfc003::Error::Btsieve)
+            .and_then(|transaction| Ok(Refunded { transaction }))
         };
 
         let redeemed_future = {
-            let connector = self.clone();
+            let connector = self.connector.clone();
             let pattern = TransactionPattern {
                 to_address: None,
                 from_outpoint: Some(htlc_deployment.location),
                 unlock_script: Some(vec![vec![1u8]]),
             };
+            let scan_progress =
+                SwapScanProgress::new(self.db.clone(), htlc_params.swap_id, &pattern);
+            let script = htlc_params.compute_address().script_pubkey();
 
-            async { matching_transaction(connector, pattern, None).await }
-                .boxed()
-                .compat()
-                .map_err(|_| rfc003::Error::Btsieve)
-                .and_then({
-                    move |tx| {
-                        let secret =
-                            extract_secret(&tx, &htlc_params.secret_hash).ok_or_else(|| {
+            async {
+                connector
+                    .matching_transaction(pattern, script, scan_progress)
+                    .await
+            }
+            .boxed()
+            .compat()
+            .map_err(|_| rfc003::Error::Btsieve)
+            .and_then({
+                move |tx| {
+                    let secret =
+                        extract_secret(&tx, htlc_params.hash_function, &htlc_params.secret_hash)
+                            .ok_or_else(|| {
                                 log::error!(
                                     "Redeem transaction didn't have secret it in: {:?}",
                                     tx
@@ -127,12 +141,12 @@ impl HtlcEvents<Bitcoin, Amount> for BitcoindConnector {
                                 )
                             })?;
 
-                        Ok(Redeemed {
-                            transaction: tx,
-                            secret,
-                        })
-                    }
-                })
+                    Ok(Redeemed {
+                        transaction: tx,
+                        secret,
+                    })
+                }
+            })
         };
 
         Box::new(
@@ -148,4 +162,22 @@ impl HtlcEvents<Bitcoin, Amount> for BitcoindConnector {
                 }),
         )
     }
+
+    fn transaction_retracted(
+        &self,
+        _: Bitcoin,
+        transaction: &bitcoin::Transaction,
+    ) -> Box<RetractedFuture> {
+        let connector = self.connector.clone();
+        let transaction = transaction.clone();
+
+        Box::new(
+            async move {
+                connector.transaction_retracted(transaction).await;
+                Ok(())
+            }
+            .boxed()
+            .compat(),
+        )
+    }
 }
@@ -1,23 +1,26 @@
 mod extract_secret;
+mod htlc;
 mod htlc_events;
 
 use crate::swap_protocols::{
     ledger::Bitcoin,
     rfc003::{state_machine::HtlcParams, Ledger},
+    HashFunction,
 };
 use bitcoin::{
     hashes::{hash160, Hash},
     Address, Amount, OutPoint,
 };
-use blockchain_contracts::bitcoin::rfc003::bitcoin_htlc::BitcoinHtlc;
 
-pub use self::htlc_events::*;
+pub use self::{extract_secret::extract_secret, htlc::Htlc, htlc_events::*};
 
 impl Ledger for Bitcoin {
     type HtlcLocation = OutPoint;
+
+    const HASH_FUNCTIONS: &'static [HashFunction] = &[HashFunction::Sha256, HashFunction::Hash160];
 }
 
-impl From<HtlcParams<Bitcoin, Amount>> for BitcoinHtlc {
+impl From<HtlcParams<Bitcoin, Amount>> for Htlc {
     fn from(htlc_params: HtlcParams<Bitcoin, Amount>) -> Self {
         let refund_public_key = htlc_params.refund_identity.into_inner();
         let redeem_public_key = htlc_params.redeem_identity.into_inner();
@@ -25,17 +28,18 @@ impl From<HtlcParams<Bitcoin, Amount>> for BitcoinHtlc {
         let refund_identity = hash160::Hash::hash(&refund_public_key.key.serialize());
         let redeem_identity = hash160::Hash::hash(&redeem_public_key.key.serialize());
 
-        BitcoinHtlc::new(
+        Htlc::new(
+            htlc_params.hash_function,
             htlc_params.expiry.into(),
             refund_identity,
             redeem_identity,
-            htlc_params.secret_hash.into_raw(),
+            htlc_params.secret_hash,
         )
     }
 }
 
 impl HtlcParams<Bitcoin, Amount> {
     pub fn compute_address(&self) -> Address {
-        BitcoinHtlc::from(self.clone()).compute_address(self.ledger.network)
+        Htlc::from(self.clone()).compute_address(self.ledger.network)
     }
 }
@@ -144,3 +144,62 @@ where
         actions
     }
 }
+
+impl Actions for bob::State<Ethereum, Ethereum, Erc20Token, Erc20Token> {
+    #[allow(clippy::type_complexity)]
+    type ActionKind = Action<
+        Accept<Ethereum, Ethereum>,
+        Decline<Ethereum, Ethereum>,
+        ethereum::DeployContract,
+        ethereum::CallContract,
+        ethereum::CallContract,
+        ethereum::CallContract,
+    >;
+
+    fn actions(&self) -> Vec<Self::ActionKind> {
+        let (request, response) = match &self.swap_communication {
+            SwapCommunication::Proposed { .. } => {
+                return vec![
+                    Action::Accept(Accept::new()),
+                    Action::Decline(Decline::new()),
+                ];
+            }
+            SwapCommunication::Accepted {
+                ref request,
+                ref response,
+            } => (request, response),
+            _ => return vec![],
+        };
+
+        let alpha_state = &self.alpha_ledger_state;
+        let beta_state = &self.beta_ledger_state;
+
+        use self::LedgerState::*;
+
+        let mut actions = match (alpha_state, beta_state, self.secret) {
+            (Funded { htlc_location, .. }, _, Some(secret)) => vec![Action::Redeem(
+                erc20::redeem_action(*htlc_location, secret, request.alpha_ledger.chain_id),
+            )],
+            (Funded { .. }, NotDeployed, _) => vec![Action::Deploy(erc20::deploy_action(
+                HtlcParams::new_beta_params(request, response),
+            ))],
+            (Funded { .. }, Deployed { htlc_location, .. }, _) => {
+                vec![Action::Fund(erc20::fund_action(
+                    HtlcParams::new_beta_params(request, response),
+                    request.beta_asset.token_contract,
+                    *htlc_location,
+                ))]
+            }
+            _ => vec![],
+        };
+
+        if let Funded { htlc_location, .. } = beta_state {
+            actions.push(Action::Refund(erc20::refund_action(
+                request.beta_ledger.chain_id,
+                request.beta_expiry,
+                *htlc_location,
+            )));
+        }
+        actions
+    }
+}
@@ -88,10 +88,22 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> ActorState for State<AL, BL,
         self.secret = Some(secret)
     }
 
+    fn secret(&self) -> Option<Secret> {
+        self.secret
+    }
+
     fn set_error(&mut self, error: rfc003::Error) {
         self.error = Some(error)
     }
 
+    fn alpha_ledger(&self) -> &LedgerState<AL> {
+        &self.alpha_ledger_state
+    }
+
+    fn beta_ledger(&self) -> &LedgerState<BL> {
+        &self.beta_ledger_state
+    }
+
     fn alpha_ledger_mut(&mut self) -> &mut LedgerState<AL> {
         &mut self.alpha_ledger_state
     }
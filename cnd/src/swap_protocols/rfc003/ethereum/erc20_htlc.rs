@@ -0,0 +1,190 @@
+use crate::{
+    ethereum::{Address, Bytes, U256},
+    swap_protocols::{
+        rfc003::ethereum::{hash_lock::hash_lock, template::splice},
+        HashFunction,
+    },
+};
+use blockchain_contracts::ethereum::rfc003::erc20_htlc;
+
+// The contract is the one of `Erc721Htlc` with a `transfer` of the tokens:
+//
+// finish:
+//     mstore(0, 0xa9059cbb) // transfer(address,uint256)
+//     mstore(32, dup1), mstore(64, <token_quantity>)
+//     iszero(call(gas, <token_contract>, 0, 28, 68, 96, 32)), jumpi(failed)
+//     selfdestruct
+// failed:
+//     revert(0, 0)
+const CONTRACT_TEMPLATE: &str = concat!(
+    "61018b80600c6000396000f3361561007e57602060008037700000000000000000000000000000000000000000000000",
+    "000000000000077f100000000000000000000000000000000000000000000000000000000000000160215114166100b3",
+    "577f696e76616c69645365637265740000000000000000000000000000000000000060005260206000fd5b4263200000",
+    "02106100f3577f746f6f4561726c7900000000000000000000000000000000000000000000000060005260206000fd5b",
+    "7fb8cac300e37f03ad332e581dea21b2f0b84eaaadc184a295fef71e81f44a741360206000a173300000000000000000",
+    "000000000000000000000361012e565b7f5d26862916391bf49478b2f5103b0720a842b45ef145a268f2cd1fb2aed551",
+    "78600080a17340000000000000000000000000000000000000045b63a9059cbb600052806020527f5000000000000000",
+    "000000000000000000000000000000000000000000000005604052602060606044601c60007360000000000000000000",
+    "000000000000000000065af11561018657ff5b600080fd",
+);
+
+const HASH_LOCK_OFFSET: usize = 24;
+const SECRET_HASH_OFFSET: usize = 56;
+const EXPIRY_OFFSET: usize = 141;
+const REDEEM_IDENTITY_OFFSET: usize = 231;
+const REFUND_IDENTITY_OFFSET: usize = 294;
+const TOKEN_QUANTITY_OFFSET: usize = 328;
+const TOKEN_CONTRACT_OFFSET: usize = 374;
+
+/// An HTLC for ERC20 tokens, funded by transferring the tokens to it after its
+/// deployment.
+///
+/// SHA-256 HTLCs are the ones of `blockchain_contracts`, see `EtherHtlc`.
+pub enum Erc20Htlc {
+    Sha256(erc20_htlc::Erc20Htlc),
+    Other(Vec<u8>),
+}
+
+impl Erc20Htlc {
+    pub fn new(
+        hash_function: HashFunction,
+        expiry: u32,
+        refund_identity: Address,
+        redeem_identity: Address,
+        secret_hash: [u8; 32],
+        token_contract: Address,
+        token_quantity: U256,
+    ) -> Self {
+        if let HashFunction::Sha256 = hash_function {
+            return Erc20Htlc::Sha256(erc20_htlc::Erc20Htlc::new(
+                expiry,
+                refund_identity,
+                redeem_identity,
+                secret_hash,
+                token_contract,
+                token_quantity,
+            ));
+        }
+
+        let mut quantity_bytes = [0u8; 32];
+        token_quantity.to_big_endian(&mut quantity_bytes);
+
+        let mut contract = hex::decode(CONTRACT_TEMPLATE).expect("template is valid hex");
+        let hash_lock = hex::decode(hash_lock(hash_function)).expect("hash lock is valid hex");
+        splice(&mut contract, HASH_LOCK_OFFSET, &hash_lock);
+        splice(&mut contract, SECRET_HASH_OFFSET, &secret_hash);
+        splice(&mut contract, EXPIRY_OFFSET, &expiry.to_be_bytes());
+        splice(
+            &mut contract,
+            REDEEM_IDENTITY_OFFSET,
+            redeem_identity.as_bytes(),
+        );
+        splice(
+            &mut contract,
+            REFUND_IDENTITY_OFFSET,
+            refund_identity.as_bytes(),
+        );
+        splice(&mut contract, TOKEN_QUANTITY_OFFSET, &quantity_bytes);
+        splice(
+            &mut contract,
+            TOKEN_CONTRACT_OFFSET,
+            token_contract.as_bytes(),
+        );
+
+        Erc20Htlc::Other(contract)
+    }
+
+    pub fn deployment_gas_limit(&self) -> U256 {
+        match self {
+            Erc20Htlc::Sha256(htlc) => htlc.deployment_gas_limit(),
+            Erc20Htlc::Other(bytes) => {
+                let bytes = bytes.len() as u64;
+
+                U256::from(21_000 + 32_000 + bytes * (68 + 200) + 10_000)
+            }
+        }
+    }
+}
+
+impl From<Erc20Htlc> for Bytes {
+    fn from(htlc: Erc20Htlc) -> Self {
+        match htlc {
+            Erc20Htlc::Sha256(htlc) => htlc.into(),
+            Erc20Htlc::Other(bytes) => Bytes(bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swap_protocols::rfc003::ethereum::hash_lock::HASH_LOCK_PLACEHOLDER;
+
+    const SECRET_HASH_PLACEHOLDER: &str =
+        "1000000000000000000000000000000000000000000000000000000000000001";
+    const EXPIRY_PLACEHOLDER: &str = "20000002";
+    const REDEEM_IDENTITY_PLACEHOLDER: &str = "3000000000000000000000000000000000000003";
+    const REFUND_IDENTITY_PLACEHOLDER: &str = "4000000000000000000000000000000000000004";
+    const TOKEN_QUANTITY_PLACEHOLDER: &str =
+        "5000000000000000000000000000000000000000000000000000000000000005";
+    const TOKEN_CONTRACT_PLACEHOLDER: &str = "6000000000000000000000000000000000000006";
+
+    #[test]
+    fn placeholders_are_at_their_offsets() {
+        let offsets = [
+            (HASH_LOCK_OFFSET, HASH_LOCK_PLACEHOLDER),
+            (SECRET_HASH_OFFSET, SECRET_HASH_PLACEHOLDER),
+            (EXPIRY_OFFSET, EXPIRY_PLACEHOLDER),
+            (REDEEM_IDENTITY_OFFSET, REDEEM_IDENTITY_PLACEHOLDER),
+            (REFUND_IDENTITY_OFFSET, REFUND_IDENTITY_PLACEHOLDER),
+            (TOKEN_QUANTITY_OFFSET, TOKEN_QUANTITY_PLACEHOLDER),
+            (TOKEN_CONTRACT_OFFSET, TOKEN_CONTRACT_PLACEHOLDER),
+        ];
+
+        for (offset, placeholder) in offsets.iter() {
+            assert_eq!(
+                &CONTRACT_TEMPLATE[offset * 2..offset * 2 + placeholder.len()],
+                *placeholder
+            );
+        }
+    }
+
+    #[test]
+    fn placeholders_are_replaced_with_htlc_params() {
+        let htlc: Bytes = Erc20Htlc::new(
+            HashFunction::Keccak256,
+            2_000_000_000,
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+            [0x33; 32],
+            Address::repeat_byte(0x44),
+            U256::from(42),
+        )
+        .into();
+        let contract = hex::encode(htlc.0);
+
+        for placeholder in &[
+            HASH_LOCK_PLACEHOLDER,
+            SECRET_HASH_PLACEHOLDER,
+            EXPIRY_PLACEHOLDER,
+            REDEEM_IDENTITY_PLACEHOLDER,
+            REFUND_IDENTITY_PLACEHOLDER,
+            TOKEN_QUANTITY_PLACEHOLDER,
+            TOKEN_CONTRACT_PLACEHOLDER,
+        ] {
+            assert!(!contract.contains(placeholder));
+        }
+        assert!(contract.contains(hash_lock(HashFunction::Keccak256)));
+        assert!(contract.contains(&format!("{:064x}", 42)));
+        assert_eq!(contract.len(), CONTRACT_TEMPLATE.len());
+    }
+
+    #[test]
+    fn deploy_header_returns_the_whole_contract() {
+        let bytes = hex::decode(CONTRACT_TEMPLATE).unwrap();
+        let header_length = usize::from(bytes[5]);
+        let contract_length = usize::from(u16::from_be_bytes([bytes[1], bytes[2]]));
+
+        assert_eq!(header_length + contract_length, bytes.len());
+    }
+}
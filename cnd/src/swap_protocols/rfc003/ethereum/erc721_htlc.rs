@@ -0,0 +1,231 @@
+use crate::{
+    ethereum::{Address, Bytes, U256},
+    swap_protocols::{
+        rfc003::ethereum::{hash_lock::hash_lock, template::splice},
+        HashFunction,
+    },
+};
+
+// The deploy header copies the contract below into memory and returns it, the
+// contract itself:
+//
+//     calldatasize, iszero, jumpi(check_expiry)
+//     calldatacopy(0, 0, 32)
+//     <hash_lock>                                   // see `hash_lock`
+//     and(eq(mload(33), <secret_hash>)), jumpi(redeem)
+//     mstore(0, "invalidSecret"), revert(0, 32)
+// check_expiry:
+//     lt(<expiry>, timestamp), jumpi(refund)
+//     mstore(0, "tooEarly"), revert(0, 32)
+// redeem:
+//     log1(0, 32, keccak256("Redeemed()")), <redeem_identity>, jump(finish)
+// refund:
+//     log1(0, 0, keccak256("Refunded()")), <refund_identity>
+// finish:
+//     mstore(0, 0x23b872dd) // transferFrom(address,address,uint256)
+//     mstore(32, address), mstore(64, dup1), mstore(96, <token_id>)
+//     iszero(call(gas, <token_contract>, 0, 28, 100, 128, 32)), jumpi(failed)
+//     selfdestruct
+// failed:
+//     revert(0, 0)
+//
+// Other than the ERC20 HTLC, the contract reverts if the transfer fails. The
+// token would be locked in a destroyed contract otherwise.
+const CONTRACT_TEMPLATE: &str = concat!(
+    "61018f80600c6000396000f3361561007e57602060008037700000000000000000000000000000000000000000000000",
+    "000000000000077f100000000000000000000000000000000000000000000000000000000000000160215114166100b3",
+    "577f696e76616c69645365637265740000000000000000000000000000000000000060005260206000fd5b4263200000",
+    "02106100f3577f746f6f4561726c7900000000000000000000000000000000000000000000000060005260206000fd5b",
+    "7fb8cac300e37f03ad332e581dea21b2f0b84eaaadc184a295fef71e81f44a741360206000a173300000000000000000",
+    "000000000000000000000361012e565b7f5d26862916391bf49478b2f5103b0720a842b45ef145a268f2cd1fb2aed551",
+    "78600080a17340000000000000000000000000000000000000045b6323b872dd60005230602052806040527f50000000",
+    "00000000000000000000000000000000000000000000000000000005606052602060806064601c600073600000000000",
+    "00000000000000000000000000065af11561018a57ff5b600080fd",
+);
+
+const HASH_LOCK_OFFSET: usize = 24;
+const SECRET_HASH_OFFSET: usize = 56;
+const EXPIRY_OFFSET: usize = 141;
+const REDEEM_IDENTITY_OFFSET: usize = 231;
+const REFUND_IDENTITY_OFFSET: usize = 294;
+const TOKEN_ID_OFFSET: usize = 332;
+const TOKEN_CONTRACT_OFFSET: usize = 378;
+
+/// keccak256(transferFrom(address,address,uint256))
+const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+
+/// An HTLC for a single ERC721 token. It is deployed first and funded by
+/// transferring the token to it with `transferFrom`. `safeTransferFrom` does
+/// not work because the contract would treat the `onERC721Received` call as an
+/// attempt to redeem.
+#[derive(Debug, Clone)]
+pub struct Erc721Htlc(Vec<u8>);
+
+impl Erc721Htlc {
+    pub fn new(
+        hash_function: HashFunction,
+        expiry: u32,
+        refund_identity: Address,
+        redeem_identity: Address,
+        secret_hash: [u8; 32],
+        token_contract: Address,
+        token_id: U256,
+    ) -> Self {
+        let mut token_id_bytes = [0u8; 32];
+        token_id.to_big_endian(&mut token_id_bytes);
+
+        let mut contract = hex::decode(CONTRACT_TEMPLATE).expect("template is valid hex");
+        let hash_lock = hex::decode(hash_lock(hash_function)).expect("hash lock is valid hex");
+        splice(&mut contract, HASH_LOCK_OFFSET, &hash_lock);
+        splice(&mut contract, SECRET_HASH_OFFSET, &secret_hash);
+        splice(&mut contract, EXPIRY_OFFSET, &expiry.to_be_bytes());
+        splice(
+            &mut contract,
+            REDEEM_IDENTITY_OFFSET,
+            redeem_identity.as_bytes(),
+        );
+        splice(
+            &mut contract,
+            REFUND_IDENTITY_OFFSET,
+            refund_identity.as_bytes(),
+        );
+        splice(&mut contract, TOKEN_ID_OFFSET, &token_id_bytes);
+        splice(
+            &mut contract,
+            TOKEN_CONTRACT_OFFSET,
+            token_contract.as_bytes(),
+        );
+
+        Erc721Htlc(contract)
+    }
+
+    /// The base costs of a contract creation, its data and storing its code.
+    pub fn deployment_gas_limit(&self) -> U256 {
+        let bytes = self.0.len() as u64;
+
+        U256::from(21_000 + 32_000 + bytes * (68 + 200) + 10_000)
+    }
+
+    /// Redeeming or refunding calls `transferFrom` on the token contract.
+    pub fn tx_gas_limit() -> U256 {
+        U256::from(150_000)
+    }
+
+    pub fn fund_tx_gas_limit() -> U256 {
+        U256::from(150_000)
+    }
+
+    /// The payload of the `transferFrom` call that moves the token from its
+    /// owner into the HTLC.
+    pub fn transfer_from_tx_payload(owner: Address, htlc: Address, token_id: U256) -> Bytes {
+        let mut data = TRANSFER_FROM_SELECTOR.to_vec();
+        data.extend_from_slice(&[0u8; 12]);
+        data.extend_from_slice(owner.as_bytes());
+        data.extend_from_slice(&[0u8; 12]);
+        data.extend_from_slice(htlc.as_bytes());
+
+        let mut token_id_bytes = [0u8; 32];
+        token_id.to_big_endian(&mut token_id_bytes);
+        data.extend_from_slice(&token_id_bytes);
+
+        Bytes(data)
+    }
+}
+
+impl From<Erc721Htlc> for Bytes {
+    fn from(htlc: Erc721Htlc) -> Self {
+        Bytes(htlc.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swap_protocols::rfc003::ethereum::hash_lock::HASH_LOCK_PLACEHOLDER;
+
+    const SECRET_HASH_PLACEHOLDER: &str =
+        "1000000000000000000000000000000000000000000000000000000000000001";
+    const EXPIRY_PLACEHOLDER: &str = "20000002";
+    const REDEEM_IDENTITY_PLACEHOLDER: &str = "3000000000000000000000000000000000000003";
+    const REFUND_IDENTITY_PLACEHOLDER: &str = "4000000000000000000000000000000000000004";
+    const TOKEN_ID_PLACEHOLDER: &str =
+        "5000000000000000000000000000000000000000000000000000000000000005";
+    const TOKEN_CONTRACT_PLACEHOLDER: &str = "6000000000000000000000000000000000000006";
+
+    #[test]
+    fn placeholders_are_at_their_offsets() {
+        let offsets = [
+            (HASH_LOCK_OFFSET, HASH_LOCK_PLACEHOLDER),
+            (SECRET_HASH_OFFSET, SECRET_HASH_PLACEHOLDER),
+            (EXPIRY_OFFSET, EXPIRY_PLACEHOLDER),
+            (REDEEM_IDENTITY_OFFSET, REDEEM_IDENTITY_PLACEHOLDER),
+            (REFUND_IDENTITY_OFFSET, REFUND_IDENTITY_PLACEHOLDER),
+            (TOKEN_ID_OFFSET, TOKEN_ID_PLACEHOLDER),
+            (TOKEN_CONTRACT_OFFSET, TOKEN_CONTRACT_PLACEHOLDER),
+        ];
+
+        for (offset, placeholder) in offsets.iter() {
+            assert_eq!(
+                &CONTRACT_TEMPLATE[offset * 2..offset * 2 + placeholder.len()],
+                *placeholder
+            );
+        }
+    }
+
+    #[test]
+    fn placeholders_are_replaced_with_htlc_params() {
+        let htlc = Erc721Htlc::new(
+            HashFunction::Keccak256,
+            2_000_000_000,
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+            [0x33; 32],
+            Address::repeat_byte(0x44),
+            U256::from(42),
+        );
+        let contract = hex::encode(&htlc.0);
+
+        for placeholder in &[
+            HASH_LOCK_PLACEHOLDER,
+            SECRET_HASH_PLACEHOLDER,
+            EXPIRY_PLACEHOLDER,
+            REDEEM_IDENTITY_PLACEHOLDER,
+            REFUND_IDENTITY_PLACEHOLDER,
+            TOKEN_ID_PLACEHOLDER,
+            TOKEN_CONTRACT_PLACEHOLDER,
+        ] {
+            assert!(!contract.contains(placeholder));
+        }
+        assert!(contract.contains(&format!("{:08x}", 2_000_000_000u32)));
+        assert!(contract.contains(&format!("{:064x}", 42)));
+        assert_eq!(htlc.0.len(), CONTRACT_TEMPLATE.len() / 2);
+    }
+
+    #[test]
+    fn deploy_header_returns_the_whole_contract() {
+        let bytes = hex::decode(CONTRACT_TEMPLATE).unwrap();
+        let header_length = usize::from(bytes[5]);
+        let contract_length = usize::from(u16::from_be_bytes([bytes[1], bytes[2]]));
+
+        assert_eq!(header_length + contract_length, bytes.len());
+    }
+
+    #[test]
+    fn transfer_from_payload_is_abi_encoded() {
+        let payload = Erc721Htlc::transfer_from_tx_payload(
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+            U256::from(42),
+        );
+
+        assert_eq!(
+            hex::encode(payload.0),
+            format!(
+                "23b872dd{:0>64}{:0>64}{:064x}",
+                "11".repeat(20),
+                "22".repeat(20),
+                42
+            )
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{
+    ethereum::{Address, Bytes, U256},
+    swap_protocols::{
+        rfc003::ethereum::{hash_lock::hash_lock, template::splice},
+        HashFunction,
+    },
+};
+use blockchain_contracts::ethereum::rfc003::ether_htlc;
+
+// The contract is the one of `Erc721Htlc` without the token transfer:
+//
+// redeem:
+//     log1(0, 32, keccak256("Redeemed()")), selfdestruct(<redeem_identity>)
+// refund:
+//     log1(0, 0, keccak256("Refunded()")), selfdestruct(<refund_identity>)
+const CONTRACT_TEMPLATE: &str = concat!(
+    "61012c80600c6000396000f3361561007e57602060008037700000000000000000000000000000000000000000000000",
+    "000000000000077f100000000000000000000000000000000000000000000000000000000000000160215114166100b3",
+    "577f696e76616c69645365637265740000000000000000000000000000000000000060005260206000fd5b4263200000",
+    "02106100f0577f746f6f4561726c7900000000000000000000000000000000000000000000000060005260206000fd5b",
+    "7fb8cac300e37f03ad332e581dea21b2f0b84eaaadc184a295fef71e81f44a741360206000a173300000000000000000",
+    "0000000000000000000003ff5b7f5d26862916391bf49478b2f5103b0720a842b45ef145a268f2cd1fb2aed551786000",
+    "80a1734000000000000000000000000000000000000004ff",
+);
+
+const HASH_LOCK_OFFSET: usize = 24;
+const SECRET_HASH_OFFSET: usize = 56;
+const EXPIRY_OFFSET: usize = 141;
+const REDEEM_IDENTITY_OFFSET: usize = 231;
+const REFUND_IDENTITY_OFFSET: usize = 291;
+
+/// An HTLC for ether, funded with its deployment.
+///
+/// SHA-256 HTLCs are the ones of `blockchain_contracts`, swaps that were
+/// started before other hash functions were supported still find their
+/// deployment by its bytecode.
+pub enum EtherHtlc {
+    Sha256(ether_htlc::EtherHtlc),
+    Other(Vec<u8>),
+}
+
+impl EtherHtlc {
+    pub fn new(
+        hash_function: HashFunction,
+        expiry: u32,
+        refund_identity: Address,
+        redeem_identity: Address,
+        secret_hash: [u8; 32],
+    ) -> Self {
+        if let HashFunction::Sha256 = hash_function {
+            return EtherHtlc::Sha256(ether_htlc::EtherHtlc::new(
+                expiry,
+                refund_identity,
+                redeem_identity,
+                secret_hash,
+            ));
+        }
+
+        let mut contract = hex::decode(CONTRACT_TEMPLATE).expect("template is valid hex");
+        let hash_lock = hex::decode(hash_lock(hash_function)).expect("hash lock is valid hex");
+        splice(&mut contract, HASH_LOCK_OFFSET, &hash_lock);
+        splice(&mut contract, SECRET_HASH_OFFSET, &secret_hash);
+        splice(&mut contract, EXPIRY_OFFSET, &expiry.to_be_bytes());
+        splice(
+            &mut contract,
+            REDEEM_IDENTITY_OFFSET,
+            redeem_identity.as_bytes(),
+        );
+        splice(
+            &mut contract,
+            REFUND_IDENTITY_OFFSET,
+            refund_identity.as_bytes(),
+        );
+
+        EtherHtlc::Other(contract)
+    }
+
+    pub fn deployment_gas_limit(&self) -> U256 {
+        match self {
+            EtherHtlc::Sha256(htlc) => htlc.deployment_gas_limit(),
+            EtherHtlc::Other(bytes) => {
+                let bytes = bytes.len() as u64;
+
+                U256::from(21_000 + 32_000 + bytes * (68 + 200) + 10_000)
+            }
+        }
+    }
+}
+
+impl From<EtherHtlc> for Bytes {
+    fn from(htlc: EtherHtlc) -> Self {
+        match htlc {
+            EtherHtlc::Sha256(htlc) => htlc.into(),
+            EtherHtlc::Other(bytes) => Bytes(bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swap_protocols::rfc003::ethereum::hash_lock::HASH_LOCK_PLACEHOLDER;
+
+    const SECRET_HASH_PLACEHOLDER: &str =
+        "1000000000000000000000000000000000000000000000000000000000000001";
+    const EXPIRY_PLACEHOLDER: &str = "20000002";
+    const REDEEM_IDENTITY_PLACEHOLDER: &str = "3000000000000000000000000000000000000003";
+    const REFUND_IDENTITY_PLACEHOLDER: &str = "4000000000000000000000000000000000000004";
+
+    #[test]
+    fn placeholders_are_at_their_offsets() {
+        let offsets = [
+            (HASH_LOCK_OFFSET, HASH_LOCK_PLACEHOLDER),
+            (SECRET_HASH_OFFSET, SECRET_HASH_PLACEHOLDER),
+            (EXPIRY_OFFSET, EXPIRY_PLACEHOLDER),
+            (REDEEM_IDENTITY_OFFSET, REDEEM_IDENTITY_PLACEHOLDER),
+            (REFUND_IDENTITY_OFFSET, REFUND_IDENTITY_PLACEHOLDER),
+        ];
+
+        for (offset, placeholder) in offsets.iter() {
+            assert_eq!(
+                &CONTRACT_TEMPLATE[offset * 2..offset * 2 + placeholder.len()],
+                *placeholder
+            );
+        }
+    }
+
+    fn htlc(hash_function: HashFunction) -> Bytes {
+        EtherHtlc::new(
+            hash_function,
+            2_000_000_000,
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+            [0x33; 32],
+        )
+        .into()
+    }
+
+    #[test]
+    fn placeholders_are_replaced_with_htlc_params() {
+        let contract = hex::encode(htlc(HashFunction::Hash160).0);
+
+        for placeholder in &[
+            HASH_LOCK_PLACEHOLDER,
+            SECRET_HASH_PLACEHOLDER,
+            EXPIRY_PLACEHOLDER,
+            REDEEM_IDENTITY_PLACEHOLDER,
+            REFUND_IDENTITY_PLACEHOLDER,
+        ] {
+            assert!(!contract.contains(placeholder));
+        }
+        assert!(contract.contains(hash_lock(HashFunction::Hash160)));
+        assert!(contract.contains(&"33".repeat(32)));
+        assert_eq!(contract.len(), CONTRACT_TEMPLATE.len());
+    }
+
+    #[test]
+    fn parameters_resembling_placeholders_are_left_intact() {
+        let mut secret_hash = [0u8; 32];
+        secret_hash[..4].copy_from_slice(&[0x20, 0x00, 0x00, 0x02]);
+        let redeem_identity =
+            Address::from_slice(&hex::decode(REFUND_IDENTITY_PLACEHOLDER).unwrap());
+
+        let htlc: Bytes = EtherHtlc::new(
+            HashFunction::Keccak256,
+            2_000_000_000,
+            Address::repeat_byte(0x11),
+            redeem_identity,
+            secret_hash,
+        )
+        .into();
+        let contract = htlc.0;
+
+        assert_eq!(&contract[SECRET_HASH_OFFSET..][..32], &secret_hash[..]);
+        assert_eq!(
+            &contract[REDEEM_IDENTITY_OFFSET..][..20],
+            redeem_identity.as_bytes()
+        );
+        assert_eq!(
+            &contract[REFUND_IDENTITY_OFFSET..][..20],
+            Address::repeat_byte(0x11).as_bytes()
+        );
+    }
+
+    #[test]
+    fn bytecode_depends_on_the_hash_function() {
+        let sha256 = htlc(HashFunction::Sha256);
+        let keccak256 = htlc(HashFunction::Keccak256);
+        let hash160 = htlc(HashFunction::Hash160);
+
+        assert_ne!(sha256, keccak256);
+        assert_ne!(sha256, hash160);
+        assert_ne!(keccak256, hash160);
+    }
+
+    #[test]
+    fn deploy_header_returns_the_whole_contract() {
+        let bytes = hex::decode(CONTRACT_TEMPLATE).unwrap();
+        let header_length = usize::from(bytes[5]);
+        let contract_length = usize::from(u16::from_be_bytes([bytes[1], bytes[2]]));
+
+        assert_eq!(header_length + contract_length, bytes.len());
+    }
+}
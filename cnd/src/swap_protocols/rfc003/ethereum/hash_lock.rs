@@ -0,0 +1,42 @@
+use crate::swap_protocols::HashFunction;
+
+/// Placeholder for the 31 bytes of the HTLC templates that hash the secret.
+///
+/// The code put in its place expects the secret at memory 0. It stores the
+/// hash, left-padded to 32 bytes, at memory 33 and leaves 1 on the stack if
+/// hashing succeeded. Shorter code is padded with `JUMPDEST`s.
+pub const HASH_LOCK_PLACEHOLDER: &str =
+    "70000000000000000000000000000000000000000000000000000000000007";
+
+/// call(72, 0x02, 0, 0, 32, 33, 32)
+const SHA256: &str = "60206021602060008060026048f15b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b";
+
+/// mstore(33, keccak256(0, 32)), 1
+const KECCAK256: &str = "602060002060215260015b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b5b";
+
+/// and(call(72, 0x02, 0, 0, 32, 33, 32), call(720, 0x03, 0, 33, 32, 33, 32))
+const HASH160: &str = "60206021602060008060026048f16020602160206021600060036102d0f116";
+
+pub fn hash_lock(hash_function: HashFunction) -> &'static str {
+    match hash_function {
+        HashFunction::Sha256 => SHA256,
+        HashFunction::Keccak256 => KECCAK256,
+        HashFunction::Hash160 => HASH160,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_locks_fill_the_placeholder() {
+        for hash_function in &[
+            HashFunction::Sha256,
+            HashFunction::Keccak256,
+            HashFunction::Hash160,
+        ] {
+            assert_eq!(hash_lock(*hash_function).len(), HASH_LOCK_PLACEHOLDER.len());
+        }
+    }
+}
@@ -1,7 +1,14 @@
 use crate::{
-    btsieve::ethereum::{matching_transaction, Event, Topic, TransactionPattern, Web3Connector},
+    btsieve::{
+        ethereum::{
+            matching_transaction_by_logs, matching_transaction_with_progress,
+            transaction_retracted, Event, Topic, TransactionPattern, Web3Connector,
+        },
+        BlockPoller,
+    },
+    db::SwapScanProgress,
     ethereum::{
-        Address, CalculateContractAddress, Erc20Token, EtherQuantity, Transaction,
+        Address, CalculateContractAddress, Erc20Token, Erc721Token, EtherQuantity, Transaction,
         TransactionAndReceipt, H256,
     },
     swap_protocols::{
@@ -10,8 +17,8 @@ use crate::{
         rfc003::{
             self,
             events::{
-                Deployed, DeployedFuture, Funded, FundedFuture, HtlcEvents, Redeemed,
-                RedeemedOrRefundedFuture, Refunded,
+                Deployed, DeployedFuture, Funded, FundedFuture, HtlcEvents, HtlcWatcher, Redeemed,
+                RedeemedOrRefundedFuture, Refunded, RetractedFuture,
             },
             state_machine::HtlcParams,
             Secret,
@@ -33,13 +40,13 @@ lazy_static::lazy_static! {
     pub static ref TRANSFER_LOG_MSG: H256 = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".parse().expect("to be valid hex");
 }
 
-impl HtlcEvents<Ethereum, EtherQuantity> for Web3Connector {
+impl HtlcEvents<Ethereum, EtherQuantity> for HtlcWatcher<BlockPoller<Web3Connector>> {
     fn htlc_deployed(
         &self,
         htlc_params: HtlcParams<Ethereum, EtherQuantity>,
     ) -> Box<DeployedFuture<Ethereum>> {
         let future = {
-            let connector = self.clone();
+            let connector = self.connector.clone();
             let pattern = TransactionPattern {
                 from_address: None,
                 to_address: None,
@@ -48,8 +55,12 @@ impl HtlcEvents<Ethereum, EtherQuantity> for Web3Connector {
                 transaction_data_length: None,
                 events: None,
             };
+            let scan_progress =
+                SwapScanProgress::new(self.db.clone(), htlc_params.swap_id, &pattern);
 
-            async { matching_transaction(connector, pattern, None).await }
+            async {
+                matching_transaction_with_progress(connector, pattern, None, scan_progress).await
+            }
         }
         .unit_error()
         .boxed()
@@ -82,6 +93,30 @@ impl HtlcEvents<Ethereum, EtherQuantity> for Web3Connector {
     ) -> Box<RedeemedOrRefundedFuture<Ethereum>> {
         htlc_redeemed_or_refunded(self.clone(), htlc_params, htlc_deployment, htlc_funding)
     }
+
+    fn transaction_retracted(
+        &self,
+        _: Ethereum,
+        transaction: &Transaction,
+    ) -> Box<RetractedFuture> {
+        htlc_transaction_retracted(self.connector.clone(), transaction)
+    }
+}
+
+fn htlc_transaction_retracted(
+    connector: BlockPoller<Web3Connector>,
+    transaction: &Transaction,
+) -> Box<RetractedFuture> {
+    let transaction = transaction.clone();
+
+    Box::new(
+        async move {
+            transaction_retracted(connector, transaction).await;
+            Ok(())
+        }
+        .boxed()
+        .compat(),
+    )
 }
 
 fn calculate_contract_address_from_deployment_transaction(tx: &Transaction) -> Address {
@@ -89,13 +124,13 @@ fn calculate_contract_address_from_deployment_transaction(tx: &Transaction) -> A
 }
 
 fn htlc_redeemed_or_refunded<A: Asset>(
-    connector: Web3Connector,
-    _htlc_params: HtlcParams<Ethereum, A>,
+    watcher: HtlcWatcher<BlockPoller<Web3Connector>>,
+    htlc_params: HtlcParams<Ethereum, A>,
     htlc_deployment: &Deployed<Ethereum>,
     _: &Funded<Ethereum, A>,
 ) -> Box<RedeemedOrRefundedFuture<Ethereum>> {
     let refunded_future = {
-        let connector = connector.clone();
+        let connector = watcher.connector.clone();
         let pattern = TransactionPattern {
             from_address: None,
             to_address: None,
@@ -108,8 +143,10 @@ fn htlc_redeemed_or_refunded<A: Asset>(
                 topics: vec![Some(Topic(*REFUND_LOG_MSG))],
             }]),
         };
+        let scan_progress =
+            SwapScanProgress::new(watcher.db.clone(), htlc_params.swap_id, &pattern);
 
-        async { matching_transaction(connector, pattern, None).await }
+        async { matching_transaction_by_logs(connector, pattern, scan_progress).await }
             .unit_error()
             .boxed()
             .compat()
@@ -120,6 +157,7 @@ fn htlc_redeemed_or_refunded<A: Asset>(
     };
 
     let redeemed_future = {
+        let connector = watcher.connector;
         let pattern = TransactionPattern {
             from_address: None,
             to_address: None,
@@ -132,15 +170,9 @@ fn htlc_redeemed_or_refunded<A: Asset>(
                 topics: vec![Some(Topic(*REDEEM_LOG_MSG))],
             }]),
         };
+        let scan_progress = SwapScanProgress::new(watcher.db, htlc_params.swap_id, &pattern);
 
-        async {
-            matching_transaction(
-                connector,
-                pattern,
-                None,
-            )
-            .await
-        }
+        async { matching_transaction_by_logs(connector, pattern, scan_progress).await }
         .unit_error()
         .boxed()
         .compat()
@@ -182,13 +214,13 @@ mod erc20 {
     use super::*;
     use crate::ethereum::{Erc20Quantity, U256};
 
-    impl HtlcEvents<Ethereum, Erc20Token> for Web3Connector {
+    impl HtlcEvents<Ethereum, Erc20Token> for HtlcWatcher<BlockPoller<Web3Connector>> {
         fn htlc_deployed(
             &self,
             htlc_params: HtlcParams<Ethereum, Erc20Token>,
         ) -> Box<DeployedFuture<Ethereum>> {
             let future = {
-                let connector = self.clone();
+                let connector = self.connector.clone();
                 let pattern = TransactionPattern {
                     from_address: None,
                     to_address: None,
@@ -197,18 +229,23 @@ mod erc20 {
                     transaction_data_length: None,
                     events: None,
                 };
+                let scan_progress =
+                    SwapScanProgress::new(self.db.clone(), htlc_params.swap_id, &pattern);
 
-                async { matching_transaction(connector, pattern, None).await }
-                    .unit_error()
-                    .boxed()
-                    .compat()
-                    .map_err(|_| rfc003::Error::Btsieve)
-                    .map(|txr| Deployed {
-                        location: calculate_contract_address_from_deployment_transaction(
-                            &txr.transaction,
-                        ),
-                        transaction: txr.transaction,
-                    })
+                async {
+                    matching_transaction_with_progress(connector, pattern, None, scan_progress)
+                        .await
+                }
+                .unit_error()
+                .boxed()
+                .compat()
+                .map_err(|_| rfc003::Error::Btsieve)
+                .map(|txr| Deployed {
+                    location: calculate_contract_address_from_deployment_transaction(
+                        &txr.transaction,
+                    ),
+                    transaction: txr.transaction,
+                })
             };
 
             Box::new(future)
@@ -220,7 +257,7 @@ mod erc20 {
             htlc_deployment: &Deployed<Ethereum>,
         ) -> Box<FundedFuture<Ethereum, Erc20Token>> {
             let future = {
-                let connector = self.clone();
+                let connector = self.connector.clone();
                 let events = Some(vec![Event {
                     address: Some(htlc_params.asset.token_contract),
                     data: None,
@@ -230,22 +267,18 @@ mod erc20 {
                         Some(Topic(htlc_deployment.location.into())),
                     ],
                 }]);
+                let pattern = TransactionPattern {
+                    from_address: None,
+                    to_address: None,
+                    is_contract_creation: None,
+                    transaction_data: None,
+                    transaction_data_length: None,
+                    events,
+                };
+                let scan_progress =
+                    SwapScanProgress::new(self.db.clone(), htlc_params.swap_id, &pattern);
 
-                async {
-                    matching_transaction(
-                        connector,
-                        TransactionPattern {
-                            from_address: None,
-                            to_address: None,
-                            is_contract_creation: None,
-                            transaction_data: None,
-                            transaction_data_length: None,
-                            events,
-                        },
-                        None,
-                    )
-                        .await
-                }
+                async { matching_transaction_by_logs(connector, pattern, scan_progress).await }
                 .unit_error()
                     .boxed()
                     .compat()
@@ -288,5 +321,143 @@ mod erc20 {
         ) -> Box<RedeemedOrRefundedFuture<Ethereum>> {
             htlc_redeemed_or_refunded(self.clone(), htlc_params, htlc_deployment, htlc_funding)
         }
+        fn transaction_retracted(
+            &self,
+            _: Ethereum,
+            transaction: &Transaction,
+        ) -> Box<RetractedFuture> {
+            htlc_transaction_retracted(self.connector.clone(), transaction)
+        }
+    }
+}
+
+mod erc721 {
+    use super::*;
+    use crate::ethereum::{Erc721TokenId, U256};
+
+    impl HtlcEvents<Ethereum, Erc721Token> for HtlcWatcher<BlockPoller<Web3Connector>> {
+        fn htlc_deployed(
+            &self,
+            htlc_params: HtlcParams<Ethereum, Erc721Token>,
+        ) -> Box<DeployedFuture<Ethereum>> {
+            let future = {
+                let connector = self.connector.clone();
+                let pattern = TransactionPattern {
+                    from_address: None,
+                    to_address: None,
+                    is_contract_creation: Some(true),
+                    transaction_data: Some(htlc_params.bytecode()),
+                    transaction_data_length: None,
+                    events: None,
+                };
+                let scan_progress =
+                    SwapScanProgress::new(self.db.clone(), htlc_params.swap_id, &pattern);
+
+                async {
+                    matching_transaction_with_progress(connector, pattern, None, scan_progress)
+                        .await
+                }
+                .unit_error()
+                .boxed()
+                .compat()
+                .map_err(|_| rfc003::Error::Btsieve)
+                .map(|txr| Deployed {
+                    location: calculate_contract_address_from_deployment_transaction(
+                        &txr.transaction,
+                    ),
+                    transaction: txr.transaction,
+                })
+            };
+
+            Box::new(future)
+        }
+
+        /// Other than for ERC20 the token id of an ERC721 `Transfer` is
+        /// indexed, the event has a fourth topic and no data.
+        fn htlc_funded(
+            &self,
+            htlc_params: HtlcParams<Ethereum, Erc721Token>,
+            htlc_deployment: &Deployed<Ethereum>,
+        ) -> Box<FundedFuture<Ethereum, Erc721Token>> {
+            let future = {
+                let connector = self.connector.clone();
+                let mut token_id = [0u8; 32];
+                htlc_params.asset.token_id.0.to_big_endian(&mut token_id);
+                let events = Some(vec![Event {
+                    address: Some(htlc_params.asset.token_contract),
+                    data: None,
+                    topics: vec![
+                        Some(Topic(*super::TRANSFER_LOG_MSG)),
+                        None,
+                        Some(Topic(htlc_deployment.location.into())),
+                        Some(Topic(H256::from(token_id))),
+                    ],
+                }]);
+                let pattern = TransactionPattern {
+                    from_address: None,
+                    to_address: None,
+                    is_contract_creation: None,
+                    transaction_data: None,
+                    transaction_data_length: None,
+                    events,
+                };
+                let scan_progress =
+                    SwapScanProgress::new(self.db.clone(), htlc_params.swap_id, &pattern);
+
+                async { matching_transaction_by_logs(connector, pattern, scan_progress).await }
+                    .unit_error()
+                    .boxed()
+                    .compat()
+                    .map_err(|_| rfc003::Error::Btsieve)
+                    .and_then(
+                        |TransactionAndReceipt {
+                             transaction,
+                             receipt,
+                         }| {
+                            receipt
+                                .logs
+                                .into_iter()
+                                .find(|log| {
+                                    log.topics.len() == 4
+                                        && log.topics[0] == *super::TRANSFER_LOG_MSG
+                                })
+                                .ok_or_else(|| {
+                                    log::warn!(
+                                        "receipt for transaction {:?} did not contain any ERC721 Transfer events",
+                                        transaction.hash
+                                    );
+                                    rfc003::Error::IncorrectFunding
+                                })
+                                .map(|log| {
+                                    let token_id = Erc721TokenId(U256::from_big_endian(
+                                        log.topics[3].as_bytes(),
+                                    ));
+                                    let asset = Erc721Token::new(log.address, token_id);
+
+                                    Funded { transaction, asset }
+                                })
+                        },
+                    )
+            };
+
+            Box::new(future)
+        }
+
+        fn htlc_redeemed_or_refunded(
+            &self,
+            htlc_params: HtlcParams<Ethereum, Erc721Token>,
+            htlc_deployment: &Deployed<Ethereum>,
+            htlc_funding: &Funded<Ethereum, Erc721Token>,
+        ) -> Box<RedeemedOrRefundedFuture<Ethereum>> {
+            htlc_redeemed_or_refunded(self.clone(), htlc_params, htlc_deployment, htlc_funding)
+        }
+
+        fn transaction_retracted(
+            &self,
+            _: Ethereum,
+            transaction: &Transaction,
+        ) -> Box<RetractedFuture> {
+            htlc_transaction_retracted(self.connector.clone(), transaction)
+        }
     }
 }
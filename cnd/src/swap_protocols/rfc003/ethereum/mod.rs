@@ -1,14 +1,22 @@
+pub mod erc20_htlc;
+pub mod erc721_htlc;
+pub mod ether_htlc;
+pub mod hash_lock;
 pub mod htlc_events;
+pub mod template;
 
 use crate::{
-    ethereum::{Address, Bytes, Erc20Token, EtherQuantity},
+    ethereum::{Address, Bytes, Erc20Token, Erc721Token, EtherQuantity},
     swap_protocols::{
         actions::ethereum::DeployContract,
         ledger::Ethereum,
         rfc003::{state_machine::HtlcParams, Ledger},
+        HashFunction,
     },
 };
-use blockchain_contracts::ethereum::rfc003::{erc20_htlc::Erc20Htlc, ether_htlc::EtherHtlc};
+use erc20_htlc::Erc20Htlc;
+use erc721_htlc::Erc721Htlc;
+use ether_htlc::EtherHtlc;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -42,11 +50,18 @@ impl From<Seconds> for Duration {
 
 impl Ledger for Ethereum {
     type HtlcLocation = Address;
+
+    const HASH_FUNCTIONS: &'static [HashFunction] = &[
+        HashFunction::Sha256,
+        HashFunction::Keccak256,
+        HashFunction::Hash160,
+    ];
 }
 
 impl From<HtlcParams<Ethereum, EtherQuantity>> for EtherHtlc {
     fn from(htlc_params: HtlcParams<Ethereum, EtherQuantity>) -> Self {
         EtherHtlc::new(
+            htlc_params.hash_function,
             htlc_params.expiry.into(),
             htlc_params.refund_identity,
             htlc_params.redeem_identity,
@@ -64,6 +79,7 @@ impl HtlcParams<Ethereum, EtherQuantity> {
 impl From<HtlcParams<Ethereum, Erc20Token>> for Erc20Htlc {
     fn from(htlc_params: HtlcParams<Ethereum, Erc20Token>) -> Self {
         Erc20Htlc::new(
+            htlc_params.hash_function,
             htlc_params.expiry.into(),
             htlc_params.refund_identity,
             htlc_params.redeem_identity,
@@ -80,6 +96,26 @@ impl HtlcParams<Ethereum, Erc20Token> {
     }
 }
 
+impl From<HtlcParams<Ethereum, Erc721Token>> for Erc721Htlc {
+    fn from(htlc_params: HtlcParams<Ethereum, Erc721Token>) -> Self {
+        Erc721Htlc::new(
+            htlc_params.hash_function,
+            htlc_params.expiry.into(),
+            htlc_params.refund_identity,
+            htlc_params.redeem_identity,
+            htlc_params.secret_hash.into(),
+            htlc_params.asset.token_contract,
+            htlc_params.asset.token_id.0,
+        )
+    }
+}
+
+impl HtlcParams<Ethereum, Erc721Token> {
+    pub fn bytecode(&self) -> Bytes {
+        Erc721Htlc::from(self.clone()).into()
+    }
+}
+
 impl From<HtlcParams<Ethereum, EtherQuantity>> for DeployContract {
     fn from(htlc_params: HtlcParams<Ethereum, EtherQuantity>) -> Self {
         let htlc = EtherHtlc::from(htlc_params.clone());
@@ -107,3 +143,17 @@ impl From<HtlcParams<Ethereum, Erc20Token>> for DeployContract {
         }
     }
 }
+
+impl From<HtlcParams<Ethereum, Erc721Token>> for DeployContract {
+    fn from(htlc_params: HtlcParams<Ethereum, Erc721Token>) -> Self {
+        let htlc = Erc721Htlc::from(htlc_params.clone());
+        let gas_limit = htlc.deployment_gas_limit();
+
+        DeployContract {
+            data: htlc.into(),
+            amount: EtherQuantity::zero(),
+            gas_limit,
+            chain_id: htlc_params.ledger.chain_id,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+/// Writes `bytes` over the placeholder at `offset` of an HTLC template.
+///
+/// The placeholders are not searched for: a parameter filled in before could
+/// contain the digits of a later placeholder.
+pub fn splice(contract: &mut [u8], offset: usize, bytes: &[u8]) {
+    contract[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
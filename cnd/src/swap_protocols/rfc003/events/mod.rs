@@ -2,9 +2,12 @@
 // see: https://github.com/rust-lang/rust/issues/21903
 #![allow(type_alias_bounds)]
 
-use crate::swap_protocols::{
-    asset::Asset,
-    rfc003::{self, ledger::Ledger, state_machine::HtlcParams, Secret},
+use crate::{
+    db::Sqlite,
+    swap_protocols::{
+        asset::Asset,
+        rfc003::{self, ledger::Ledger, state_machine::HtlcParams, Secret},
+    },
 };
 use serde::{Deserialize, Serialize};
 use tokio::{self, prelude::future::Either};
@@ -46,6 +49,7 @@ impl<L: Ledger> Refunded<L> {
 pub type DeployedFuture<L: Ledger> = Future<Deployed<L>>;
 pub type FundedFuture<L: Ledger, A: Asset> = Future<Funded<L, A>>;
 pub type RedeemedOrRefundedFuture<L: Ledger> = Future<Either<Redeemed<L>, Refunded<L>>>;
+pub type RetractedFuture = Future<()>;
 
 pub trait HtlcEvents<L: Ledger, A: Asset>: Send + Sync + 'static {
     fn htlc_deployed(&self, htlc_params: HtlcParams<L, A>) -> Box<DeployedFuture<L>>;
@@ -60,4 +64,28 @@ pub trait HtlcEvents<L: Ledger, A: Asset>: Send + Sync + 'static {
         htlc_deployment: &Deployed<L>,
         htlc_funding: &Funded<L, A>,
     ) -> Box<RedeemedOrRefundedFuture<L>>;
+    /// Resolves once `transaction` is no longer part of the best chain of
+    /// `ledger` because the block that included it was orphaned.
+    fn transaction_retracted(
+        &self,
+        ledger: L,
+        transaction: &L::Transaction,
+    ) -> Box<RetractedFuture>;
+}
+
+/// Reports how many confirmations the HTLC transactions we have been waiting
+/// for currently have.
+pub trait TransactionConfirmations<L: Ledger>: Send + Sync + 'static {
+    fn transaction_confirmations(&self, transaction: &L::Transaction) -> Option<u32>;
+    /// Stops counting the confirmations of `transaction`, e.g. because its
+    /// swap finished.
+    fn forget_transaction(&self, transaction: &L::Transaction);
+}
+
+/// Watches the blockchain behind `connector` for HTLC events and remembers in
+/// `db` how far it was scanned for each of them.
+#[derive(Debug, Clone)]
+pub struct HtlcWatcher<C> {
+    pub connector: C,
+    pub db: Sqlite,
 }
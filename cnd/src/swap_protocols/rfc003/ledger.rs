@@ -1,7 +1,10 @@
-use crate::swap_protocols;
+use crate::swap_protocols::{self, HashFunction};
 use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 
 pub trait Ledger: swap_protocols::Ledger {
     type HtlcLocation: PartialEq + Debug + Clone + DeserializeOwned + Serialize + Send + Sync;
+
+    /// The hash functions the HTLCs on this ledger can check the secret with.
+    const HASH_FUNCTIONS: &'static [HashFunction];
 }
@@ -5,7 +5,7 @@ use strum_macros::EnumDiscriminants;
 #[derive(Clone, Debug, PartialEq, EnumDiscriminants)]
 #[strum_discriminants(
     name(HtlcState),
-    derive(Serialize),
+    derive(Serialize, Display, EnumString, EnumIter),
     serde(rename_all = "SCREAMING_SNAKE_CASE")
 )]
 pub enum LedgerState<L: Ledger> {
@@ -38,6 +38,41 @@ pub enum LedgerState<L: Ledger> {
     },
 }
 
+impl<L: Ledger> LedgerState<L> {
+    /// All transactions that were seen on this ledger so far.
+    pub fn transactions(&self) -> Vec<&L::Transaction> {
+        use self::LedgerState::*;
+        match self {
+            NotDeployed => vec![],
+            Deployed {
+                deploy_transaction, ..
+            } => vec![deploy_transaction],
+            Funded {
+                deploy_transaction,
+                fund_transaction,
+                ..
+            }
+            | IncorrectlyFunded {
+                deploy_transaction,
+                fund_transaction,
+                ..
+            } => vec![deploy_transaction, fund_transaction],
+            Redeemed {
+                deploy_transaction,
+                fund_transaction,
+                redeem_transaction,
+                ..
+            } => vec![deploy_transaction, fund_transaction, redeem_transaction],
+            Refunded {
+                deploy_transaction,
+                fund_transaction,
+                refund_transaction,
+                ..
+            } => vec![deploy_transaction, fund_transaction, refund_transaction],
+        }
+    }
+}
+
 impl Default for HtlcState {
     fn default() -> Self {
         HtlcState::NotDeployed
@@ -0,0 +1,175 @@
+use crate::{
+    btsieve::{
+        bitcoin::{
+            matching_transaction_with_progress, transaction_retracted, TransactionExt,
+            TransactionPattern,
+        },
+        litecoin::LitecoindConnector,
+        BlockPoller,
+    },
+    db::SwapScanProgress,
+    litecoin,
+    swap_protocols::{
+        ledger::Litecoin,
+        rfc003::{
+            self,
+            bitcoin::extract_secret,
+            events::{
+                Deployed, DeployedFuture, Funded, FundedFuture, HtlcEvents, HtlcWatcher, Redeemed,
+                RedeemedOrRefundedFuture, Refunded, RetractedFuture,
+            },
+            state_machine::HtlcParams,
+        },
+    },
+};
+use bitcoin::OutPoint;
+use futures::{
+    future::{self, Either},
+    Future,
+};
+use futures_core::future::{FutureExt, TryFutureExt};
+
+impl HtlcEvents<Litecoin, litecoin::Amount> for HtlcWatcher<BlockPoller<LitecoindConnector>> {
+    fn htlc_deployed(
+        &self,
+        htlc_params: HtlcParams<Litecoin, litecoin::Amount>,
+    ) -> Box<DeployedFuture<Litecoin>> {
+        let connector = self.connector.clone();
+        let htlc_address = htlc_params.compute_address().to_bitcoin_address();
+        let pattern = TransactionPattern {
+            to_address: Some(htlc_address.clone()),
+            from_outpoint: None,
+            unlock_script: None,
+        };
+        let scan_progress = SwapScanProgress::new(self.db.clone(), htlc_params.swap_id, &pattern);
+
+        let future = async move {
+            matching_transaction_with_progress(connector, pattern, None, scan_progress).await
+        }
+        .boxed()
+        .compat()
+        .map_err(|_| rfc003::Error::Btsieve)
+        .and_then(move |tx| {
+            let (vout, _txout) = tx.find_output(&htlc_address).ok_or_else(|| {
+                rfc003::Error::Internal(
+                    "Query returned Litecoin transaction that didn't match the requested address"
+                        .into(),
+                )
+            })?;
+
+            Ok(Deployed {
+                location: OutPoint {
+                    txid: tx.txid(),
+                    vout,
+                },
+                transaction: tx,
+            })
+        });
+
+        Box::new(future)
+    }
+
+    fn htlc_funded(
+        &self,
+        _htlc_params: HtlcParams<Litecoin, litecoin::Amount>,
+        htlc_deployment: &Deployed<Litecoin>,
+    ) -> Box<FundedFuture<Litecoin, litecoin::Amount>> {
+        let tx = &htlc_deployment.transaction;
+        let asset =
+            litecoin::Amount::from_litoshi(tx.output[htlc_deployment.location.vout as usize].value);
+        Box::new(future::ok(Funded {
+            transaction: tx.clone(),
+            asset,
+        }))
+    }
+
+    fn htlc_redeemed_or_refunded(
+        &self,
+        htlc_params: HtlcParams<Litecoin, litecoin::Amount>,
+        htlc_deployment: &Deployed<Litecoin>,
+        _htlc_funding: &Funded<Litecoin, litecoin::Amount>,
+    ) -> Box<RedeemedOrRefundedFuture<Litecoin>> {
+        let refunded_future = {
+            let connector = self.connector.clone();
+            let pattern = TransactionPattern {
+                to_address: None,
+                from_outpoint: Some(htlc_deployment.location),
+                unlock_script: Some(vec![vec![]]),
+            };
+            let scan_progress =
+                SwapScanProgress::new(self.db.clone(), htlc_params.swap_id, &pattern);
+
+            async move {
+                matching_transaction_with_progress(connector, pattern, None, scan_progress).await
+            }
+            .boxed()
+            .compat()
+            .map_err(|_| rfc003::Error::Btsieve)
+            .map(|transaction| Refunded { transaction })
+        };
+
+        let redeemed_future = {
+            let connector = self.connector.clone();
+            let pattern = TransactionPattern {
+                to_address: None,
+                from_outpoint: Some(htlc_deployment.location),
+                unlock_script: Some(vec![vec![1u8]]),
+            };
+            let scan_progress =
+                SwapScanProgress::new(self.db.clone(), htlc_params.swap_id, &pattern);
+
+            async move {
+                matching_transaction_with_progress(connector, pattern, None, scan_progress).await
+            }
+            .boxed()
+            .compat()
+            .map_err(|_| rfc003::Error::Btsieve)
+            .and_then(move |tx| {
+                let secret =
+                    extract_secret(&tx, htlc_params.hash_function, &htlc_params.secret_hash)
+                        .ok_or_else(|| {
+                            log::error!("Redeem transaction didn't have secret it in: {:?}", tx);
+                            rfc003::Error::Internal(
+                                "Redeem transaction didn't have the secret in it".into(),
+                            )
+                        })?;
+
+                Ok(Redeemed {
+                    transaction: tx,
+                    secret,
+                })
+            })
+        };
+
+        Box::new(
+            redeemed_future
+                .select2(refunded_future)
+                .map(|tx| match tx {
+                    Either::A((tx, _)) => Either::A(tx),
+                    Either::B((tx, _)) => Either::B(tx),
+                })
+                .map_err(|either| match either {
+                    Either::A((error, _)) => error,
+                    Either::B((error, _)) => error,
+                }),
+        )
+    }
+
+    fn transaction_retracted(
+        &self,
+        _: Litecoin,
+        transaction: &bitcoin::Transaction,
+    ) -> Box<RetractedFuture> {
+        let connector = self.connector.clone();
+        let txid = transaction.txid();
+
+        Box::new(
+            async move {
+                transaction_retracted(connector, txid).await;
+                Ok(())
+            }
+            .boxed()
+            .compat(),
+        )
+    }
+}
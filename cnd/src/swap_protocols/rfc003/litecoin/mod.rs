@@ -0,0 +1,52 @@
+mod htlc_events;
+
+use crate::{
+    litecoin,
+    swap_protocols::{
+        ledger::Litecoin,
+        rfc003::{bitcoin::Htlc, state_machine::HtlcParams, Ledger},
+        HashFunction,
+    },
+};
+use bitcoin::{
+    hashes::{hash160, Hash},
+    OutPoint,
+};
+
+pub use self::htlc_events::*;
+
+impl Ledger for Litecoin {
+    type HtlcLocation = OutPoint;
+
+    const HASH_FUNCTIONS: &'static [HashFunction] = &[HashFunction::Sha256, HashFunction::Hash160];
+}
+
+/// Litecoin runs the HTLC scripts of Bitcoin unchanged.
+impl From<HtlcParams<Litecoin, litecoin::Amount>> for Htlc {
+    fn from(htlc_params: HtlcParams<Litecoin, litecoin::Amount>) -> Self {
+        let refund_public_key = htlc_params.refund_identity.into_inner();
+        let redeem_public_key = htlc_params.redeem_identity.into_inner();
+
+        let refund_identity = hash160::Hash::hash(&refund_public_key.key.serialize());
+        let redeem_identity = hash160::Hash::hash(&redeem_public_key.key.serialize());
+
+        Htlc::new(
+            htlc_params.hash_function,
+            htlc_params.expiry.into(),
+            refund_identity,
+            redeem_identity,
+            htlc_params.secret_hash,
+        )
+    }
+}
+
+impl HtlcParams<Litecoin, litecoin::Amount> {
+    pub fn compute_address(&self) -> litecoin::Address {
+        let address = Htlc::from(self.clone()).compute_address(self.ledger.network);
+
+        litecoin::Address {
+            payload: address.payload,
+            network: self.ledger.network,
+        }
+    }
+}
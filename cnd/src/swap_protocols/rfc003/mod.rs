@@ -7,6 +7,7 @@ pub mod bob;
 pub mod ethereum;
 pub mod events;
 pub mod ledger_state;
+pub mod litecoin;
 pub mod messages;
 pub mod state_machine;
 pub mod state_store;
@@ -1,30 +1,58 @@
+use crate::swap_protocols::HashFunction;
+use bitcoin::hashes::{hash160, Hash};
 use crypto::{digest::Digest, sha2::Sha256};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     fmt::{self, Debug},
     str::FromStr,
 };
+use tiny_keccak::{Hasher, Keccak};
 
 #[derive(PartialEq, Clone, Copy, Debug, thiserror::Error)]
 pub enum FromErr {
     #[error("invalid length, expected: {expected:?}, got: {got:?}")]
     InvalidLength { expected: usize, got: usize },
+    #[error("invalid length, expected: 20 or 32, got: {got:?}")]
+    InvalidHashLength { got: usize },
     #[error("hex: ")]
     FromHex(#[from] hex::FromHexError),
 }
 
+/// The hash of a `Secret`. It is 32 bytes long for SHA-256 and Keccak-256
+/// and 20 bytes long for HASH160.
 #[derive(Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
-pub struct SecretHash([u8; Self::LENGTH]);
+pub struct SecretHash {
+    bytes: [u8; Self::MAX_LENGTH],
+    length: usize,
+}
 
 impl SecretHash {
-    pub const LENGTH: usize = 32;
+    pub const MAX_LENGTH: usize = 32;
+    pub const HASH160_LENGTH: usize = 20;
 
-    pub fn as_raw(&self) -> &[u8; Self::LENGTH] {
-        &self.0
+    pub fn from_slice(slice: &[u8]) -> Result<Self, FromErr> {
+        if slice.len() != Self::MAX_LENGTH && slice.len() != Self::HASH160_LENGTH {
+            return Err(FromErr::InvalidHashLength { got: slice.len() });
+        }
+        let mut bytes = [0; Self::MAX_LENGTH];
+        bytes[..slice.len()].copy_from_slice(slice);
+
+        Ok(SecretHash {
+            bytes,
+            length: slice.len(),
+        })
     }
 
-    pub fn into_raw(self) -> [u8; Self::LENGTH] {
-        self.0
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.length]
+    }
+
+    /// Whether this hash has the length of the output of `hash_function`.
+    pub fn fits(&self, hash_function: HashFunction) -> bool {
+        match hash_function {
+            HashFunction::Sha256 | HashFunction::Keccak256 => self.length == Self::MAX_LENGTH,
+            HashFunction::Hash160 => self.length == Self::HASH160_LENGTH,
+        }
     }
 }
 
@@ -48,7 +76,7 @@ impl fmt::Display for SecretHash {
 
 impl fmt::LowerHex for SecretHash {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        f.write_str(hex::encode(&self.0).as_str())
+        f.write_str(hex::encode(self.as_bytes()).as_str())
     }
 }
 
@@ -72,7 +100,7 @@ impl<'de> Deserialize<'de> for SecretHash {
             type Value = SecretHash;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-                formatter.write_str("a hex encoded 32 byte value")
+                formatter.write_str("a hex encoded 20 or 32 byte value")
             }
 
             fn visit_str<E>(self, v: &str) -> Result<SecretHash, E>
@@ -94,22 +122,22 @@ impl FromStr for SecretHash {
 
     fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
         let vec = hex::decode(s)?;
-        if vec.len() != Self::LENGTH {
-            return Err(FromErr::InvalidLength {
-                expected: Self::LENGTH,
-                got: vec.len(),
-            });
+        Self::from_slice(&vec)
+    }
+}
+
+impl From<[u8; SecretHash::MAX_LENGTH]> for SecretHash {
+    fn from(hash: [u8; SecretHash::MAX_LENGTH]) -> Self {
+        SecretHash {
+            bytes: hash,
+            length: Self::MAX_LENGTH,
         }
-        let mut data = [0; Self::LENGTH];
-        let vec = &vec[..Self::LENGTH];
-        data.copy_from_slice(vec);
-        Ok(SecretHash(data))
     }
 }
 
-impl From<[u8; Self::LENGTH]> for SecretHash {
-    fn from(hash: [u8; Self::LENGTH]) -> Self {
-        SecretHash(hash)
+impl From<[u8; SecretHash::HASH160_LENGTH]> for SecretHash {
+    fn from(hash: [u8; SecretHash::HASH160_LENGTH]) -> Self {
+        Self::from_slice(&hash).expect("a HASH160 is 20 bytes long")
     }
 }
 
@@ -122,12 +150,6 @@ impl From<[u8; Self::LENGTH]> for Secret {
     }
 }
 
-impl From<Secret> for SecretHash {
-    fn from(secret: Secret) -> Self {
-        secret.hash()
-    }
-}
-
 impl Secret {
     // Both values need to stay the same!
     pub const LENGTH: usize = 32;
@@ -146,13 +168,26 @@ impl Secret {
         Ok(Secret(data))
     }
 
-    pub fn hash(&self) -> SecretHash {
-        let mut sha = Sha256::new();
-        sha.input(&self.0);
+    pub fn hash(&self, hash_function: HashFunction) -> SecretHash {
+        match hash_function {
+            HashFunction::Sha256 => {
+                let mut sha = Sha256::new();
+                sha.input(&self.0);
 
-        let mut result: [u8; Self::LENGTH] = [0; SecretHash::LENGTH];
-        sha.result(&mut result);
-        SecretHash::from(result)
+                let mut result = [0; SecretHash::MAX_LENGTH];
+                sha.result(&mut result);
+                SecretHash::from(result)
+            }
+            HashFunction::Keccak256 => {
+                let mut keccak = Keccak::v256();
+                keccak.update(&self.0);
+
+                let mut result = [0; SecretHash::MAX_LENGTH];
+                keccak.finalize(&mut result);
+                SecretHash::from(result)
+            }
+            HashFunction::Hash160 => SecretHash::from(hash160::Hash::hash(&self.0).into_inner()),
+        }
     }
 
     pub fn as_raw_secret(&self) -> &[u8; Self::LENGTH] {
@@ -164,6 +199,12 @@ impl Secret {
     }
 }
 
+impl fmt::Display for Secret {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(&format!("{:x}", self))
+    }
+}
+
 impl fmt::LowerHex for Secret {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         f.write_str(hex::encode(&self.0).as_str())
@@ -179,9 +220,14 @@ impl FromStr for Secret {
     }
 }
 
+/// Hashes shorter than 32 bytes are left-padded with zeros, the way the EVM
+/// stores them in a word.
 impl From<SecretHash> for [u8; 32] {
     fn from(secret_hash: SecretHash) -> [u8; 32] {
-        secret_hash.0
+        let mut word = [0; 32];
+        word[32 - secret_hash.length..].copy_from_slice(secret_hash.as_bytes());
+
+        word
     }
 }
 
@@ -231,8 +277,52 @@ mod tests {
         let bytes = b"hello world, you are beautiful!!";
         let secret = Secret::from(*bytes);
         assert_eq!(
-            secret.hash().to_string(),
+            secret.hash(HashFunction::Sha256).to_string(),
+            "68d627971643a6f97f27c58957826fcba853ec2077fd10ec6b93d8e61deb4cec"
+        );
+    }
+
+    #[test]
+    fn new_keccak256_secret_hash_as_hex() {
+        let bytes = b"hello world, you are beautiful!!";
+        let secret = Secret::from(*bytes);
+        assert_eq!(
+            secret.hash(HashFunction::Keccak256).to_string(),
+            "e96cb429b7adbbfd242cf8faa243ba5c5ede8ec527d23bc4464efd1e230e3a4f"
+        );
+    }
+
+    #[test]
+    fn new_hash160_secret_hash_as_hex() {
+        let bytes = b"hello world, you are beautiful!!";
+        let secret = Secret::from(*bytes);
+        assert_eq!(
+            secret.hash(HashFunction::Hash160).to_string(),
+            "ce4be827894106ecf7bedfe6c0343a81e1e7814a"
+        );
+    }
+
+    #[test]
+    fn hash160_secret_hash_is_left_padded_to_a_word() {
+        let secret = Secret::from(*b"hello world, you are beautiful!!");
+        let word: [u8; 32] = secret.hash(HashFunction::Hash160).into();
+
+        assert_eq!(
+            hex::encode(word),
+            "000000000000000000000000ce4be827894106ecf7bedfe6c0343a81e1e7814a"
+        );
+    }
+
+    #[test]
+    fn secret_hash_from_str_accepts_20_and_32_bytes() {
+        assert!(SecretHash::from_str("ce4be827894106ecf7bedfe6c0343a81e1e7814a").is_ok());
+        assert!(SecretHash::from_str(
             "68d627971643a6f97f27c58957826fcba853ec2077fd10ec6b93d8e61deb4cec"
+        )
+        .is_ok());
+        assert_eq!(
+            SecretHash::from_str("ce4be827894106ecf7bedfe6c0343a81e1e781").unwrap_err(),
+            FromErr::InvalidHashLength { got: 19 }
         );
     }
 
@@ -241,7 +331,7 @@ mod tests {
         let bytes = b"hello world, you are beautiful!!";
         let secret = Secret::from(*bytes);
 
-        let hash = secret.hash();
+        let hash = secret.hash(HashFunction::Sha256);
 
         let formatted_hash = hash.to_string();
 
@@ -275,6 +365,24 @@ mod tests {
         });
     }
 
+    #[test]
+    fn secret_hash_fits_the_hash_function_it_was_computed_with() {
+        let secret = Secret::from(*b"hello world, you are beautiful!!");
+
+        assert!(secret
+            .hash(HashFunction::Keccak256)
+            .fits(HashFunction::Sha256));
+        assert!(secret
+            .hash(HashFunction::Hash160)
+            .fits(HashFunction::Hash160));
+        assert!(!secret
+            .hash(HashFunction::Hash160)
+            .fits(HashFunction::Sha256));
+        assert!(!secret
+            .hash(HashFunction::Sha256)
+            .fits(HashFunction::Hash160));
+    }
+
     #[test]
     fn secret_length_is_consistent() {
         assert_eq!(Secret::LENGTH, usize::from(Secret::LENGTH_U8));
@@ -8,12 +8,12 @@ use crate::{
             self,
             events::{
                 Deployed, DeployedFuture, Funded, FundedFuture, HtlcEvents, Redeemed,
-                RedeemedOrRefundedFuture, Refunded,
+                RedeemedOrRefundedFuture, Refunded, RetractedFuture,
             },
             ledger::Ledger,
-            Accept, Request, SaveState, SecretHash,
+            Accept, LedgerState, Request, SaveState, Secret, SecretHash,
         },
-        HashFunction,
+        HashFunction, SwapId,
     },
     timestamp::Timestamp,
 };
@@ -24,11 +24,13 @@ use std::{cmp::Ordering::*, fmt, sync::Arc};
 
 #[derive(Clone, Debug)]
 pub struct HtlcParams<L: Ledger, A: Asset> {
+    pub swap_id: SwapId,
     pub asset: A,
     pub ledger: L,
     pub redeem_identity: L::Identity,
     pub refund_identity: L::Identity,
     pub expiry: Timestamp,
+    pub hash_function: HashFunction,
     pub secret_hash: SecretHash,
 }
 
@@ -38,11 +40,13 @@ impl<L: Ledger, A: Asset> HtlcParams<L, A> {
         accept_response: &rfc003::Accept<L, BL>,
     ) -> Self {
         HtlcParams {
+            swap_id: request.swap_id,
             asset: request.alpha_asset,
             ledger: request.alpha_ledger,
             redeem_identity: accept_response.alpha_ledger_redeem_identity,
             refund_identity: request.alpha_ledger_refund_identity,
             expiry: request.alpha_expiry,
+            hash_function: request.hash_function,
             secret_hash: request.secret_hash,
         }
     }
@@ -52,11 +56,13 @@ impl<L: Ledger, A: Asset> HtlcParams<L, A> {
         accept_response: &rfc003::Accept<AL, L>,
     ) -> Self {
         HtlcParams {
+            swap_id: request.swap_id,
             asset: request.beta_asset,
             ledger: request.beta_ledger,
             redeem_identity: request.beta_ledger_redeem_identity,
             refund_identity: accept_response.beta_ledger_refund_identity,
             expiry: request.beta_expiry,
+            hash_function: request.hash_function,
             secret_hash: request.secret_hash,
         }
     }
@@ -64,6 +70,7 @@ impl<L: Ledger, A: Asset> HtlcParams<L, A> {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct OngoingSwap<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> {
+    pub swap_id: SwapId,
     pub alpha_ledger: AL,
     pub beta_ledger: BL,
     pub alpha_asset: AA,
@@ -81,6 +88,7 @@ pub struct OngoingSwap<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> {
 impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> OngoingSwap<AL, BL, AA, BA> {
     pub fn new(request: Request<AL, BL, AA, BA>, accept: Accept<AL, BL>) -> Self {
         OngoingSwap {
+            swap_id: request.swap_id,
             alpha_ledger: request.alpha_ledger,
             beta_ledger: request.beta_ledger,
             alpha_asset: request.alpha_asset,
@@ -98,22 +106,26 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> OngoingSwap<AL, BL, AA, BA> {
 
     pub fn alpha_htlc_params(&self) -> HtlcParams<AL, AA> {
         HtlcParams {
+            swap_id: self.swap_id,
             asset: self.alpha_asset,
             ledger: self.alpha_ledger,
             redeem_identity: self.alpha_ledger_redeem_identity,
             refund_identity: self.alpha_ledger_refund_identity,
             expiry: self.alpha_expiry,
+            hash_function: self.hash_function,
             secret_hash: self.secret_hash,
         }
     }
 
     pub fn beta_htlc_params(&self) -> HtlcParams<BL, BA> {
         HtlcParams {
+            swap_id: self.swap_id,
             asset: self.beta_asset,
             ledger: self.beta_ledger,
             redeem_identity: self.beta_ledger_redeem_identity,
             refund_identity: self.beta_ledger_refund_identity,
             expiry: self.beta_expiry,
+            hash_function: self.hash_function,
             secret_hash: self.secret_hash,
         }
     }
@@ -186,18 +198,26 @@ pub struct Context<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> {
 #[allow(missing_debug_implementations)]
 pub struct HtlcEventFutures<L: Ledger, A: Asset> {
     htlc_events: Box<dyn HtlcEvents<L, A>>,
+    ledger: L,
     htlc_deployed: Option<Box<DeployedFuture<L>>>,
     htlc_funded: Option<Box<FundedFuture<L, A>>>,
     htlc_redeemed_or_refunded: Option<Box<RedeemedOrRefundedFuture<L>>>,
+    htlc_deployment_retracted: Option<Box<RetractedFuture>>,
+    htlc_funding_retracted: Option<Box<RetractedFuture>>,
+    htlc_redemption_or_refund_retracted: Option<Box<RetractedFuture>>,
 }
 
 impl<L: Ledger, A: Asset> HtlcEventFutures<L, A> {
-    fn new(htlc_events: Box<dyn HtlcEvents<L, A>>) -> Self {
+    fn new(htlc_events: Box<dyn HtlcEvents<L, A>>, ledger: L) -> Self {
         Self {
             htlc_events,
+            ledger,
             htlc_deployed: None,
             htlc_funded: None,
             htlc_redeemed_or_refunded: None,
+            htlc_deployment_retracted: None,
+            htlc_funding_retracted: None,
+            htlc_redemption_or_refund_retracted: None,
         }
     }
 
@@ -228,6 +248,55 @@ impl<L: Ledger, A: Asset> HtlcEventFutures<L, A> {
             htlc_events.htlc_redeemed_or_refunded(htlc_params, htlc_deployment, htlc_funding)
         })
     }
+
+    fn htlc_deployment_retracted(&mut self, htlc_deployment: &Deployed<L>) -> &mut RetractedFuture {
+        let htlc_events = &self.htlc_events;
+        let ledger = self.ledger;
+        self.htlc_deployment_retracted.get_or_insert_with(move || {
+            htlc_events.transaction_retracted(ledger, &htlc_deployment.transaction)
+        })
+    }
+
+    fn htlc_funding_retracted(&mut self, htlc_funding: &Funded<L, A>) -> &mut RetractedFuture {
+        let htlc_events = &self.htlc_events;
+        let ledger = self.ledger;
+        self.htlc_funding_retracted.get_or_insert_with(move || {
+            htlc_events.transaction_retracted(ledger, &htlc_funding.transaction)
+        })
+    }
+
+    fn htlc_redemption_or_refund_retracted(
+        &mut self,
+        transaction: &L::Transaction,
+    ) -> &mut RetractedFuture {
+        let htlc_events = &self.htlc_events;
+        let ledger = self.ledger;
+        self.htlc_redemption_or_refund_retracted
+            .get_or_insert_with(move || htlc_events.transaction_retracted(ledger, transaction))
+    }
+
+    /// Forgets the deployment of the HTLC and everything that happened
+    /// afterwards, so that we start watching for it again.
+    fn forget_deployment(&mut self) {
+        self.htlc_deployed = None;
+        self.htlc_deployment_retracted = None;
+        self.forget_funding();
+    }
+
+    /// Forgets the funding of the HTLC and everything that happened
+    /// afterwards, so that we start watching for it again.
+    fn forget_funding(&mut self) {
+        self.htlc_funded = None;
+        self.htlc_funding_retracted = None;
+        self.forget_redemption_or_refund();
+    }
+
+    /// Forgets the redemption or refund of the HTLC, so that we start watching
+    /// for it again.
+    fn forget_redemption_or_refund(&mut self) {
+        self.htlc_redeemed_or_refunded = None;
+        self.htlc_redemption_or_refund_retracted = None;
+    }
 }
 
 #[derive(StateMachineFuture)]
@@ -237,20 +306,20 @@ pub enum Swap<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> {
     #[state_machine_future(start, transitions(AlphaDeployed))]
     Start { swap: OngoingSwap<AL, BL, AA, BA> },
 
-    #[state_machine_future(transitions(AlphaFunded, AlphaIncorrectlyFunded, Final))]
+    #[state_machine_future(transitions(Start, AlphaFunded, AlphaIncorrectlyFunded, Final))]
     AlphaDeployed {
         swap: OngoingSwap<AL, BL, AA, BA>,
         alpha_deployed: Deployed<AL>,
     },
 
-    #[state_machine_future(transitions(AlphaFundedBetaDeployed, Final))]
+    #[state_machine_future(transitions(Start, AlphaDeployed, AlphaFundedBetaDeployed, Final))]
     AlphaFunded {
         swap: OngoingSwap<AL, BL, AA, BA>,
         alpha_deployed: Deployed<AL>,
         alpha_funded: Funded<AL, AA>,
     },
 
-    #[state_machine_future(transitions(BothFunded, Final))]
+    #[state_machine_future(transitions(Start, AlphaDeployed, AlphaFunded, BothFunded, Final))]
     AlphaFundedBetaDeployed {
         swap: OngoingSwap<AL, BL, AA, BA>,
         alpha_deployed: Deployed<AL>,
@@ -259,6 +328,10 @@ pub enum Swap<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> {
     },
 
     #[state_machine_future(transitions(
+        Start,
+        AlphaDeployed,
+        AlphaFunded,
+        AlphaFundedBetaDeployed,
         AlphaFundedBetaRedeemed,
         AlphaFundedBetaRefunded,
         AlphaRefundedBetaFunded,
@@ -272,7 +345,7 @@ pub enum Swap<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> {
         beta_funded: Funded<BL, BA>,
     },
 
-    #[state_machine_future(transitions(Final))]
+    #[state_machine_future(transitions(Start, AlphaDeployed, BothFunded, Final))]
     AlphaFundedBetaRefunded {
         swap: OngoingSwap<AL, BL, AA, BA>,
         alpha_deployed: Deployed<AL>,
@@ -282,7 +355,7 @@ pub enum Swap<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> {
         beta_refund_transaction: Refunded<BL>,
     },
 
-    #[state_machine_future(transitions(Final))]
+    #[state_machine_future(transitions(Start, AlphaDeployed, BothFunded, Final))]
     AlphaRefundedBetaFunded {
         swap: OngoingSwap<AL, BL, AA, BA>,
         alpha_deployed: Deployed<AL>,
@@ -292,7 +365,7 @@ pub enum Swap<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> {
         alpha_refunded: Refunded<AL>,
     },
 
-    #[state_machine_future(transitions(Final))]
+    #[state_machine_future(transitions(Start, AlphaDeployed, BothFunded, Final))]
     AlphaRedeemedBetaFunded {
         swap: OngoingSwap<AL, BL, AA, BA>,
         alpha_deployed: Deployed<AL>,
@@ -302,7 +375,7 @@ pub enum Swap<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> {
         alpha_redeemed: Redeemed<AL>,
     },
 
-    #[state_machine_future(transitions(Final))]
+    #[state_machine_future(transitions(Start, AlphaDeployed, BothFunded, Final))]
     AlphaFundedBetaRedeemed {
         swap: OngoingSwap<AL, BL, AA, BA>,
         alpha_deployed: Deployed<AL>,
@@ -312,7 +385,7 @@ pub enum Swap<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> {
         beta_redeem_transaction: Redeemed<BL>,
     },
 
-    #[state_machine_future(transitions(Final))]
+    #[state_machine_future(transitions(Start, AlphaDeployed, Final))]
     AlphaIncorrectlyFunded {
         swap: OngoingSwap<AL, BL, AA, BA>,
         alpha_deployed: Deployed<AL>,
@@ -338,27 +411,331 @@ where
     T: HtlcEvents<AL, AA> + HtlcEvents<BL, BA> + Clone,
 {
     let id = request.swap_id;
+    let alpha_ledger = request.alpha_ledger;
+    let beta_ledger = request.beta_ledger;
 
+    resume_swap(events, id, alpha_ledger, beta_ledger, Start {
+        swap: OngoingSwap::new(request, accept),
+    })
+}
+
+/// Executes a swap from the given state onwards.
+///
+/// This allows us to continue a swap after a restart without having to observe
+/// all the events that already happened on the ledgers again.
+pub fn resume_swap<T, S, AL: Ledger, BL: Ledger, AA: Asset, BA: Asset>(
+    events: T,
+    id: SwapId,
+    alpha_ledger: AL,
+    beta_ledger: BL,
+    state: S,
+) -> (
+    impl Future<Item = (), Error = ()> + Send + 'static,
+    impl Stream<Item = SwapStates<AL, BL, AA, BA>, Error = ()> + Send + 'static,
+)
+where
+    T: HtlcEvents<AL, AA> + HtlcEvents<BL, BA> + Clone,
+    S: Into<SwapStates<AL, BL, AA, BA>>,
+{
     let (sender, receiver) = mpsc::unbounded();
 
     let context = Context {
-        alpha_ledger_events: HtlcEventFutures::new(Box::new(events.clone())),
-        beta_ledger_events: HtlcEventFutures::new(Box::new(events)),
+        alpha_ledger_events: HtlcEventFutures::new(Box::new(events.clone()), alpha_ledger),
+        beta_ledger_events: HtlcEventFutures::new(Box::new(events), beta_ledger),
         state_repo: Arc::new(sender),
     };
 
-    let swap_execution = Swap::start_in(
-        Start {
-            swap: OngoingSwap::new(request, accept),
-        },
-        context,
-    )
-    .map(move |outcome| log::info!("Swap {} finished with {:?}", id, outcome))
-    .map_err(move |e| log::error!("Swap {} failed with {:?}", id, e));
+    let swap_execution = Swap::start_in(state, context)
+        .map(move |outcome| log::info!("Swap {} finished with {:?}", id, outcome))
+        .map_err(move |e| log::error!("Swap {} failed with {:?}", id, e));
 
     (swap_execution, receiver)
 }
 
+impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> SwapStates<AL, BL, AA, BA> {
+    /// Reconstructs the state of a swap from the last known state of its
+    /// ledgers.
+    ///
+    /// `LedgerState` does not record the asset that was used to fund an HTLC.
+    /// We therefore assume the expected asset, which is correct for every
+    /// HTLC that is not `IncorrectlyFunded`.
+    pub fn from_ledger_states(
+        swap: OngoingSwap<AL, BL, AA, BA>,
+        alpha_ledger_state: LedgerState<AL>,
+        beta_ledger_state: LedgerState<BL>,
+        secret: Option<Secret>,
+    ) -> Result<Self, rfc003::Error> {
+        let alpha = HtlcHistory::new(alpha_ledger_state, swap.alpha_asset, secret)?;
+        let beta = HtlcHistory::new(beta_ledger_state, swap.beta_asset, secret)?;
+
+        let (alpha_deployed, alpha_funded) = match (alpha.deployed, alpha.funded) {
+            (None, _) => return Ok(Start { swap }.into()),
+            (Some(alpha_deployed), None) => {
+                return Ok(AlphaDeployed {
+                    swap,
+                    alpha_deployed,
+                }
+                .into())
+            }
+            (Some(alpha_deployed), Some(alpha_funded)) => (alpha_deployed, alpha_funded),
+        };
+
+        let state = match (
+            alpha.redeemed_or_refunded,
+            beta.deployed,
+            beta.funded,
+            beta.redeemed_or_refunded,
+        ) {
+            (None, ..) if alpha.incorrectly_funded => AlphaIncorrectlyFunded {
+                swap,
+                alpha_deployed,
+                alpha_funded,
+            }
+            .into(),
+            (None, None, ..) => AlphaFunded {
+                swap,
+                alpha_deployed,
+                alpha_funded,
+            }
+            .into(),
+            (None, Some(beta_deployed), None, _) => AlphaFundedBetaDeployed {
+                swap,
+                alpha_deployed,
+                alpha_funded,
+                beta_deployed,
+            }
+            .into(),
+            (None, Some(beta_deployed), Some(beta_funded), None) => BothFunded {
+                swap,
+                alpha_deployed,
+                alpha_funded,
+                beta_deployed,
+                beta_funded,
+            }
+            .into(),
+            (None, Some(beta_deployed), Some(beta_funded), Some(Either::Left(beta_redeemed))) => {
+                AlphaFundedBetaRedeemed {
+                    swap,
+                    alpha_deployed,
+                    alpha_funded,
+                    beta_deployed,
+                    beta_funded,
+                    beta_redeem_transaction: beta_redeemed,
+                }
+                .into()
+            }
+            (None, Some(beta_deployed), Some(beta_funded), Some(Either::Right(beta_refunded))) => {
+                AlphaFundedBetaRefunded {
+                    swap,
+                    alpha_deployed,
+                    alpha_funded,
+                    beta_deployed,
+                    beta_funded,
+                    beta_refund_transaction: beta_refunded,
+                }
+                .into()
+            }
+            (Some(Either::Left(alpha_redeemed)), _, None, _)
+            | (Some(Either::Left(alpha_redeemed)), None, ..) => Final(SwapOutcome::AlphaRedeemed {
+                swap,
+                alpha_deployed,
+                alpha_funded,
+                alpha_redeemed,
+            })
+            .into(),
+            (Some(Either::Right(alpha_refunded)), _, None, _)
+            | (Some(Either::Right(alpha_refunded)), None, ..) => {
+                Final(SwapOutcome::AlphaRefunded {
+                    swap,
+                    alpha_deployed,
+                    alpha_funded,
+                    alpha_refunded,
+                })
+                .into()
+            }
+            (Some(Either::Left(alpha_redeemed)), Some(beta_deployed), Some(beta_funded), None) => {
+                AlphaRedeemedBetaFunded {
+                    swap,
+                    alpha_deployed,
+                    alpha_funded,
+                    beta_deployed,
+                    beta_funded,
+                    alpha_redeemed,
+                }
+                .into()
+            }
+            (Some(Either::Right(alpha_refunded)), Some(beta_deployed), Some(beta_funded), None) => {
+                AlphaRefundedBetaFunded {
+                    swap,
+                    alpha_deployed,
+                    alpha_funded,
+                    beta_deployed,
+                    beta_funded,
+                    alpha_refunded,
+                }
+                .into()
+            }
+            // Once both ledgers reached a final state, we no longer know which
+            // event was observed last. We attribute the outcome to alpha because
+            // this is the order in which a successful swap usually ends.
+            (
+                Some(Either::Left(alpha_redeemed)),
+                Some(beta_deployed),
+                Some(beta_funded),
+                Some(Either::Left(_)),
+            ) => Final(SwapOutcome::BothRedeemed {
+                swap,
+                alpha_deployed,
+                alpha_funded,
+                beta_deployed,
+                beta_funded,
+                alpha_or_beta_redeemed: Either::Left(alpha_redeemed),
+            })
+            .into(),
+            (
+                Some(Either::Left(alpha_redeemed)),
+                Some(beta_deployed),
+                Some(beta_funded),
+                Some(Either::Right(_)),
+            ) => Final(SwapOutcome::AlphaRedeemedBetaRefunded {
+                swap,
+                alpha_deployed,
+                alpha_funded,
+                beta_deployed,
+                beta_funded,
+                alpha_redeemed_or_beta_refunded: Either::Left(alpha_redeemed),
+            })
+            .into(),
+            (
+                Some(Either::Right(alpha_refunded)),
+                Some(beta_deployed),
+                Some(beta_funded),
+                Some(Either::Left(_)),
+            ) => Final(SwapOutcome::AlphaRefundedBetaRedeemed {
+                swap,
+                alpha_deployed,
+                alpha_funded,
+                beta_deployed,
+                beta_funded,
+                alpha_refunded_or_beta_redeemed: Either::Left(alpha_refunded),
+            })
+            .into(),
+            (
+                Some(Either::Right(alpha_refunded)),
+                Some(beta_deployed),
+                Some(beta_funded),
+                Some(Either::Right(_)),
+            ) => Final(SwapOutcome::BothRefunded {
+                swap,
+                alpha_deployed,
+                alpha_funded,
+                beta_deployed,
+                beta_funded,
+                alpha_or_beta_refunded: Either::Left(alpha_refunded),
+            })
+            .into(),
+        };
+
+        Ok(state)
+    }
+}
+
+/// The events of a single HTLC, as far as they can be recovered from its
+/// `LedgerState`.
+struct HtlcHistory<L: Ledger, A: Asset> {
+    deployed: Option<Deployed<L>>,
+    funded: Option<Funded<L, A>>,
+    incorrectly_funded: bool,
+    redeemed_or_refunded: Option<Either<Redeemed<L>, Refunded<L>>>,
+}
+
+impl<L: Ledger, A: Asset> HtlcHistory<L, A> {
+    fn new(
+        ledger_state: LedgerState<L>,
+        asset: A,
+        secret: Option<Secret>,
+    ) -> Result<Self, rfc003::Error> {
+        let deployed = |location, transaction| Deployed {
+            transaction,
+            location,
+        };
+        let funded = |transaction| Funded { transaction, asset };
+
+        let history = match ledger_state {
+            LedgerState::NotDeployed => HtlcHistory {
+                deployed: None,
+                funded: None,
+                incorrectly_funded: false,
+                redeemed_or_refunded: None,
+            },
+            LedgerState::Deployed {
+                htlc_location,
+                deploy_transaction,
+            } => HtlcHistory {
+                deployed: Some(deployed(htlc_location, deploy_transaction)),
+                funded: None,
+                incorrectly_funded: false,
+                redeemed_or_refunded: None,
+            },
+            LedgerState::Funded {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+            } => HtlcHistory {
+                deployed: Some(deployed(htlc_location, deploy_transaction)),
+                funded: Some(funded(fund_transaction)),
+                incorrectly_funded: false,
+                redeemed_or_refunded: None,
+            },
+            LedgerState::IncorrectlyFunded {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+            } => HtlcHistory {
+                deployed: Some(deployed(htlc_location, deploy_transaction)),
+                funded: Some(funded(fund_transaction)),
+                incorrectly_funded: true,
+                redeemed_or_refunded: None,
+            },
+            LedgerState::Redeemed {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+                redeem_transaction,
+            } => {
+                let secret = secret.ok_or_else(|| {
+                    rfc003::Error::Internal(String::from(
+                        "cannot restore a redeemed HTLC without the secret",
+                    ))
+                })?;
+
+                HtlcHistory {
+                    deployed: Some(deployed(htlc_location, deploy_transaction)),
+                    funded: Some(funded(fund_transaction)),
+                    incorrectly_funded: false,
+                    redeemed_or_refunded: Some(Either::Left(Redeemed {
+                        transaction: redeem_transaction,
+                        secret,
+                    })),
+                }
+            }
+            LedgerState::Refunded {
+                htlc_location,
+                deploy_transaction,
+                fund_transaction,
+                refund_transaction,
+            } => HtlcHistory {
+                deployed: Some(deployed(htlc_location, deploy_transaction)),
+                funded: Some(funded(fund_transaction)),
+                incorrectly_funded: false,
+                redeemed_or_refunded: Some(Either::Right(Refunded::new(refund_transaction))),
+            },
+        };
+
+        Ok(history)
+    }
+}
+
 impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> PollSwap<AL, BL, AA, BA>
     for Swap<AL, BL, AA, BA>
 {
@@ -381,6 +758,16 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> PollSwap<AL, BL, AA, BA>
         state: &'s mut RentToOwn<'s, AlphaDeployed<AL, BL, AA, BA>>,
         context: &'c mut RentToOwn<'c, Context<AL, BL, AA, BA>>,
     ) -> Result<Async<AfterAlphaDeployed<AL, BL, AA, BA>>, rfc003::Error> {
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_deployment_retracted(&state.alpha_deployed)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, Start { swap: state.swap });
+        }
+
         let alpha_funded = try_ready!(context
             .alpha_ledger_events
             .htlc_funded(state.swap.alpha_htlc_params(), &state.alpha_deployed)
@@ -405,6 +792,29 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> PollSwap<AL, BL, AA, BA>
         state: &'s mut RentToOwn<'s, AlphaFunded<AL, BL, AA, BA>>,
         context: &'c mut RentToOwn<'c, Context<AL, BL, AA, BA>>,
     ) -> Result<Async<AfterAlphaFunded<AL, BL, AA, BA>>, rfc003::Error> {
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_deployment_retracted(&state.alpha_deployed)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, Start { swap: state.swap });
+        }
+
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_funding_retracted(&state.alpha_funded)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_funding();
+            let state = state.take();
+            transition_save!(context.state_repo, AlphaDeployed {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+            });
+        }
+
         if let Async::Ready(alpha_redeemed_or_refunded) = context
             .alpha_ledger_events
             .htlc_redeemed_or_refunded(
@@ -454,6 +864,29 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> PollSwap<AL, BL, AA, BA>
         state: &'s mut RentToOwn<'s, AlphaIncorrectlyFunded<AL, BL, AA, BA>>,
         context: &'c mut RentToOwn<'c, Context<AL, BL, AA, BA>>,
     ) -> Result<Async<AfterAlphaIncorrectlyFunded<AL, BL, AA, BA>>, rfc003::Error> {
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_deployment_retracted(&state.alpha_deployed)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, Start { swap: state.swap });
+        }
+
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_funding_retracted(&state.alpha_funded)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_funding();
+            let state = state.take();
+            transition_save!(context.state_repo, AlphaDeployed {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+            });
+        }
+
         let alpha_redeemed_or_refunded = try_ready!(context
             .alpha_ledger_events
             .htlc_redeemed_or_refunded(
@@ -494,6 +927,45 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> PollSwap<AL, BL, AA, BA>
         state: &'s mut RentToOwn<'s, AlphaFundedBetaDeployed<AL, BL, AA, BA>>,
         context: &'c mut RentToOwn<'c, Context<AL, BL, AA, BA>>,
     ) -> Result<Async<AfterAlphaFundedBetaDeployed<AL, BL, AA, BA>>, rfc003::Error> {
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_deployment_retracted(&state.alpha_deployed)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_deployment();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, Start { swap: state.swap });
+        }
+
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_funding_retracted(&state.alpha_funded)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_funding();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, AlphaDeployed {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+            });
+        }
+
+        if let Async::Ready(()) = context
+            .beta_ledger_events
+            .htlc_deployment_retracted(&state.beta_deployed)
+            .poll()?
+        {
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, AlphaFunded {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+                alpha_funded: state.alpha_funded,
+            });
+        }
+
         if let Async::Ready(alpha_redeemed_or_refunded) = context
             .alpha_ledger_events
             .htlc_redeemed_or_refunded(
@@ -548,6 +1020,60 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> PollSwap<AL, BL, AA, BA>
         state: &'s mut RentToOwn<'s, BothFunded<AL, BL, AA, BA>>,
         context: &'c mut RentToOwn<'c, Context<AL, BL, AA, BA>>,
     ) -> Result<Async<AfterBothFunded<AL, BL, AA, BA>>, rfc003::Error> {
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_deployment_retracted(&state.alpha_deployed)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_deployment();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, Start { swap: state.swap });
+        }
+
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_funding_retracted(&state.alpha_funded)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_funding();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, AlphaDeployed {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+            });
+        }
+
+        if let Async::Ready(()) = context
+            .beta_ledger_events
+            .htlc_deployment_retracted(&state.beta_deployed)
+            .poll()?
+        {
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, AlphaFunded {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+                alpha_funded: state.alpha_funded,
+            });
+        }
+
+        if let Async::Ready(()) = context
+            .beta_ledger_events
+            .htlc_funding_retracted(&state.beta_funded)
+            .poll()?
+        {
+            context.beta_ledger_events.forget_funding();
+            let state = state.take();
+            transition_save!(context.state_repo, AlphaFundedBetaDeployed {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+                alpha_funded: state.alpha_funded,
+                beta_deployed: state.beta_deployed,
+            });
+        }
+
         if let Async::Ready(redeemed_or_refunded) = context
             .beta_ledger_events
             .htlc_redeemed_or_refunded(
@@ -620,6 +1146,47 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> PollSwap<AL, BL, AA, BA>
         state: &'s mut RentToOwn<'s, AlphaFundedBetaRefunded<AL, BL, AA, BA>>,
         context: &'c mut RentToOwn<'c, Context<AL, BL, AA, BA>>,
     ) -> Result<Async<AfterAlphaFundedBetaRefunded<AL, BL, AA, BA>>, rfc003::Error> {
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_deployment_retracted(&state.alpha_deployed)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_deployment();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, Start { swap: state.swap });
+        }
+
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_funding_retracted(&state.alpha_funded)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_funding();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, AlphaDeployed {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+            });
+        }
+
+        if let Async::Ready(()) = context
+            .beta_ledger_events
+            .htlc_redemption_or_refund_retracted(&state.beta_refund_transaction.transaction)
+            .poll()?
+        {
+            context.beta_ledger_events.forget_redemption_or_refund();
+            let state = state.take();
+            transition_save!(context.state_repo, BothFunded {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+                alpha_funded: state.alpha_funded,
+                beta_deployed: state.beta_deployed,
+                beta_funded: state.beta_funded,
+            });
+        }
+
         match try_ready!(context
             .alpha_ledger_events
             .htlc_redeemed_or_refunded(
@@ -666,6 +1233,47 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> PollSwap<AL, BL, AA, BA>
         state: &'s mut RentToOwn<'s, AlphaRefundedBetaFunded<AL, BL, AA, BA>>,
         context: &'c mut RentToOwn<'c, Context<AL, BL, AA, BA>>,
     ) -> Result<Async<AfterAlphaRefundedBetaFunded<AL, BL, AA, BA>>, rfc003::Error> {
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_deployment_retracted(&state.alpha_deployed)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_deployment();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, Start { swap: state.swap });
+        }
+
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_funding_retracted(&state.alpha_funded)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_funding();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, AlphaDeployed {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+            });
+        }
+
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_redemption_or_refund_retracted(&state.alpha_refunded.transaction)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_redemption_or_refund();
+            let state = state.take();
+            transition_save!(context.state_repo, BothFunded {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+                alpha_funded: state.alpha_funded,
+                beta_deployed: state.beta_deployed,
+                beta_funded: state.beta_funded,
+            });
+        }
+
         match try_ready!(context
             .beta_ledger_events
             .htlc_redeemed_or_refunded(
@@ -712,6 +1320,47 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> PollSwap<AL, BL, AA, BA>
         state: &'s mut RentToOwn<'s, AlphaRedeemedBetaFunded<AL, BL, AA, BA>>,
         context: &'c mut RentToOwn<'c, Context<AL, BL, AA, BA>>,
     ) -> Result<Async<AfterAlphaRedeemedBetaFunded<AL, BL, AA, BA>>, rfc003::Error> {
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_deployment_retracted(&state.alpha_deployed)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_deployment();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, Start { swap: state.swap });
+        }
+
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_funding_retracted(&state.alpha_funded)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_funding();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, AlphaDeployed {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+            });
+        }
+
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_redemption_or_refund_retracted(&state.alpha_redeemed.transaction)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_redemption_or_refund();
+            let state = state.take();
+            transition_save!(context.state_repo, BothFunded {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+                alpha_funded: state.alpha_funded,
+                beta_deployed: state.beta_deployed,
+                beta_funded: state.beta_funded,
+            });
+        }
+
         match try_ready!(context
             .beta_ledger_events
             .htlc_redeemed_or_refunded(
@@ -757,6 +1406,47 @@ impl<AL: Ledger, BL: Ledger, AA: Asset, BA: Asset> PollSwap<AL, BL, AA, BA>
         state: &'s mut RentToOwn<'s, AlphaFundedBetaRedeemed<AL, BL, AA, BA>>,
         context: &'c mut RentToOwn<'c, Context<AL, BL, AA, BA>>,
     ) -> Result<Async<AfterAlphaFundedBetaRedeemed<AL, BL, AA, BA>>, rfc003::Error> {
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_deployment_retracted(&state.alpha_deployed)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_deployment();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, Start { swap: state.swap });
+        }
+
+        if let Async::Ready(()) = context
+            .alpha_ledger_events
+            .htlc_funding_retracted(&state.alpha_funded)
+            .poll()?
+        {
+            context.alpha_ledger_events.forget_funding();
+            context.beta_ledger_events.forget_deployment();
+            let state = state.take();
+            transition_save!(context.state_repo, AlphaDeployed {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+            });
+        }
+
+        if let Async::Ready(()) = context
+            .beta_ledger_events
+            .htlc_redemption_or_refund_retracted(&state.beta_redeem_transaction.transaction)
+            .poll()?
+        {
+            context.beta_ledger_events.forget_redemption_or_refund();
+            let state = state.take();
+            transition_save!(context.state_repo, BothFunded {
+                swap: state.swap,
+                alpha_deployed: state.alpha_deployed,
+                alpha_funded: state.alpha_funded,
+                beta_deployed: state.beta_deployed,
+                beta_funded: state.beta_funded,
+            });
+        }
+
         match try_ready!(context
             .alpha_ledger_events
             .htlc_redeemed_or_refunded(
@@ -821,3 +1511,269 @@ impl_display!(AlphaRefundedBetaFunded);
 impl_display!(AlphaFundedBetaRedeemed);
 impl_display!(AlphaRedeemedBetaFunded);
 impl_display!(Final);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ethereum::{Address, EtherQuantity},
+        swap_protocols::ledger::{Bitcoin, Ethereum},
+    };
+    use bitcoin::{Amount, OutPoint, Transaction};
+    use spectral::prelude::*;
+    use std::{
+        sync::atomic::{AtomicBool, Ordering::SeqCst},
+        time::Duration,
+    };
+    use tokio::timer::Timeout;
+
+    /// Funds the alpha HTLC, deploys the beta HTLC and retracts the alpha
+    /// funding transaction once, as if the block that included it got orphaned
+    /// and the transaction was mined again in the new best chain.
+    #[derive(Clone)]
+    struct ReorgedFunding {
+        deploy_transaction: Transaction,
+        fund_transaction: Transaction,
+        beta_deployed: Deployed<Ethereum>,
+        retracted: Arc<AtomicBool>,
+    }
+
+    impl HtlcEvents<Bitcoin, Amount> for ReorgedFunding {
+        fn htlc_deployed(&self, _: HtlcParams<Bitcoin, Amount>) -> Box<DeployedFuture<Bitcoin>> {
+            Box::new(future::empty())
+        }
+
+        fn htlc_funded(
+            &self,
+            htlc_params: HtlcParams<Bitcoin, Amount>,
+            _: &Deployed<Bitcoin>,
+        ) -> Box<FundedFuture<Bitcoin, Amount>> {
+            Box::new(future::ok(Funded {
+                transaction: self.fund_transaction.clone(),
+                asset: htlc_params.asset,
+            }))
+        }
+
+        fn htlc_redeemed_or_refunded(
+            &self,
+            _: HtlcParams<Bitcoin, Amount>,
+            _: &Deployed<Bitcoin>,
+            _: &Funded<Bitcoin, Amount>,
+        ) -> Box<RedeemedOrRefundedFuture<Bitcoin>> {
+            Box::new(future::empty())
+        }
+
+        fn transaction_retracted(
+            &self,
+            _: Bitcoin,
+            transaction: &Transaction,
+        ) -> Box<RetractedFuture> {
+            if *transaction == self.fund_transaction && !self.retracted.swap(true, SeqCst) {
+                Box::new(future::ok(()))
+            } else {
+                Box::new(future::empty())
+            }
+        }
+    }
+
+    impl HtlcEvents<Ethereum, EtherQuantity> for ReorgedFunding {
+        fn htlc_deployed(
+            &self,
+            _: HtlcParams<Ethereum, EtherQuantity>,
+        ) -> Box<DeployedFuture<Ethereum>> {
+            Box::new(future::ok(self.beta_deployed.clone()))
+        }
+
+        fn htlc_funded(
+            &self,
+            _: HtlcParams<Ethereum, EtherQuantity>,
+            _: &Deployed<Ethereum>,
+        ) -> Box<FundedFuture<Ethereum, EtherQuantity>> {
+            Box::new(future::empty())
+        }
+
+        fn htlc_redeemed_or_refunded(
+            &self,
+            _: HtlcParams<Ethereum, EtherQuantity>,
+            _: &Deployed<Ethereum>,
+            _: &Funded<Ethereum, EtherQuantity>,
+        ) -> Box<RedeemedOrRefundedFuture<Ethereum>> {
+            Box::new(future::empty())
+        }
+
+        fn transaction_retracted(
+            &self,
+            _: Ethereum,
+            _: &crate::ethereum::Transaction,
+        ) -> Box<RetractedFuture> {
+            Box::new(future::empty())
+        }
+    }
+
+    fn beta_deployed() -> Deployed<Ethereum> {
+        Deployed {
+            transaction: crate::ethereum::Transaction::default(),
+            location: "3ee0c7a2a1d6e7e4d9a81a4a8e3f0c1b5d6e7f80".parse().unwrap(),
+        }
+    }
+
+    fn transaction(lock_time: u32) -> Transaction {
+        Transaction {
+            version: 2,
+            lock_time,
+            input: Vec::new(),
+            output: Vec::new(),
+        }
+    }
+
+    fn swap() -> OngoingSwap<Bitcoin, Ethereum, Amount, EtherQuantity> {
+        let bitcoin_pub_key = crate::bitcoin::PublicKey::new(
+            "02c2a8efce029526d364c2cf39d89e3cdda05e5df7b2cbfc098b4e3d02b70b5275"
+                .parse()
+                .unwrap(),
+        );
+        let ethereum_address: Address = "8457037fcd80a8650c4692d7fcfc1d0a96b92867".parse().unwrap();
+
+        OngoingSwap {
+            swap_id: SwapId::default(),
+            alpha_ledger: Bitcoin::default(),
+            beta_ledger: Ethereum::default(),
+            alpha_asset: Amount::from_btc(1.0).unwrap(),
+            beta_asset: EtherQuantity::from_eth(10.0),
+            hash_function: HashFunction::Sha256,
+            alpha_ledger_redeem_identity: bitcoin_pub_key,
+            alpha_ledger_refund_identity: bitcoin_pub_key,
+            beta_ledger_redeem_identity: ethereum_address,
+            beta_ledger_refund_identity: ethereum_address,
+            alpha_expiry: Timestamp::from(2_000_000_000),
+            beta_expiry: Timestamp::from(2_000_000_000),
+            secret_hash: Secret::from(*b"hello world, you are beautiful!!")
+                .hash(HashFunction::Sha256),
+        }
+    }
+
+    #[test]
+    fn alpha_funded_swap_rolls_back_and_funds_again_when_funding_is_retracted() {
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+        let events = ReorgedFunding {
+            deploy_transaction: transaction(1),
+            fund_transaction: transaction(2),
+            beta_deployed: beta_deployed(),
+            retracted: Arc::new(AtomicBool::new(false)),
+        };
+        let swap = swap();
+        let alpha_deployed = Deployed {
+            transaction: events.deploy_transaction.clone(),
+            location: OutPoint {
+                txid: events.deploy_transaction.txid(),
+                vout: 0,
+            },
+        };
+        let alpha_funded = Funded {
+            transaction: events.fund_transaction.clone(),
+            asset: swap.alpha_asset,
+        };
+
+        let (swap_execution, states) = resume_swap(
+            events,
+            swap.swap_id,
+            swap.alpha_ledger,
+            swap.beta_ledger,
+            AlphaFunded {
+                swap: swap.clone(),
+                alpha_deployed: alpha_deployed.clone(),
+                alpha_funded: alpha_funded.clone(),
+            },
+        );
+        runtime.spawn(swap_execution);
+
+        let states = runtime
+            .block_on(Timeout::new(
+                states.take(2).collect(),
+                Duration::from_secs(1),
+            ))
+            .unwrap();
+
+        assert_that(&states).is_equal_to(vec![
+            AlphaDeployed {
+                swap: swap.clone(),
+                alpha_deployed: alpha_deployed.clone(),
+            }
+            .into(),
+            AlphaFunded {
+                swap,
+                alpha_deployed,
+                alpha_funded,
+            }
+            .into(),
+        ]);
+    }
+
+    #[test]
+    fn swap_rolls_back_when_alpha_funding_is_retracted_after_beta_is_deployed() {
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+        let events = ReorgedFunding {
+            deploy_transaction: transaction(1),
+            fund_transaction: transaction(2),
+            beta_deployed: beta_deployed(),
+            retracted: Arc::new(AtomicBool::new(false)),
+        };
+        let swap = swap();
+        let alpha_deployed = Deployed {
+            transaction: events.deploy_transaction.clone(),
+            location: OutPoint {
+                txid: events.deploy_transaction.txid(),
+                vout: 0,
+            },
+        };
+        let alpha_funded = Funded {
+            transaction: events.fund_transaction.clone(),
+            asset: swap.alpha_asset,
+        };
+        let beta_deployed = events.beta_deployed.clone();
+
+        let (swap_execution, states) = resume_swap(
+            events,
+            swap.swap_id,
+            swap.alpha_ledger,
+            swap.beta_ledger,
+            AlphaFundedBetaDeployed {
+                swap: swap.clone(),
+                alpha_deployed: alpha_deployed.clone(),
+                alpha_funded: alpha_funded.clone(),
+                beta_deployed: beta_deployed.clone(),
+            },
+        );
+        runtime.spawn(swap_execution);
+
+        let states = runtime
+            .block_on(Timeout::new(
+                states.take(3).collect(),
+                Duration::from_secs(1),
+            ))
+            .unwrap();
+
+        assert_that(&states).is_equal_to(vec![
+            AlphaDeployed {
+                swap: swap.clone(),
+                alpha_deployed: alpha_deployed.clone(),
+            }
+            .into(),
+            AlphaFunded {
+                swap: swap.clone(),
+                alpha_deployed: alpha_deployed.clone(),
+                alpha_funded: alpha_funded.clone(),
+            }
+            .into(),
+            AlphaFundedBetaDeployed {
+                swap,
+                alpha_deployed,
+                alpha_funded,
+                beta_deployed,
+            }
+            .into(),
+        ]);
+    }
+}
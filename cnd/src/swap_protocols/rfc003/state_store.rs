@@ -1,18 +1,27 @@
-use crate::swap_protocols::{
-    rfc003::{
-        ledger_state::LedgerState,
-        state_machine::{
-            AlphaDeployed, AlphaFunded, AlphaFundedBetaDeployed, AlphaFundedBetaRedeemed,
-            AlphaFundedBetaRefunded, AlphaIncorrectlyFunded, AlphaRedeemedBetaFunded,
-            AlphaRefundedBetaFunded, BothFunded, Error as ErrorState, Final, SwapOutcome,
-            SwapStates,
+use crate::{
+    db::{LedgerStates, Save, Sqlite},
+    swap_protocols::{
+        rfc003::{
+            ledger_state::LedgerState,
+            state_machine::{
+                AlphaDeployed, AlphaFunded, AlphaFundedBetaDeployed, AlphaFundedBetaRedeemed,
+                AlphaFundedBetaRefunded, AlphaIncorrectlyFunded, AlphaRedeemedBetaFunded,
+                AlphaRefundedBetaFunded, BothFunded, Error as ErrorState, Final, SwapOutcome,
+                SwapStates,
+            },
+            ActorState,
         },
-        ActorState,
+        swap_id::SwapId,
     },
-    swap_id::SwapId,
 };
 use either::Either;
-use std::{any::Any, collections::HashMap, sync::Mutex};
+use futures_core::future::{BoxFuture, FutureExt};
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{mpsc, Mutex},
+    thread,
+};
 
 #[derive(Debug, Clone, Copy, thiserror::Error)]
 pub enum Error {
@@ -64,15 +73,19 @@ impl StateStore for InMemoryStateStore {
         };
 
         match update {
+            // Transitions to earlier states happen if the ledger reorganises. The
+            // ledger states are therefore reset for all ledgers that did not
+            // progress in these states.
             SS::Start(_) => {
-                log::warn!("Attempted to update Start state for key {}", key);
-                return;
+                *actor_state.alpha_ledger_mut() = NotDeployed;
+                *actor_state.beta_ledger_mut() = NotDeployed;
             }
             SS::AlphaDeployed(AlphaDeployed { alpha_deployed, .. }) => {
                 *actor_state.alpha_ledger_mut() = Deployed {
                     htlc_location: alpha_deployed.location,
                     deploy_transaction: alpha_deployed.transaction,
-                }
+                };
+                *actor_state.beta_ledger_mut() = NotDeployed;
             }
 
             SS::AlphaIncorrectlyFunded(AlphaIncorrectlyFunded {
@@ -84,7 +97,8 @@ impl StateStore for InMemoryStateStore {
                     htlc_location: alpha_deployed.location,
                     deploy_transaction: alpha_deployed.transaction,
                     fund_transaction: alpha_funded.transaction,
-                }
+                };
+                *actor_state.beta_ledger_mut() = NotDeployed;
             }
             SS::AlphaFunded(AlphaFunded {
                 alpha_deployed,
@@ -95,7 +109,8 @@ impl StateStore for InMemoryStateStore {
                     htlc_location: alpha_deployed.location,
                     deploy_transaction: alpha_deployed.transaction,
                     fund_transaction: alpha_funded.transaction,
-                }
+                };
+                *actor_state.beta_ledger_mut() = NotDeployed;
             }
             SS::AlphaFundedBetaDeployed(AlphaFundedBetaDeployed {
                 alpha_deployed,
@@ -255,6 +270,77 @@ impl StateStore for InMemoryStateStore {
     }
 }
 
+/// A `StateStore` that additionally persists the ledger states of every swap
+/// to the database whenever they change.
+///
+/// All reads are served from memory. The ledger states are read back from the
+/// database when swaps are loaded on startup.
+#[derive(Debug)]
+pub struct SqliteStateStore {
+    in_memory: InMemoryStateStore,
+    db: Sqlite,
+    writes: Mutex<mpsc::Sender<BoxFuture<'static, ()>>>,
+}
+
+impl SqliteStateStore {
+    pub fn new(db: Sqlite) -> Self {
+        let (writes, pending_writes) = mpsc::channel::<BoxFuture<'static, ()>>();
+
+        // Updates have to be written in order, hence they are written one after
+        // another by a dedicated thread instead of blocking the executor that
+        // drives the swaps.
+        thread::spawn(move || {
+            for write in pending_writes {
+                async_std::task::block_on(write);
+            }
+        });
+
+        Self {
+            in_memory: InMemoryStateStore::default(),
+            db,
+            writes: Mutex::new(writes),
+        }
+    }
+}
+
+impl StateStore for SqliteStateStore {
+    fn insert<A: ActorState>(&self, key: SwapId, value: A) {
+        self.in_memory.insert(key, value)
+    }
+
+    fn get<A: ActorState>(&self, key: &SwapId) -> Result<Option<A>, Error> {
+        self.in_memory.get(key)
+    }
+
+    fn update<A: ActorState>(&self, key: &SwapId, update: SwapStates<A::AL, A::BL, A::AA, A::BA>) {
+        self.in_memory.update::<A>(key, update);
+
+        let actor_state = match self.in_memory.get::<A>(key) {
+            Ok(Some(actor_state)) => actor_state,
+            _ => return,
+        };
+
+        let ledger_states = LedgerStates {
+            swap_id: *key,
+            alpha_ledger_state: actor_state.alpha_ledger().clone(),
+            beta_ledger_state: actor_state.beta_ledger().clone(),
+            secret: actor_state.secret(),
+        };
+
+        let db = self.db.clone();
+        let swap_id = *key;
+        let write = async move {
+            if let Err(e) = db.save(ledger_states).await {
+                log::error!("failed to save ledger states for swap {}: {:?}", swap_id, e);
+            }
+        };
+
+        if self.writes.lock().unwrap().send(write.boxed()).is_err() {
+            log::error!("ledger states of swap {} could not be saved", key);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -293,7 +379,8 @@ mod tests {
             beta_ledger_redeem_identity: ethereum_address,
             alpha_expiry: Timestamp::from(2_000_000_000),
             beta_expiry: Timestamp::from(2_000_000_000),
-            secret_hash: Secret::from(*b"hello world, you are beautiful!!").hash(),
+            secret_hash: Secret::from(*b"hello world, you are beautiful!!")
+                .hash(HashFunction::Sha256),
         };
         let accept = Accept {
             swap_id: SwapId::default(),
@@ -0,0 +1,6 @@
+//! Wallets cnd holds itself, derived from the `Seed` of the node, and the
+//! watch-only wallet it builds PSBTs for.
+
+pub mod bitcoin;
+pub mod ethereum;
+pub mod psbt;
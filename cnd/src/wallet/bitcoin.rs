@@ -0,0 +1,1079 @@
+use crate::{
+    btsieve::{BlockByHash, LatestBlock},
+    db::Sqlite,
+    seed::Seed,
+};
+use bitcoin::{
+    hashes::sha256d,
+    secp256k1,
+    util::{
+        bip143::SighashComponents,
+        bip32::{ChildNumber, ExtendedPrivKey},
+    },
+    Address, Amount, BitcoinHash, Block, Network, OutPoint, Script, SigHashType, Transaction, TxIn,
+    TxOut,
+};
+use futures_core::compat::Future01CompatExt;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+    ops::Add,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::timer::Delay;
+
+/// How many addresses past the last used one of each keychain we look for
+/// incoming payments.
+const GAP_LIMIT: u32 = 20;
+
+/// How many blocks we walk back to catch up with the blockchain, e.g. after
+/// cnd was not running for a while.
+const MAX_CATCH_UP_BLOCKS: usize = 1008;
+
+/// How many of the last processed blocks we remember to undo them if they get
+/// orphaned.
+const REORG_DEPTH: usize = 100;
+
+/// How long the outputs selected by `fund` are kept from being selected again
+/// if the transaction is neither broadcast nor given up on.
+const RESERVATION_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Change below this value is left to the miners, an output that small costs
+/// more to spend than it is worth.
+pub const DUST_LIMIT: u64 = 546;
+
+/// Upper bound of the witness of a P2WPKH input: item count, a DER signature
+/// with its sighash type and a compressed public key, each with its length.
+pub const P2WPKH_WITNESS_WEIGHT: u64 = 1 + 1 + 73 + 1 + 33;
+
+/// Segwit marker and flag, they only count if there are witnesses.
+const SEGWIT_HEADER_WEIGHT: u64 = 2;
+
+/// Signals replaceability as defined in BIP125.
+const SEQUENCE_ENABLE_RBF: u32 = 0xFFFF_FFFD;
+
+/// Gives access to the Bitcoin wallet of cnd.
+pub trait BitcoinWallet: Send + Sync + 'static {
+    fn bitcoin_wallet(&self) -> &Wallet;
+}
+
+#[derive(Debug, Clone, Copy, thiserror::Error, PartialEq)]
+pub enum Error {
+    #[error("insufficient funds, need {needed} but only have {available}")]
+    InsufficientFunds { needed: Amount, available: Amount },
+    #[error("the fee is higher than the system supports")]
+    OverflowingFee,
+    #[error("wallet is on {wallet} but the transaction is for {transaction}")]
+    NetworkMismatch {
+        wallet: Network,
+        transaction: Network,
+    },
+    #[error("the size of the input spending {0} is unknown, configure a supported watch_only_descriptor")]
+    UnknownInputSize(OutPoint),
+}
+
+/// A BIP84 wallet derived from the `Seed` of the node.
+///
+/// Coins are tracked by following the blockchain through `sync`, the
+/// receiving and change addresses are native segwit (P2WPKH) addresses of the
+/// account `m/84'/coin_type'/0'`.
+#[derive(Clone)]
+pub struct Wallet {
+    network: Network,
+    account: ExtendedPrivKey,
+    state: Arc<Mutex<State>>,
+}
+
+impl Debug for Wallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Wallet")
+            .field("network", &self.network)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// Everything about the wallet that is not derived from the seed, stored in
+/// the database so the wallet survives restarts.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct State {
+    /// The last block that was processed.
+    tip: Option<sha256d::Hash>,
+    /// The last `REORG_DEPTH` blocks that were processed, oldest first.
+    #[serde(default)]
+    recent_blocks: Vec<sha256d::Hash>,
+    /// Index of the first receiving address that was not handed out yet.
+    next_receive_index: u32,
+    /// Index of the first change address that was not used yet.
+    next_change_index: u32,
+    utxos: Vec<Utxo>,
+    /// Outputs selected by `fund` that no other payment may select.
+    #[serde(skip)]
+    reserved: HashMap<OutPoint, Reservation>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Reservation {
+    payment: TxOut,
+    until: Instant,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Keychain {
+    Receive,
+    Change,
+}
+
+impl Keychain {
+    fn child_number(self) -> ChildNumber {
+        match self {
+            Keychain::Receive => ChildNumber::Normal { index: 0 },
+            Keychain::Change => ChildNumber::Normal { index: 1 },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Utxo {
+    outpoint: OutPoint,
+    value: u64,
+    keychain: Keychain,
+    index: u32,
+    /// Set once the output was spent by a transaction we built, it stays in
+    /// the wallet until that transaction is seen in a block.
+    locked: bool,
+    /// The block that created the output.
+    #[serde(default)]
+    block: Option<sha256d::Hash>,
+    /// The block that spent the output, it is forgotten once that block is
+    /// older than `REORG_DEPTH` blocks.
+    #[serde(default)]
+    spent_in: Option<sha256d::Hash>,
+}
+
+impl Utxo {
+    fn is_spendable(&self) -> bool {
+        !self.locked && self.spent_in.is_none()
+    }
+}
+
+impl Wallet {
+    pub fn new(seed: &Seed, network: Network, state: State) -> Self {
+        let master =
+            ExtendedPrivKey::new_master(network, &seed.sha256_with_seed(&[b"BITCOIN_WALLET"]))
+                .expect("32 bytes are a valid seed");
+
+        let coin_type = match network {
+            Network::Bitcoin => 0,
+            Network::Testnet | Network::Regtest => 1,
+        };
+        let account = master
+            .derive_priv(&*crate::SECP, &[
+                ChildNumber::Hardened { index: 84 },
+                ChildNumber::Hardened { index: coin_type },
+                ChildNumber::Hardened { index: 0 },
+            ])
+            .expect("deriving hardened keys does not fail");
+
+        Self {
+            network,
+            account,
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// The value of all outputs we can spend, outputs that are spent by a
+    /// transaction which did not make it into a block yet are not included.
+    pub fn balance(&self) -> Amount {
+        let state = self.state.lock().unwrap();
+
+        Amount::from_sat(
+            state
+                .utxos
+                .iter()
+                .filter(|utxo| utxo.is_spendable())
+                .map(|utxo| utxo.value)
+                .sum(),
+        )
+    }
+
+    /// The first receiving address that was not handed out yet, it stays the
+    /// same until a payment to it shows up in a block.
+    pub fn receive_address(&self) -> Address {
+        let state = self.state.lock().unwrap();
+
+        self.address(Keychain::Receive, state.next_receive_index)
+    }
+
+    /// Hands out a receiving address that is not returned again, e.g. to
+    /// redeem or refund a swap to.
+    pub fn new_address(&self) -> Address {
+        let mut state = self.state.lock().unwrap();
+        let index = state.next_receive_index;
+        state.next_receive_index += 1;
+
+        self.address(Keychain::Receive, index)
+    }
+
+    /// A snapshot of the state, to be saved to the database. Reservations are
+    /// not part of it.
+    pub fn state(&self) -> State {
+        State {
+            reserved: HashMap::new(),
+            ..self.state.lock().unwrap().clone()
+        }
+    }
+
+    /// Builds and signs a transaction that pays `amount` to `to`, paying
+    /// `fee_per_wu` satoshis per weight unit, the change goes to a change
+    /// address of the wallet.
+    ///
+    /// The selected outputs are reserved for this payment so that no other
+    /// payment spends them, asking for the same payment again returns the same
+    /// transaction. They are locked by `lock_spent_outputs` once the
+    /// transaction was broadcast, released by `release_reserved_outputs` if
+    /// that failed or free again after `RESERVATION_TIMEOUT` otherwise.
+    pub fn fund(
+        &self,
+        to: &Address,
+        amount: Amount,
+        network: Network,
+        fee_per_wu: u64,
+    ) -> Result<Transaction, Error> {
+        if network != self.network {
+            return Err(Error::NetworkMismatch {
+                wallet: self.network,
+                transaction: network,
+            });
+        }
+
+        let mut state = self.state.lock().unwrap();
+
+        let now = Instant::now();
+        state
+            .reserved
+            .retain(|_, reservation| reservation.until > now);
+
+        let payment = TxOut {
+            value: amount.as_sat(),
+            script_pubkey: to.script_pubkey(),
+        };
+        let candidates = state
+            .utxos
+            .iter()
+            .filter(|utxo| {
+                let reserved_for_other_payment = state
+                    .reserved
+                    .get(&utxo.outpoint)
+                    .map(|reservation| reservation.payment != payment)
+                    .unwrap_or(false);
+
+                utxo.is_spendable() && !reserved_for_other_payment
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        let change = self
+            .address(Keychain::Change, state.next_change_index)
+            .script_pubkey();
+
+        let (mut transaction, selected) =
+            select_coins(candidates, payment.clone(), change, fee_per_wu)?;
+
+        for utxo in &selected {
+            state.reserved.insert(utxo.outpoint, Reservation {
+                payment: payment.clone(),
+                until: now + RESERVATION_TIMEOUT,
+            });
+        }
+
+        let spent = selected
+            .iter()
+            .map(|utxo| (utxo.keychain, utxo.index, utxo.value))
+            .collect::<Vec<_>>();
+        self.sign(&mut transaction, &spent);
+
+        Ok(transaction)
+    }
+
+    /// Locks the outputs of the wallet `transaction` spends once it was
+    /// broadcast, so they are not selected again until the transaction is seen
+    /// in a block. If it pays change to the wallet, the next transaction uses
+    /// a new change address.
+    pub fn lock_spent_outputs(&self, transaction: &Transaction) {
+        let mut state = self.state.lock().unwrap();
+
+        let change = self
+            .address(Keychain::Change, state.next_change_index)
+            .script_pubkey();
+        if transaction
+            .output
+            .iter()
+            .any(|output| output.script_pubkey == change)
+        {
+            state.next_change_index += 1;
+        }
+
+        for utxo in state.utxos.iter_mut() {
+            if transaction
+                .input
+                .iter()
+                .any(|input| input.previous_output == utxo.outpoint)
+            {
+                utxo.locked = true;
+            }
+        }
+        for input in &transaction.input {
+            state.reserved.remove(&input.previous_output);
+        }
+    }
+
+    /// Releases the outputs `fund` reserved for `transaction`, e.g. because it
+    /// could not be broadcast, so they can be selected again right away.
+    pub fn release_reserved_outputs(&self, transaction: &Transaction) {
+        let mut state = self.state.lock().unwrap();
+
+        for input in &transaction.input {
+            state.reserved.remove(&input.previous_output);
+        }
+    }
+
+    /// Builds and signs a transaction that spends the output of `parent` that
+    /// belongs to the wallet back to the wallet, paying enough fees for both
+    /// transactions to get `fee_per_wu` (CPFP). The fee `parent` pays already
+    /// is not deducted.
+    ///
+    /// Returns `None` if no output of `parent` belongs to the wallet. The
+    /// child signals replaceability itself, a later child for the same parent
+    /// with a higher fee replaces it. Like for `fund`, the wallet is only
+    /// updated by `lock_spent_outputs` once the child was broadcast.
+    pub fn child_pays_for_parent(
+        &self,
+        parent: &Transaction,
+        fee_per_wu: u64,
+    ) -> Result<Option<Transaction>, Error> {
+        let state = self.state.lock().unwrap();
+        let scripts = self.scripts(&state);
+
+        let owned = parent.output.iter().enumerate().find_map(|(vout, output)| {
+            scripts
+                .get(&output.script_pubkey)
+                .map(|derivation| (vout, output, *derivation))
+        });
+        let (vout, output, (keychain, index)) = match owned {
+            Some(owned) => owned,
+            None => return Ok(None),
+        };
+
+        let change_index = state.next_change_index;
+        let mut transaction = Transaction {
+            version: 2,
+            lock_time: 0,
+            input: vec![TxIn {
+                previous_output: OutPoint {
+                    txid: parent.txid(),
+                    vout: vout as u32,
+                },
+                script_sig: Script::new(),
+                sequence: SEQUENCE_ENABLE_RBF,
+                witness: vec![],
+            }],
+            output: vec![TxOut {
+                value: 0,
+                script_pubkey: self.address(Keychain::Change, change_index).script_pubkey(),
+            }],
+        };
+
+        let parent_fee = (parent.get_weight() as u64)
+            .checked_mul(fee_per_wu)
+            .ok_or(Error::OverflowingFee)?;
+        let needed = estimate_fee(&transaction, fee_per_wu)
+            .and_then(|fee| fee.checked_add(parent_fee))
+            .and_then(|fee| fee.checked_add(DUST_LIMIT))
+            .ok_or(Error::OverflowingFee)?;
+        if output.value < needed {
+            return Err(Error::InsufficientFunds {
+                needed: Amount::from_sat(needed),
+                available: Amount::from_sat(output.value),
+            });
+        }
+
+        transaction.output[0].value = output.value - (needed - DUST_LIMIT);
+        self.sign(&mut transaction, &[(keychain, index, output.value)]);
+
+        Ok(Some(transaction))
+    }
+
+    /// Follows the blockchain and keeps track of the outputs of the wallet.
+    ///
+    /// If one of the last `REORG_DEPTH` processed blocks is orphaned, the
+    /// outputs it added are removed and the outputs it spent are spendable
+    /// again before the blocks that replaced it are processed.
+    pub async fn sync<C, E>(self, mut blockchain_connector: C, db: Sqlite)
+    where
+        C: LatestBlock<Block = Block, Error = E>
+            + BlockByHash<Block = Block, BlockHash = sha256d::Hash, Error = E>
+            + Clone,
+        E: Debug + Send + 'static,
+    {
+        let mut saved_state = self.state();
+
+        loop {
+            match blockchain_connector.latest_block().compat().await {
+                Ok(latest_block) => self.catch_up(&blockchain_connector, latest_block).await,
+                Err(e) => log::warn!("Could not get latest block: {:?}", e),
+            }
+
+            let state = self.state();
+            if state != saved_state {
+                match db.save_bitcoin_wallet_state(&state).await {
+                    Ok(()) => saved_state = state,
+                    Err(e) => log::warn!("Could not save Bitcoin wallet: {:?}", e),
+                }
+            }
+
+            delay().await;
+        }
+    }
+
+    async fn catch_up<C, E>(&self, blockchain_connector: &C, latest_block: Block)
+    where
+        C: BlockByHash<Block = Block, BlockHash = sha256d::Hash, Error = E>,
+        E: Debug + Send + 'static,
+    {
+        let (tip, recent_blocks) = {
+            let state = self.state.lock().unwrap();
+            (state.tip, state.recent_blocks.clone())
+        };
+        if tip == Some(latest_block.bitcoin_hash()) {
+            return;
+        }
+        let known = |block_hash: &sha256d::Hash| {
+            tip.as_ref() == Some(block_hash) || recent_blocks.contains(block_hash)
+        };
+
+        // The newest block we processed that is still part of the best chain.
+        let mut common_ancestor = None;
+        let mut missed_blocks = Vec::new();
+        if known(&latest_block.bitcoin_hash()) {
+            common_ancestor = Some(latest_block.bitcoin_hash());
+        } else {
+            missed_blocks.push(latest_block);
+        }
+
+        // A new wallet has nothing to catch up with, it starts at the latest
+        // block.
+        if tip.is_some() {
+            while common_ancestor.is_none() && missed_blocks.len() < MAX_CATCH_UP_BLOCKS {
+                let prev_blockhash = missed_blocks
+                    .last()
+                    .expect("starts with the latest block")
+                    .header
+                    .prev_blockhash;
+                if known(&prev_blockhash) {
+                    common_ancestor = Some(prev_blockhash);
+                    break;
+                }
+
+                match blockchain_connector
+                    .block_by_hash(prev_blockhash)
+                    .compat()
+                    .await
+                {
+                    Ok(block) => missed_blocks.push(block),
+                    Err(e) => {
+                        log::warn!("Could not get block with hash {}: {:?}", prev_blockhash, e);
+                        return;
+                    }
+                }
+            }
+        }
+
+        if let Some(common_ancestor) = common_ancestor {
+            self.disconnect_blocks_after(common_ancestor);
+        }
+        for block in missed_blocks.iter().rev() {
+            self.process_block(block);
+        }
+    }
+
+    /// Undoes the blocks that were processed after the given one, they are no
+    /// longer part of the best chain.
+    fn disconnect_blocks_after(&self, block_hash: sha256d::Hash) {
+        let mut state = self.state.lock().unwrap();
+
+        while let Some(orphaned) = state.recent_blocks.last().copied() {
+            if orphaned == block_hash {
+                break;
+            }
+            log::info!("Bitcoin wallet undoes orphaned block {}", orphaned);
+
+            state.recent_blocks.pop();
+            state.utxos.retain(|utxo| utxo.block != Some(orphaned));
+            for utxo in state.utxos.iter_mut() {
+                if utxo.spent_in == Some(orphaned) {
+                    utxo.spent_in = None;
+                }
+            }
+        }
+
+        state.tip = Some(block_hash);
+    }
+
+    fn process_block(&self, block: &Block) {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let scripts = self.scripts(state);
+        let block_hash = block.bitcoin_hash();
+
+        for transaction in &block.txdata {
+            for utxo in state.utxos.iter_mut() {
+                if utxo.spent_in.is_none()
+                    && transaction
+                        .input
+                        .iter()
+                        .any(|input| input.previous_output == utxo.outpoint)
+                {
+                    utxo.spent_in = Some(block_hash);
+                }
+            }
+
+            let txid = transaction.txid();
+            for (vout, output) in transaction.output.iter().enumerate() {
+                let (keychain, index) = match scripts.get(&output.script_pubkey) {
+                    Some(derivation) => *derivation,
+                    None => continue,
+                };
+                let outpoint = OutPoint {
+                    txid,
+                    vout: vout as u32,
+                };
+
+                if state.utxos.iter().any(|utxo| utxo.outpoint == outpoint) {
+                    continue;
+                }
+
+                log::info!(
+                    "Bitcoin wallet received {} in transaction {}",
+                    Amount::from_sat(output.value),
+                    txid
+                );
+                state.utxos.push(Utxo {
+                    outpoint,
+                    value: output.value,
+                    keychain,
+                    index,
+                    locked: false,
+                    block: Some(block_hash),
+                    spent_in: None,
+                });
+
+                let next_index = match keychain {
+                    Keychain::Receive => &mut state.next_receive_index,
+                    Keychain::Change => &mut state.next_change_index,
+                };
+                *next_index = (*next_index).max(index + 1);
+            }
+        }
+
+        state.tip = Some(block_hash);
+        state.recent_blocks.push(block_hash);
+        if state.recent_blocks.len() > REORG_DEPTH {
+            state.recent_blocks.remove(0);
+        }
+
+        // Outputs spent in a block that is too old to be orphaned are gone.
+        let recent_blocks = &state.recent_blocks;
+        state.utxos.retain(|utxo| {
+            utxo.spent_in
+                .map(|block_hash| recent_blocks.contains(&block_hash))
+                .unwrap_or(true)
+        });
+    }
+
+    /// The scripts of all addresses we watch for incoming payments.
+    fn scripts(&self, state: &State) -> HashMap<Script, (Keychain, u32)> {
+        let receive = (0..state.next_receive_index + GAP_LIMIT).map(|i| (Keychain::Receive, i));
+        let change = (0..state.next_change_index + GAP_LIMIT).map(|i| (Keychain::Change, i));
+
+        receive
+            .chain(change)
+            .map(|(keychain, index)| {
+                (
+                    self.address(keychain, index).script_pubkey(),
+                    (keychain, index),
+                )
+            })
+            .collect()
+    }
+
+    /// Signs every input of `transaction`, `spent` holds the derivation and
+    /// the value of the output each input spends.
+    fn sign(&self, transaction: &mut Transaction, spent: &[(Keychain, u32, u64)]) {
+        let sighash_components = SighashComponents::new(transaction);
+        let witnesses = transaction
+            .input
+            .iter()
+            .zip(spent)
+            .map(|(input, (keychain, index, value))| {
+                let key = self.key(*keychain, *index);
+                let public_key = key.private_key.public_key(&*crate::SECP);
+                let script_code = Address::p2pkh(&public_key, self.network).script_pubkey();
+
+                let sighash = sighash_components.sighash_all(input, &script_code, *value);
+                let message =
+                    secp256k1::Message::from_slice(&sighash[..]).expect("sighash is 32 bytes");
+                let signature = crate::SECP.sign(&message, &key.private_key.key);
+
+                let mut signature = signature.serialize_der().to_vec();
+                signature.push(SigHashType::All as u8);
+
+                vec![signature, public_key.key.serialize().to_vec()]
+            })
+            .collect::<Vec<_>>();
+
+        for (input, witness) in transaction.input.iter_mut().zip(witnesses) {
+            input.witness = witness;
+        }
+    }
+
+    fn key(&self, keychain: Keychain, index: u32) -> ExtendedPrivKey {
+        self.account
+            .derive_priv(&*crate::SECP, &[
+                keychain.child_number(),
+                ChildNumber::Normal { index },
+            ])
+            .expect("deriving normal keys does not fail")
+    }
+
+    fn address(&self, keychain: Keychain, index: u32) -> Address {
+        let public_key = self
+            .key(keychain, index)
+            .private_key
+            .public_key(&*crate::SECP);
+
+        Address::p2wpkh(&public_key, self.network)
+    }
+}
+
+/// An output `select_coins` can spend.
+pub trait Coin {
+    fn outpoint(&self) -> OutPoint;
+    fn value(&self) -> u64;
+    /// The weight the script sig and witness of an input spending the output
+    /// add to a transaction once it is signed.
+    fn satisfaction_weight(&self) -> u64;
+}
+
+impl Coin for Utxo {
+    fn outpoint(&self) -> OutPoint {
+        self.outpoint
+    }
+
+    fn value(&self) -> u64 {
+        self.value
+    }
+
+    fn satisfaction_weight(&self) -> u64 {
+        P2WPKH_WITNESS_WEIGHT
+    }
+}
+
+/// Builds an unsigned transaction that pays `payment` from `coins`, paying
+/// `fee_per_wu` satoshis per weight unit. The change goes to `change_script`
+/// unless it is dust.
+///
+/// The inputs of the transaction spend the returned coins in the same order.
+pub fn select_coins<C: Coin>(
+    mut coins: Vec<C>,
+    payment: TxOut,
+    change_script: Script,
+    fee_per_wu: u64,
+) -> Result<(Transaction, Vec<C>), Error> {
+    // Spending the biggest outputs first keeps the transaction small.
+    coins.sort_by(|a, b| b.value().cmp(&a.value()));
+
+    let available = coins.iter().map(Coin::value).sum::<u64>();
+    let amount = payment.value;
+    let change = TxOut {
+        value: 0,
+        script_pubkey: change_script,
+    };
+
+    let mut transaction = Transaction {
+        version: 2,
+        lock_time: 0,
+        input: vec![],
+        output: vec![payment, change],
+    };
+    let mut selected = Vec::new();
+    let mut selected_value = 0u64;
+    let mut satisfaction_weight = 0u64;
+    let mut needed = amount;
+
+    for coin in coins {
+        transaction.input.push(TxIn {
+            previous_output: coin.outpoint(),
+            script_sig: Script::new(),
+            sequence: 0xFFFF_FFFF,
+            witness: vec![],
+        });
+        selected_value += coin.value();
+        satisfaction_weight += coin.satisfaction_weight();
+        selected.push(coin);
+
+        let fee = fee_once_signed(&transaction, satisfaction_weight, fee_per_wu)
+            .ok_or(Error::OverflowingFee)?;
+        needed = amount.checked_add(fee).ok_or(Error::OverflowingFee)?;
+
+        if selected_value >= needed {
+            break;
+        }
+    }
+
+    if selected.is_empty() || selected_value < needed {
+        return Err(Error::InsufficientFunds {
+            needed: Amount::from_sat(needed),
+            available: Amount::from_sat(available),
+        });
+    }
+
+    let change_value = selected_value - needed;
+    if change_value < DUST_LIMIT {
+        transaction.output.pop();
+    } else {
+        transaction.output[1].value = change_value;
+    }
+
+    Ok((transaction, selected))
+}
+
+/// The fee for `transaction` once all of its inputs, which spend outputs of
+/// the wallet, are signed.
+pub fn estimate_fee(transaction: &Transaction, fee_per_wu: u64) -> Option<u64> {
+    let satisfaction_weight = P2WPKH_WITNESS_WEIGHT * transaction.input.len() as u64;
+
+    fee_once_signed(transaction, satisfaction_weight, fee_per_wu)
+}
+
+fn fee_once_signed(
+    transaction: &Transaction,
+    satisfaction_weight: u64,
+    fee_per_wu: u64,
+) -> Option<u64> {
+    let weight = transaction.get_weight() as u64 + SEGWIT_HEADER_WEIGHT + satisfaction_weight;
+
+    weight.checked_mul(fee_per_wu)
+}
+
+async fn delay() {
+    Delay::new(std::time::Instant::now().add(std::time::Duration::from_secs(1)))
+        .compat()
+        .await
+        .unwrap_or_else(|e| log::warn!("Failed to wait for delay: {:?}", e));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::{blockdata::block::BlockHeader, hashes::Hash};
+    use std::{collections::HashSet, sync::Barrier, thread};
+
+    struct Blocks(Vec<Block>);
+
+    impl BlockByHash for Blocks {
+        type Error = ();
+        type Block = Block;
+        type BlockHash = sha256d::Hash;
+
+        fn block_by_hash(
+            &self,
+            block_hash: sha256d::Hash,
+        ) -> Box<dyn futures::Future<Item = Block, Error = ()> + Send + 'static> {
+            let block = self
+                .0
+                .iter()
+                .find(|block| block.bitcoin_hash() == block_hash)
+                .cloned();
+
+            Box::new(futures::future::result(block.ok_or(())))
+        }
+    }
+
+    fn wallet() -> Wallet {
+        Wallet::new(&Seed::from([1u8; 32]), Network::Regtest, State::default())
+    }
+
+    fn block_paying_to(address: &Address, value: u64) -> Block {
+        let transaction = Transaction {
+            version: 2,
+            lock_time: 0,
+            input: vec![],
+            output: vec![TxOut {
+                value,
+                script_pubkey: address.script_pubkey(),
+            }],
+        };
+
+        Block {
+            header: BlockHeader {
+                version: 1,
+                prev_blockhash: sha256d::Hash::default(),
+                merkle_root: sha256d::Hash::hash(&[value as u8]),
+                time: 0,
+                bits: 0,
+                nonce: 0,
+            },
+            txdata: vec![transaction],
+        }
+    }
+
+    fn child_of(parent: &Block, block: Block) -> Block {
+        Block {
+            header: BlockHeader {
+                prev_blockhash: parent.bitcoin_hash(),
+                ..block.header
+            },
+            ..block
+        }
+    }
+
+    fn other_address() -> Address {
+        Wallet::new(&Seed::from([2u8; 32]), Network::Regtest, State::default()).receive_address()
+    }
+
+    #[test]
+    fn addresses_are_derived_deterministically() {
+        let address = wallet().receive_address();
+
+        assert_eq!(address, wallet().receive_address());
+        assert_eq!(address.network, Network::Regtest);
+        assert!(address.script_pubkey().is_v0_p2wpkh());
+    }
+
+    #[test]
+    fn receive_address_changes_once_it_was_paid_to() {
+        let wallet = wallet();
+        let address = wallet.receive_address();
+
+        wallet.process_block(&block_paying_to(&address, 100_000));
+
+        assert_eq!(wallet.balance(), Amount::from_sat(100_000));
+        assert_ne!(wallet.receive_address(), address);
+    }
+
+    #[test]
+    fn payments_to_new_addresses_within_gap_limit_are_found() {
+        let wallet = wallet();
+        let address = (0..5).map(|_| wallet.new_address()).last().unwrap();
+
+        wallet.process_block(&block_paying_to(&address, 100_000));
+
+        assert_eq!(wallet.balance(), Amount::from_sat(100_000));
+    }
+
+    #[test]
+    fn fund_pays_amount_and_returns_change_to_wallet() {
+        let wallet = wallet();
+        wallet.process_block(&block_paying_to(&wallet.receive_address(), 100_000));
+        let to = Wallet::new(&Seed::from([2u8; 32]), Network::Regtest, State::default())
+            .receive_address();
+
+        let transaction = wallet
+            .fund(&to, Amount::from_sat(60_000), Network::Regtest, 10)
+            .unwrap();
+
+        assert_eq!(transaction.input.len(), 1);
+        assert_eq!(transaction.output[0].value, 60_000);
+        assert_eq!(transaction.output[0].script_pubkey, to.script_pubkey());
+        assert_eq!(transaction.input[0].witness.len(), 2);
+
+        wallet.lock_spent_outputs(&transaction);
+        assert_eq!(wallet.balance(), Amount::from_sat(0));
+
+        let change = transaction.output[1].value;
+        let fee = 100_000 - 60_000 - change;
+        assert!(fee >= transaction.get_weight() as u64 * 10);
+
+        wallet.process_block(&Block {
+            txdata: vec![transaction],
+            ..block_paying_to(&to, 0)
+        });
+        assert_eq!(wallet.balance(), Amount::from_sat(change));
+    }
+
+    #[test]
+    fn fund_reserves_outputs_until_they_are_released() {
+        let wallet = wallet();
+        wallet.process_block(&block_paying_to(&wallet.receive_address(), 100_000));
+        let to = wallet.new_address();
+
+        let first = wallet
+            .fund(&to, Amount::from_sat(60_000), Network::Regtest, 10)
+            .unwrap();
+        let again = wallet
+            .fund(&to, Amount::from_sat(60_000), Network::Regtest, 10)
+            .unwrap();
+        let result = wallet.fund(&to, Amount::from_sat(50_000), Network::Regtest, 10);
+
+        assert_eq!(first, again);
+        assert_eq!(wallet.balance(), Amount::from_sat(100_000));
+        match result {
+            Err(Error::InsufficientFunds { available, .. }) => {
+                assert_eq!(available, Amount::from_sat(0))
+            }
+            _ => panic!("expected insufficient funds, got {:?}", result),
+        }
+
+        wallet.release_reserved_outputs(&first);
+        let second = wallet
+            .fund(&to, Amount::from_sat(50_000), Network::Regtest, 10)
+            .unwrap();
+
+        wallet.lock_spent_outputs(&second);
+
+        assert_eq!(wallet.balance(), Amount::from_sat(0));
+        assert_eq!(wallet.state().next_change_index, 1);
+    }
+
+    #[test]
+    fn reserved_outputs_can_be_selected_again_after_timeout() {
+        let wallet = wallet();
+        wallet.process_block(&block_paying_to(&wallet.receive_address(), 100_000));
+        let to = wallet.new_address();
+
+        let first = wallet
+            .fund(&to, Amount::from_sat(60_000), Network::Regtest, 10)
+            .unwrap();
+        for reservation in wallet.state.lock().unwrap().reserved.values_mut() {
+            reservation.until = Instant::now();
+        }
+        let second = wallet
+            .fund(&to, Amount::from_sat(50_000), Network::Regtest, 10)
+            .unwrap();
+
+        assert_eq!(first.input, second.input);
+    }
+
+    #[test]
+    fn concurrent_funds_spend_different_outputs() {
+        let wallet = wallet();
+        for value in 100_000..100_004 {
+            wallet.process_block(&block_paying_to(&wallet.new_address(), value));
+        }
+        let barrier = Arc::new(Barrier::new(4));
+
+        let funds = (0..4)
+            .map(|i| {
+                let wallet = wallet.clone();
+                let barrier = barrier.clone();
+
+                thread::spawn(move || {
+                    barrier.wait();
+                    wallet
+                        .fund(
+                            &other_address(),
+                            Amount::from_sat(60_000 + i),
+                            Network::Regtest,
+                            10,
+                        )
+                        .unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+        let spent = funds
+            .into_iter()
+            .flat_map(|fund| fund.join().unwrap().input)
+            .map(|input| input.previous_output)
+            .collect::<HashSet<_>>();
+
+        assert_eq!(spent.len(), 4);
+    }
+
+    #[test]
+    fn catching_up_undoes_orphaned_blocks() {
+        let wallet = wallet();
+        let block1 = block_paying_to(&wallet.receive_address(), 100_000);
+        wallet.process_block(&block1);
+
+        let spend = Transaction {
+            version: 2,
+            lock_time: 0,
+            input: vec![TxIn {
+                previous_output: OutPoint {
+                    txid: block1.txdata[0].txid(),
+                    vout: 0,
+                },
+                script_sig: Script::new(),
+                sequence: 0xFFFF_FFFF,
+                witness: vec![],
+            }],
+            output: vec![],
+        };
+        let mut block2a = child_of(&block1, block_paying_to(&wallet.new_address(), 50_000));
+        block2a.txdata.insert(0, spend);
+        wallet.process_block(&block2a);
+        assert_eq!(wallet.balance(), Amount::from_sat(50_000));
+
+        let block2b = child_of(&block1, block_paying_to(&other_address(), 20_000));
+        let block3b = child_of(&block2b, block_paying_to(&other_address(), 30_000));
+        let blocks = Blocks(vec![block1.clone(), block2b.clone(), block3b.clone()]);
+        async_std::task::block_on(wallet.catch_up(&blocks, block3b.clone()));
+
+        assert_eq!(wallet.balance(), Amount::from_sat(100_000));
+        assert_eq!(wallet.state().tip, Some(block3b.bitcoin_hash()));
+        assert_eq!(wallet.state().recent_blocks, vec![
+            block1.bitcoin_hash(),
+            block2b.bitcoin_hash(),
+            block3b.bitcoin_hash()
+        ]);
+    }
+
+    #[test]
+    fn fund_fails_without_enough_coins() {
+        let wallet = wallet();
+        wallet.process_block(&block_paying_to(&wallet.receive_address(), 10_000));
+        let to = wallet.new_address();
+
+        let result = wallet.fund(&to, Amount::from_sat(10_000), Network::Regtest, 1);
+
+        match result {
+            Err(Error::InsufficientFunds { available, .. }) => {
+                assert_eq!(available, Amount::from_sat(10_000))
+            }
+            _ => panic!("expected insufficient funds, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn child_pays_for_itself_and_its_parent() {
+        let wallet = wallet();
+        let parent = block_paying_to(&wallet.new_address(), 100_000)
+            .txdata
+            .remove(0);
+
+        let child = wallet.child_pays_for_parent(&parent, 10).unwrap().unwrap();
+
+        assert_eq!(child.input[0].previous_output.txid, parent.txid());
+        assert_eq!(child.input[0].sequence, SEQUENCE_ENABLE_RBF);
+        assert_eq!(child.input[0].witness.len(), 2);
+
+        let fee = 100_000 - child.output[0].value;
+        assert!(fee >= (parent.get_weight() + child.get_weight()) as u64 * 10);
+    }
+
+    #[test]
+    fn child_pays_for_parent_needs_an_output_of_the_wallet() {
+        let to = Wallet::new(&Seed::from([2u8; 32]), Network::Regtest, State::default())
+            .receive_address();
+        let parent = block_paying_to(&to, 100_000).txdata.remove(0);
+
+        assert_eq!(wallet().child_pays_for_parent(&parent, 10), Ok(None));
+    }
+}
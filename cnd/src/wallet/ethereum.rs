@@ -0,0 +1,406 @@
+use crate::{
+    btsieve::ethereum::{UnsupportedEthereumChain, Web3Connector},
+    ethereum::{Address, BlockNumber, Bytes, H256, U256},
+    seed::Seed,
+    swap_protocols::ledger::ethereum::ChainId,
+};
+use async_std::sync::Mutex;
+use bitcoin::secp256k1::{Message, PublicKey, SecretKey};
+use futures_core::compat::Future01CompatExt;
+use rlp::RlpStream;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    ops::Add,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tiny_keccak::{Hasher, Keccak};
+use tokio::timer::Delay;
+
+/// How long a transaction may stay unmined before it is replaced by one with
+/// a higher gas price.
+const REPLACE_AFTER: Duration = Duration::from_secs(5 * 60);
+
+/// How often we look for mined and stuck transactions.
+const CHECK_PENDING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Gives access to the Ethereum account of cnd.
+pub trait EthereumAccount: Send + Sync + 'static {
+    fn ethereum_account(&self) -> &Account;
+}
+
+/// A transaction for the account to sign and send, the nonce and gas price
+/// are filled in by the account.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnsignedTransaction {
+    /// `None` deploys a contract.
+    pub to: Option<Address>,
+    pub value: U256,
+    pub data: Bytes,
+    pub gas_limit: U256,
+    pub chain_id: ChainId,
+}
+
+/// An Ethereum account derived from the `Seed` of the node.
+///
+/// Nonces are handed out by the account itself so several swaps can send
+/// transactions at the same time, separately for each chain. Transactions that
+/// are not mined within `REPLACE_AFTER` are sent again with a higher gas price
+/// by `replace_stuck_transactions`.
+#[derive(Clone)]
+pub struct Account {
+    secret_key: SecretKey,
+    address: Address,
+    chains: HashMap<ChainId, Chain>,
+}
+
+impl fmt::Debug for Account {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Account")
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+/// The node the account sends the transactions of a chain through and the
+/// nonces it uses on that chain.
+#[derive(Clone)]
+struct Chain {
+    connector: Web3Connector,
+    nonces: Arc<Mutex<Nonces>>,
+}
+
+#[derive(Debug, Default)]
+struct Nonces {
+    /// The nonce of the next transaction, `None` if it has to be asked from
+    /// the node.
+    next: Option<U256>,
+    pending: BTreeMap<U256, PendingTransaction>,
+}
+
+#[derive(Debug)]
+struct PendingTransaction {
+    transaction: UnsignedTransaction,
+    gas_price: U256,
+    hash: H256,
+    sent_at: Instant,
+}
+
+impl Account {
+    /// The account can send transactions on the chains of `connectors`.
+    pub fn new(seed: &Seed, connectors: HashMap<ChainId, Web3Connector>) -> Self {
+        let secret_key = SecretKey::from_slice(&seed.sha256_with_seed(&[b"ETHEREUM_ACCOUNT"]))
+            .expect("The probability of this happening is < 1 in 2^120");
+
+        let chains = connectors
+            .into_iter()
+            .map(|(chain_id, connector)| {
+                (chain_id, Chain {
+                    connector,
+                    nonces: Arc::new(Mutex::new(Nonces::default())),
+                })
+            })
+            .collect();
+
+        Self {
+            address: address(&secret_key),
+            secret_key,
+            chains,
+        }
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Signs `transaction` with the next nonce of the account on its chain
+    /// and sends it through `eth_sendRawTransaction`, the signed transaction
+    /// is returned alongside its hash so it can be broadcast again.
+    pub async fn send(
+        &self,
+        transaction: UnsignedTransaction,
+        gas_price: U256,
+    ) -> anyhow::Result<(H256, Bytes)> {
+        let chain = self.chain(transaction.chain_id)?;
+
+        // Holding the lock until the node accepted the transaction makes sure
+        // concurrent swaps never use the same nonce.
+        let mut nonces = chain.nonces.lock().await;
+
+        let nonce = match nonces.next {
+            Some(nonce) => nonce,
+            None => chain
+                .connector
+                .transaction_count(self.address, BlockNumber::Pending)
+                .compat()
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!("failed to get nonce of {:?}: {:?}", self.address, e)
+                })?,
+        };
+
+        let signed = sign(&self.secret_key, &transaction, nonce, gas_price);
+        let hash = chain.broadcast(signed.clone()).await?;
+        log::info!("Sent transaction {:?} with nonce {}", hash, nonce);
+
+        nonces.next = Some(nonce + 1);
+        nonces.pending.insert(nonce, PendingTransaction {
+            transaction,
+            gas_price,
+            hash,
+            sent_at: Instant::now(),
+        });
+
+        Ok((hash, signed))
+    }
+
+    /// Sends a transaction signed by `send` for the chain `chain_id` again,
+    /// e.g. because the node dropped it or was restarted before it was mined.
+    pub async fn rebroadcast(&self, chain_id: ChainId, signed: Bytes) -> anyhow::Result<H256> {
+        self.chain(chain_id)?.broadcast(signed).await
+    }
+
+    /// Forgets about mined transactions and replaces the ones that are stuck
+    /// with a higher gas price, forever.
+    pub async fn replace_stuck_transactions(self) {
+        loop {
+            for (chain_id, chain) in self.chains.iter() {
+                if let Err(e) = self.check_pending_transactions(chain).await {
+                    log::warn!(
+                        "Could not check pending transactions on chain {}: {:?}",
+                        chain_id,
+                        e
+                    );
+                }
+            }
+
+            delay(CHECK_PENDING_INTERVAL).await;
+        }
+    }
+
+    fn chain(&self, chain_id: ChainId) -> anyhow::Result<&Chain> {
+        self.chains
+            .get(&chain_id)
+            .ok_or_else(|| anyhow::Error::from(UnsupportedEthereumChain { chain_id }))
+    }
+
+    async fn check_pending_transactions(&self, chain: &Chain) -> anyhow::Result<()> {
+        let mut nonces = chain.nonces.lock().await;
+        if nonces.pending.is_empty() {
+            return Ok(());
+        }
+
+        let mined = chain
+            .connector
+            .transaction_count(self.address, BlockNumber::Latest)
+            .compat()
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to get nonce of {:?}: {:?}", self.address, e))?;
+
+        nonces.pending = nonces.pending.split_off(&mined);
+        if nonces.pending.is_empty() {
+            // Nothing is in flight, the next nonce is asked from the node
+            // again in case the account was used by someone else.
+            nonces.next = None;
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        for (nonce, pending) in nonces.pending.iter_mut() {
+            if now.duration_since(pending.sent_at) < REPLACE_AFTER {
+                continue;
+            }
+
+            let gas_price = replacement_gas_price(pending.gas_price);
+            match chain
+                .broadcast(sign(
+                    &self.secret_key,
+                    &pending.transaction,
+                    *nonce,
+                    gas_price,
+                ))
+                .await
+            {
+                Ok(hash) => {
+                    log::info!(
+                        "Replaced stuck transaction {:?} with {:?} at gas price {}",
+                        pending.hash,
+                        hash,
+                        gas_price
+                    );
+                    pending.gas_price = gas_price;
+                    pending.hash = hash;
+                    pending.sent_at = now;
+                }
+                // E.g. because the transaction was mined in the meantime.
+                Err(e) => log::warn!("Could not replace transaction {:?}: {:?}", pending.hash, e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Chain {
+    async fn broadcast(&self, signed: Bytes) -> anyhow::Result<H256> {
+        self.connector
+            .send_raw_transaction(signed)
+            .compat()
+            .await
+            .map_err(|e| anyhow::anyhow!("node did not accept transaction: {:?}", e))
+    }
+}
+
+/// Geth only accepts a replacement that pays at least 10% more, Parity 12.5%.
+fn replacement_gas_price(gas_price: U256) -> U256 {
+    gas_price + gas_price / 5
+}
+
+fn address(secret_key: &SecretKey) -> Address {
+    let public_key = PublicKey::from_secret_key(&*crate::SECP, secret_key);
+    // The address is made from the uncompressed key without its 0x04 prefix.
+    let hash = keccak256(&public_key.serialize_uncompressed()[1..]);
+
+    let mut address = Address::default();
+    address.assign_from_slice(&hash[12..]);
+    address
+}
+
+/// Signs `transaction` with replay protection as defined in EIP-155 and
+/// returns it RLP encoded.
+fn sign(
+    secret_key: &SecretKey,
+    transaction: &UnsignedTransaction,
+    nonce: U256,
+    gas_price: U256,
+) -> Bytes {
+    let chain_id = u32::from(transaction.chain_id);
+
+    let mut stream = RlpStream::new_list(9);
+    append_fields(&mut stream, transaction, nonce, gas_price);
+    stream.append(&chain_id).append(&0u8).append(&0u8);
+    let hash = keccak256(stream.as_raw());
+
+    let message = Message::from_slice(&hash).expect("keccak256 hashes are 32 bytes");
+    let (recovery_id, signature) = crate::SECP
+        .sign_recoverable(&message, secret_key)
+        .serialize_compact();
+    let v = u64::from(chain_id) * 2 + 35 + recovery_id.to_i32() as u64;
+
+    let mut stream = RlpStream::new_list(9);
+    append_fields(&mut stream, transaction, nonce, gas_price);
+    stream
+        .append(&v)
+        .append(&U256::from_big_endian(&signature[..32]))
+        .append(&U256::from_big_endian(&signature[32..]));
+
+    Bytes(stream.out())
+}
+
+fn append_fields(
+    stream: &mut RlpStream,
+    transaction: &UnsignedTransaction,
+    nonce: U256,
+    gas_price: U256,
+) {
+    stream
+        .append(&nonce)
+        .append(&gas_price)
+        .append(&transaction.gas_limit);
+    match &transaction.to {
+        Some(to) => stream.append(to),
+        None => stream.append_empty_data(),
+    };
+    let data: &[u8] = &transaction.data.0;
+    stream.append(&transaction.value).append(&data);
+}
+
+fn keccak256(input: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+
+    let mut hasher = Keccak::v256();
+    hasher.update(input);
+    hasher.finalize(&mut output);
+
+    output
+}
+
+async fn delay(duration: Duration) {
+    Delay::new(Instant::now().add(duration))
+        .compat()
+        .await
+        .unwrap_or_else(|e| log::warn!("Failed to wait for delay: {:?}", e));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_core::{FutureExt, TryFutureExt};
+    use std::str::FromStr;
+
+    #[test]
+    fn address_is_derived_from_public_key() {
+        let mut secret_key = [0u8; 32];
+        secret_key[31] = 1;
+        let secret_key = SecretKey::from_slice(&secret_key).unwrap();
+
+        assert_eq!(
+            address(&secret_key),
+            Address::from_str("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap()
+        );
+    }
+
+    // The example from EIP-155
+    #[test]
+    fn transaction_is_signed_with_replay_protection() {
+        let secret_key = SecretKey::from_slice(&[0x46; 32]).unwrap();
+        let transaction = UnsignedTransaction {
+            to: Some(Address::from_str("3535353535353535353535353535353535353535").unwrap()),
+            value: U256::from(1_000_000_000_000_000_000u64),
+            data: Bytes(vec![]),
+            gas_limit: U256::from(21_000),
+            chain_id: ChainId::mainnet(),
+        };
+
+        let signed = sign(
+            &secret_key,
+            &transaction,
+            U256::from(9),
+            U256::from(20_000_000_000u64),
+        );
+
+        assert_eq!(
+            hex::encode(signed.0),
+            "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+        );
+    }
+
+    #[test]
+    fn transactions_for_chains_without_node_are_not_sent() {
+        let account = Account::new(&Seed::from([1u8; 32]), HashMap::new());
+        let transaction = UnsignedTransaction {
+            to: None,
+            value: U256::zero(),
+            data: Bytes(vec![]),
+            gas_limit: U256::from(21_000),
+            chain_id: ChainId::ropsten(),
+        };
+
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(
+            async move { account.send(transaction, U256::from(1)).await }
+                .boxed()
+                .compat(),
+        );
+
+        assert!(result.unwrap_err().is::<UnsupportedEthereumChain>());
+    }
+
+    #[test]
+    fn replacement_pays_enough_more_gas() {
+        let gas_price = U256::from(20_000_000_000u64);
+
+        assert!(replacement_gas_price(gas_price) * 8 > gas_price * 9);
+    }
+}
@@ -0,0 +1,554 @@
+use crate::{
+    btsieve::bitcoin::{BitcoinConnector, Error as ConnectorError},
+    wallet::bitcoin::{select_coins, Coin, Error, P2WPKH_WITNESS_WEIGHT},
+};
+use bitcoin::{
+    hashes::sha256d, util::psbt::PartiallySignedTransaction, Address, Amount, Network, OutPoint,
+    Script, Transaction, TxOut,
+};
+use std::str::FromStr;
+
+/// Gives access to the watch-only wallet of cnd.
+pub trait BitcoinWatchOnlyWallet: Send + Sync + 'static {
+    fn bitcoin_watch_only_wallet(&self) -> &WatchOnlyWallet;
+}
+
+/// An output a PSBT can spend. Signers need the spent output itself to sign
+/// for a segwit input, it ends up as the `witness_utxo` of the input. The
+/// transaction that created it is added as the `non_witness_utxo` by
+/// `WatchOnlyWallet::add_previous_transactions`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Utxo {
+    pub outpoint: OutPoint,
+    pub output: TxOut,
+}
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq)]
+#[error("expected txid:vout:value:script_pubkey, got {0}")]
+pub struct ParseUtxoError(String);
+
+/// Parses `txid:vout:value:script_pubkey` with the value in satoshis and the
+/// script hex encoded.
+impl FromStr for Utxo {
+    type Err = ParseUtxoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseUtxoError(s.to_owned());
+
+        let mut parts = s.split(':');
+        let txid = parts
+            .next()
+            .and_then(|txid| sha256d::Hash::from_str(txid).ok())
+            .ok_or_else(error)?;
+        let vout = parts
+            .next()
+            .and_then(|vout| vout.parse().ok())
+            .ok_or_else(error)?;
+        let value = parts
+            .next()
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(error)?;
+        let script_pubkey = parts
+            .next()
+            .and_then(|script| hex::decode(script).ok())
+            .ok_or_else(error)?;
+        if parts.next().is_some() {
+            return Err(error());
+        }
+
+        Ok(Utxo {
+            outpoint: OutPoint { txid, vout },
+            output: TxOut {
+                value,
+                script_pubkey: Script::from(script_pubkey),
+            },
+        })
+    }
+}
+
+/// The wallet behind the `watch_only_descriptor` of the `bitcoin` section of
+/// the config file, e.g. a multisig or hardware wallet. cnd has none of its
+/// keys, it only builds PSBTs (BIP174) spending its outputs.
+#[derive(Clone, Debug)]
+pub struct WatchOnlyWallet {
+    descriptor: Option<String>,
+    connector: BitcoinConnector,
+}
+
+impl WatchOnlyWallet {
+    pub fn new(descriptor: Option<String>, connector: BitcoinConnector) -> Self {
+        Self {
+            descriptor,
+            connector,
+        }
+    }
+
+    pub fn descriptor(&self) -> Option<&str> {
+        self.descriptor.as_ref().map(String::as_str)
+    }
+
+    /// The unspent outputs of the descriptor, `None` if there is none in the
+    /// config file.
+    pub async fn utxos(&self) -> Result<Option<Vec<Utxo>>, ConnectorError> {
+        let descriptor = match &self.descriptor {
+            Some(descriptor) => descriptor,
+            None => return Ok(None),
+        };
+
+        let utxos = self
+            .connector
+            .scan_descriptor(descriptor)
+            .await?
+            .into_iter()
+            .map(|(outpoint, output)| Utxo { outpoint, output })
+            .collect();
+
+        Ok(Some(utxos))
+    }
+
+    /// Adds the transactions that created the outputs `psbt` spends as their
+    /// `non_witness_utxo`, hardware wallets need them to verify the amounts
+    /// they sign for. Transactions the backend cannot find are left out.
+    pub async fn add_previous_transactions(&self, psbt: &mut PartiallySignedTransaction) {
+        let previous_outputs = psbt
+            .global
+            .unsigned_tx
+            .input
+            .iter()
+            .map(|input| input.previous_output)
+            .collect::<Vec<_>>();
+
+        for (input, previous_output) in psbt.inputs.iter_mut().zip(previous_outputs) {
+            match self.connector.transaction(previous_output.txid).await {
+                Ok(transaction) => input.non_witness_utxo = Some(transaction),
+                Err(e) => log::warn!(
+                    "Could not look up transaction {} spent by PSBT: {:?}",
+                    previous_output.txid,
+                    e
+                ),
+            }
+        }
+    }
+}
+
+/// An output `fund` may spend and what it takes to sign for it.
+#[derive(Debug)]
+struct Spendable {
+    utxo: Utxo,
+    satisfaction: Satisfaction,
+}
+
+impl Coin for Spendable {
+    fn outpoint(&self) -> OutPoint {
+        self.utxo.outpoint
+    }
+
+    fn value(&self) -> u64 {
+        self.utxo.output.value
+    }
+
+    fn satisfaction_weight(&self) -> u64 {
+        self.satisfaction.weight
+    }
+}
+
+/// Builds an unsigned PSBT that pays `amount` to `to` from `utxos`, paying
+/// `fee_per_wu` satoshis per weight unit. The change goes to `change_address`.
+///
+/// The size of the signed inputs is known for P2WPKH and P2PKH outputs, for
+/// other outputs it is taken from `descriptor`, the `watch_only_descriptor`
+/// of the wallet they belong to.
+pub fn fund(
+    to: &Address,
+    amount: Amount,
+    network: Network,
+    utxos: Vec<Utxo>,
+    change_address: &Address,
+    fee_per_wu: u64,
+    descriptor: Option<&str>,
+) -> Result<PartiallySignedTransaction, Error> {
+    if change_address.network != network {
+        return Err(Error::NetworkMismatch {
+            wallet: change_address.network,
+            transaction: network,
+        });
+    }
+
+    let coins = utxos
+        .into_iter()
+        .map(|utxo| {
+            let satisfaction = satisfaction(&utxo.output.script_pubkey, descriptor)
+                .ok_or(Error::UnknownInputSize(utxo.outpoint))?;
+
+            Ok(Spendable { utxo, satisfaction })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let payment = TxOut {
+        value: amount.as_sat(),
+        script_pubkey: to.script_pubkey(),
+    };
+
+    let (transaction, selected) =
+        select_coins(coins, payment, change_address.script_pubkey(), fee_per_wu)?;
+
+    let mut psbt = PartiallySignedTransaction::from_unsigned_tx(transaction)
+        .expect("the transaction has neither script sigs nor witnesses");
+    for (input, spendable) in psbt.inputs.iter_mut().zip(selected) {
+        if spendable.satisfaction.segwit {
+            input.witness_utxo = Some(spendable.utxo.output);
+        }
+    }
+
+    Ok(psbt)
+}
+
+/// How an input spending an output is signed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Satisfaction {
+    /// The weight of the script sig and the witness of the signed input.
+    weight: u64,
+    segwit: bool,
+}
+
+/// A DER signature with its sighash type and its length.
+const SIGNATURE_SIZE: u64 = 1 + 73;
+
+/// A compressed public key and its length.
+const PUBLIC_KEY_SIZE: u64 = 1 + 33;
+
+/// Script sig bytes count four times as much as witness bytes.
+const WITNESS_SCALE_FACTOR: u64 = 4;
+
+const P2WPKH: Satisfaction = Satisfaction {
+    weight: P2WPKH_WITNESS_WEIGHT,
+    segwit: true,
+};
+
+const P2PKH: Satisfaction = Satisfaction {
+    weight: (SIGNATURE_SIZE + PUBLIC_KEY_SIZE) * WITNESS_SCALE_FACTOR,
+    segwit: false,
+};
+
+fn satisfaction(script_pubkey: &Script, descriptor: Option<&str>) -> Option<Satisfaction> {
+    if script_pubkey.is_v0_p2wpkh() {
+        Some(P2WPKH)
+    } else if script_pubkey.is_p2pkh() {
+        Some(P2PKH)
+    } else {
+        descriptor.and_then(descriptor_satisfaction)
+    }
+}
+
+/// Understands the descriptors of single key and multisig wallets, keys are
+/// assumed to be compressed.
+fn descriptor_satisfaction(descriptor: &str) -> Option<Satisfaction> {
+    // The checksum is optional.
+    let descriptor = descriptor.split('#').next()?.trim();
+
+    match split_function(descriptor)? {
+        ("pkh", _) => Some(P2PKH),
+        ("wpkh", _) => Some(P2WPKH),
+        ("wsh", multi) => Some(Satisfaction {
+            weight: multisig_witness_weight(multi)?,
+            segwit: true,
+        }),
+        ("sh", inner) => match split_function(inner)? {
+            // The script sig pushes the 22 byte witness program.
+            ("wpkh", _) => Some(Satisfaction {
+                weight: (1 + 22) * WITNESS_SCALE_FACTOR + P2WPKH_WITNESS_WEIGHT,
+                segwit: true,
+            }),
+            // The script sig pushes the 34 byte witness program.
+            ("wsh", multi) => Some(Satisfaction {
+                weight: (1 + 34) * WITNESS_SCALE_FACTOR + multisig_witness_weight(multi)?,
+                segwit: true,
+            }),
+            _ => {
+                let (threshold, keys) = multisig(inner)?;
+                let script = multisig_script_size(keys);
+                let push = match script {
+                    0..=75 => 1,
+                    76..=255 => 2,
+                    _ => 3,
+                };
+                // The dummy element CHECKMULTISIG pops, the signatures and
+                // the redeem script.
+                let script_sig = 1 + threshold * SIGNATURE_SIZE + push + script;
+
+                Some(Satisfaction {
+                    weight: (script_sig + var_int_size(script_sig) - 1) * WITNESS_SCALE_FACTOR,
+                    segwit: false,
+                })
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Splits `function(arguments)` into its parts.
+fn split_function(expression: &str) -> Option<(&str, &str)> {
+    let open = expression.find('(')?;
+    if !expression.ends_with(')') {
+        return None;
+    }
+
+    Some((
+        &expression[..open],
+        &expression[open + 1..expression.len() - 1],
+    ))
+}
+
+/// The threshold and the number of keys of `multi(k,KEY,...)` or
+/// `sortedmulti(k,KEY,...)`.
+fn multisig(expression: &str) -> Option<(u64, u64)> {
+    let (function, arguments) = split_function(expression)?;
+    if function != "multi" && function != "sortedmulti" {
+        return None;
+    }
+
+    let mut arguments = arguments.split(',');
+    let threshold = arguments.next()?.trim().parse::<u64>().ok()?;
+    let keys = arguments.count() as u64;
+    if threshold == 0 || threshold > keys {
+        return None;
+    }
+
+    Some((threshold, keys))
+}
+
+/// `OP_k <keys> OP_n OP_CHECKMULTISIG`
+fn multisig_script_size(keys: u64) -> u64 {
+    1 + keys * PUBLIC_KEY_SIZE + 1 + 1
+}
+
+/// The number of witness elements, the dummy element CHECKMULTISIG pops, the
+/// signatures and the witness script.
+fn multisig_witness_weight(expression: &str) -> Option<u64> {
+    let (threshold, keys) = multisig(expression)?;
+    let script = multisig_script_size(keys);
+
+    Some(1 + 1 + threshold * SIGNATURE_SIZE + var_int_size(script) + script)
+}
+
+fn var_int_size(value: u64) -> u64 {
+    match value {
+        0..=0xFC => 1,
+        0xFD..=0xFFFF => 3,
+        _ => 5,
+    }
+}
+
+/// The transaction of a PSBT whose inputs were all finalized by the signers,
+/// `None` if there are inputs left to sign.
+pub fn finalized_transaction(psbt: PartiallySignedTransaction) -> Option<Transaction> {
+    let finalized = psbt
+        .inputs
+        .iter()
+        .all(|input| input.final_script_sig.is_some() || input.final_script_witness.is_some());
+    if !finalized {
+        return None;
+    }
+
+    Some(psbt.extract_tx())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        seed::Seed,
+        wallet::bitcoin::{State, Wallet},
+    };
+
+    const SCRIPT_PUBKEY: &str = "0014751e76e8199196d454941c45d1b3a323f1433bd6";
+    const P2WSH_SCRIPT_PUBKEY: &str =
+        "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262";
+    const MULTISIG_DESCRIPTOR: &str = "wsh(multi(2,\
+        03a0434d9e47f3c86235477c7b1ae6ae5d3442d49b1943c2b752a68e2a47e247c7,\
+        03774ae7f858a9411e5ef4246b70c65aac5649980be5c17891bbec17895da008cb,\
+        03d01115d548e7561b15c38f004d734633687cf4419620095bc5b0f47070afe85a))";
+
+    fn address() -> Address {
+        Wallet::new(&Seed::from([1u8; 32]), Network::Regtest, State::default()).new_address()
+    }
+
+    fn utxo(vout: u32, value: u64) -> Utxo {
+        format!(
+            "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:{}:{}:{}",
+            vout, value, SCRIPT_PUBKEY
+        )
+        .parse()
+        .unwrap()
+    }
+
+    fn p2wsh_utxo(vout: u32, value: u64) -> Utxo {
+        format!(
+            "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:{}:{}:{}",
+            vout, value, P2WSH_SCRIPT_PUBKEY
+        )
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn utxo_is_parsed_from_its_parts() {
+        let utxo = utxo(1, 10_000);
+
+        assert_eq!(utxo.outpoint.vout, 1);
+        assert_eq!(utxo.output.value, 10_000);
+        assert!(utxo.output.script_pubkey.is_v0_p2wpkh());
+        assert!(Utxo::from_str("4a5e1e4b:1:10000").is_err());
+    }
+
+    #[test]
+    fn fund_spends_biggest_utxos_and_returns_change() {
+        let to = address();
+
+        let psbt = fund(
+            &to,
+            Amount::from_sat(60_000),
+            Network::Regtest,
+            vec![utxo(0, 10_000), utxo(1, 100_000)],
+            &address(),
+            10,
+            None,
+        )
+        .unwrap();
+
+        let transaction = &psbt.global.unsigned_tx;
+        assert_eq!(transaction.input.len(), 1);
+        assert_eq!(transaction.input[0].previous_output.vout, 1);
+        assert_eq!(transaction.output[0].value, 60_000);
+        assert_eq!(transaction.output[0].script_pubkey, to.script_pubkey());
+        let witness_utxo = psbt.inputs[0].witness_utxo.clone().unwrap();
+        assert_eq!(witness_utxo.value, 100_000);
+
+        let fee = 100_000 - 60_000 - transaction.output[1].value;
+        assert!(fee >= transaction.get_weight() as u64 * 10);
+    }
+
+    #[test]
+    fn fund_fails_without_enough_utxos() {
+        let result = fund(
+            &address(),
+            Amount::from_sat(10_000),
+            Network::Regtest,
+            vec![utxo(0, 10_000)],
+            &address(),
+            1,
+            None,
+        );
+
+        match result {
+            Err(Error::InsufficientFunds { available, .. }) => {
+                assert_eq!(available, Amount::from_sat(10_000))
+            }
+            _ => panic!("expected insufficient funds, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn fund_weighs_inputs_by_descriptor() {
+        let fee_paid = |psbt: PartiallySignedTransaction| {
+            let transaction = psbt.global.unsigned_tx;
+            100_000 - transaction.output.iter().map(|o| o.value).sum::<u64>()
+        };
+
+        let p2wpkh = fund(
+            &address(),
+            Amount::from_sat(10_000),
+            Network::Regtest,
+            vec![utxo(0, 100_000)],
+            &address(),
+            10,
+            None,
+        )
+        .unwrap();
+        let multisig = fund(
+            &address(),
+            Amount::from_sat(10_000),
+            Network::Regtest,
+            vec![p2wsh_utxo(0, 100_000)],
+            &address(),
+            10,
+            Some(MULTISIG_DESCRIPTOR),
+        )
+        .unwrap();
+
+        let multisig_satisfaction = descriptor_satisfaction(MULTISIG_DESCRIPTOR).unwrap();
+        assert!(multisig_satisfaction.segwit);
+        assert_eq!(
+            fee_paid(multisig) - fee_paid(p2wpkh),
+            (multisig_satisfaction.weight - P2WPKH_WITNESS_WEIGHT) * 10
+        );
+    }
+
+    #[test]
+    fn fund_fails_to_weigh_inputs_without_descriptor() {
+        let result = fund(
+            &address(),
+            Amount::from_sat(10_000),
+            Network::Regtest,
+            vec![p2wsh_utxo(0, 100_000)],
+            &address(),
+            1,
+            None,
+        );
+
+        match result {
+            Err(Error::UnknownInputSize(outpoint)) => assert_eq!(outpoint.vout, 0),
+            _ => panic!("expected unknown input size, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn descriptors_of_single_key_and_multisig_wallets_are_understood() {
+        assert_eq!(
+            descriptor_satisfaction("wpkh(xpub/0/*)#8zl0zxma"),
+            Some(P2WPKH)
+        );
+        assert_eq!(descriptor_satisfaction("pkh(xpub/0/*)"), Some(P2PKH));
+        assert_eq!(
+            descriptor_satisfaction("sh(wpkh(xpub/0/*))"),
+            Some(Satisfaction {
+                weight: 92 + P2WPKH_WITNESS_WEIGHT,
+                segwit: true,
+            })
+        );
+        // 2 of 3: item count, dummy, two signatures, script length and script
+        assert_eq!(
+            descriptor_satisfaction("wsh(sortedmulti(2,a,b,c))"),
+            Some(Satisfaction {
+                weight: 1 + 1 + 2 * 74 + 1 + 105,
+                segwit: true,
+            })
+        );
+        // dummy, two signatures, redeem script push and redeem script, the
+        // script sig length takes two more bytes
+        assert_eq!(
+            descriptor_satisfaction("sh(multi(2,a,b,c))"),
+            Some(Satisfaction {
+                weight: (1 + 2 * 74 + 2 + 105 + 2) * 4,
+                segwit: false,
+            })
+        );
+        assert_eq!(descriptor_satisfaction("tr(xpub/0/*)"), None);
+        assert_eq!(descriptor_satisfaction("wsh(multi(4,a,b,c))"), None);
+    }
+
+    #[test]
+    fn unsigned_psbt_is_not_finalized() {
+        let psbt = fund(
+            &address(),
+            Amount::from_sat(10_000),
+            Network::Regtest,
+            vec![utxo(0, 100_000)],
+            &address(),
+            1,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(finalized_transaction(psbt), None);
+    }
+}
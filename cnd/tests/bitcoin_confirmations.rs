@@ -0,0 +1,79 @@
+pub mod bitcoin_helper;
+
+use bitcoin_helper::BitcoinConnectorMock;
+use cnd::btsieve::{bitcoin::transaction_confirmed, BlockPoller, Confirmations};
+use futures_core::{FutureExt, TryFutureExt};
+use std::time::Duration;
+use tokio::timer::Timeout;
+
+#[test]
+fn transaction_is_confirmed_once_buried_deep_enough() {
+    let transaction: bitcoin::Transaction = include_hex!(
+        "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/transaction.hex"
+    );
+    let connector = BitcoinConnectorMock::new(
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block2_with_transaction.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block3.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block4.hex"),
+        ],
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block1.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block2_with_transaction.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block3.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block4.hex"),
+        ],
+    );
+    let confirmations = Confirmations::new(3);
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let confirmed = Timeout::new(
+        transaction_confirmed(
+            BlockPoller::new(connector),
+            transaction.txid(),
+            confirmations.clone(),
+        )
+        .unit_error()
+        .boxed()
+        .compat(),
+        Duration::from_secs(10),
+    );
+
+    assert!(runtime.block_on(confirmed).is_ok());
+    assert_eq!(confirmations.get(&transaction.txid()), Some(3));
+}
+
+#[test]
+fn transaction_is_not_confirmed_before_buried_deep_enough() {
+    let transaction: bitcoin::Transaction = include_hex!(
+        "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/transaction.hex"
+    );
+    let connector = BitcoinConnectorMock::new(
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block2_with_transaction.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block3.hex"),
+        ],
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block1.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block2_with_transaction.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block3.hex"),
+        ],
+    );
+    let confirmations = Confirmations::new(6);
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let confirmed = Timeout::new(
+        transaction_confirmed(
+            BlockPoller::new(connector),
+            transaction.txid(),
+            confirmations.clone(),
+        )
+        .unit_error()
+        .boxed()
+        .compat(),
+        Duration::from_secs(3),
+    );
+
+    assert!(runtime.block_on(confirmed).unwrap_err().is_elapsed());
+    assert_eq!(confirmations.get(&transaction.txid()), Some(2));
+}
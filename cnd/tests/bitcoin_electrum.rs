@@ -0,0 +1,90 @@
+pub mod bitcoin_helper;
+
+use bitcoin::{hashes::sha256d, Address};
+use bitcoin_helper::ElectrumMock;
+use cnd::btsieve::{
+    bitcoin::{BitcoinConnector, ElectrumConnector, TransactionPattern},
+    Confirmations,
+};
+use futures_core::{FutureExt, TryFutureExt};
+use std::str::FromStr;
+
+fn transaction() -> bitcoin::Transaction {
+    include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/transaction.hex")
+}
+
+fn address() -> Address {
+    Address::from_str(
+        include_str!("test_data/bitcoin/find_transaction_in_missing_block/address").trim(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn find_transaction_in_history_of_script() {
+    let electrum = ElectrumMock::serve(transaction(), 2, 3);
+    let connector = BitcoinConnector::Electrum(ElectrumConnector::new(&electrum.url()).unwrap());
+
+    let pattern = TransactionPattern {
+        to_address: Some(address()),
+        from_outpoint: None,
+        unlock_script: None,
+    };
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let found = runtime
+        .block_on(
+            async {
+                connector
+                    .matching_transaction(pattern, address().script_pubkey(), ())
+                    .await
+            }
+            .boxed()
+            .compat(),
+        )
+        .unwrap();
+
+    assert_eq!(found, transaction());
+}
+
+#[test]
+fn confirmations_are_counted_from_the_tip() {
+    let electrum = ElectrumMock::serve(transaction(), 2, 3);
+    let connector = BitcoinConnector::Electrum(ElectrumConnector::new(&electrum.url()).unwrap());
+    let confirmations = Confirmations::<sha256d::Hash>::new(2);
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime
+        .block_on(
+            async {
+                connector
+                    .transaction_confirmed(transaction(), confirmations.clone())
+                    .await;
+                Ok::<_, ()>(())
+            }
+            .boxed()
+            .compat(),
+        )
+        .unwrap();
+
+    assert_eq!(confirmations.get(&transaction().txid()), Some(2));
+}
+
+#[test]
+fn orphaned_transaction_is_retracted() {
+    let electrum = ElectrumMock::serve(transaction(), 2, 3);
+    let connector = BitcoinConnector::Electrum(ElectrumConnector::new(&electrum.url()).unwrap());
+    electrum.orphan();
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime
+        .block_on(
+            async {
+                connector.transaction_retracted(transaction()).await;
+                Ok::<_, ()>(())
+            }
+            .boxed()
+            .compat(),
+        )
+        .unwrap();
+}
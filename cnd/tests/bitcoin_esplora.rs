@@ -0,0 +1,63 @@
+pub mod bitcoin_helper;
+
+use bitcoin::{Address, Network};
+use bitcoin_helper::EsploraMock;
+use cnd::btsieve::{
+    bitcoin::{matching_transaction, EsploraConnector, TransactionPattern},
+    BlockPoller, LatestBlock,
+};
+use futures_core::{FutureExt, TryFutureExt};
+use std::str::FromStr;
+
+#[test]
+fn latest_block_is_the_tip_of_the_chain() {
+    let tip: bitcoin::Block =
+        include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/block3.hex");
+    let esplora = EsploraMock::serve(vec![
+        include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/block1.hex"),
+        tip.clone(),
+    ]);
+    let mut connector = EsploraConnector::new(esplora.url(), Network::Regtest).unwrap();
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let latest_block = runtime.block_on(connector.latest_block()).unwrap();
+
+    assert_eq!(latest_block, tip);
+}
+
+#[test]
+fn find_transaction_in_block_before_the_tip() {
+    let esplora = EsploraMock::serve(vec![
+        include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/block1.hex"),
+        include_hex!(
+            "./test_data/bitcoin/find_transaction_in_missing_block/block2_with_transaction.hex"
+        ),
+        include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/block3.hex"),
+    ]);
+    let connector = EsploraConnector::new(esplora.url(), Network::Regtest).unwrap();
+
+    let pattern = TransactionPattern {
+        to_address: Some(
+            Address::from_str(
+                include_str!("test_data/bitcoin/find_transaction_in_missing_block/address").trim(),
+            )
+            .unwrap(),
+        ),
+        from_outpoint: None,
+        unlock_script: None,
+    };
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let transaction: bitcoin::Transaction = runtime
+        .block_on(
+            async { matching_transaction(BlockPoller::new(connector), pattern, None).await }
+                .boxed()
+                .compat(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        transaction,
+        include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/transaction.hex")
+    );
+}
@@ -0,0 +1,121 @@
+use bitcoin::{consensus::encode::serialize, util::hash::BitcoinHash};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread,
+};
+
+/// Answers `getbestblockhash` and `getblock` like the JSON-RPC interface of
+/// bitcoind, but only for requests that authenticate as `username` with
+/// `password`. The last block is reported as the best block.
+pub struct BitcoindRpcMock {
+    address: SocketAddr,
+}
+
+impl BitcoindRpcMock {
+    pub fn serve(blocks: Vec<bitcoin::Block>, username: &str, password: &str) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let authorization = format!(
+            "Basic {}",
+            base64::encode(&format!("{}:{}", username, password))
+        );
+        let best_block = blocks.last().expect("at least one block").bitcoin_hash();
+        let blocks = blocks
+            .iter()
+            .map(|block| (block.bitcoin_hash().to_string(), serialize(block)))
+            .collect::<HashMap<_, _>>();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                answer(
+                    stream.unwrap(),
+                    &authorization,
+                    &best_block.to_string(),
+                    &blocks,
+                );
+            }
+        });
+
+        Self { address }
+    }
+
+    pub fn url(&self) -> reqwest::Url {
+        format!("http://{}", self.address).parse().unwrap()
+    }
+}
+
+fn answer(
+    stream: TcpStream,
+    authorization: &str,
+    best_block: &str,
+    blocks: &HashMap<String, Vec<u8>>,
+) {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+
+    let mut authorized = false;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).unwrap() == 0 || header.trim().is_empty() {
+            break;
+        }
+        let mut parts = header.splitn(2, ':');
+        let name = parts.next().unwrap_or_default().trim().to_lowercase();
+        let value = parts.next().unwrap_or_default().trim();
+
+        match name.as_str() {
+            "authorization" => authorized = value == authorization,
+            "content-length" => content_length = value.parse().unwrap(),
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).unwrap();
+    let mut stream = reader.into_inner();
+
+    if !authorized {
+        stream
+            .write_all(
+                b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            )
+            .unwrap();
+        return;
+    }
+
+    let request: Value = serde_json::from_slice(&body).unwrap();
+    let result = match request["method"].as_str().unwrap() {
+        "getbestblockhash" => json!(best_block),
+        "getblock" => {
+            assert_eq!(
+                request["params"][1],
+                json!(0),
+                "only raw blocks are supported"
+            );
+            json!(blocks
+                .get(request["params"][0].as_str().unwrap())
+                .map(hex::encode))
+        }
+        method => panic!("unexpected method {}", method),
+    };
+    let response = json!({
+        "result": result,
+        "error": null,
+        "id": request["id"],
+    })
+    .to_string();
+
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.len(),
+        response
+    )
+    .unwrap();
+}
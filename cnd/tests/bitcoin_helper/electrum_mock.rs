@@ -0,0 +1,93 @@
+use bitcoin::consensus::encode::serialize;
+use serde_json::{json, Value};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// An Electrum server that knows a single transaction, confirmed at
+/// `height`. The transaction shows up in the history of every script.
+#[derive(Clone)]
+pub struct ElectrumMock {
+    address: SocketAddr,
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    transaction: bitcoin::Transaction,
+    height: i64,
+    tip_height: u32,
+}
+
+impl ElectrumMock {
+    pub fn serve(transaction: bitcoin::Transaction, height: i64, tip_height: u32) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let mock = Self {
+            address,
+            state: Arc::new(Mutex::new(State {
+                transaction,
+                height,
+                tip_height,
+            })),
+        };
+
+        thread::spawn({
+            let mock = mock.clone();
+
+            move || {
+                for stream in listener.incoming() {
+                    mock.answer(stream.unwrap());
+                }
+            }
+        });
+
+        mock
+    }
+
+    pub fn url(&self) -> reqwest::Url {
+        format!("tcp://{}", self.address).parse().unwrap()
+    }
+
+    /// Moves the transaction back into the mempool, as if its block was
+    /// orphaned.
+    pub fn orphan(&self) {
+        self.state.lock().unwrap().height = 0;
+    }
+
+    fn answer(&self, stream: TcpStream) {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap() == 0 {
+            return;
+        }
+        let request: Value = serde_json::from_str(&line).unwrap();
+
+        let result = {
+            let state = self.state.lock().unwrap();
+
+            match request["method"].as_str().unwrap() {
+                "blockchain.scripthash.get_history" => json!([{
+                    "tx_hash": state.transaction.txid().to_string(),
+                    "height": state.height,
+                }]),
+                "blockchain.transaction.get" => json!(hex::encode(serialize(&state.transaction))),
+                "blockchain.headers.subscribe" => json!({
+                    "height": state.tip_height,
+                    "hex": "",
+                }),
+                method => panic!("unexpected method {}", method),
+            }
+        };
+
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": result,
+        });
+        let mut stream = reader.into_inner();
+        writeln!(stream, "{}", response).unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+use bitcoin::{consensus::encode::serialize, util::hash::BitcoinHash};
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread,
+};
+
+/// Serves the given blocks through the parts of the Esplora REST API we use,
+/// the last block is reported as the tip of the chain.
+pub struct EsploraMock {
+    address: SocketAddr,
+}
+
+impl EsploraMock {
+    pub fn serve(blocks: Vec<bitcoin::Block>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let tip = blocks.last().expect("at least one block").bitcoin_hash();
+        let blocks = blocks
+            .iter()
+            .map(|block| (block.bitcoin_hash().to_string(), serialize(block)))
+            .collect::<HashMap<_, _>>();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                answer(stream.unwrap(), &tip.to_string(), &blocks);
+            }
+        });
+
+        Self { address }
+    }
+
+    pub fn url(&self) -> reqwest::Url {
+        format!("http://{}/api", self.address).parse().unwrap()
+    }
+}
+
+fn answer(stream: TcpStream, tip: &str, blocks: &HashMap<String, Vec<u8>>) {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+
+    // Skip the headers, the requests have no body.
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).unwrap() == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+    let body = match path
+        .trim_start_matches("/api/")
+        .split('/')
+        .collect::<Vec<_>>()[..]
+    {
+        ["blocks", "tip", "hash"] => Some(tip.as_bytes().to_vec()),
+        ["block", hash, "raw"] => blocks.get(hash).cloned(),
+        _ => None,
+    };
+
+    let mut stream = reader.into_inner();
+    match body {
+        Some(body) => {
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(&body).unwrap();
+        }
+        None => stream
+            .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            .unwrap(),
+    }
+}
@@ -1,4 +1,11 @@
+pub mod bitcoind_rpc_mock;
 pub mod connector_mock;
+pub mod electrum_mock;
+pub mod esplora_mock;
 pub mod include_hex;
+pub mod zmq_publisher;
 
-pub use self::{connector_mock::*, include_hex::*};
+pub use self::{
+    bitcoind_rpc_mock::*, connector_mock::*, electrum_mock::*, esplora_mock::*, include_hex::*,
+    zmq_publisher::*,
+};
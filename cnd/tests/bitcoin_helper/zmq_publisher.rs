@@ -0,0 +1,82 @@
+use bitcoin::consensus::encode::serialize;
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener},
+    thread,
+};
+
+/// Stands in for the `zmqpubrawblock` socket of bitcoind: publishes the given
+/// blocks to the first subscriber that connects and keeps the connection
+/// open afterwards.
+pub struct ZmqPublisherMock {
+    address: SocketAddr,
+}
+
+impl ZmqPublisherMock {
+    pub fn publish(blocks: Vec<bitcoin::Block>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut greeting = [0u8; 64];
+            stream.read_exact(&mut greeting).unwrap();
+            greeting[12..16].copy_from_slice(b"NULL");
+            stream.write_all(&greeting).unwrap();
+
+            let _ready = read_frame(&mut stream);
+            write_frame(&mut stream, 0x04, &ready_command());
+            let _subscription = read_frame(&mut stream);
+
+            for (sequence, block) in blocks.iter().enumerate() {
+                write_frame(&mut stream, 0x01, b"rawblock");
+                write_frame(&mut stream, 0x01, &serialize(block));
+                write_frame(&mut stream, 0x00, &(sequence as u32).to_le_bytes());
+            }
+
+            // Closing the connection would make the subscriber fall back to polling.
+            let _ = stream.read(&mut [0u8; 1]);
+        });
+
+        Self { address }
+    }
+
+    pub fn url(&self) -> reqwest::Url {
+        format!("tcp://{}", self.address).parse().unwrap()
+    }
+}
+
+fn ready_command() -> Vec<u8> {
+    let mut command = vec![5];
+    command.extend_from_slice(b"READY");
+    command.push(11);
+    command.extend_from_slice(b"Socket-Type");
+    command.extend_from_slice(&3u32.to_be_bytes());
+    command.extend_from_slice(b"PUB");
+
+    command
+}
+
+fn write_frame(stream: &mut impl Write, flags: u8, body: &[u8]) {
+    if body.len() > 255 {
+        stream.write_all(&[flags | 0x02]).unwrap();
+        stream
+            .write_all(&(body.len() as u64).to_be_bytes())
+            .unwrap();
+    } else {
+        stream.write_all(&[flags, body.len() as u8]).unwrap();
+    }
+    stream.write_all(body).unwrap();
+}
+
+fn read_frame(stream: &mut impl Read) -> Vec<u8> {
+    let mut header = [0u8; 2];
+    stream.read_exact(&mut header).unwrap();
+    assert_eq!(header[0] & 0x02, 0, "subscriber only sends short frames");
+
+    let mut body = vec![0u8; usize::from(header[1])];
+    stream.read_exact(&mut body).unwrap();
+
+    body
+}
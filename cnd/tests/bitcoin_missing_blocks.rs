@@ -2,7 +2,10 @@ pub mod bitcoin_helper;
 
 use bitcoin::Address;
 use bitcoin_helper::BitcoinConnectorMock;
-use cnd::btsieve::bitcoin::{matching_transaction, TransactionPattern};
+use cnd::btsieve::{
+    bitcoin::{matching_transaction, TransactionPattern},
+    BlockPoller,
+};
 use futures_core::{FutureExt, TryFutureExt};
 use std::str::FromStr;
 use tokio::prelude::Future;
@@ -25,7 +28,7 @@ fn find_transaction_in_missing_block() {
 
     let expected_transaction: bitcoin::Transaction = async {
         matching_transaction(
-            connector,
+            BlockPoller::new(connector),
             TransactionPattern {
                 to_address: Some(
                     Address::from_str(
@@ -73,7 +76,7 @@ fn find_transaction_in_missing_block_with_big_gap() {
 
     let expected_transaction: bitcoin::Transaction = async {
         matching_transaction(
-            connector,
+            BlockPoller::new(connector),
             TransactionPattern {
                 to_address: Some(
                     Address::from_str(
@@ -121,7 +124,7 @@ fn find_transaction_if_blockchain_reorganisation() {
 
     let expected_transaction: bitcoin::Transaction = async {
         matching_transaction(
-            connector,
+            BlockPoller::new(connector),
             TransactionPattern {
                 to_address: Some(
                     Address::from_str(
@@ -172,7 +175,7 @@ fn find_transaction_if_blockchain_reorganisation_with_long_chain() {
 
     let expected_transaction: bitcoin::Transaction = async {
         matching_transaction(
-            connector,
+            BlockPoller::new(connector),
             TransactionPattern {
                 to_address: Some(
                     Address::from_str(
@@ -201,3 +204,46 @@ fn find_transaction_if_blockchain_reorganisation_with_long_chain() {
     )
     );
 }
+
+#[test]
+fn find_transaction_in_missing_block_through_block_poller() {
+    let connector = BlockPoller::new(BitcoinConnectorMock::new(
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/block1.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/block3.hex"),
+        ],
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/block1.hex"),
+            include_hex!(
+                "./test_data/bitcoin/find_transaction_in_missing_block/block2_with_transaction.hex"
+            ),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/block3.hex"),
+        ],
+    ));
+    let pattern = TransactionPattern {
+        to_address: Some(
+            Address::from_str(
+                include_str!("test_data/bitcoin/find_transaction_in_missing_block/address").trim(),
+            )
+            .unwrap(),
+        ),
+        from_outpoint: None,
+        unlock_script: None,
+    };
+
+    let (first, second) = matching_transaction(connector.clone(), pattern.clone(), None)
+        .boxed()
+        .compat()
+        .join(
+            matching_transaction(connector, pattern, None)
+                .boxed()
+                .compat(),
+        )
+        .wait()
+        .unwrap();
+
+    let expected_transaction: bitcoin::Transaction =
+        include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/transaction.hex");
+    assert_eq!(first, expected_transaction);
+    assert_eq!(second, expected_transaction);
+}
@@ -2,7 +2,10 @@ pub mod bitcoin_helper;
 
 use bitcoin::Address;
 use bitcoin_helper::BitcoinConnectorMock;
-use cnd::btsieve::bitcoin::{matching_transaction, TransactionPattern};
+use cnd::btsieve::{
+    bitcoin::{matching_transaction, TransactionPattern},
+    BlockPoller,
+};
 use futures_core::{FutureExt, TryFutureExt};
 use std::str::FromStr;
 use tokio::prelude::Future;
@@ -29,7 +32,7 @@ fn find_transaction_in_old_block() {
 
     let expected_transaction: bitcoin::Transaction = async {
         matching_transaction(
-            connector,
+            BlockPoller::new(connector),
             TransactionPattern {
                 to_address: Some(
                     Address::from_str(
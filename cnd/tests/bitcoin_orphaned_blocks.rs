@@ -0,0 +1,143 @@
+pub mod bitcoin_helper;
+
+use bitcoin::BitcoinHash;
+use bitcoin_helper::BitcoinConnectorMock;
+use cnd::btsieve::{
+    bitcoin::{block_height, transaction_retracted},
+    BlockPoller, IncludingBlock, IncludingBlocks,
+};
+use futures_core::{FutureExt, TryFutureExt};
+use std::time::Duration;
+use tokio::timer::Timeout;
+
+#[test]
+fn transaction_is_retracted_if_including_block_gets_orphaned() {
+    let transaction: bitcoin::Transaction = include_hex!(
+        "./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/transaction.hex"
+    );
+    let connector = BitcoinConnectorMock::new(
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block2_with_transaction.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block1b_stale.hex"),
+        ],
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block1.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block2_with_transaction.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block1b_stale.hex"),
+        ],
+    );
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let retracted = Timeout::new(
+        transaction_retracted(BlockPoller::new(connector), transaction.txid())
+            .unit_error()
+            .boxed()
+            .compat(),
+        Duration::from_secs(10),
+    );
+
+    assert!(runtime.block_on(retracted).is_ok());
+}
+
+#[test]
+fn transaction_is_retracted_if_including_block_was_orphaned_before_watching_it() {
+    let transaction: bitcoin::Transaction = include_hex!(
+        "./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/transaction.hex"
+    );
+    let including_block: bitcoin::Block = include_hex!(
+        "./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block2_with_transaction.hex"
+    );
+    let connector = BitcoinConnectorMock::new(
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block1b_stale.hex"),
+        ],
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block1.hex"),
+            including_block.clone(),
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block1b_stale.hex"),
+        ],
+    );
+    let poller = BlockPoller::new(connector);
+    poller.remember_including_block(transaction.txid(), IncludingBlock {
+        block_hash: including_block.bitcoin_hash(),
+        block_height: block_height(&including_block).unwrap().into(),
+    });
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let retracted = Timeout::new(
+        transaction_retracted(poller, transaction.txid())
+            .unit_error()
+            .boxed()
+            .compat(),
+        Duration::from_secs(10),
+    );
+
+    assert!(runtime.block_on(retracted).is_ok());
+}
+
+#[test]
+fn transaction_is_not_retracted_if_including_block_stays_in_best_chain() {
+    let transaction: bitcoin::Transaction = include_hex!(
+        "./test_data/bitcoin/find_transaction_if_blockchain_reorganisation_with_long_chain/transaction.hex"
+    );
+    let connector = BitcoinConnectorMock::new(
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation_with_long_chain/block5_with_transaction.hex"),
+        ],
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation_with_long_chain/block1.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation_with_long_chain/block2.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation_with_long_chain/block3.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation_with_long_chain/block4.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation_with_long_chain/block4b_stale.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation_with_long_chain/block5_with_transaction.hex"),
+        ],
+    );
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let retracted = Timeout::new(
+        transaction_retracted(BlockPoller::new(connector), transaction.txid())
+            .unit_error()
+            .boxed()
+            .compat(),
+        Duration::from_secs(3),
+    );
+
+    assert!(runtime.block_on(retracted).unwrap_err().is_elapsed());
+}
+
+#[test]
+fn transaction_is_not_retracted_if_competing_fork_includes_it_as_well() {
+    let transaction: bitcoin::Transaction = include_hex!(
+        "./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/transaction.hex"
+    );
+    let competing_block_with_transaction = {
+        let mut block: bitcoin::Block = include_hex!(
+            "./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block1b_stale.hex"
+        );
+        block.txdata.push(transaction.clone());
+        block
+    };
+    let connector = BitcoinConnectorMock::new(
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block2_with_transaction.hex"),
+            competing_block_with_transaction.clone(),
+        ],
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block1.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_if_blockchain_reorganisation/block2_with_transaction.hex"),
+            competing_block_with_transaction,
+        ],
+    );
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let retracted = Timeout::new(
+        transaction_retracted(BlockPoller::new(connector), transaction.txid())
+            .unit_error()
+            .boxed()
+            .compat(),
+        Duration::from_secs(3),
+    );
+
+    assert!(runtime.block_on(retracted).unwrap_err().is_elapsed());
+}
@@ -0,0 +1,70 @@
+pub mod bitcoin_helper;
+
+use bitcoin::{util::hash::BitcoinHash, Network};
+use bitcoin_helper::BitcoindRpcMock;
+use cnd::btsieve::{
+    bitcoin::{BitcoindRpcConnector, RpcAuth},
+    BlockByHash, LatestBlock,
+};
+
+fn blocks() -> Vec<bitcoin::Block> {
+    vec![
+        include_hex!("./test_data/bitcoin/find_transaction_in_missing_block/block1.hex"),
+        include_hex!(
+            "./test_data/bitcoin/find_transaction_in_missing_block/block2_with_transaction.hex"
+        ),
+    ]
+}
+
+#[test]
+fn latest_block_and_block_by_hash_with_user_and_password() {
+    let bitcoind = BitcoindRpcMock::serve(blocks(), "bitcoin", "t68ej4");
+    let mut connector =
+        BitcoindRpcConnector::new(bitcoind.url(), Network::Regtest, RpcAuth::UserPassword {
+            username: "bitcoin".to_owned(),
+            password: "t68ej4".to_owned(),
+        });
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let latest_block = runtime.block_on(connector.latest_block()).unwrap();
+    let parent = runtime
+        .block_on(connector.block_by_hash(latest_block.header.prev_blockhash))
+        .unwrap();
+
+    assert_eq!(latest_block, blocks()[1]);
+    assert_eq!(parent.bitcoin_hash(), blocks()[0].bitcoin_hash());
+}
+
+#[test]
+fn credentials_are_taken_from_cookie_file() {
+    let bitcoind = BitcoindRpcMock::serve(blocks(), "__cookie__", "c0ffee");
+    let dir = tempfile::tempdir().unwrap();
+    let cookie_file = dir.path().join(".cookie");
+    std::fs::write(&cookie_file, "__cookie__:c0ffee").unwrap();
+
+    let mut connector = BitcoindRpcConnector::new(
+        bitcoind.url(),
+        Network::Regtest,
+        RpcAuth::CookieFile(cookie_file),
+    );
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let latest_block = runtime.block_on(connector.latest_block()).unwrap();
+
+    assert_eq!(latest_block, blocks()[1]);
+}
+
+#[test]
+fn wrong_credentials_are_rejected() {
+    let bitcoind = BitcoindRpcMock::serve(blocks(), "bitcoin", "t68ej4");
+    let mut connector =
+        BitcoindRpcConnector::new(bitcoind.url(), Network::Regtest, RpcAuth::UserPassword {
+            username: "bitcoin".to_owned(),
+            password: "wrong".to_owned(),
+        });
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let result = runtime.block_on(connector.latest_block());
+
+    assert!(result.is_err());
+}
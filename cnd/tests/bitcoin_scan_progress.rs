@@ -0,0 +1,203 @@
+pub mod bitcoin_helper;
+
+use async_trait::async_trait;
+use bitcoin::{
+    hashes::{sha256d, Hash},
+    util::hash::BitcoinHash,
+    Address,
+};
+use bitcoin_helper::BitcoinConnectorMock;
+use cnd::btsieve::{
+    bitcoin::{matching_transaction_with_progress, TransactionPattern},
+    BlockPoller, Checkpoint, ScanProgress,
+};
+use futures_core::{FutureExt, TryFutureExt};
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::timer::Timeout;
+
+#[derive(Clone, Default)]
+struct InMemoryScanProgress {
+    checkpoint: Arc<Mutex<Option<Checkpoint<sha256d::Hash>>>>,
+}
+
+#[async_trait]
+impl ScanProgress<sha256d::Hash> for InMemoryScanProgress {
+    async fn load_checkpoint(&self) -> Option<Checkpoint<sha256d::Hash>> {
+        *self.checkpoint.lock().unwrap()
+    }
+
+    async fn save_checkpoint(&self, checkpoint: Checkpoint<sha256d::Hash>) {
+        *self.checkpoint.lock().unwrap() = Some(checkpoint);
+    }
+}
+
+#[test]
+fn resumed_scan_finds_transaction_in_block_after_checkpoint() {
+    let block1: bitcoin::Block = include_hex!(
+        "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block1.hex"
+    );
+    let connector = BitcoinConnectorMock::new(
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block8.hex"),
+        ],
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block2_with_transaction.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block3.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block4.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block5.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block6.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block7.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block8.hex"),
+        ],
+    );
+    let scan_progress = InMemoryScanProgress::default();
+    *scan_progress.checkpoint.lock().unwrap() = Some(Checkpoint {
+        block_hash: block1.bitcoin_hash(),
+        block_height: 527,
+    });
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let transaction = runtime
+        .block_on(Timeout::new(
+            matching_transaction_with_progress(
+                BlockPoller::new(connector),
+                address_pattern(),
+                None,
+                scan_progress,
+            )
+            .boxed()
+            .compat(),
+            Duration::from_secs(10),
+        ))
+        .unwrap();
+
+    assert_eq!(
+        transaction,
+        include_hex!(
+            "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/transaction.hex"
+        )
+    );
+}
+
+#[test]
+fn resumed_scan_does_not_rescan_blocks_before_checkpoint() {
+    let block2: bitcoin::Block = include_hex!(
+        "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block2_with_transaction.hex"
+    );
+    let block8: bitcoin::Block = include_hex!(
+        "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block8.hex"
+    );
+    let connector = BitcoinConnectorMock::new(vec![block8.clone()], vec![
+        block2.clone(),
+        include_hex!(
+            "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block3.hex"
+        ),
+        include_hex!(
+            "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block4.hex"
+        ),
+        include_hex!(
+            "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block5.hex"
+        ),
+        include_hex!(
+            "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block6.hex"
+        ),
+        include_hex!(
+            "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block7.hex"
+        ),
+        block8.clone(),
+    ]);
+    let scan_progress = InMemoryScanProgress::default();
+    *scan_progress.checkpoint.lock().unwrap() = Some(Checkpoint {
+        block_hash: block2.bitcoin_hash(),
+        block_height: 528,
+    });
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let result = runtime.block_on(Timeout::new(
+        matching_transaction_with_progress(
+            BlockPoller::new(connector),
+            address_pattern(),
+            None,
+            scan_progress.clone(),
+        )
+        .boxed()
+        .compat(),
+        Duration::from_secs(10),
+    ));
+
+    assert!(result.unwrap_err().is_elapsed());
+    assert_eq!(
+        *scan_progress.checkpoint.lock().unwrap(),
+        Some(Checkpoint {
+            block_hash: block8.bitcoin_hash(),
+            block_height: 534,
+        })
+    );
+}
+
+#[test]
+fn resumed_scan_finds_transaction_below_orphaned_checkpoint() {
+    let connector = BitcoinConnectorMock::new(
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block8.hex"),
+        ],
+        vec![
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block2_with_transaction.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block3.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block4.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block5.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block6.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block7.hex"),
+            include_hex!("./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block8.hex"),
+        ],
+    );
+    let scan_progress = InMemoryScanProgress::default();
+    // The checkpoint is at the height of the block with the transaction but on
+    // a chain that got orphaned in the meantime.
+    *scan_progress.checkpoint.lock().unwrap() = Some(Checkpoint {
+        block_hash: sha256d::Hash::hash(b"orphaned block"),
+        block_height: 528,
+    });
+
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let transaction = runtime
+        .block_on(Timeout::new(
+            matching_transaction_with_progress(
+                BlockPoller::new(connector),
+                address_pattern(),
+                None,
+                scan_progress,
+            )
+            .boxed()
+            .compat(),
+            Duration::from_secs(10),
+        ))
+        .unwrap();
+
+    assert_eq!(
+        transaction,
+        include_hex!(
+            "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/transaction.hex"
+        )
+    );
+}
+
+fn address_pattern() -> TransactionPattern {
+    TransactionPattern {
+        to_address: Some(
+            Address::from_str(
+                include_str!(
+                    "test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/address"
+                )
+                .trim(),
+            )
+            .unwrap(),
+        ),
+        from_outpoint: None,
+        unlock_script: None,
+    }
+}
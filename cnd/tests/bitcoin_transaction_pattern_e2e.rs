@@ -1,6 +1,9 @@
 use bitcoin::{Amount, Network};
 use bitcoincore_rpc::RpcApi;
-use cnd::btsieve::bitcoin::{matching_transaction, BitcoindConnector, TransactionPattern};
+use cnd::btsieve::{
+    bitcoin::{matching_transaction, BitcoindConnector, TransactionPattern},
+    BlockPoller,
+};
 use futures::future::Future;
 use futures_core::{FutureExt, TryFutureExt};
 use images::coblox_bitcoincore::BitcoinCore;
@@ -39,7 +42,7 @@ fn bitcoin_transaction_pattern_e2e_test() {
             unlock_script: None,
         };
 
-        async { matching_transaction(connector, pattern, None).await }
+        async { matching_transaction(BlockPoller::new(connector), pattern, None).await }
             .boxed()
             .compat()
             .map_err(|_| ())
@@ -0,0 +1,67 @@
+pub mod bitcoin_helper;
+
+use bitcoin::Network;
+use bitcoin_helper::ZmqPublisherMock;
+use cnd::btsieve::{
+    bitcoin::{BitcoindConnector, ZmqBlockNotifications},
+    LatestBlock,
+};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn latest_block_is_taken_from_zmq_notifications() {
+    let block: bitcoin::Block = include_hex!(
+        "./test_data/bitcoin/find_transaction_in_missing_block_with_big_gap/block8.hex"
+    );
+    let publisher = ZmqPublisherMock::publish(vec![block.clone()]);
+    let notifications = ZmqBlockNotifications::subscribe(&publisher.url()).unwrap();
+
+    wait_until(|| notifications.latest_block().is_some());
+
+    // Nothing is listening on the REST port, the block can only come from the
+    // notifications.
+    let mut connector =
+        BitcoindConnector::new("http://127.0.0.1:1".parse().unwrap(), Network::Regtest)
+            .unwrap()
+            .with_block_notifications(notifications);
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let latest_block = runtime.block_on(connector.latest_block()).unwrap();
+
+    assert_eq!(latest_block, block);
+}
+
+#[test]
+fn notifications_are_not_used_without_publisher() {
+    let unused_port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let notifications = ZmqBlockNotifications::subscribe(
+        &format!("tcp://127.0.0.1:{}", unused_port).parse().unwrap(),
+    )
+    .unwrap();
+
+    assert!(notifications.latest_block().is_none());
+}
+
+#[test]
+fn subscribing_requires_tcp_url() {
+    let result = ZmqBlockNotifications::subscribe(&"ipc:///tmp/bitcoind".parse().unwrap());
+
+    assert!(result.is_err());
+}
+
+fn wait_until(condition: impl Fn() -> bool) {
+    let start = Instant::now();
+    while !condition() {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "condition not met in time"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+}
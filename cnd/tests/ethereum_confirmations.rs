@@ -0,0 +1,51 @@
+pub mod ethereum_helper;
+
+use cnd::{
+    btsieve::{ethereum::transaction_confirmed, BlockPoller, Confirmations},
+    ethereum::{Block, Transaction},
+};
+use ethereum_helper::EthereumConnectorMock;
+use futures_core::{FutureExt, TryFutureExt};
+use std::time::Duration;
+use tokio::timer::Timeout;
+
+#[test]
+fn transaction_is_confirmed_once_buried_deep_enough() {
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let transaction: Transaction = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/transaction.json"
+    );
+    let block1: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block1.json"
+    );
+    let block2_with_transaction: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block2_with_transaction.json"
+    );
+    let block3: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block3.json"
+    );
+
+    let connector = EthereumConnectorMock::new(
+        vec![block2_with_transaction.clone(), block3.clone()],
+        vec![block1, block2_with_transaction, block3],
+        vec![],
+        runtime.executor(),
+    );
+    let confirmations = Confirmations::new(2);
+
+    let confirmed = Timeout::new(
+        transaction_confirmed(
+            BlockPoller::new(connector),
+            transaction.clone(),
+            confirmations.clone(),
+        )
+        .unit_error()
+        .boxed()
+        .compat(),
+        Duration::from_secs(10),
+    );
+
+    assert!(runtime.block_on(confirmed).is_ok());
+    assert_eq!(confirmations.get(&transaction.hash), Some(2));
+}
@@ -1,6 +1,9 @@
 use cnd::{
-    btsieve::{BlockByHash, LatestBlock, ReceiptByHash},
-    ethereum::{Block, Transaction, TransactionReceipt, H256},
+    btsieve::{
+        ethereum::{LogFilter, Topic},
+        BlockByHash, LatestBlock, LogsByFilter, ReceiptByHash,
+    },
+    ethereum::{Block, Log, Transaction, TransactionReceipt, H256, U256},
 };
 use std::{
     collections::HashMap,
@@ -94,6 +97,49 @@ impl ReceiptByHash for EthereumConnectorMock {
     }
 }
 
+impl LogsByFilter for EthereumConnectorMock {
+    type Error = ();
+    type Filter = LogFilter;
+    type Log = Log;
+
+    fn logs_by_filter(
+        &self,
+        filter: Self::Filter,
+    ) -> Box<dyn Future<Item = Vec<Self::Log>, Error = Self::Error> + Send + 'static> {
+        let from_block = U256::from(filter.from_block);
+        let to_block = U256::from(filter.to_block);
+
+        let logs = self
+            .receipts
+            .values()
+            .flat_map(|receipt| receipt.logs.iter())
+            .filter(|log| {
+                log.block_number
+                    .map_or(false, |number| from_block <= number && number <= to_block)
+            })
+            .filter(|log| {
+                filter
+                    .event
+                    .address
+                    .map_or(true, |address| address == log.address)
+            })
+            .filter(|log| {
+                filter
+                    .event
+                    .topics
+                    .iter()
+                    .zip(log.topics.iter())
+                    .all(|(expected, actual)| {
+                        expected.map_or(true, |Topic(expected)| expected == *actual)
+                    })
+            })
+            .cloned()
+            .collect();
+
+        Box::new(Ok(logs).into_future())
+    }
+}
+
 impl tokio::executor::Executor for EthereumConnectorMock {
     fn spawn(
         &mut self,
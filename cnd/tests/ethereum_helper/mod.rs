@@ -1,4 +1,5 @@
 pub mod connector_mock;
 pub mod include_json_test_data;
+pub mod websocket_node_mock;
 
-pub use self::{connector_mock::*, include_json_test_data::*};
+pub use self::{connector_mock::*, include_json_test_data::*, websocket_node_mock::*};
@@ -0,0 +1,186 @@
+use cnd::ethereum::{Block, Transaction, H256};
+use futures::{
+    future,
+    sync::{mpsc, oneshot},
+    Future, Sink, Stream,
+};
+use serde_json::{json, Value};
+use soketto::{
+    base,
+    connection::{Connection, Mode},
+    handshake,
+};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    codec::{Framed, FramedParts},
+    net::TcpListener,
+};
+
+const SUBSCRIPTION_ID: &str = "0x1";
+
+/// An Ethereum node that speaks JSON-RPC over WebSocket.
+///
+/// It answers `eth_subscribe` and `eth_getBlockByHash` and publishes new heads
+/// when told to. Polling for the latest block with `eth_getBlockByNumber`
+/// always yields `null`, blocks can only be learned about through the
+/// subscription.
+#[derive(Clone)]
+pub struct WebSocketNodeMock {
+    address: SocketAddr,
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Default)]
+struct Inner {
+    blocks: HashMap<H256, Block<Transaction>>,
+    connection: Option<(mpsc::UnboundedSender<String>, oneshot::Sender<()>)>,
+    subscriptions: usize,
+    requests: HashMap<String, usize>,
+}
+
+impl WebSocketNodeMock {
+    pub fn new(
+        blocks: impl IntoIterator<Item = Block<Transaction>>,
+        task_executor: tokio::runtime::TaskExecutor,
+    ) -> Self {
+        let listener = TcpListener::bind(&"127.0.0.1:0".parse().unwrap()).unwrap();
+        let address = listener.local_addr().unwrap();
+        let inner = Arc::new(Mutex::new(Inner {
+            blocks: blocks
+                .into_iter()
+                .map(|block| (block.hash.unwrap(), block))
+                .collect(),
+            ..Inner::default()
+        }));
+
+        let mock = Self { address, inner };
+        task_executor.spawn({
+            let mock = mock.clone();
+            let task_executor = task_executor.clone();
+
+            listener.incoming().map_err(|_| ()).for_each(move |stream| {
+                task_executor.spawn(mock.serve(stream));
+                Ok(())
+            })
+        });
+
+        mock
+    }
+
+    pub fn url(&self) -> reqwest::Url {
+        format!("ws://{}", self.address).parse().unwrap()
+    }
+
+    /// How often a client subscribed to `newHeads`.
+    pub fn subscriptions(&self) -> usize {
+        self.inner.lock().unwrap().subscriptions
+    }
+
+    /// How often `method` was called.
+    pub fn requests(&self, method: &str) -> usize {
+        *self
+            .inner
+            .lock()
+            .unwrap()
+            .requests
+            .get(method)
+            .unwrap_or(&0)
+    }
+
+    /// Publishes `block` to the subscriber.
+    pub fn new_head(&self, block: &Block<Transaction>) {
+        if let Some((outgoing, _)) = &self.inner.lock().unwrap().connection {
+            let notification = json!({
+                "jsonrpc": "2.0",
+                "method": "eth_subscription",
+                "params": {
+                    "subscription": SUBSCRIPTION_ID,
+                    "result": block,
+                },
+            });
+            let _ = outgoing.unbounded_send(notification.to_string());
+        }
+    }
+
+    /// Drops the connection to the current client.
+    pub fn disconnect(&self) {
+        if let Some((_, close)) = self.inner.lock().unwrap().connection.take() {
+            let _ = close.send(());
+        }
+    }
+
+    fn serve(&self, stream: tokio::net::TcpStream) -> impl Future<Item = (), Error = ()> {
+        let mock = self.clone();
+
+        Framed::new(stream, handshake::Server::new())
+            .into_future()
+            .map_err(|_| ())
+            .and_then(|(request, framed)| {
+                let key = request.expect("handshake request").key().to_vec();
+                framed.send(Ok(handshake::Accept::new(key))).map_err(|_| ())
+            })
+            .and_then(move |framed| {
+                let handshake = framed.into_parts();
+                let mut parts = FramedParts::new(handshake.io, base::Codec::new());
+                parts.read_buf = handshake.read_buf;
+                parts.write_buf = handshake.write_buf;
+                let (sink, stream) =
+                    Connection::from_framed(Framed::from_parts(parts), Mode::Server).split();
+
+                let (outgoing, receiver) = mpsc::unbounded();
+                let (close, closed) = oneshot::channel();
+                mock.inner.lock().unwrap().connection = Some((outgoing.clone(), close));
+
+                let writer = sink
+                    .send_all(
+                        receiver
+                            .map(|message| base::Data::Text(message.into()))
+                            .map_err(|()| soketto::connection::Error::Closed),
+                    )
+                    .map(|_| ())
+                    .map_err(|_| ());
+                let reader = stream
+                    .for_each(move |message| {
+                        let request: Value = serde_json::from_slice(&message.into_bytes()).unwrap();
+                        let response = mock.answer(&request);
+                        let _ = outgoing.unbounded_send(response.to_string());
+                        Ok(())
+                    })
+                    .map_err(|_| ());
+
+                reader
+                    .select(writer)
+                    .map_err(|_| ())
+                    .select2(closed.map_err(|_| ()))
+                    .then(|_| future::ok(()))
+            })
+    }
+
+    fn answer(&self, request: &Value) -> Value {
+        let method = request["method"].as_str().unwrap().to_owned();
+        let mut inner = self.inner.lock().unwrap();
+        *inner.requests.entry(method.clone()).or_default() += 1;
+
+        let result = match method.as_str() {
+            "eth_subscribe" => {
+                inner.subscriptions += 1;
+                json!(SUBSCRIPTION_ID)
+            }
+            "eth_getBlockByHash" => {
+                let hash: H256 = serde_json::from_value(request["params"][0].clone()).unwrap();
+                json!(inner.blocks.get(&hash))
+            }
+            _ => Value::Null,
+        };
+
+        json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": result,
+        })
+    }
+}
@@ -0,0 +1,176 @@
+pub mod ethereum_helper;
+
+use async_trait::async_trait;
+use cnd::{
+    btsieve::{
+        ethereum::{matching_transaction_by_logs, Event, Topic, TransactionPattern},
+        BlockPoller, Checkpoint, ScanProgress,
+    },
+    ethereum::{
+        Address, Block, Bytes, Log, Transaction, TransactionAndReceipt, TransactionReceipt, H256,
+    },
+};
+use ethereum_helper::EthereumConnectorMock;
+use futures_core::{FutureExt, TryFutureExt};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::timer::Timeout;
+
+#[derive(Clone, Default)]
+struct InMemoryScanProgress {
+    checkpoint: Arc<Mutex<Option<Checkpoint<H256>>>>,
+}
+
+#[async_trait]
+impl ScanProgress<H256> for InMemoryScanProgress {
+    async fn load_checkpoint(&self) -> Option<Checkpoint<H256>> {
+        *self.checkpoint.lock().unwrap()
+    }
+
+    async fn save_checkpoint(&self, checkpoint: Checkpoint<H256>) {
+        *self.checkpoint.lock().unwrap() = Some(checkpoint);
+    }
+}
+
+#[test]
+fn find_transaction_by_logs_without_fetching_blocks_in_between() {
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let transaction: Transaction = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/transaction.json"
+    );
+    let receipt = receipt_with_log(&transaction, H256::repeat_byte(0x11));
+    let block1: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block1.json"
+    );
+    let block5: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block5.json"
+    );
+
+    // Blocks 3 and 4 are unknown to the connector, the log query has to find the
+    // transaction on its own.
+    let connector = EthereumConnectorMock::new(
+        vec![block5.clone()],
+        vec![
+            include_json_test_data!(
+                "./test_data/ethereum/find_transaction_in_missing_block/block2_with_transaction.json"
+            ),
+            block5,
+        ],
+        vec![(transaction.hash, receipt.clone())],
+        runtime.executor(),
+    );
+    let scan_progress = InMemoryScanProgress::default();
+    *scan_progress.checkpoint.lock().unwrap() = Some(Checkpoint {
+        block_hash: block1.hash.unwrap(),
+        block_height: block1.number.unwrap().as_u64(),
+    });
+
+    let transaction_and_receipt = runtime
+        .block_on(Timeout::new(
+            matching_transaction_by_logs(
+                BlockPoller::new(connector),
+                event_pattern(0x11),
+                scan_progress,
+            )
+            .unit_error()
+            .boxed()
+            .compat(),
+            Duration::from_secs(10),
+        ))
+        .unwrap();
+
+    assert_eq!(transaction_and_receipt, TransactionAndReceipt {
+        transaction,
+        receipt
+    });
+}
+
+#[test]
+fn scanning_logs_records_latest_block_as_checkpoint() {
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let transaction: Transaction = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/transaction.json"
+    );
+    let block1: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block1.json"
+    );
+    let block5: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block5.json"
+    );
+
+    let connector = EthereumConnectorMock::new(
+        vec![block5.clone()],
+        vec![block5.clone()],
+        vec![(
+            transaction.hash,
+            receipt_with_log(&transaction, H256::repeat_byte(0x22)),
+        )],
+        runtime.executor(),
+    );
+    let scan_progress = InMemoryScanProgress::default();
+    *scan_progress.checkpoint.lock().unwrap() = Some(Checkpoint {
+        block_hash: block1.hash.unwrap(),
+        block_height: block1.number.unwrap().as_u64(),
+    });
+
+    let result = runtime.block_on(Timeout::new(
+        matching_transaction_by_logs(
+            BlockPoller::new(connector),
+            event_pattern(0x11),
+            scan_progress.clone(),
+        )
+        .unit_error()
+        .boxed()
+        .compat(),
+        Duration::from_secs(3),
+    ));
+
+    assert!(result.unwrap_err().is_elapsed());
+    assert_eq!(
+        *scan_progress.checkpoint.lock().unwrap(),
+        Some(Checkpoint {
+            block_hash: block5.hash.unwrap(),
+            block_height: block5.number.unwrap().as_u64(),
+        })
+    );
+}
+
+fn event_pattern(topic: u8) -> TransactionPattern {
+    TransactionPattern {
+        from_address: None,
+        to_address: None,
+        is_contract_creation: None,
+        transaction_data: None,
+        transaction_data_length: None,
+        events: Some(vec![Event {
+            address: Some(Address::repeat_byte(0xaa)),
+            data: None,
+            topics: vec![Some(Topic(H256::repeat_byte(topic)))],
+        }]),
+    }
+}
+
+fn receipt_with_log(transaction: &Transaction, topic: H256) -> TransactionReceipt {
+    let mut receipt: TransactionReceipt = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/receipt.json"
+    );
+    receipt.logs.push(Log {
+        address: Address::repeat_byte(0xaa),
+        topics: vec![topic],
+        data: Bytes(vec![]),
+        block_hash: receipt.block_hash,
+        block_number: receipt.block_number.map(|number| number.as_u64().into()),
+        transaction_hash: Some(transaction.hash),
+        transaction_index: None,
+        log_index: None,
+        transaction_log_index: None,
+        log_type: None,
+        removed: None,
+    });
+
+    receipt
+}
@@ -1,11 +1,15 @@
 pub mod ethereum_helper;
 
 use cnd::{
-    btsieve::ethereum::{matching_transaction, TransactionPattern},
+    btsieve::{
+        ethereum::{matching_transaction, TransactionPattern},
+        BlockPoller,
+    },
     ethereum::{Transaction, TransactionAndReceipt, TransactionReceipt},
 };
 use ethereum_helper::EthereumConnectorMock;
 use futures_core::{FutureExt, TryFutureExt};
+use std::time::Duration;
 use tokio::prelude::Future;
 
 #[test]
@@ -44,7 +48,7 @@ fn find_transaction_in_missing_block() {
 
     let expected_transaction_and_receipt: TransactionAndReceipt = async {
         matching_transaction(
-            connector,
+            BlockPoller::new(connector),
             TransactionPattern {
                 from_address: None,
                 to_address: Some(transaction.to.unwrap()),
@@ -111,7 +115,7 @@ fn find_transaction_in_missing_block_with_big_gap() {
 
     let expected_transaction_and_receipt: TransactionAndReceipt = async {
         matching_transaction(
-            connector,
+            BlockPoller::new(connector),
             TransactionPattern {
                 from_address: None,
                 to_address: Some(transaction.to.unwrap()),
@@ -135,3 +139,67 @@ fn find_transaction_in_missing_block_with_big_gap() {
         receipt
     });
 }
+
+#[test]
+fn given_matching_transaction_was_found_all_spawned_tasks_shut_down() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let transaction: Transaction = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/transaction.json"
+    );
+    let receipt: TransactionReceipt = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/receipt.json"
+    );
+    let connector = EthereumConnectorMock::new(
+        vec![
+            include_json_test_data!(
+                "./test_data/ethereum/find_transaction_in_missing_block/block1.json"
+            ),
+            include_json_test_data!(
+                "./test_data/ethereum/find_transaction_in_missing_block/block3.json"
+            ),
+        ],
+        vec![
+            include_json_test_data!(
+                "./test_data/ethereum/find_transaction_in_missing_block/block1.json"
+            ),
+            include_json_test_data!(
+                "./test_data/ethereum/find_transaction_in_missing_block/block2_with_transaction.json"
+            ),
+            include_json_test_data!(
+                "./test_data/ethereum/find_transaction_in_missing_block/block3.json"
+            ),
+        ],
+        vec![(transaction.hash, receipt)],
+        runtime.executor(),
+    );
+
+    matching_transaction(
+        BlockPoller::new(connector),
+        TransactionPattern {
+            from_address: None,
+            to_address: Some(transaction.to.unwrap()),
+            is_contract_creation: None,
+            transaction_data: None,
+            transaction_data_length: None,
+            events: None,
+        },
+        None,
+    )
+    .unit_error()
+    .boxed()
+    .compat()
+    .wait()
+    .unwrap();
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        runtime.shutdown_on_idle().wait().unwrap();
+        sender.send(()).unwrap();
+    });
+
+    assert!(
+        receiver.recv_timeout(Duration::from_secs(10)).is_ok(),
+        "runtime still had tasks running after the transaction was found"
+    );
+}
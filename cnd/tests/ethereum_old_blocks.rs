@@ -1,7 +1,10 @@
 pub mod ethereum_helper;
 
 use cnd::{
-    btsieve::ethereum::{matching_transaction, TransactionPattern},
+    btsieve::{
+        ethereum::{matching_transaction, TransactionPattern},
+        BlockPoller,
+    },
     ethereum::{Block, Transaction, TransactionAndReceipt, TransactionReceipt},
 };
 use ethereum_helper::EthereumConnectorMock;
@@ -50,7 +53,7 @@ fn find_transaction_in_old_block() {
 
     let expected_transaction_and_receipt: TransactionAndReceipt = async {
         matching_transaction(
-            connector,
+            BlockPoller::new(connector),
             TransactionPattern {
                 from_address: None,
                 to_address: Some(transaction.to.unwrap()),
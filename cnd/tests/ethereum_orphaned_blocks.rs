@@ -0,0 +1,147 @@
+pub mod ethereum_helper;
+
+use cnd::{
+    btsieve::{ethereum::transaction_retracted, BlockPoller},
+    ethereum::{Block, Transaction, H256},
+};
+use ethereum_helper::EthereumConnectorMock;
+use futures_core::{FutureExt, TryFutureExt};
+use std::time::Duration;
+use tokio::timer::Timeout;
+
+/// Builds a block that competes with `block` for the same height.
+fn competing_block(
+    block: &Block<Transaction>,
+    parent_hash: H256,
+    hash: H256,
+) -> Block<Transaction> {
+    Block {
+        hash: Some(hash),
+        parent_hash,
+        transactions: Vec::new(),
+        ..block.clone()
+    }
+}
+
+#[test]
+fn transaction_is_retracted_if_including_block_gets_orphaned() {
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let transaction: Transaction = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/transaction.json"
+    );
+    let block1: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block1.json"
+    );
+    let block2_with_transaction: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block2_with_transaction.json"
+    );
+    let block3: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block3.json"
+    );
+    let block2b = competing_block(
+        &block2_with_transaction,
+        block1.hash.unwrap(),
+        H256::repeat_byte(0x2b),
+    );
+    let block3b = competing_block(&block3, block2b.hash.unwrap(), H256::repeat_byte(0x3b));
+
+    let connector = EthereumConnectorMock::new(
+        vec![block2_with_transaction.clone(), block3b.clone()],
+        vec![block1, block2_with_transaction, block2b, block3b],
+        vec![],
+        runtime.executor(),
+    );
+
+    let retracted = Timeout::new(
+        transaction_retracted(BlockPoller::new(connector), transaction)
+            .unit_error()
+            .boxed()
+            .compat(),
+        Duration::from_secs(10),
+    );
+
+    assert!(runtime.block_on(retracted).is_ok());
+}
+
+#[test]
+fn transaction_is_retracted_if_including_block_was_orphaned_before_watching_it() {
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let transaction: Transaction = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/transaction.json"
+    );
+    let block1: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block1.json"
+    );
+    let block2_with_transaction: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block2_with_transaction.json"
+    );
+    let block3: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block3.json"
+    );
+    let block2b = competing_block(
+        &block2_with_transaction,
+        block1.hash.unwrap(),
+        H256::repeat_byte(0x2b),
+    );
+    let block3b = competing_block(&block3, block2b.hash.unwrap(), H256::repeat_byte(0x3b));
+
+    // The first block the watcher sees is already on the competing chain.
+    let connector = EthereumConnectorMock::new(
+        vec![block3b.clone()],
+        vec![block1, block2_with_transaction, block2b, block3b],
+        vec![],
+        runtime.executor(),
+    );
+
+    let retracted = Timeout::new(
+        transaction_retracted(BlockPoller::new(connector), transaction)
+            .unit_error()
+            .boxed()
+            .compat(),
+        Duration::from_secs(10),
+    );
+
+    assert!(runtime.block_on(retracted).is_ok());
+}
+
+#[test]
+fn transaction_is_not_retracted_if_including_block_stays_in_best_chain() {
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let transaction: Transaction = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/transaction.json"
+    );
+    let block1: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block1.json"
+    );
+    let block2_with_transaction: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block2_with_transaction.json"
+    );
+    let block3: Block<Transaction> = include_json_test_data!(
+        "./test_data/ethereum/find_transaction_in_missing_block/block3.json"
+    );
+    let block2b = competing_block(
+        &block2_with_transaction,
+        block1.hash.unwrap(),
+        H256::repeat_byte(0x2b),
+    );
+
+    let connector = EthereumConnectorMock::new(
+        vec![block2_with_transaction.clone(), block3.clone()],
+        vec![block1, block2_with_transaction, block2b, block3],
+        vec![],
+        runtime.executor(),
+    );
+
+    let retracted = Timeout::new(
+        transaction_retracted(BlockPoller::new(connector), transaction)
+            .unit_error()
+            .boxed()
+            .compat(),
+        Duration::from_secs(3),
+    );
+
+    assert!(runtime.block_on(retracted).unwrap_err().is_elapsed());
+}
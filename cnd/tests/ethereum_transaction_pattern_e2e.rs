@@ -1,5 +1,8 @@
 use cnd::{
-    btsieve::ethereum::{matching_transaction, TransactionPattern, Web3Connector},
+    btsieve::{
+        ethereum::{matching_transaction, TransactionPattern, Web3Connector},
+        BlockPoller,
+    },
     ethereum::{
         web3::{
             transports::{EventLoopHandle, Http},
@@ -51,7 +54,7 @@ fn ethereum_transaction_pattern_e2e_test() {
             events: None,
         };
 
-        async { matching_transaction(connector, pattern, None).await }
+        async { matching_transaction(BlockPoller::new(connector), pattern, None).await }
             .unit_error()
             .boxed()
             .compat()
@@ -0,0 +1,80 @@
+pub mod ethereum_helper;
+
+use cnd::{
+    btsieve::{ethereum::Web3Connector, LatestBlock},
+    ethereum::{Block, Transaction},
+};
+use ethereum_helper::WebSocketNodeMock;
+use futures::Future;
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn latest_block_follows_new_heads() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (block1, block5) = blocks();
+    let node = WebSocketNodeMock::new(vec![block1.clone(), block5.clone()], runtime.executor());
+    let (mut connector, event_loop_handle) =
+        Web3Connector::new(node.url(), runtime.executor()).unwrap();
+    assert!(event_loop_handle.is_none());
+
+    wait_until(|| node.subscriptions() == 1);
+    node.new_head(&block1);
+    wait_until(|| latest_block(&mut connector) == Some(block1.clone()));
+
+    // Until the next head arrives, the node is not asked again.
+    let polled = node.requests("eth_getBlockByNumber");
+    let fetched = node.requests("eth_getBlockByHash");
+    assert_eq!(latest_block(&mut connector), Some(block1));
+    assert_eq!(node.requests("eth_getBlockByHash"), fetched);
+
+    node.new_head(&block5);
+    wait_until(|| latest_block(&mut connector) == Some(block5.clone()));
+    assert_eq!(node.requests("eth_getBlockByHash"), fetched + 1);
+    assert_eq!(node.requests("eth_getBlockByNumber"), polled);
+}
+
+#[test]
+fn resubscribes_after_connection_is_lost() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (block1, block5) = blocks();
+    let node = WebSocketNodeMock::new(vec![block1.clone(), block5.clone()], runtime.executor());
+    let (mut connector, _) = Web3Connector::new(node.url(), runtime.executor()).unwrap();
+
+    wait_until(|| node.subscriptions() == 1);
+    node.new_head(&block1);
+    wait_until(|| latest_block(&mut connector) == Some(block1.clone()));
+
+    node.disconnect();
+    wait_until(|| node.subscriptions() == 2);
+    node.new_head(&block5);
+    wait_until(|| latest_block(&mut connector) == Some(block5.clone()));
+}
+
+fn latest_block(connector: &mut Web3Connector) -> Option<Block<Transaction>> {
+    connector.latest_block().wait().ok().flatten()
+}
+
+fn blocks() -> (Block<Transaction>, Block<Transaction>) {
+    (
+        include_json_test_data!(
+            "./test_data/ethereum/find_transaction_in_missing_block/block1.json"
+        ),
+        include_json_test_data!(
+            "./test_data/ethereum/find_transaction_in_missing_block/block5.json"
+        ),
+    )
+}
+
+fn wait_until(mut condition: impl FnMut() -> bool) {
+    let start = Instant::now();
+    while !condition() {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "condition not met in time"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+}